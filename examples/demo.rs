@@ -0,0 +1,146 @@
+use iced::{canvas::Canvas, executor, Application, Column, Command, Container, Element, Settings};
+use plotters_iced::prelude::*;
+use plotters_iced::{
+    shade_y_band, Axis, DualCursors, HoverHighlight, Readout, ReferenceLine, StatsBox, UnitSet,
+};
+
+pub fn main() -> iced::Result {
+    Plot::run(Settings {
+        antialiasing: true,
+        ..Settings::default()
+    })
+}
+
+struct Plot {
+    chart: ChartWidget<Message, DemoChart>,
+    /// The last error building the chart, shown above it.
+    error: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    ChartError(ChartError),
+}
+
+impl Application for Plot {
+    type Executor = executor::Default;
+    type Message = Message;
+    type Flags = ();
+
+    fn new(_flags: ()) -> (Self, Command<Message>) {
+        (
+            Plot {
+                chart: ChartWidget::new(DemoChart)
+                    .view(ViewState::new(0.0..10.0, 0.0..10.0).with_secondary_y(0.0..100.0))
+                    .readout(Readout::new())
+                    .hover_highlight(HoverHighlight::default())
+                    // Right-click the y axis to switch between °C, °F and K
+                    .units(Axis::Y, UnitSet::temperature())
+                    // A threshold the user can drag in half-degree steps
+                    .reference_line(
+                        ReferenceLine::new(Axis::Y, 6.0)
+                            .bounds(0.0, 10.0)
+                            .snap(0.5)
+                            .label("threshold"),
+                    )
+                    // Compare the red series between two draggable cursors
+                    .dual_cursors(DualCursors::new(2.0, 6.0).series_name(SeriesId(0), "red"))
+                    .stats_box(StatsBox::new())
+                    .on_error(Message::ChartError),
+                error: None,
+            },
+            Command::none(),
+        )
+    }
+
+    fn title(&self) -> String {
+        String::from("Plotters Iced Backend")
+    }
+
+    fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::ChartError(error) => {
+                self.error = Some(format!("failed to build chart: {}", error));
+            }
+        }
+        Command::none()
+    }
+
+    fn view(&mut self) -> Element<'_, Message> {
+        let canvas = Canvas::new(&mut self.chart)
+            .width(Length::Units(1000))
+            .height(Length::Units(1000));
+
+        let mut content = Column::new().spacing(10);
+        if let Some(error) = &self.error {
+            content = content.push(iced::Text::new(error.as_str()).color([0.8, 0.0, 0.0]));
+        }
+
+        Container::new(content.push(canvas))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding(20)
+            .center_x()
+            .center_y()
+            .into()
+    }
+}
+
+struct DemoChart;
+
+impl Chart for DemoChart {
+    fn build_chart(
+        &self,
+        ctx: &mut BuildContext<'_>,
+        root: DrawingArea<IcedBackend<'_>, Shift>,
+    ) -> Result<(), BuildError> {
+        ctx.fill_background(&root)?;
+
+        let root = root.margin(10, 10, 10, 10);
+        // After this point, we should be able to draw construct a chart context
+        let mut chart = ChartBuilder::on(&root)
+            // Set the caption of the chart
+            .caption("This is our first plot", ("sans-serif", 40).into_font())
+            // Set the size of the label region
+            .x_label_area_size(20)
+            .y_label_area_size(40)
+            .right_y_label_area_size(40)
+            // Finally attach a coordinate on the drawing area and make a chart context
+            .build_cartesian_2d(ctx.x_range(), ctx.y_range())?
+            // The secondary axis on the right can be scaled independently by dragging it
+            .set_secondary_coord(ctx.x_range(), ctx.secondary_y_range().unwrap_or(0.0..100.0));
+        // Let the widget know where the data ends up, so panning and zooming work
+        ctx.set_plotting_area(chart.plotting_area());
+
+        // Then we can draw a mesh
+        // The number of labels follows the size of the plotting area
+        ctx.fit_labels(&mut chart.configure_mesh())
+            // We can also change the format of the label text
+            .y_label_formatter(&|y| ctx.format(Axis::Y, *y))
+            .draw()?;
+        chart.configure_secondary_axes().y_labels(5).draw()?;
+
+        // Shade a tolerance band first, so it lies under the data
+        shade_y_band(chart.plotting_area(), 4.0..6.0, &BLUE.mix(0.1))?;
+
+        // And we can draw something in the drawing area
+        let data = vec![(0.0, 0.0), (5.0, 5.0), (8.0, 7.0)];
+        chart.draw_series(LineSeries::new(data.clone(), &RED))?;
+        // Registering the series lets the widget highlight it on hover
+        ctx.register_line(SeriesId(0), chart.plotting_area(), &data);
+        ctx.set_series_color(SeriesId(0), &RED);
+        // Tracked series are summarized over the visible window in the stats box
+        ctx.track_stats(SeriesId(0), "red", &data);
+        chart.draw_secondary_series(LineSeries::new(
+            vec![(0.0, 90.0), (4.0, 40.0), (9.0, 20.0)],
+            &BLUE,
+        ))?;
+        // Similarly, we can draw point series
+        chart.draw_series(PointSeries::of_element(data, 5, &RED, &|c, s, st| {
+            EmptyElement::at(c)    // We want to construct a composed element on-the-fly
+                + Circle::new((0,0),s,st.filled()) // At this point, the new pixel coordinate is established
+                + Text::new(format!("{:?}", c), (10, 0), ("sans-serif", 10).into_font())
+        }))?;
+        Ok(())
+    }
+}
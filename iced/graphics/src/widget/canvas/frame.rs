@@ -1,4 +1,4 @@
-use iced_native::{Point, Rectangle, Size, Vector};
+use iced_native::{Color, Point, Rectangle, Size, Vector};
 
 use crate::{
     canvas::{Fill, Geometry, Path, Stroke, Text},
@@ -235,6 +235,46 @@ impl Frame {
         });
     }
 
+    /// Draws triangles on the [`Frame`], given by the indices of their corners
+    /// in `vertices`, each corner with its own color.
+    ///
+    /// The vertices are written straight into the vertex buffer of the
+    /// [`Frame`] without tessellating a [`Path`], so many small shapes can be
+    /// drawn at a fraction of the cost of filling them one by one.
+    ///
+    /// [`Path`]: path/struct.Path.html
+    /// [`Frame`]: struct.Frame.html
+    pub fn fill_triangles(
+        &mut self,
+        vertices: &[(Point, Color)],
+        indices: &[u32],
+    ) {
+        let base = self.buffers.vertices.len() as u32;
+        let transform = self.transforms.current;
+
+        self.buffers
+            .vertices
+            .extend(vertices.iter().map(|&(point, color)| {
+                let position = if transform.is_identity {
+                    [point.x, point.y]
+                } else {
+                    let point = transform.raw.transform_point(
+                        lyon::math::Point::new(point.x, point.y),
+                    );
+
+                    [point.x, point.y]
+                };
+
+                triangle::Vertex2D {
+                    position,
+                    color: color.into_linear(),
+                }
+            }));
+        self.buffers
+            .indices
+            .extend(indices.iter().map(|index| base + index));
+    }
+
     /// Stores the current transform of the [`Frame`] and executes the given
     /// drawing operations, restoring the transform afterwards.
     ///
@@ -8,7 +8,7 @@ impl Clipboard {
     ///
     /// [`Clipboard`]: struct.Clipboard.html
     pub fn new(window: &winit::window::Window) -> Option<Clipboard> {
-        window_clipboard::Clipboard::new(window).map(Clipboard).ok()
+        window_clipboard::Clipboard::connect(window).map(Clipboard).ok()
     }
 }
 
@@ -0,0 +1,214 @@
+use crate::{Chart, ChartWidget};
+use iced::canvas::{Canvas, Program};
+use iced::{Align, Length, Point, Size};
+use iced_native::layout::{Limits, Node};
+use iced_native::{column, mouse, overlay, Clipboard, Element, Event, Hasher, Layout, Widget};
+use std::hash::Hash;
+
+/// A chart with iced widgets placed over it at data coordinates, such as an "ack" button
+/// next to every alarm point.
+///
+/// The positions are computed from the view and the plotting area of the chart's last
+/// draw when the container is created, so build it in the application's `view` and set
+/// [`ChartWidget::on_view_change`] for the view to be rebuilt, and the widgets to follow,
+/// while the user pans and zooms:
+///
+/// ```ignore
+/// let mut anchored = Anchored::new(&mut self.chart);
+/// for alarm in &mut self.alarms {
+///     let ack = Button::new(&mut alarm.ack, Text::new("Ack")).on_press(Message::Ack(alarm.id));
+///     anchored = anchored.push((alarm.time, alarm.value), ack);
+/// }
+/// anchored.into()
+/// ```
+///
+/// Widgets anchored outside the plotting area are left out. Clicks and scrolls over a
+/// widget don't reach the chart.
+#[allow(missing_debug_implementations)]
+pub struct Anchored<'a, Message, Renderer> {
+    /// The chart's canvas, followed by the anchored widgets.
+    children: Vec<Element<'a, Message, Renderer>>,
+    /// The positions of the anchored widgets on the canvas and how they are aligned to
+    /// them.
+    anchors: Vec<(Point, Align, Align)>,
+    to_canvas: Box<dyn Fn((f64, f64)) -> Option<Point> + 'a>,
+    width: Length,
+    height: Length,
+}
+
+impl<'a, Message: 'a, Renderer: 'a> Anchored<'a, Message, Renderer> {
+    /// Shows `chart`, filling the space given to it, with no widgets over it yet.
+    pub fn new<C>(chart: &'a mut ChartWidget<Message, C>) -> Self
+    where
+        C: Chart + 'a,
+        &'a mut ChartWidget<Message, C>: Program<Message>,
+        Canvas<Message, &'a mut ChartWidget<Message, C>>: Into<Element<'a, Message, Renderer>>,
+    {
+        let view = chart.view_state().clone();
+        let plot = chart.plotting_area();
+        let to_canvas = move |point: (f64, f64)| {
+            let plot = plot?;
+            Some(view.to_pixel(plot, point)).filter(|at| plot.contains(*at))
+        };
+        let canvas = Canvas::new(chart).width(Length::Fill).height(Length::Fill);
+        Self {
+            children: vec![canvas.into()],
+            anchors: vec![],
+            to_canvas: Box::new(to_canvas),
+            width: Length::Fill,
+            height: Length::Fill,
+        }
+    }
+
+    pub fn width(mut self, width: Length) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn height(mut self, height: Length) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Places `element` centered on the data point `point`.
+    pub fn push(
+        self,
+        point: (f64, f64),
+        element: impl Into<Element<'a, Message, Renderer>>,
+    ) -> Self {
+        self.push_aligned(point, Align::Center, Align::Center, element)
+    }
+
+    /// Places `element` at the data point `point`, with its left edge, center or right edge
+    /// (`horizontal`) and its top edge, center or bottom edge (`vertical`) on it.
+    pub fn push_aligned(
+        mut self,
+        point: (f64, f64),
+        horizontal: Align,
+        vertical: Align,
+        element: impl Into<Element<'a, Message, Renderer>>,
+    ) -> Self {
+        if let Some(at) = (self.to_canvas)(point) {
+            self.children.push(element.into());
+            self.anchors.push((at, horizontal, vertical));
+        }
+        self
+    }
+
+    /// Whether `point` lies on one of the anchored widgets, given the layouts of all
+    /// children.
+    fn over_widget(&self, layout: Layout<'_>, point: Point) -> bool {
+        layout
+            .children()
+            .skip(1)
+            .any(|child| child.bounds().contains(point))
+    }
+}
+
+impl<'a, Message, Renderer> Widget<Message, Renderer> for Anchored<'a, Message, Renderer>
+where
+    Renderer: column::Renderer,
+{
+    fn width(&self) -> Length {
+        self.width
+    }
+
+    fn height(&self) -> Length {
+        self.height
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &Limits) -> Node {
+        let limits = limits.width(self.width).height(self.height);
+        let size = limits.resolve(Size::ZERO);
+        let mut nodes = vec![self.children[0].layout(renderer, &Limits::new(size, size))];
+        for (child, (at, horizontal, vertical)) in self.children[1..].iter().zip(&self.anchors) {
+            let mut node = child.layout(renderer, &Limits::new(Size::ZERO, size));
+            let bounds = node.bounds();
+            let offset = |align: &Align, length: f32| match align {
+                Align::Start => 0.0,
+                Align::Center => length / 2.0,
+                Align::End => length,
+            };
+            node.move_to(Point::new(
+                at.x - offset(horizontal, bounds.width),
+                at.y - offset(vertical, bounds.height),
+            ));
+            nodes.push(node);
+        }
+        Node::with_children(size, nodes)
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        defaults: &Renderer::Defaults,
+        layout: Layout<'_>,
+        cursor_position: Point,
+    ) -> Renderer::Output {
+        // The column renderer draws every child at its own layout, in order, so the
+        // widgets end up over the chart.
+        column::Renderer::draw(renderer, defaults, &self.children, layout, cursor_position)
+    }
+
+    fn hash_layout(&self, state: &mut Hasher) {
+        struct Marker;
+        std::any::TypeId::of::<Marker>().hash(state);
+        self.width.hash(state);
+        self.height.hash(state);
+        for (at, _, _) in &self.anchors {
+            at.x.to_bits().hash(state);
+            at.y.to_bits().hash(state);
+        }
+        for child in &self.children {
+            child.hash_layout(state);
+        }
+    }
+
+    fn on_event(
+        &mut self,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        messages: &mut Vec<Message>,
+        renderer: &Renderer,
+        clipboard: Option<&dyn Clipboard>,
+    ) {
+        let shielded = matches!(
+            event,
+            Event::Mouse(mouse::Event::ButtonPressed(_))
+                | Event::Mouse(mouse::Event::WheelScrolled { .. })
+        ) && self.over_widget(layout, cursor_position);
+        for (index, (child, layout)) in self.children.iter_mut().zip(layout.children()).enumerate()
+        {
+            if index == 0 && shielded {
+                continue;
+            }
+            child.on_event(
+                event.clone(),
+                layout,
+                cursor_position,
+                messages,
+                renderer,
+                clipboard,
+            );
+        }
+    }
+
+    fn overlay(&mut self, layout: Layout<'_>) -> Option<overlay::Element<'_, Message, Renderer>> {
+        self.children
+            .iter_mut()
+            .zip(layout.children())
+            .filter_map(|(child, layout)| child.overlay(layout))
+            .next()
+    }
+}
+
+impl<'a, Message, Renderer> From<Anchored<'a, Message, Renderer>> for Element<'a, Message, Renderer>
+where
+    Message: 'a,
+    Renderer: 'a + column::Renderer,
+{
+    fn from(anchored: Anchored<'a, Message, Renderer>) -> Self {
+        Element::new(anchored)
+    }
+}
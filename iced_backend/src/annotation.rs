@@ -0,0 +1,138 @@
+use crate::ViewState;
+use iced::canvas::{Frame, Path, Text};
+use iced::{Color, Point, Rectangle, Size};
+
+/// The padding between an annotation's box and its text.
+const PADDING: f32 = 3.0;
+/// A rough average glyph width relative to the text size, used to size the box.
+const GLYPH_WIDTH: f32 = 0.6;
+const TEXT_SIZE: f32 = 13.0;
+
+/// Identifies an annotation in an [`AnnotationStore`]. Ids are never reused, also not
+/// after a store was saved and loaded again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AnnotationId(pub u64);
+
+/// A note attached to a point in data coordinates.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Annotation {
+    pub x: f64,
+    pub y: f64,
+    pub text: String,
+}
+
+impl Annotation {
+    pub fn new(x: f64, y: f64, text: impl Into<String>) -> Self {
+        Self {
+            x,
+            y,
+            text: text.into(),
+        }
+    }
+}
+
+/// What happened to an annotation, as reported through
+/// [`ChartWidget::on_annotation_change`](crate::ChartWidget::on_annotation_change).
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnnotationChange {
+    Added(AnnotationId, Annotation),
+    Updated(AnnotationId, Annotation),
+    Removed(AnnotationId),
+}
+
+/// The annotations of a chart, in the order they were added.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AnnotationStore {
+    next_id: u64,
+    items: Vec<(AnnotationId, Annotation)>,
+}
+
+impl AnnotationStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, annotation: Annotation) -> AnnotationId {
+        let id = AnnotationId(self.next_id);
+        self.next_id += 1;
+        self.items.push((id, annotation));
+        id
+    }
+
+    /// Replaces the annotation with `id`, returning the previous one.
+    pub fn update(&mut self, id: AnnotationId, annotation: Annotation) -> Option<Annotation> {
+        let slot = self.get_mut(id)?;
+        Some(std::mem::replace(slot, annotation))
+    }
+
+    pub fn remove(&mut self, id: AnnotationId) -> Option<Annotation> {
+        let index = self.items.iter().position(|(item, _)| *item == id)?;
+        Some(self.items.remove(index).1)
+    }
+
+    pub fn get(&self, id: AnnotationId) -> Option<&Annotation> {
+        self.items
+            .iter()
+            .find(|(item, _)| *item == id)
+            .map(|(_, annotation)| annotation)
+    }
+
+    fn get_mut(&mut self, id: AnnotationId) -> Option<&mut Annotation> {
+        self.items
+            .iter_mut()
+            .find(|(item, _)| *item == id)
+            .map(|(_, annotation)| annotation)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (AnnotationId, &Annotation)> + '_ {
+        self.items.iter().map(|(id, annotation)| (*id, annotation))
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Draws every annotation whose anchor lies in `plot` as a dot with a boxed note.
+    pub(crate) fn draw(&self, frame: &mut Frame, view: &ViewState, plot: Rectangle) {
+        for (_, annotation) in self.iter() {
+            let anchor = view.to_pixel(plot, (annotation.x, annotation.y));
+            if !plot.contains(anchor) {
+                continue;
+            }
+            frame.fill(&Path::circle(anchor, 3.0), Color::BLACK);
+
+            let lines = annotation.text.lines().count().max(1) as f32;
+            let columns = annotation
+                .text
+                .lines()
+                .map(|l| l.chars().count())
+                .max()
+                .unwrap_or(0) as f32;
+            let size = Size::new(
+                columns * TEXT_SIZE * GLYPH_WIDTH + 2.0 * PADDING,
+                lines * TEXT_SIZE + 2.0 * PADDING,
+            );
+            // Keep the note inside the plot, preferring the upper right of the anchor.
+            let x = (anchor.x + 6.0).min(plot.x + plot.width - size.width);
+            let y = (anchor.y - 6.0 - size.height).max(plot.y);
+            frame.fill_rectangle(
+                Point::new(x, y),
+                size,
+                Color::from_rgba(1.0, 1.0, 0.85, 0.9),
+            );
+            frame.fill_text(Text {
+                content: annotation.text.clone(),
+                position: Point::new(x + PADDING, y + PADDING),
+                size: TEXT_SIZE,
+                ..Text::default()
+            });
+        }
+    }
+}
@@ -1,43 +1,242 @@
+use crate::clip::{self, Envelope};
+use crate::trace;
+use crate::{DiagnosticKind, Diagnostics, FrameLike, GeometryGroups, Gradient, MarkerShape};
 use iced::widget::canvas::{Frame, Path, Stroke};
-use iced::{Point, Size};
+use iced::{Point, Rectangle, Size, Vector};
+use plotters::style::{Color, RGBAColor, WHITE};
+use plotters_backend::text_anchor::{HPos, VPos};
 use plotters_backend::{
     BackendColor, BackendCoord, BackendStyle, BackendTextStyle, DrawingBackend, DrawingErrorKind,
-    FontStyle, FontTransform,
 };
+use std::cell::RefCell;
+use std::collections::HashMap;
 
-#[derive(Debug)]
-pub struct IcedError;
+/// Frames narrower or shorter than this many pixels cannot hold a meaningful chart.
+const MIN_FRAME_SIZE: f32 = 2.0;
+/// The height of the solid bands approximating a vertical gradient, in pixels.
+const GRADIENT_BAND: f32 = 2.0;
+/// The most bands a gradient is split into along its direction.
+const MAX_GRADIENT_BANDS: usize = 96;
+/// The number of sectors the rings of a radial gradient are split into.
+const GRADIENT_SECTORS: usize = 32;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum IcedError {
+    /// Plotters handed the backend a coordinate too far away from the frame to be drawn
+    CoordinateOutOfRange(BackendCoord),
+    /// The frame is too small (or not finite) to draw into, e.g. while a layout collapses
+    DegenerateFrame { width: f32, height: f32 },
+}
 
 impl std::fmt::Display for IcedError {
     fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(fmt, "{:?}", self)
+        match self {
+            IcedError::CoordinateOutOfRange((x, y)) => write!(
+                fmt,
+                "coordinate ({}, {}) exceeds the drawable range of +/-{}",
+                x,
+                y,
+                clip::MAX_COORDINATE
+            ),
+            IcedError::DegenerateFrame { width, height } => {
+                write!(fmt, "cannot draw into a {}x{} frame", width, height)
+            }
+        }
     }
 }
 
 impl std::error::Error for IcedError {}
 
+/// A contrasting outline drawn around text, so labels stay readable over dense data.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextHalo {
+    pub color: RGBAColor,
+    /// How far the outline reaches beyond the glyphs, in pixels.
+    pub width: u32,
+}
+
+impl TextHalo {
+    pub fn new<C: Color>(color: &C, width: u32) -> Self {
+        Self {
+            color: color.to_rgba(),
+            width,
+        }
+    }
+}
+
+impl Default for TextHalo {
+    fn default() -> Self {
+        Self::new(&WHITE.mix(0.8), 2)
+    }
+}
+
 /// The drawing backend that is backed with a Cairo context
-pub struct IcedBackend<'a> {
-    frame: &'a mut Frame,
+///
+/// It draws onto a canvas [`Frame`] by default, or onto any other [`FrameLike`] surface.
+pub struct IcedBackend<'a, F: FrameLike = Frame> {
+    frame: &'a mut F,
     width: u32,
     height: u32,
-    init_flag: bool,
+    text_halo: Option<TextHalo>,
+    /// Axis-aligned lines of one style waiting to be stroked as a single path.
+    lines: Option<LineBatch>,
+    diagnostics: Option<&'a RefCell<Diagnostics>>,
+    groups: Option<&'a GeometryGroups<F>>,
+    /// Where the area drawn into lies on the frame, if it doesn't cover all of it.
+    origin: Option<Vector>,
 }
 
-impl<'a> IcedBackend<'a> {
-    pub fn new(frame: &'a mut Frame) -> Result<Self, IcedError> {
-        let width = frame.width() as u32;
-        let height = frame.height() as u32;
+/// Consecutive axis-aligned lines of the same color and width, such as the lines of a
+/// mesh, collected so a whole grid costs one stroke instead of one per line.
+struct LineBatch {
+    key: ([u8; 4], u32),
+    /// The group selected when the batch was started.
+    group: Option<&'static str>,
+    stroke: Stroke,
+    segments: Vec<(Point, Point)>,
+}
+
+impl IcedBackend<'_> {
+    /// Whether a frame of this size is too small to draw a chart into.
+    pub fn is_degenerate(size: Size) -> bool {
+        !(size.width.is_finite() && size.height.is_finite())
+            || size.width < MIN_FRAME_SIZE
+            || size.height < MIN_FRAME_SIZE
+    }
+}
+
+impl<'a, F: FrameLike> IcedBackend<'a, F> {
+    /// Creates a backend drawing into `frame`.
+    ///
+    /// Fails with [`IcedError::DegenerateFrame`] if the frame is smaller than 2x2 pixels.
+    pub fn new(frame: &'a mut F) -> Result<Self, IcedError> {
+        let size = frame.size();
+        if IcedBackend::is_degenerate(size) {
+            return Err(IcedError::DegenerateFrame {
+                width: size.width,
+                height: size.height,
+            });
+        }
         let ret = Self {
             frame,
-            width,
-            height,
-            init_flag: false,
+            width: size.width as u32,
+            height: size.height as u32,
+            text_halo: None,
+            lines: None,
+            diagnostics: None,
+            groups: None,
+            origin: None,
         };
         Ok(ret)
     }
 
-    fn from_backend_color_to_iced_color(&self, color: &BackendColor) -> iced::Color {
+    /// Creates a backend drawing into `area` of `frame`, e.g. for one of several charts
+    /// sharing a canvas. Its size is that of the area, and its coordinates start at the
+    /// area's top left corner. Like the edges of a frame, those of the area don't clip
+    /// what is drawn across them.
+    ///
+    /// Fails with [`IcedError::DegenerateFrame`] if the area is smaller than 2x2 pixels.
+    pub fn sub_area(frame: &'a mut F, area: Rectangle) -> Result<Self, IcedError> {
+        if IcedBackend::is_degenerate(area.size()) {
+            return Err(IcedError::DegenerateFrame {
+                width: area.width,
+                height: area.height,
+            });
+        }
+        let mut backend = Self::new(frame)?;
+        backend.width = area.width as u32;
+        backend.height = area.height as u32;
+        backend.origin = Some(Vector::new(area.x, area.y));
+        Ok(backend)
+    }
+
+    /// Creates a backend drawing into `frame` at `factor` times its size, for a frame
+    /// scaled down by as much.
+    pub(crate) fn supersampled(frame: &'a mut F, factor: u32) -> Result<Self, IcedError> {
+        let mut backend = Self::new(frame)?;
+        backend.width *= factor;
+        backend.height *= factor;
+        Ok(backend)
+    }
+
+    /// Outlines all text drawn from now on with `halo`, or stops outlining it.
+    pub fn with_text_halo(mut self, halo: Option<TextHalo>) -> Self {
+        self.text_halo = halo;
+        self
+    }
+
+    /// Records the calls from now on that draw nothing or less than asked for into
+    /// `diagnostics`, or stops recording them. Read them after `present()`.
+    pub fn with_diagnostics(mut self, diagnostics: Option<&'a RefCell<Diagnostics>>) -> Self {
+        self.diagnostics = diagnostics;
+        self
+    }
+
+    /// Draws every call from now on into the group of `groups` selected at the time of the
+    /// call instead of into the frame, or stops doing so. The frame then only gives the size.
+    pub fn with_groups(mut self, groups: Option<&'a GeometryGroups<F>>) -> Self {
+        self.groups = groups;
+        self
+    }
+
+    /// The group the next call draws into, if the backend draws into groups.
+    fn group(&self) -> Option<&'static str> {
+        self.groups.map(GeometryGroups::selected)
+    }
+
+    /// Runs `f` on what the next call draws onto.
+    fn paint(&mut self, f: impl FnOnce(&mut F)) {
+        let group = self.group();
+        self.paint_into(group, f);
+    }
+
+    /// Runs `f` on the surface of `group`, unless it is skipped, or on the frame if the
+    /// backend doesn't draw into groups.
+    fn paint_into(&mut self, group: Option<&'static str>, f: impl FnOnce(&mut F)) {
+        let origin = self.origin;
+        let f = |surface: &mut F| match origin {
+            Some(origin) => surface.with_save(|surface| {
+                surface.translate(origin);
+                f(surface);
+            }),
+            None => f(surface),
+        };
+        match (self.groups, group) {
+            (Some(groups), Some(group)) => {
+                groups.draw(group, f);
+            }
+            _ => f(self.frame),
+        }
+    }
+
+    fn diagnose(&self, kind: DiagnosticKind, call: &'static str, example: impl FnOnce() -> String) {
+        if let Some(diagnostics) = self.diagnostics {
+            diagnostics.borrow_mut().record(kind, call, example);
+        }
+    }
+
+    /// Diagnoses a transparent color, and a zero width if the call strokes with `style`.
+    fn diagnose_style<S: BackendStyle>(&self, call: &'static str, style: &S, stroked: bool) {
+        if self.diagnostics.is_none() {
+            return;
+        }
+        let color = style.color();
+        if color.alpha <= 0.0 {
+            self.diagnose(DiagnosticKind::Transparent, call, || {
+                format!(
+                    "rgba{:?}",
+                    (color.rgb.0, color.rgb.1, color.rgb.2, color.alpha)
+                )
+            });
+        }
+        if stroked && style.stroke_width() == 0 {
+            self.diagnose(DiagnosticKind::ZeroWidth, call, || {
+                format!("rgb{:?}", color.rgb)
+            });
+        }
+    }
+
+    fn from_backend_color_to_iced_color(color: &BackendColor) -> iced::Color {
         iced::Color::from_rgba(
             f32::from(color.rgb.0) / 255.0,
             f32::from(color.rgb.1) / 255.0,
@@ -45,22 +244,205 @@ impl<'a> IcedBackend<'a> {
             color.alpha as f32,
         )
     }
-    fn from_backend_point_to_iced_point(&self, point: &(i32, i32)) -> iced::Point {
-        Point::new(point.0 as f32, point.1 as f32)
+    fn checked_point(
+        &self,
+        call: &'static str,
+        point: BackendCoord,
+    ) -> Result<Point, DrawingErrorKind<IcedError>> {
+        clip::validate(point).ok_or_else(|| {
+            self.diagnose(DiagnosticKind::CoordinateOutOfRange, call, || {
+                format!("{:?}", point)
+            });
+            DrawingErrorKind::DrawingError(IcedError::CoordinateOutOfRange(point))
+        })
     }
 
-    fn from_backend_style_to_iced_stroke<S: BackendStyle>(
+    fn checked_points<I: IntoIterator<Item = BackendCoord>>(
         &self,
-        style: &S,
-    ) -> iced::widget::canvas::Stroke {
-        let mut stroke: Stroke = Default::default();
-        stroke.color = self.from_backend_color_to_iced_color(&style.color());
-        stroke.width = style.stroke_width() as f32;
-        stroke
+        call: &'static str,
+        points: I,
+    ) -> Result<Vec<Point>, DrawingErrorKind<IcedError>> {
+        points
+            .into_iter()
+            .map(|point| self.checked_point(call, point))
+            .collect()
+    }
+
+    /// Draws markers of one shape given by their center, color and size as a single mesh:
+    /// the corners of every marker go straight into the frame's vertex buffer along with
+    /// their color, so a huge scatter plot costs no path building or tessellation. Custom
+    /// shapes, which need not be convex, are filled as one path per distinct color.
+    pub(crate) fn draw_markers<I>(&mut self, shape: MarkerShape, markers: I)
+    where
+        I: IntoIterator<Item = (BackendCoord, iced::Color, f32)>,
+    {
+        trace::span!("draw_markers", ?shape);
+        self.flush_lines();
+        let (width, height) = (self.width as f32, self.height as f32);
+        let (mut vertices, mut indices) = (vec![], vec![]);
+        let mut buckets: HashMap<[u8; 4], Vec<(Point, f32)>> = HashMap::new();
+        for (center, color, size) in markers {
+            let center = match clip::validate(center) {
+                Some(center) => center,
+                None => continue,
+            };
+            if center.x + size < 0.0
+                || center.y + size < 0.0
+                || center.x - size > width
+                || center.y - size > height
+            {
+                continue;
+            }
+            let at = (center.x, center.y);
+            if !shape.triangulate(at, size, color, &mut vertices, &mut indices) {
+                let key = [color.r, color.g, color.b, color.a].map(|c| (c * 255.0).round() as u8);
+                buckets.entry(key).or_default().push((center, size));
+            }
+        }
+        if !indices.is_empty() {
+            self.paint(|frame| frame.fill_triangles(&vertices, &indices));
+        }
+        for ([r, g, b, a], markers) in buckets {
+            let color = iced::Color::from_rgba8(r, g, b, f32::from(a) / 255.0);
+            let path = Path::new(|builder| {
+                for &(center, size) in &markers {
+                    let outline = shape
+                        .outline((center.x, center.y), size)
+                        .unwrap_or_default();
+                    for (index, &(x, y)) in outline.iter().enumerate() {
+                        if index == 0 {
+                            builder.move_to(Point::new(x, y));
+                        } else {
+                            builder.line_to(Point::new(x, y));
+                        }
+                    }
+                    builder.close();
+                }
+            });
+            self.paint(|frame| frame.fill(&path, color));
+        }
+    }
+
+    /// Fills a polygon with a gradient, approximated by bands of solid color: horizontal
+    /// strips for a vertical gradient and rings of convex sectors for a radial one. The
+    /// bands do not overlap, so translucent gradients blend correctly with what lies below.
+    pub(crate) fn fill_gradient<I: IntoIterator<Item = BackendCoord>>(
+        &mut self,
+        vert: I,
+        gradient: &Gradient,
+    ) -> Result<(), DrawingErrorKind<IcedError>> {
+        self.flush_lines();
+        let vert = self
+            .envelope()
+            .clip_polygon(&self.checked_points("fill_gradient", vert)?);
+        if is_flat(&vert) {
+            return Ok(());
+        }
+        let (mut min, mut max) = (vert[0], vert[0]);
+        for point in &vert {
+            min = Point::new(min.x.min(point.x), min.y.min(point.y));
+            max = Point::new(max.x.max(point.x), max.y.max(point.y));
+        }
+        let bands =
+            |length: f32| ((length / GRADIENT_BAND).ceil() as usize).clamp(1, MAX_GRADIENT_BANDS);
+
+        match gradient {
+            Gradient::Vertical { .. } => {
+                let count = bands(max.y - min.y);
+                let height = (max.y - min.y) / count as f32;
+                for band in 0..count {
+                    let strip = Envelope::of(iced::Rectangle {
+                        x: min.x,
+                        y: min.y + band as f32 * height,
+                        width: max.x - min.x,
+                        height,
+                    });
+                    let t = (band as f32 + 0.5) / count as f32;
+                    self.fill_band(&strip.clip_polygon(&vert), gradient.color_at(t));
+                }
+            }
+            Gradient::Radial { .. } => {
+                let center = Point::new((min.x + max.x) / 2.0, (min.y + max.y) / 2.0);
+                let radius = center.distance(max);
+                let count = bands(radius);
+                let step = std::f32::consts::PI * 2.0 / GRADIENT_SECTORS as f32;
+                // Sector corners lie on circles of a slightly larger radius, so the
+                // straight outer edges of the last ring still reach the corners.
+                let scale = 1.0 / (step / 2.0).cos();
+                let at = |r: f32, angle: f32| {
+                    Point::new(
+                        center.x + r * scale * angle.cos(),
+                        center.y + r * scale * angle.sin(),
+                    )
+                };
+                for ring in 0..count {
+                    let inner = radius * ring as f32 / count as f32;
+                    let outer = radius * (ring + 1) as f32 / count as f32;
+                    let color = gradient.color_at((ring as f32 + 0.5) / count as f32);
+                    for sector in 0..GRADIENT_SECTORS {
+                        let (from, to) = (sector as f32 * step, (sector + 1) as f32 * step);
+                        let quad = [
+                            at(inner, from),
+                            at(outer, from),
+                            at(outer, to),
+                            at(inner, to),
+                        ];
+                        self.fill_band(&clip::clip_convex(&vert, &quad), color);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn fill_band(&mut self, band: &[Point], color: iced::Color) {
+        if is_flat(band) {
+            return;
+        }
+        let path = Path::new(|builder| {
+            builder.move_to(band[0]);
+            for point in &band[1..] {
+                builder.line_to(*point);
+            }
+            builder.close();
+        });
+        self.paint(|frame| frame.fill(&path, color));
+    }
+
+    /// Strokes the pending batch of lines. Every other drawing operation calls this first,
+    /// so batching never changes what is drawn on top of what.
+    fn flush_lines(&mut self) {
+        if let Some(batch) = self.lines.take() {
+            let path = Path::new(|builder| {
+                for &(from, to) in &batch.segments {
+                    builder.move_to(from);
+                    builder.line_to(to);
+                }
+            });
+            self.paint_into(batch.group, |frame| frame.stroke(&path, batch.stroke));
+        }
+    }
+
+    fn envelope(&self) -> Envelope {
+        Envelope::around(self.width, self.height)
+    }
+
+    fn from_backend_style_to_iced_stroke<S: BackendStyle>(style: &S) -> Stroke {
+        Stroke {
+            color: Self::from_backend_color_to_iced_color(&style.color()),
+            width: style.stroke_width() as f32,
+            ..Stroke::default()
+        }
+    }
+}
+
+impl<F: FrameLike> Drop for IcedBackend<'_, F> {
+    fn drop(&mut self) {
+        self.flush_lines();
     }
 }
 
-impl<'a> DrawingBackend for IcedBackend<'a> {
+impl<'a, F: FrameLike> DrawingBackend for IcedBackend<'a, F> {
     type ErrorType = IcedError;
 
     fn get_size(&self) -> (u32, u32) {
@@ -72,6 +454,7 @@ impl<'a> DrawingBackend for IcedBackend<'a> {
     }
 
     fn present(&mut self) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        self.flush_lines();
         Ok(())
     }
 
@@ -80,11 +463,21 @@ impl<'a> DrawingBackend for IcedBackend<'a> {
         point: (i32, i32),
         color: BackendColor,
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        self.frame.fill_rectangle(
-            self.from_backend_point_to_iced_point(&point),
-            Size::new(1.0, 1.0),
-            self.from_backend_color_to_iced_color(&color),
-        );
+        self.flush_lines();
+        let point = self.checked_point("draw_pixel", point)?;
+        self.diagnose_style("draw_pixel", &color, false);
+        if point.x < 0.0
+            || point.y < 0.0
+            || point.x >= self.width as f32
+            || point.y >= self.height as f32
+        {
+            self.diagnose(DiagnosticKind::OutsideFrame, "draw_pixel", || {
+                format!("{:?}", point)
+            });
+            return Ok(());
+        }
+        let color = Self::from_backend_color_to_iced_color(&color);
+        self.paint(|frame| frame.fill_rectangle(point, Size::new(1.0, 1.0), color));
         Ok(())
     }
 
@@ -94,13 +487,130 @@ impl<'a> DrawingBackend for IcedBackend<'a> {
         to: (i32, i32),
         style: &S,
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        self.frame.stroke(
-            &Path::line(
-                self.from_backend_point_to_iced_point(&from),
-                self.from_backend_point_to_iced_point(&to),
-            ),
-            self.from_backend_style_to_iced_stroke(style),
+        let from = self.checked_point("draw_line", from)?;
+        let to = self.checked_point("draw_line", to)?;
+        if from == to {
+            return Ok(());
+        }
+        self.diagnose_style("draw_line", style, true);
+        let (from, to) = match self.envelope().clip_segment(from, to) {
+            Some(segment) => segment,
+            None => {
+                self.diagnose(DiagnosticKind::OutsideFrame, "draw_line", || {
+                    format!("{:?} to {:?}", from, to)
+                });
+                return Ok(());
+            }
+        };
+        if from.x != to.x && from.y != to.y {
+            self.flush_lines();
+            let stroke = Self::from_backend_style_to_iced_stroke(style);
+            self.paint(|frame| frame.stroke(&Path::line(from, to), stroke));
+            return Ok(());
+        }
+
+        let color = style.color();
+        let (r, g, b) = color.rgb;
+        let key = (
+            [r, g, b, (color.alpha * 255.0).round() as u8],
+            style.stroke_width(),
         );
+        let group = self.group();
+        if self.lines.as_ref().map(|batch| (batch.key, batch.group)) != Some((key, group)) {
+            self.flush_lines();
+            self.lines = Some(LineBatch {
+                key,
+                group,
+                stroke: Self::from_backend_style_to_iced_stroke(style),
+                segments: vec![],
+            });
+        }
+        if let Some(batch) = &mut self.lines {
+            batch.segments.push((from, to));
+        }
+        Ok(())
+    }
+
+    /// Rasterizes text like the default implementation, but collects the glyph pixels and
+    /// fills them in one path per color, after the halo around them if one is set.
+    fn draw_text<TStyle: BackendTextStyle>(
+        &mut self,
+        text: &str,
+        style: &TStyle,
+        pos: BackendCoord,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        self.flush_lines();
+        if style.color().alpha == 0.0 {
+            self.diagnose(DiagnosticKind::Transparent, "draw_text", || {
+                format!("{:?}", text)
+            });
+            return Ok(());
+        }
+        let font_error = |e| {
+            self.diagnose(DiagnosticKind::Font, "draw_text", || {
+                format!("{:?} in {}: {:?}", text, style.family().as_str(), e)
+            });
+            DrawingErrorKind::FontError(Box::new(e))
+        };
+        let ((min_x, min_y), (max_x, max_y)) = style.layout_box(text).map_err(font_error)?;
+        let (width, height) = (max_x - min_x, max_y - min_y);
+        let dx = match style.anchor().h_pos {
+            HPos::Left => 0,
+            HPos::Right => -width,
+            HPos::Center => -width / 2,
+        };
+        let dy = match style.anchor().v_pos {
+            VPos::Top => 0,
+            VPos::Center => -height / 2,
+            VPos::Bottom => -height,
+        };
+        let transform = style.transform();
+        let (w, h) = (self.width as i32, self.height as i32);
+        let mut pixels: HashMap<[u8; 4], Vec<Point>> = HashMap::new();
+        style
+            .draw(text, (0, 0), |x, y, color| {
+                let (x, y) = transform.transform(x + dx - min_x, y + dy - min_y);
+                let (x, y) = (pos.0 + x, pos.1 + y);
+                if x >= 0 && x < w && y >= 0 && y < h && color.alpha > 0.0 {
+                    let (r, g, b) = color.rgb;
+                    let a = (color.alpha * 255.0).round() as u8;
+                    pixels
+                        .entry([r, g, b, a])
+                        .or_default()
+                        .push(Point::new(x as f32, y as f32));
+                }
+                Ok::<(), IcedError>(())
+            })
+            .map_err(font_error)?
+            .map_err(DrawingErrorKind::DrawingError)?;
+        if pixels.is_empty() && !text.trim().is_empty() {
+            self.diagnose(DiagnosticKind::OutsideFrame, "draw_text", || {
+                format!("{:?} at {:?}", text, pos)
+            });
+        }
+
+        if let Some(halo) = &self.text_halo {
+            let reach = halo.width as f32;
+            let path = Path::new(|builder| {
+                for point in pixels.values().flatten() {
+                    builder.rectangle(
+                        Point::new(point.x - reach, point.y - reach),
+                        Size::new(1.0 + 2.0 * reach, 1.0 + 2.0 * reach),
+                    );
+                }
+            });
+            let color = Self::from_backend_color_to_iced_color(&halo.color.color());
+            self.paint(|frame| frame.fill(&path, color));
+        }
+        for ([r, g, b, a], points) in pixels {
+            let path = Path::new(|builder| {
+                for point in &points {
+                    builder.rectangle(*point, Size::new(1.0, 1.0));
+                }
+            });
+            let color = iced::Color::from_rgba8(r, g, b, f32::from(a) / 255.0);
+            self.paint(|frame| frame.fill(&path, color));
+        }
         Ok(())
     }
 
@@ -111,30 +621,56 @@ impl<'a> DrawingBackend for IcedBackend<'a> {
         style: &S,
         fill: bool,
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        self.flush_lines();
+        let (upper_left, bottom_right) = normalize_rect(
+            self.checked_point("draw_rect", upper_left)?,
+            self.checked_point("draw_rect", bottom_right)?,
+        );
+        self.diagnose_style("draw_rect", style, !fill);
+        if bottom_right.x < 0.0
+            || bottom_right.y < 0.0
+            || upper_left.x > self.width as f32
+            || upper_left.y > self.height as f32
+        {
+            self.diagnose(DiagnosticKind::OutsideFrame, "draw_rect", || {
+                format!("{:?} to {:?}", upper_left, bottom_right)
+            });
+        }
+        // Clamping an axis-aligned rectangle to the envelope only moves edges that are
+        // off-screen anyway, so the visible part stays exact.
+        let envelope = self.envelope();
+        let (upper_left, bottom_right) = (envelope.clamp(upper_left), envelope.clamp(bottom_right));
+        let (width, height) = (bottom_right.x - upper_left.x, bottom_right.y - upper_left.y);
+        if width == 0.0 && height == 0.0 {
+            return Ok(());
+        }
+        if width == 0.0 || height == 0.0 {
+            // A flat rectangle has nothing to fill, and its outline is just a line; stroking
+            // it as a closed path would produce miter spikes at the folded corners.
+            if !fill {
+                let stroke = Self::from_backend_style_to_iced_stroke(style);
+                self.paint(|frame| frame.stroke(&Path::line(upper_left, bottom_right), stroke));
+            }
+            return Ok(());
+        }
         if fill {
-            let size = Size::new(
-                (bottom_right.0 - upper_left.0) as f32,
-                (bottom_right.1 - upper_left.1) as f32,
-            );
-            self.frame.fill_rectangle(
-                self.from_backend_point_to_iced_point(&upper_left),
-                size,
-                self.from_backend_color_to_iced_color(&style.color()),
-            );
+            // Skipping invisible fills keeps a transparent `root.fill` from costing a
+            // full-frame rectangle.
+            if style.color().alpha <= 0.0 {
+                return Ok(());
+            }
+            let color = Self::from_backend_color_to_iced_color(&style.color());
+            self.paint(|frame| frame.fill_rectangle(upper_left, Size::new(width, height), color));
         } else {
             let path = Path::new(|builder| {
-                builder.move_to(self.from_backend_point_to_iced_point(&upper_left));
-                builder.line_to(
-                    self.from_backend_point_to_iced_point(&(bottom_right.0, upper_left.1)),
-                );
-                builder.line_to(self.from_backend_point_to_iced_point(&bottom_right));
-                builder.line_to(
-                    self.from_backend_point_to_iced_point(&(upper_left.0, bottom_right.1)),
-                );
-                builder.line_to(self.from_backend_point_to_iced_point(&upper_left));
+                builder.move_to(upper_left);
+                builder.line_to(Point::new(bottom_right.x, upper_left.y));
+                builder.line_to(bottom_right);
+                builder.line_to(Point::new(upper_left.x, bottom_right.y));
+                builder.line_to(upper_left);
             });
-            self.frame
-                .stroke(&path, self.from_backend_style_to_iced_stroke(style));
+            let stroke = Self::from_backend_style_to_iced_stroke(style);
+            self.paint(|frame| frame.stroke(&path, stroke));
         }
 
         Ok(())
@@ -145,18 +681,40 @@ impl<'a> DrawingBackend for IcedBackend<'a> {
         path: I,
         style: &S,
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        self.flush_lines();
+        let points = self.checked_points("draw_path", path)?;
+        let envelope = self.envelope();
+        let mut drawable = points
+            .windows(2)
+            .filter(|segment| segment[0] != segment[1])
+            .peekable();
+        if drawable.peek().is_some() {
+            self.diagnose_style("draw_path", style, true);
+        }
+        let segments: Vec<_> = drawable
+            .clone()
+            .filter_map(|segment| envelope.clip_segment(segment[0], segment[1]))
+            .collect();
+        if segments.is_empty() {
+            if drawable.next().is_some() {
+                self.diagnose(DiagnosticKind::OutsideFrame, "draw_path", || {
+                    format!("{} points from {:?}", points.len(), points[0])
+                });
+            }
+            return Ok(());
+        }
         let drawing_path = Path::new(|builder| {
-            let iterator = path.into_iter();
-            for (index, point) in iterator.enumerate() {
-                if index == 0 {
-                    builder.move_to(self.from_backend_point_to_iced_point(&point));
-                } else {
-                    builder.line_to(self.from_backend_point_to_iced_point(&point));
+            let mut last: Option<Point> = None;
+            for &(from, to) in &segments {
+                if last != Some(from) {
+                    builder.move_to(from);
                 }
+                builder.line_to(to);
+                last = Some(to);
             }
         });
-        self.frame
-            .stroke(&drawing_path, self.from_backend_style_to_iced_stroke(style));
+        let stroke = Self::from_backend_style_to_iced_stroke(style);
+        self.paint(|frame| frame.stroke(&drawing_path, stroke));
         Ok(())
     }
 
@@ -167,22 +725,33 @@ impl<'a> DrawingBackend for IcedBackend<'a> {
         style: &S,
         fill: bool,
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        self.flush_lines();
+        if radius > clip::MAX_COORDINATE as u32 {
+            self.diagnose(DiagnosticKind::CoordinateOutOfRange, "draw_circle", || {
+                format!("radius {}", radius)
+            });
+            return Err(DrawingErrorKind::DrawingError(
+                IcedError::CoordinateOutOfRange((radius.min(i32::MAX as u32) as i32, 0)),
+            ));
+        }
+        let center = self.checked_point("draw_circle", center)?;
+        if radius == 0 {
+            return Ok(());
+        }
+        self.diagnose_style("draw_circle", style, !fill);
+        if !self.envelope().touches_circle(center, radius as f32) {
+            self.diagnose(DiagnosticKind::OutsideFrame, "draw_circle", || {
+                format!("{:?} with radius {}", center, radius)
+            });
+            return Ok(());
+        }
+        let circle = Path::circle(center, radius as f32);
         if fill {
-            self.frame.fill(
-                &Path::circle(
-                    self.from_backend_point_to_iced_point(&center),
-                    radius as f32,
-                ),
-                self.from_backend_color_to_iced_color(&style.color()),
-            );
+            let color = Self::from_backend_color_to_iced_color(&style.color());
+            self.paint(|frame| frame.fill(&circle, color));
         } else {
-            self.frame.stroke(
-                &Path::circle(
-                    self.from_backend_point_to_iced_point(&center),
-                    radius as f32,
-                ),
-                self.from_backend_style_to_iced_stroke(style),
-            );
+            let stroke = Self::from_backend_style_to_iced_stroke(style);
+            self.paint(|frame| frame.stroke(&circle, stroke));
         }
 
         Ok(())
@@ -193,18 +762,314 @@ impl<'a> DrawingBackend for IcedBackend<'a> {
         vert: I,
         style: &S,
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        self.flush_lines();
+        let points = self.checked_points("fill_polygon", vert)?;
+        if is_flat(&points) {
+            return Ok(());
+        }
+        self.diagnose_style("fill_polygon", style, false);
+        let vert = self.envelope().clip_polygon(&points);
+        if is_flat(&vert) {
+            self.diagnose(DiagnosticKind::OutsideFrame, "fill_polygon", || {
+                format!("{} vertices from {:?}", points.len(), points[0])
+            });
+            return Ok(());
+        }
         let path = Path::new(|builder| {
-            let iterator = vert.into_iter();
-            for (index, point) in iterator.enumerate() {
+            for (index, point) in vert.iter().enumerate() {
                 if index == 0 {
-                    builder.move_to(self.from_backend_point_to_iced_point(&point));
+                    builder.move_to(*point);
                 } else {
-                    builder.line_to(self.from_backend_point_to_iced_point(&point));
+                    builder.line_to(*point);
                 }
             }
         });
-        self.frame
-            .fill(&path, self.from_backend_color_to_iced_color(&style.color()));
+        let color = Self::from_backend_color_to_iced_color(&style.color());
+        self.paint(|frame| frame.fill(&path, color));
         Ok(())
     }
 }
+
+/// Orders the corners of a rectangle so that the first one is the upper left.
+fn normalize_rect(a: Point, b: Point) -> (Point, Point) {
+    (
+        Point::new(a.x.min(b.x), a.y.min(b.y)),
+        Point::new(a.x.max(b.x), a.y.max(b.y)),
+    )
+}
+
+/// Whether a polygon encloses no area, i.e. it has fewer than three vertices or all of
+/// them lie on a single line.
+fn is_flat(vert: &[Point]) -> bool {
+    let origin = match vert.first() {
+        Some(origin) => *origin,
+        None => return true,
+    };
+    let direction = match vert.iter().find(|point| **point != origin) {
+        Some(point) => *point - origin,
+        None => return true,
+    };
+    vert.iter().all(|point| {
+        let offset = *point - origin;
+        (direction.x * offset.y - direction.y * offset.x).abs() <= f32::EPSILON
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLACK: BackendColor = BackendColor {
+        alpha: 1.0,
+        rgb: (0, 0, 0),
+    };
+
+    fn with_backend(f: impl FnOnce(&mut IcedBackend)) {
+        let mut frame = Frame::new(Size::new(100.0, 100.0));
+        let mut backend = IcedBackend::new(&mut frame).unwrap();
+        f(&mut backend);
+    }
+
+    /// A 100x100 surface counting what is drawn onto it.
+    #[derive(Default)]
+    struct CountingFrame {
+        fills: usize,
+        strokes: usize,
+        /// The triangles of every mesh drawn.
+        meshes: Vec<usize>,
+    }
+
+    impl FrameLike for CountingFrame {
+        fn size(&self) -> Size {
+            Size::new(100.0, 100.0)
+        }
+
+        fn fill(&mut self, _: &Path, _: iced::Color) {
+            self.fills += 1;
+        }
+
+        fn fill_rectangle(&mut self, _: Point, _: Size, _: iced::Color) {
+            self.fills += 1;
+        }
+
+        fn stroke(&mut self, _: &Path, _: Stroke) {
+            self.strokes += 1;
+        }
+
+        fn fill_text(&mut self, _: iced::canvas::Text) {}
+
+        fn fill_triangles(&mut self, vertices: &[(Point, iced::Color)], indices: &[u32]) {
+            assert!(indices
+                .iter()
+                .all(|&index| (index as usize) < vertices.len()));
+            self.meshes.push(indices.len() / 3);
+        }
+
+        fn with_save(&mut self, f: impl FnOnce(&mut Self)) {
+            f(self);
+        }
+
+        fn translate(&mut self, _: iced::Vector) {}
+
+        fn rotate(&mut self, _: f32) {}
+    }
+
+    #[test]
+    fn rejects_degenerate_frames() {
+        for &(width, height) in &[(0.0, 0.0), (1.0, 100.0), (100.0, 1.0), (f32::NAN, 10.0)] {
+            let mut frame = Frame::new(Size::new(width, height));
+            assert!(IcedBackend::new(&mut frame).is_err());
+        }
+    }
+
+    #[test]
+    fn empty_and_single_point_paths() {
+        with_backend(|backend| {
+            assert!(backend.draw_path(vec![], &BLACK).is_ok());
+            assert!(backend.draw_path(vec![(10, 10)], &BLACK).is_ok());
+            assert!(backend.draw_path(vec![(10, 10), (10, 10)], &BLACK).is_ok());
+        });
+    }
+
+    #[test]
+    fn zero_radius_circles() {
+        with_backend(|backend| {
+            assert!(backend.draw_circle((50, 50), 0, &BLACK, true).is_ok());
+            assert!(backend.draw_circle((50, 50), 0, &BLACK, false).is_ok());
+        });
+    }
+
+    #[test]
+    fn zero_area_rectangles() {
+        with_backend(|backend| {
+            assert!(backend.draw_rect((10, 10), (10, 10), &BLACK, true).is_ok());
+            assert!(backend.draw_rect((10, 10), (10, 10), &BLACK, false).is_ok());
+            assert!(backend.draw_rect((10, 10), (50, 10), &BLACK, true).is_ok());
+            assert!(backend.draw_rect((10, 10), (50, 10), &BLACK, false).is_ok());
+            assert!(backend.draw_rect((50, 50), (10, 10), &BLACK, true).is_ok());
+        });
+    }
+
+    #[test]
+    fn degenerate_polygons() {
+        with_backend(|backend| {
+            assert!(backend.fill_polygon(vec![], &BLACK).is_ok());
+            assert!(backend
+                .fill_polygon(vec![(10, 10), (20, 20)], &BLACK)
+                .is_ok());
+            assert!(backend
+                .fill_polygon(vec![(10, 10), (20, 20), (30, 30)], &BLACK)
+                .is_ok());
+        });
+    }
+
+    #[test]
+    fn zero_length_lines() {
+        with_backend(|backend| {
+            assert!(backend.draw_line((10, 10), (10, 10), &BLACK).is_ok());
+        });
+    }
+
+    #[test]
+    fn axis_aligned_lines_are_batched() {
+        let mut frame = CountingFrame::default();
+        {
+            let mut backend = IcedBackend::new(&mut frame).unwrap();
+            for y in 0..10 {
+                backend
+                    .draw_line((0, y * 10), (100, y * 10), &BLACK)
+                    .unwrap();
+                backend
+                    .draw_line((y * 10, 0), (y * 10, 100), &BLACK)
+                    .unwrap();
+            }
+        }
+        assert_eq!(frame.strokes, 1);
+    }
+
+    #[test]
+    fn draws_into_selected_groups() {
+        let mut frame = CountingFrame::default();
+        let groups = GeometryGroups::with_surfaces(Size::new(100.0, 100.0), "mesh", |_| {
+            CountingFrame::default()
+        })
+        .skip("cached");
+        {
+            let mut backend = IcedBackend::new(&mut frame)
+                .unwrap()
+                .with_groups(Some(&groups));
+            backend.draw_line((0, 10), (100, 10), &BLACK).unwrap();
+            groups.select("series");
+            // The pending mesh line must not be batched with this one.
+            backend.draw_line((0, 20), (100, 20), &BLACK).unwrap();
+            backend.draw_circle((50, 50), 10, &BLACK, true).unwrap();
+            groups.select("cached");
+            backend.draw_circle((50, 50), 10, &BLACK, true).unwrap();
+        }
+        let surfaces = groups.into_surfaces();
+        let counts: Vec<_> = surfaces
+            .iter()
+            .map(|(group, surface)| (*group, surface.fills, surface.strokes))
+            .collect();
+        assert_eq!(counts, vec![("mesh", 0, 1), ("series", 1, 1)]);
+        assert_eq!((frame.fills, frame.strokes), (0, 0));
+    }
+
+    #[test]
+    fn sub_areas_are_drawn_into_like_frames() {
+        let mut frame = CountingFrame::default();
+        let area = Rectangle::new(Point::new(50.0, 50.0), Size::new(40.0, 40.0));
+        {
+            let mut backend = IcedBackend::sub_area(&mut frame, area).unwrap();
+            assert_eq!(backend.get_size(), (40, 40));
+            backend.draw_line((200, 0), (200, 100), &BLACK).unwrap();
+            backend.draw_circle((20, 20), 5, &BLACK, true).unwrap();
+        }
+        assert_eq!((frame.fills, frame.strokes), (1, 0));
+        let collapsed = Rectangle::new(Point::new(0.0, 0.0), Size::new(1.0, 40.0));
+        assert!(IcedBackend::sub_area(&mut frame, collapsed).is_err());
+    }
+
+    #[test]
+    fn markers_are_drawn_as_one_mesh() {
+        let draw = |shape, markers: &[(BackendCoord, f32)]| {
+            let mut frame = CountingFrame::default();
+            IcedBackend::new(&mut frame).unwrap().draw_markers(
+                shape,
+                markers
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &(at, size))| (at, iced::Color::from_rgb(i as f32, 0.0, 0.0), size)),
+            );
+            (frame.meshes, frame.fills)
+        };
+        let markers = [((10, 10), 3.0), ((20, 20), 3.0), ((500, 500), 3.0)];
+        // Two triangles per square and per bar of a cross; the marker off the frame is culled.
+        assert_eq!(draw(MarkerShape::Square, &markers), (vec![4], 0));
+        assert_eq!(draw(MarkerShape::Cross, &markers), (vec![8], 0));
+        assert_eq!(draw(MarkerShape::Triangle, &markers), (vec![2], 0));
+        // A small circle has eight sides, so six triangles.
+        assert_eq!(draw(MarkerShape::Circle, &markers[..1]), (vec![6], 0));
+        // Custom outlines are filled as paths, one per color.
+        const ARROW: &[(f32, f32)] = &[(0.0, -1.0), (1.0, 1.0), (0.0, 0.5), (-1.0, 1.0)];
+        assert_eq!(draw(MarkerShape::Custom(ARROW), &markers), (vec![], 2));
+        assert_eq!(draw(MarkerShape::Square, &[]), (vec![], 0));
+    }
+
+    #[test]
+    fn degenerate_shapes_draw_nothing() {
+        let mut frame = CountingFrame::default();
+        {
+            let mut backend = IcedBackend::new(&mut frame).unwrap();
+            backend.draw_line((10, 10), (10, 10), &BLACK).unwrap();
+            backend.draw_circle((50, 50), 0, &BLACK, true).unwrap();
+            backend.draw_rect((10, 10), (10, 10), &BLACK, true).unwrap();
+            backend
+                .fill_polygon(vec![(10, 10), (20, 20), (30, 30)], &BLACK)
+                .unwrap();
+        }
+        assert_eq!((frame.fills, frame.strokes), (0, 0));
+    }
+
+    #[test]
+    fn diagnoses_invisible_calls() {
+        let diagnostics = RefCell::new(Diagnostics::new());
+        let mut frame = CountingFrame::default();
+        {
+            let mut backend = IcedBackend::new(&mut frame)
+                .unwrap()
+                .with_diagnostics(Some(&diagnostics));
+            let clear = BackendColor {
+                alpha: 0.0,
+                ..BLACK
+            };
+            backend.draw_line((10, 10), (20, 20), &clear).unwrap();
+            backend.draw_circle((500, 500), 5, &BLACK, true).unwrap();
+            backend.draw_circle((600, 600), 5, &BLACK, true).unwrap();
+            backend.draw_rect((10, 10), (20, 20), &BLACK, true).unwrap();
+            assert!(backend.draw_line((0, 0), (i32::MAX, 0), &BLACK).is_err());
+        }
+        let diagnostics = diagnostics.into_inner();
+        assert_eq!(diagnostics.count(DiagnosticKind::Transparent), 1);
+        assert_eq!(diagnostics.count(DiagnosticKind::OutsideFrame), 2);
+        assert_eq!(diagnostics.count(DiagnosticKind::CoordinateOutOfRange), 1);
+        assert_eq!(diagnostics.entries().len(), 3);
+    }
+
+    #[test]
+    fn flatness() {
+        let p = |x, y| Point::new(x, y);
+        assert!(is_flat(&[]));
+        assert!(is_flat(&[p(1.0, 1.0), p(1.0, 1.0), p(1.0, 1.0)]));
+        assert!(is_flat(&[p(0.0, 0.0), p(1.0, 1.0), p(2.0, 2.0)]));
+        assert!(!is_flat(&[p(0.0, 0.0), p(1.0, 0.0), p(0.0, 1.0)]));
+    }
+
+    #[test]
+    fn rect_corners_are_normalized() {
+        let (upper_left, bottom_right) =
+            normalize_rect(Point::new(50.0, 10.0), Point::new(10.0, 50.0));
+        assert_eq!(upper_left, Point::new(10.0, 10.0));
+        assert_eq!(bottom_right, Point::new(50.0, 50.0));
+    }
+}
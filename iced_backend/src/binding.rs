@@ -0,0 +1,59 @@
+use crate::AxisRange;
+use std::ops::{Range, RangeInclusive};
+
+/// Maps the chart's visible x range to and from external controls, such as a `Slider`
+/// scrolling through the data or two `TextInput`s holding the range's ends.
+///
+/// Bind it both ways through messages: feed the slider's or text inputs' messages to
+/// [`ChartWidget::set_x_range`] using the range built here, and update the controls from
+/// [`ChartWidget::on_x_range_change`], which reports every pan or zoom by the user.
+///
+/// [`ChartWidget::set_x_range`]: crate::ChartWidget::set_x_range
+/// [`ChartWidget::on_x_range_change`]: crate::ChartWidget::on_x_range_change
+#[derive(Debug, Clone, PartialEq)]
+pub struct XRangeBinding {
+    extent: Range<f64>,
+}
+
+impl XRangeBinding {
+    /// A binding for data spanning `extent` along x, which is what a slider scrolls over.
+    pub fn new(extent: Range<f64>) -> Self {
+        Self { extent }
+    }
+
+    /// The values a slider can take when the visible window is `x`: the window's start,
+    /// from the start of the extent to where the window ends at the end of the extent.
+    pub fn slider_range(&self, x: &AxisRange) -> RangeInclusive<f64> {
+        let last = (self.extent.end - x.span()).max(self.extent.start);
+        self.extent.start..=last
+    }
+
+    /// The slider value showing the window `x`: its start, kept within
+    /// [`XRangeBinding::slider_range`].
+    pub fn slider_value(&self, x: &AxisRange) -> f64 {
+        let range = self.slider_range(x);
+        x.start.max(*range.start()).min(*range.end())
+    }
+
+    /// The window starting at the slider value `value` with the span of `current`.
+    pub fn from_slider(&self, value: f64, current: &AxisRange) -> Range<f64> {
+        value..value + current.span()
+    }
+
+    /// The texts of a pair of inputs showing the ends of `x`.
+    pub fn text_values(&self, x: &AxisRange) -> (String, String) {
+        (format!("{}", x.start), format!("{}", x.end))
+    }
+
+    /// Parses the texts of a pair of inputs into a window, or returns `None` unless both
+    /// are numbers and the start lies before the end.
+    pub fn parse(&self, start: &str, end: &str) -> Option<Range<f64>> {
+        let start: f64 = start.trim().parse().ok()?;
+        let end: f64 = end.trim().parse().ok()?;
+        if start < end && start.is_finite() && end.is_finite() {
+            Some(start..end)
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use crate::{BuildContext, Colormap, IcedBackend, IcedError, MarkerShape, Scatter, SeriesId};
+use plotters::chart::ChartContext;
+use plotters::coord::types::RangedCoordf64;
+use plotters::drawing::DrawingAreaErrorKind;
+use plotters::element::Circle;
+use plotters::prelude::Cartesian2d;
+use plotters::style::{Color, RGBColor};
+use std::ops::Range;
+
+type BubbleResult = Result<(), DrawingAreaErrorKind<IcedError>>;
+type Coords = Cartesian2d<RangedCoordf64, RangedCoordf64>;
+
+/// One point of a [`BubbleSeries`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bubble {
+    pub x: f64,
+    pub y: f64,
+    /// The value shown by the bubble's area.
+    pub size: f64,
+    /// The value shown by the bubble's color.
+    pub value: f64,
+}
+
+/// A scatter plot whose points show two more values: one through their area and one
+/// through their color on a [`Colormap`].
+///
+/// The bubbles are drawn as one batched [`Scatter`] and registered as points of a series
+/// (see [`BubbleSeries::draw`]), so [`ChartWidget::on_hover_element`] reports the index of
+/// the hovered bubble, which [`BubbleSeries::get`] turns back into its record.
+///
+/// [`ChartWidget::on_hover_element`]: crate::ChartWidget::on_hover_element
+#[derive(Debug, Clone)]
+pub struct BubbleSeries {
+    bubbles: Vec<Bubble>,
+    name: Option<String>,
+    radius: Range<f32>,
+    colormap: Colormap,
+    opacity: f32,
+}
+
+impl BubbleSeries {
+    pub fn new(bubbles: impl Into<Vec<Bubble>>) -> Self {
+        Self {
+            bubbles: bubbles.into(),
+            name: None,
+            radius: 3.0..20.0,
+            colormap: Colormap::VIRIDIS,
+            opacity: 0.7,
+        }
+    }
+
+    /// Names the series in the series legend.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the radii in pixels of the smallest and the largest bubble.
+    pub fn radius(mut self, radius: Range<f32>) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    pub fn colormap(mut self, colormap: Colormap) -> Self {
+        self.colormap = colormap;
+        self
+    }
+
+    /// Sets the opacity of the bubbles, so overlapping ones stay visible.
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity;
+        self
+    }
+
+    /// The record of bubble `index`, e.g. as reported by a hover.
+    pub fn get(&self, index: usize) -> Option<&Bubble> {
+        self.bubbles.get(index)
+    }
+
+    pub fn bubbles(&self) -> &[Bubble] {
+        &self.bubbles
+    }
+
+    /// The smallest and largest size value, spanning the radius range.
+    pub fn size_range(&self) -> Range<f64> {
+        extent(self.bubbles.iter().map(|bubble| bubble.size))
+    }
+
+    /// The smallest and largest color value, spanning the colormap, e.g. for labelling a
+    /// color scale.
+    pub fn value_range(&self) -> Range<f64> {
+        extent(self.bubbles.iter().map(|bubble| bubble.value))
+    }
+
+    /// The radius of a bubble of size `size`. The area grows linearly with the size, so
+    /// the radius grows with its square root.
+    pub fn radius_of(&self, size: f64) -> f32 {
+        self.radius_within(size, self.size_range())
+    }
+
+    fn radius_within(&self, size: f64, range: Range<f64>) -> f32 {
+        let span = range.end - range.start;
+        let t = if span > 0.0 {
+            ((size - range.start) / span).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        let (min, max) = (self.radius.start, self.radius.end);
+        (min * min + (max * max - min * min) * t as f32).sqrt()
+    }
+
+    /// Draws the bubbles, labelled for the series legend, and registers them as the points
+    /// of `series`.
+    pub fn draw(
+        &self,
+        chart: &mut ChartContext<'_, IcedBackend<'_>, Coords>,
+        ctx: &mut BuildContext<'_>,
+        series: SeriesId,
+    ) -> BubbleResult {
+        let (sizes, values) = (self.size_range(), self.value_range());
+        let scatter = Scatter::new(self.bubbles.iter().map(|bubble| {
+            let RGBColor(r, g, b) = self.colormap.map(bubble.value, values.clone());
+            let color = iced::Color::from_rgba8(r, g, b, self.opacity);
+            (
+                (bubble.x, bubble.y),
+                color,
+                self.radius_within(bubble.size, sizes.clone()),
+            )
+        }))
+        .shape(MarkerShape::Circle);
+
+        let anno = chart.draw_series(std::iter::once(scatter))?;
+        let RGBColor(r, g, b) = self.colormap.sample(0.5);
+        if let Some(name) = &self.name {
+            anno.label(name.as_str())
+                .legend(move |(x, y)| Circle::new((x + 5, y), 5, RGBColor(r, g, b).filled()));
+        }
+
+        ctx.set_series_color(series, &RGBColor(r, g, b));
+        ctx.register_points(
+            series,
+            chart.plotting_area(),
+            self.bubbles.iter().map(|bubble| (bubble.x, bubble.y)),
+        );
+        Ok(())
+    }
+}
+
+fn extent(values: impl Iterator<Item = f64>) -> Range<f64> {
+    values.fold(f64::INFINITY..f64::NEG_INFINITY, |range, value| {
+        range.start.min(value)..range.end.max(value)
+    })
+}
@@ -0,0 +1,621 @@
+use crate::gutters::{LABEL_GAP, TICK_SIZE};
+use crate::hit::HitRegistry;
+use crate::legend::LegendEntry;
+use crate::units::AxisUnits;
+use crate::{
+    legend_size, Axis, GeometryGroups, Gutters, IcedBackend, LabelArea, LabelDensity, Layer,
+    LayerCoord, LegendPosition, LegendStyle, SeriesId, SeriesStats, SharedExponent, TimeZone,
+    UnitSet, ViewState,
+};
+use iced::{Point, Rectangle, Size};
+use plotters::chart::{ChartContext, ChartState, MeshStyle, SeriesAnno};
+use plotters::coord::ranged1d::{Ranged, ValueFormatter};
+use plotters::coord::{CoordTranslate, Shift};
+use plotters::drawing::DrawingArea;
+use plotters::element::Rectangle as PlottersRectangle;
+use plotters::style::{Color, RGBAColor, TextStyle};
+use plotters_backend::{BackendCoord, DrawingBackend};
+use std::borrow::Borrow;
+use std::ops::Range;
+
+/// The error returned by a failing [`Chart::build_chart`].
+pub type BuildError = Box<dyn std::error::Error + Send + Sync>;
+
+/// A chart that can be drawn by a [`ChartWidget`](crate::ChartWidget).
+pub trait Chart {
+    /// Draws the chart onto `root`, which covers the whole canvas.
+    ///
+    /// The chart should build its coordinate system from the ranges in `ctx` and report
+    /// the resulting plotting area back through [`BuildContext::set_plotting_area`], so
+    /// the widget can map the cursor to data coordinates.
+    ///
+    /// Errors are caught by the widget and reported through its `on_error` callback
+    /// instead of panicking inside the canvas draw pass.
+    fn build_chart(
+        &self,
+        ctx: &mut BuildContext<'_>,
+        root: DrawingArea<IcedBackend<'_>, Shift>,
+    ) -> Result<(), BuildError>;
+
+    /// Draws what changes more often than the chart itself, such as a marker following
+    /// `cursor` (the data coordinates under the mouse, if in the plotting area), on every
+    /// frame over the cached chart.
+    ///
+    /// It is only called if [`Chart::build_chart`] saved its coordinate system with
+    /// [`BuildContext::save_chart_state`]; `chart` is restored from it, so the overlay is
+    /// drawn without building the chart again. Nothing is drawn by default.
+    fn build_overlay(
+        &self,
+        view: &ViewState,
+        cursor: Option<(f64, f64)>,
+        chart: &mut ChartContext<'_, IcedBackend<'_>, LayerCoord>,
+    ) -> Result<(), BuildError> {
+        let _ = (view, cursor, chart);
+        Ok(())
+    }
+}
+
+/// What the widget hands to [`Chart::build_chart`], and what the chart reports back.
+pub struct BuildContext<'a> {
+    view: &'a ViewState,
+    units: &'a AxisUnits,
+    size: Size,
+    density: LabelDensity,
+    background: Option<RGBAColor>,
+    plot_area: Option<Rectangle>,
+    hits: HitRegistry,
+    stats: Vec<SeriesStats>,
+    /// The points reported before and after decimation.
+    decimation: (usize, usize),
+    exponents: Vec<(Axis, String)>,
+    chart_state: Option<ChartState<LayerCoord>>,
+    supersampling: u32,
+    time_zone: TimeZone,
+    /// The area [`BuildContext::fill_background`] leaves open for tiles drawn under it.
+    hole: Option<Rectangle>,
+    legend: LegendPosition,
+    legend_bounds: Option<Rectangle>,
+    /// How the widget draws the legend, if it draws it instead of the chart.
+    legend_style: Option<LegendStyle>,
+    legend_entries: Vec<LegendEntry>,
+    hidden: &'a [SeriesId],
+    gutters: Option<Gutters>,
+    /// The groups the widget's backend draws into, one per layer.
+    groups: Option<&'a GeometryGroups>,
+}
+
+impl<'a> BuildContext<'a> {
+    pub(crate) fn new(
+        view: &'a ViewState,
+        units: &'a AxisUnits,
+        size: Size,
+        density: LabelDensity,
+        background: Option<RGBAColor>,
+    ) -> Self {
+        Self {
+            view,
+            units,
+            size,
+            density,
+            background,
+            plot_area: None,
+            hits: HitRegistry::default(),
+            stats: vec![],
+            decimation: (0, 0),
+            exponents: vec![],
+            chart_state: None,
+            supersampling: 1,
+            time_zone: TimeZone::Utc,
+            hole: None,
+            legend: LegendPosition::default(),
+            legend_bounds: None,
+            legend_style: None,
+            legend_entries: vec![],
+            hidden: &[],
+            gutters: None,
+            groups: None,
+        }
+    }
+
+    pub(crate) fn with_supersampling(mut self, factor: u32) -> Self {
+        self.supersampling = factor;
+        self
+    }
+
+    pub(crate) fn with_time_zone(mut self, zone: TimeZone) -> Self {
+        self.time_zone = zone;
+        self
+    }
+
+    pub(crate) fn with_hole(mut self, hole: Option<Rectangle>) -> Self {
+        self.hole = hole;
+        self
+    }
+
+    pub(crate) fn with_legend(
+        mut self,
+        legend: LegendPosition,
+        style: Option<LegendStyle>,
+        hidden: &'a [SeriesId],
+    ) -> Self {
+        self.legend = legend;
+        self.legend_style = style;
+        self.hidden = hidden;
+        self
+    }
+
+    pub(crate) fn with_gutters(mut self, gutters: Option<Gutters>) -> Self {
+        self.gutters = gutters;
+        self
+    }
+
+    pub(crate) fn with_groups(mut self, groups: Option<&'a GeometryGroups>) -> Self {
+        self.groups = groups;
+        self
+    }
+
+    /// Draws everything from now on into `layer`, which is [`Layer::Grid`] until the chart
+    /// begins another. Beginning [`Layer::Data`] after configuring the mesh lets
+    /// [`ChartWidget::invalidate_layer`](crate::ChartWidget::invalidate_layer) redraw the
+    /// grid and the series apart, although plotters draws both in one pass.
+    ///
+    /// The chart is still built whole, but the calls of layers that are still cached are
+    /// dropped before they are tessellated. The other layers aren't drawn by the chart
+    /// build, so beginning them is ignored.
+    pub fn begin_layer(&self, layer: Layer) {
+        if let (Some(groups), Layer::Grid | Layer::Data) = (self.groups, layer) {
+            groups.select(layer.group());
+        }
+    }
+
+    pub fn view(&self) -> &ViewState {
+        self.view
+    }
+
+    /// The background color set on the widget, or `None` if the chart should leave the
+    /// content behind the canvas visible.
+    pub fn background(&self) -> Option<&RGBAColor> {
+        self.background.as_ref()
+    }
+
+    /// Fills `root` with the widget's background color, and leaves it untouched if the
+    /// background is transparent. Charts should call this instead of filling `root`
+    /// with a fixed color.
+    ///
+    /// With [`ChartWidget::tiles`](crate::ChartWidget::tiles), the plotting area is left
+    /// open so the tiles under the chart show through.
+    pub fn fill_background(
+        &self,
+        root: &DrawingArea<IcedBackend<'_>, Shift>,
+    ) -> Result<(), BuildError> {
+        let color = match &self.background {
+            Some(color) if color.alpha() > 0.0 => color,
+            _ => return Ok(()),
+        };
+        let hole = match self.hole {
+            Some(hole) => hole,
+            None => return root.fill(color).map_err(Into::into),
+        };
+        let scale = self.supersampling as f32;
+        let (width, height) = root.dim_in_pixel();
+        let (width, height) = (width as i32, height as i32);
+        let left = (hole.x * scale) as i32;
+        let top = (hole.y * scale) as i32;
+        let right = ((hole.x + hole.width) * scale).ceil() as i32;
+        let bottom = ((hole.y + hole.height) * scale).ceil() as i32;
+        let margins = [
+            [(0, 0), (width, top)],
+            [(0, bottom), (width, height)],
+            [(0, top), (left, bottom)],
+            [(right, top), (width, bottom)],
+        ];
+        for corners in margins {
+            root.draw(&PlottersRectangle::new(corners, color.filled()))?;
+        }
+        Ok(())
+    }
+
+    /// How many times larger than the canvas the chart is drawn, see
+    /// [`ChartWidget::supersampling`](crate::ChartWidget::supersampling). Pixel sizes such
+    /// as fonts, margins, label areas and line widths should be multiplied by it.
+    pub fn supersampling(&self) -> u32 {
+        self.supersampling
+    }
+
+    /// The zone time axes should be displayed in, see
+    /// [`ChartWidget::time_zone`](crate::ChartWidget::time_zone). Pass it to
+    /// [`DateTimeTicks::in_time_zone`](crate::DateTimeTicks::in_time_zone).
+    pub fn time_zone(&self) -> TimeZone {
+        self.time_zone
+    }
+
+    /// The visible x range.
+    pub fn x_range(&self) -> Range<f64> {
+        self.view.x.as_range()
+    }
+
+    /// The visible y range.
+    pub fn y_range(&self) -> Range<f64> {
+        self.view.y.as_range()
+    }
+
+    /// The visible range of the secondary y axis, if the view has one.
+    pub fn secondary_y_range(&self) -> Option<Range<f64>> {
+        self.view.secondary_y.map(|y| y.as_range())
+    }
+
+    /// The units `axis` can be switched between, if any were set on the widget.
+    pub fn units(&self, axis: Axis) -> Option<&UnitSet> {
+        self.units.get(axis)
+    }
+
+    /// Formats a value of `axis` in its active unit, for use in label formatters.
+    pub fn format(&self, axis: Axis, value: f64) -> String {
+        match self.units.get(axis) {
+            Some(units) => units.format(value),
+            None => format!("{:.3}", value),
+        }
+    }
+
+    /// The number of x labels that fits the plotting area, or the whole canvas before
+    /// [`BuildContext::set_plotting_area`] is called.
+    pub fn x_labels(&self) -> usize {
+        let width = self.plot_area.map_or(self.size.width, |plot| plot.width);
+        self.density.x_labels(width)
+    }
+
+    /// The width of the plotting area in pixels, or of the whole canvas before
+    /// [`BuildContext::set_plotting_area`] is called, e.g. for
+    /// [`LevelOfDetail::visible`](crate::LevelOfDetail::visible).
+    pub fn x_pixels(&self) -> f32 {
+        self.plot_area.map_or(self.size.width, |plot| plot.width)
+    }
+
+    /// The number of y labels that fits the plotting area, or the whole canvas before
+    /// [`BuildContext::set_plotting_area`] is called.
+    pub fn y_labels(&self) -> usize {
+        let height = self.plot_area.map_or(self.size.height, |plot| plot.height);
+        self.density.y_labels(height)
+    }
+
+    /// The margin around the chart set with [`ChartWidget::gutters`](crate::ChartWidget::gutters),
+    /// or `default`, in pixels of the backend.
+    pub fn margin(&self, default: u32) -> u32 {
+        self.gutters.map_or(default, |gutters| gutters.margin) * self.supersampling
+    }
+
+    /// The height of the x label area set with
+    /// [`ChartWidget::gutters`](crate::ChartWidget::gutters), or `default`, in pixels of the
+    /// backend. [`LabelArea::Auto`] fits the tick labels of `x` in `font`, and the
+    /// description `desc` below them if the axis has one.
+    pub fn x_label_area_size<X>(
+        &self,
+        root: &DrawingArea<IcedBackend<'_>, Shift>,
+        x: &X,
+        font: &TextStyle<'_>,
+        desc: Option<&str>,
+        default: u32,
+    ) -> Result<u32, BuildError>
+    where
+        X: Ranged + ValueFormatter<X::ValueType>,
+    {
+        match self.gutters.map(|gutters| gutters.x_label_area) {
+            None => Ok(default * self.supersampling),
+            Some(LabelArea::Fixed(size)) => Ok(size * self.supersampling),
+            Some(LabelArea::Auto) => {
+                let mut height = 0;
+                for value in x.key_points(self.x_labels()) {
+                    height = height.max(root.estimate_text_size(&X::format(&value), font)?.1);
+                }
+                if let Some(desc) = desc {
+                    height +=
+                        root.estimate_text_size(desc, font)?.1 + LABEL_GAP * self.supersampling;
+                }
+                Ok(height + (TICK_SIZE + 2 * LABEL_GAP) * self.supersampling)
+            }
+        }
+    }
+
+    /// The width of the y label area set with
+    /// [`ChartWidget::gutters`](crate::ChartWidget::gutters), or `default`, in pixels of the
+    /// backend. [`LabelArea::Auto`] fits the longest tick label of `y` in `font`, and the
+    /// description `desc` beside them if the axis has one.
+    pub fn y_label_area_size<Y>(
+        &self,
+        root: &DrawingArea<IcedBackend<'_>, Shift>,
+        y: &Y,
+        font: &TextStyle<'_>,
+        desc: Option<&str>,
+        default: u32,
+    ) -> Result<u32, BuildError>
+    where
+        Y: Ranged + ValueFormatter<Y::ValueType>,
+    {
+        match self.gutters.map(|gutters| gutters.y_label_area) {
+            None => Ok(default * self.supersampling),
+            Some(LabelArea::Fixed(size)) => Ok(size * self.supersampling),
+            Some(LabelArea::Auto) => {
+                let mut width = 0;
+                for value in y.key_points(self.y_labels()) {
+                    width = width.max(root.estimate_text_size(&Y::format(&value), font)?.0);
+                }
+                if let Some(desc) = desc {
+                    // The description is drawn rotated, so it takes its height.
+                    width +=
+                        root.estimate_text_size(desc, font)?.1 + LABEL_GAP * self.supersampling;
+                }
+                Ok(width + (TICK_SIZE + 2 * LABEL_GAP) * self.supersampling)
+            }
+        }
+    }
+
+    /// Sets the label counts of `mesh` to [`BuildContext::x_labels`] and
+    /// [`BuildContext::y_labels`].
+    pub fn fit_labels<'m, 'c, 'b, X, Y, DB>(
+        &self,
+        mesh: &'m mut MeshStyle<'c, 'b, X, Y, DB>,
+    ) -> &'m mut MeshStyle<'c, 'b, X, Y, DB>
+    where
+        X: Ranged,
+        Y: Ranged,
+        DB: DrawingBackend,
+    {
+        mesh.x_labels(self.x_labels()).y_labels(self.y_labels())
+    }
+
+    /// Records the pixel area the data is plotted in, usually `chart.plotting_area()`.
+    pub fn set_plotting_area<DB: DrawingBackend, CT: CoordTranslate>(
+        &mut self,
+        area: &DrawingArea<DB, CT>,
+    ) {
+        let (x, y) = area.get_pixel_range();
+        let start = self.to_point((x.start, y.start));
+        let end = self.to_point((x.end, y.end));
+        self.plot_area = Some(Rectangle {
+            x: start.x,
+            y: start.y,
+            width: end.x - start.x,
+            height: end.y - start.y,
+        });
+    }
+
+    /// Registers the points of `series` for [`ChartWidget::hit_test`], mapping them
+    /// through `area` (usually `chart.plotting_area()`).
+    ///
+    /// [`ChartWidget::hit_test`]: crate::ChartWidget::hit_test
+    pub fn register_points<DB, CT, I>(
+        &mut self,
+        series: SeriesId,
+        area: &DrawingArea<DB, CT>,
+        points: I,
+    ) where
+        DB: DrawingBackend,
+        CT: CoordTranslate,
+        I: IntoIterator,
+        I::Item: Borrow<CT::From>,
+    {
+        for (index, point) in points.into_iter().enumerate() {
+            let at = self.to_point(area.map_coordinate(point.borrow()));
+            self.hits.add_point(series, index, at);
+        }
+    }
+
+    /// Registers a line series: its points and the segments connecting them.
+    pub fn register_line<DB, CT, I>(
+        &mut self,
+        series: SeriesId,
+        area: &DrawingArea<DB, CT>,
+        points: I,
+    ) where
+        DB: DrawingBackend,
+        CT: CoordTranslate,
+        I: IntoIterator,
+        I::Item: Borrow<CT::From>,
+    {
+        let mut last = None;
+        for (index, point) in points.into_iter().enumerate() {
+            let at = self.to_point(area.map_coordinate(point.borrow()));
+            self.hits.add_point(series, index, at);
+            if let Some(from) = last {
+                self.hits.add_segment(series, index - 1, from, at);
+            }
+            last = Some(at);
+        }
+    }
+
+    /// Sets the color `series` is drawn in, used when highlighting it on hover.
+    pub fn set_series_color<C: Color>(&mut self, series: SeriesId, color: &C) {
+        let (r, g, b) = color.rgb();
+        self.hits.set_color(
+            series,
+            iced::Color::from_rgba8(r, g, b, color.alpha() as f32),
+        );
+    }
+
+    /// Registers a filled rectangle such as a bar, given two opposite data corners.
+    pub fn register_rect<DB, CT>(
+        &mut self,
+        series: SeriesId,
+        index: usize,
+        area: &DrawingArea<DB, CT>,
+        corners: (CT::From, CT::From),
+    ) where
+        DB: DrawingBackend,
+        CT: CoordTranslate,
+    {
+        let a = self.to_point(area.map_coordinate(&corners.0));
+        let b = self.to_point(area.map_coordinate(&corners.1));
+        self.hits.add_area(
+            series,
+            index,
+            Rectangle {
+                x: a.x.min(b.x),
+                y: a.y.min(b.y),
+                width: (a.x - b.x).abs(),
+                height: (a.y - b.y).abs(),
+            },
+        );
+    }
+
+    /// Summarizes the `(x, y)` data of `series` within the visible window for the widget's
+    /// [`StatsBox`](crate::StatsBox), under `name`.
+    pub fn track_stats<I>(&mut self, series: SeriesId, name: impl Into<String>, points: I)
+    where
+        I: IntoIterator,
+        I::Item: Borrow<(f64, f64)>,
+    {
+        let points = points.into_iter().map(|point| *point.borrow());
+        self.stats
+            .push(SeriesStats::visible(series, name.into(), self.view, points));
+    }
+
+    /// Reports that `drawn` of `points` points of a series are drawn after decimating them,
+    /// e.g. with a [`LevelOfDetail`](crate::LevelOfDetail), for the widget's
+    /// [`ChartWidget::debug_overlay`](crate::ChartWidget::debug_overlay). Reports of several
+    /// series add up.
+    pub fn report_decimation(&mut self, points: usize, drawn: usize) {
+        self.decimation.0 += points;
+        self.decimation.1 += drawn;
+    }
+
+    /// Has the widget show the shared exponent of the tick labels of `axis` at its end,
+    /// if it is not 0. Call it with the [`SharedExponent`] the labels are formatted with.
+    pub fn show_exponent(&mut self, axis: Axis, exponent: &SharedExponent) {
+        self.exponents.retain(|(shown, _)| *shown != axis);
+        if let Some(label) = exponent.label() {
+            self.exponents.push((axis, label));
+        }
+    }
+
+    /// Where the widget wants the series legend, see
+    /// [`ChartWidget::legend_position`](crate::ChartWidget::legend_position).
+    pub fn legend_position(&self) -> LegendPosition {
+        self.legend
+    }
+
+    /// Whether the widget draws the legend from the series named with
+    /// [`BuildContext::label`], see [`ChartWidget::legend`](crate::ChartWidget::legend).
+    /// Charts should then leave out `configure_series_labels`.
+    pub fn draws_legend(&self) -> bool {
+        self.legend_style.is_some()
+    }
+
+    /// The size in pixels of the legend listing `names` in `font`: the legend the widget
+    /// draws if [`BuildContext::draws_legend`], else the plotters legend. Reserve
+    /// [`LegendPosition::margins`] for it.
+    pub fn legend_size(
+        &self,
+        root: &DrawingArea<IcedBackend<'_>, Shift>,
+        names: &[&str],
+        font: &TextStyle<'_>,
+    ) -> Result<(u32, u32), BuildError> {
+        match &self.legend_style {
+            Some(style) => {
+                let size = style.size(names.iter().copied());
+                let scale = self.supersampling as f32;
+                Ok(((size.width * scale) as u32, (size.height * scale) as u32))
+            }
+            None => Ok(legend_size(root, names.iter().copied(), font)?),
+        }
+    }
+
+    /// Names `series` in the legend, in the plotters legend through `annotation` (returned
+    /// by `chart.draw_series`) and in the one the widget draws.
+    ///
+    /// ```ignore
+    /// let annotation = chart.draw_series(LineSeries::new(points, &BLUE))?;
+    /// ctx.label(SeriesId(0), annotation, "Temperature")
+    ///     .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &BLUE));
+    /// ```
+    pub fn label<'b, 'c, DB: DrawingBackend>(
+        &mut self,
+        series: SeriesId,
+        annotation: &'b mut SeriesAnno<'c, DB>,
+        name: impl Into<String>,
+    ) -> &'b mut SeriesAnno<'c, DB> {
+        let name = name.into();
+        self.legend_entries.retain(|entry| entry.series != series);
+        self.legend_entries.push(LegendEntry {
+            series,
+            name: name.clone(),
+        });
+        annotation.label(name)
+    }
+
+    /// Whether the user hid `series` by clicking its entry in the legend the widget draws.
+    /// Charts should skip drawing hidden series.
+    pub fn is_hidden(&self, series: SeriesId) -> bool {
+        self.hidden.contains(&series)
+    }
+
+    /// Records where the legend was drawn, `size` pixels large at `offset` from the top left
+    /// corner of `area` (usually `chart.plotting_area()`), so the user can drag it.
+    pub fn set_legend_bounds<DB: DrawingBackend, CT: CoordTranslate>(
+        &mut self,
+        area: &DrawingArea<DB, CT>,
+        offset: (i32, i32),
+        size: (u32, u32),
+    ) {
+        let (x, y) = area.get_pixel_range();
+        let start = self.to_point((x.start + offset.0, y.start + offset.1));
+        let end = self.to_point((
+            x.start + offset.0 + size.0 as i32,
+            y.start + offset.1 + size.1 as i32,
+        ));
+        self.legend_bounds = Some(Rectangle {
+            x: start.x,
+            y: start.y,
+            width: end.x - start.x,
+            height: end.y - start.y,
+        });
+    }
+
+    /// Keeps the coordinate system of `chart` for [`Chart::build_overlay`] and records its
+    /// plotting area like [`BuildContext::set_plotting_area`]. Call it once the chart is
+    /// built.
+    pub fn save_chart_state<DB: DrawingBackend>(
+        &mut self,
+        chart: &ChartContext<'_, DB, LayerCoord>,
+    ) {
+        self.set_plotting_area(chart.plotting_area());
+        self.chart_state = Some(chart.to_chart_state());
+    }
+
+    pub(crate) fn plot_area(&self) -> Option<Rectangle> {
+        self.plot_area
+    }
+
+    pub(crate) fn take_hits(&mut self) -> HitRegistry {
+        std::mem::take(&mut self.hits)
+    }
+
+    pub(crate) fn take_stats(&mut self) -> Vec<SeriesStats> {
+        std::mem::take(&mut self.stats)
+    }
+
+    pub(crate) fn decimation(&self) -> (usize, usize) {
+        self.decimation
+    }
+
+    pub(crate) fn exponents(&self) -> &[(Axis, String)] {
+        &self.exponents
+    }
+
+    pub(crate) fn legend_bounds(&self) -> Option<Rectangle> {
+        self.legend_bounds
+    }
+
+    pub(crate) fn take_legend_entries(&mut self) -> Vec<LegendEntry> {
+        std::mem::take(&mut self.legend_entries)
+    }
+
+    /// The canvas position of a pixel of the backend.
+    fn to_point(&self, (x, y): BackendCoord) -> Point {
+        let scale = self.supersampling as f32;
+        Point::new(x as f32 / scale, y as f32 / scale)
+    }
+
+    pub(crate) fn take_chart_state(&mut self) -> Option<ChartState<LayerCoord>> {
+        self.chart_state.take()
+    }
+}
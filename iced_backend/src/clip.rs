@@ -0,0 +1,368 @@
+use iced::{Point, Rectangle};
+use plotters_backend::BackendCoord;
+
+/// The largest coordinate magnitude that survives the conversion to `f32` without losing
+/// integer precision.
+pub(crate) const MAX_COORDINATE: i32 = 1 << 24;
+
+/// Returns the coordinate as an iced point, or `None` if it is beyond `MAX_COORDINATE`.
+pub(crate) fn validate(coord: BackendCoord) -> Option<Point> {
+    let max = MAX_COORDINATE as u32;
+    if coord.0.unsigned_abs() > max || coord.1.unsigned_abs() > max {
+        None
+    } else {
+        Some(Point::new(coord.0 as f32, coord.1 as f32))
+    }
+}
+
+/// The region around the frame geometry is clipped against.
+///
+/// It extends the frame by its larger dimension on every side, so anything that could
+/// possibly become visible is kept intact while far-away geometry is cut off before it
+/// reaches the tessellator.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Envelope {
+    min_x: f32,
+    min_y: f32,
+    max_x: f32,
+    max_y: f32,
+}
+
+impl Envelope {
+    pub(crate) fn around(width: u32, height: u32) -> Self {
+        let (width, height) = (width as f32, height as f32);
+        let margin = width.max(height);
+        Self {
+            min_x: -margin,
+            min_y: -margin,
+            max_x: width + margin,
+            max_y: height + margin,
+        }
+    }
+
+    /// An envelope covering exactly `rect`, e.g. to clip shapes to the plotting area.
+    pub(crate) fn of(rect: Rectangle) -> Self {
+        Self {
+            min_x: rect.x,
+            min_y: rect.y,
+            max_x: rect.x + rect.width,
+            max_y: rect.y + rect.height,
+        }
+    }
+
+    pub(crate) fn clamp(&self, point: Point) -> Point {
+        Point::new(
+            point.x.max(self.min_x).min(self.max_x),
+            point.y.max(self.min_y).min(self.max_y),
+        )
+    }
+
+    /// Whether a circle with the given center and radius touches the envelope at all.
+    pub(crate) fn touches_circle(&self, center: Point, radius: f32) -> bool {
+        center.x + radius >= self.min_x
+            && center.x - radius <= self.max_x
+            && center.y + radius >= self.min_y
+            && center.y - radius <= self.max_y
+    }
+
+    /// Clips a line segment with the Liang-Barsky algorithm. Segments with a NaN or
+    /// infinite end are dropped.
+    pub(crate) fn clip_segment(&self, from: Point, to: Point) -> Option<(Point, Point)> {
+        if !is_finite(from) || !is_finite(to) {
+            return None;
+        }
+        let (dx, dy) = (to.x - from.x, to.y - from.y);
+        let mut t0 = 0.0f32;
+        let mut t1 = 1.0f32;
+
+        for &(p, q) in &[
+            (-dx, from.x - self.min_x),
+            (dx, self.max_x - from.x),
+            (-dy, from.y - self.min_y),
+            (dy, self.max_y - from.y),
+        ] {
+            if p == 0.0 {
+                if q < 0.0 {
+                    return None;
+                }
+            } else {
+                let r = q / p;
+                if p < 0.0 {
+                    if r > t1 {
+                        return None;
+                    }
+                    t0 = t0.max(r);
+                } else {
+                    if r < t0 {
+                        return None;
+                    }
+                    t1 = t1.min(r);
+                }
+            }
+        }
+
+        Some((
+            Point::new(from.x + t0 * dx, from.y + t0 * dy),
+            Point::new(from.x + t1 * dx, from.y + t1 * dy),
+        ))
+    }
+
+    /// Clips a polygon with the Sutherland-Hodgman algorithm, leaving out NaN and infinite
+    /// vertices.
+    pub(crate) fn clip_polygon(&self, vert: &[Point]) -> Vec<Point> {
+        let mut output: Vec<Point> = vert.iter().copied().filter(|p| is_finite(*p)).collect();
+
+        for edge in 0..4 {
+            let input = std::mem::take(&mut output);
+            let mut prev = match input.last() {
+                Some(prev) => *prev,
+                None => break,
+            };
+            for &cur in &input {
+                match (self.inside(edge, prev), self.inside(edge, cur)) {
+                    (true, true) => output.push(cur),
+                    (true, false) => output.push(self.intersect(edge, prev, cur)),
+                    (false, true) => {
+                        output.push(self.intersect(edge, prev, cur));
+                        output.push(cur);
+                    }
+                    (false, false) => {}
+                }
+                prev = cur;
+            }
+        }
+
+        output
+    }
+
+    fn inside(&self, edge: usize, point: Point) -> bool {
+        match edge {
+            0 => point.x >= self.min_x,
+            1 => point.x <= self.max_x,
+            2 => point.y >= self.min_y,
+            _ => point.y <= self.max_y,
+        }
+    }
+
+    fn intersect(&self, edge: usize, a: Point, b: Point) -> Point {
+        match edge {
+            0 | 1 => {
+                let x = if edge == 0 { self.min_x } else { self.max_x };
+                let t = (x - a.x) / (b.x - a.x);
+                Point::new(x, a.y + t * (b.y - a.y))
+            }
+            _ => {
+                let y = if edge == 2 { self.min_y } else { self.max_y };
+                let t = (y - a.y) / (b.y - a.y);
+                Point::new(a.x + t * (b.x - a.x), y)
+            }
+        }
+    }
+}
+
+/// Clips a polygon against the convex polygon `clip` with the Sutherland-Hodgman
+/// algorithm; `clip` may be wound either way.
+pub(crate) fn clip_convex(vert: &[Point], clip: &[Point]) -> Vec<Point> {
+    let orientation = signed_area(clip).signum();
+    let mut output = vert.to_vec();
+    let mut edge_start = match clip.last() {
+        Some(last) => *last,
+        None => return output,
+    };
+
+    for &edge_end in clip {
+        let input = std::mem::take(&mut output);
+        let mut prev = match input.last() {
+            Some(prev) => *prev,
+            None => break,
+        };
+        let side = |point: Point| {
+            let cross = (edge_end.x - edge_start.x) * (point.y - edge_start.y)
+                - (edge_end.y - edge_start.y) * (point.x - edge_start.x);
+            cross * orientation
+        };
+        for &cur in &input {
+            let (from, to) = (side(prev), side(cur));
+            if (from >= 0.0) != (to >= 0.0) {
+                let t = from / (from - to);
+                output.push(Point::new(
+                    prev.x + t * (cur.x - prev.x),
+                    prev.y + t * (cur.y - prev.y),
+                ));
+            }
+            if to >= 0.0 {
+                output.push(cur);
+            }
+            prev = cur;
+        }
+        edge_start = edge_end;
+    }
+
+    output
+}
+
+fn is_finite(point: Point) -> bool {
+    point.x.is_finite() && point.y.is_finite()
+}
+
+fn signed_area(vert: &[Point]) -> f32 {
+    let mut prev = match vert.last() {
+        Some(prev) => *prev,
+        None => return 0.0,
+    };
+    let mut area = 0.0;
+    for &cur in vert {
+        area += prev.x * cur.y - cur.x * prev.y;
+        prev = cur;
+    }
+    area / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square() -> Envelope {
+        Envelope::of(Rectangle {
+            x: 0.0,
+            y: 0.0,
+            width: 10.0,
+            height: 10.0,
+        })
+    }
+
+    #[test]
+    fn validates_huge_coordinates() {
+        assert_eq!(validate((5, -5)), Some(Point::new(5.0, -5.0)));
+        assert_eq!(
+            validate((MAX_COORDINATE, 0)),
+            Some(Point::new(MAX_COORDINATE as f32, 0.0))
+        );
+        assert_eq!(validate((MAX_COORDINATE + 1, 0)), None);
+        assert_eq!(validate((0, i32::MIN)), None);
+    }
+
+    #[test]
+    fn envelope_extends_the_frame_by_its_larger_side() {
+        let envelope = Envelope::around(100, 50);
+        assert_eq!(
+            envelope.clamp(Point::new(-500.0, 500.0)),
+            Point::new(-100.0, 150.0)
+        );
+        assert!(envelope.touches_circle(Point::new(-105.0, 0.0), 10.0));
+        assert!(!envelope.touches_circle(Point::new(-115.0, 0.0), 10.0));
+    }
+
+    #[test]
+    fn keeps_segments_inside() {
+        let (from, to) = (Point::new(1.0, 2.0), Point::new(8.0, 9.0));
+        assert_eq!(square().clip_segment(from, to), Some((from, to)));
+    }
+
+    #[test]
+    fn cuts_segments_crossing_the_envelope() {
+        let clipped = square().clip_segment(Point::new(-5.0, 5.0), Point::new(15.0, 5.0));
+        assert_eq!(clipped, Some((Point::new(0.0, 5.0), Point::new(10.0, 5.0))));
+        let clipped = square().clip_segment(Point::new(5.0, 5.0), Point::new(5.0, 20.0));
+        assert_eq!(clipped, Some((Point::new(5.0, 5.0), Point::new(5.0, 10.0))));
+        let clipped = square().clip_segment(Point::new(-5.0, -5.0), Point::new(15.0, 15.0));
+        assert_eq!(
+            clipped,
+            Some((Point::new(0.0, 0.0), Point::new(10.0, 10.0)))
+        );
+    }
+
+    #[test]
+    fn drops_segments_outside() {
+        // Beside the envelope, parallel to an edge, and passing a corner diagonally.
+        assert_eq!(
+            square().clip_segment(Point::new(-5.0, 0.0), Point::new(-5.0, 10.0)),
+            None
+        );
+        assert_eq!(
+            square().clip_segment(Point::new(0.0, 12.0), Point::new(10.0, 12.0)),
+            None
+        );
+        assert_eq!(
+            square().clip_segment(Point::new(8.0, 13.0), Point::new(13.0, 8.0)),
+            None
+        );
+    }
+
+    #[test]
+    fn drops_segments_with_nan_or_infinite_ends() {
+        let inside = Point::new(5.0, 5.0);
+        assert_eq!(
+            square().clip_segment(Point::new(f32::NAN, 5.0), inside),
+            None
+        );
+        assert_eq!(
+            square().clip_segment(inside, Point::new(5.0, f32::INFINITY)),
+            None
+        );
+    }
+
+    #[test]
+    fn cuts_polygons_partly_outside() {
+        let triangle = [
+            Point::new(5.0, 5.0),
+            Point::new(15.0, 5.0),
+            Point::new(5.0, 15.0),
+        ];
+        // The hypotenuse passes the corner, so all that is left is a square.
+        let clipped = square().clip_polygon(&triangle);
+        assert_eq!(signed_area(&clipped).abs(), 25.0);
+        assert!(clipped
+            .iter()
+            .all(|p| (5.0..=10.0).contains(&p.x) && (5.0..=10.0).contains(&p.y)));
+    }
+
+    #[test]
+    fn keeps_polygons_inside_and_drops_ones_outside() {
+        let inside = [
+            Point::new(1.0, 1.0),
+            Point::new(9.0, 1.0),
+            Point::new(5.0, 9.0),
+        ];
+        assert_eq!(square().clip_polygon(&inside), inside.to_vec());
+        let outside = [
+            Point::new(20.0, 20.0),
+            Point::new(30.0, 20.0),
+            Point::new(25.0, 30.0),
+        ];
+        assert!(square().clip_polygon(&outside).is_empty());
+        assert!(square().clip_polygon(&[]).is_empty());
+    }
+
+    #[test]
+    fn leaves_out_nan_vertices() {
+        let vert = [
+            Point::new(1.0, 1.0),
+            Point::new(f32::NAN, 4.0),
+            Point::new(9.0, 1.0),
+            Point::new(5.0, 9.0),
+        ];
+        let clipped = square().clip_polygon(&vert);
+        assert_eq!(clipped.len(), 3);
+        assert!(clipped.iter().all(|point| is_finite(*point)));
+    }
+
+    #[test]
+    fn clips_to_convex_polygons_wound_either_way() {
+        let quad = [
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(10.0, 10.0),
+            Point::new(0.0, 10.0),
+        ];
+        let reversed: Vec<Point> = quad.iter().rev().copied().collect();
+        let triangle = [
+            Point::new(5.0, 5.0),
+            Point::new(25.0, 5.0),
+            Point::new(5.0, 25.0),
+        ];
+        let area = |vert: &[Point]| signed_area(vert).abs();
+        assert_eq!(area(&clip_convex(&triangle, &quad)), 25.0);
+        assert_eq!(area(&clip_convex(&triangle, &reversed)), 25.0);
+    }
+}
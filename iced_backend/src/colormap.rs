@@ -0,0 +1,234 @@
+use plotters::style::RGBColor;
+use std::ops::Range;
+
+const VIRIDIS: [RGBColor; 9] = [
+    RGBColor(68, 1, 84),
+    RGBColor(72, 40, 120),
+    RGBColor(62, 73, 137),
+    RGBColor(49, 104, 142),
+    RGBColor(38, 130, 142),
+    RGBColor(31, 158, 137),
+    RGBColor(53, 183, 121),
+    RGBColor(110, 206, 88),
+    RGBColor(253, 231, 37),
+];
+
+const PLASMA: [RGBColor; 9] = [
+    RGBColor(13, 8, 135),
+    RGBColor(76, 2, 161),
+    RGBColor(126, 3, 168),
+    RGBColor(169, 35, 149),
+    RGBColor(204, 71, 120),
+    RGBColor(229, 107, 93),
+    RGBColor(248, 149, 64),
+    RGBColor(253, 197, 39),
+    RGBColor(240, 249, 33),
+];
+
+const TURBO: [RGBColor; 9] = [
+    RGBColor(48, 18, 59),
+    RGBColor(70, 100, 220),
+    RGBColor(54, 170, 249),
+    RGBColor(26, 228, 182),
+    RGBColor(162, 252, 60),
+    RGBColor(225, 221, 55),
+    RGBColor(254, 155, 45),
+    RGBColor(228, 70, 10),
+    RGBColor(122, 4, 3),
+];
+
+const RED_BLUE: [RGBColor; 9] = [
+    RGBColor(178, 24, 43),
+    RGBColor(214, 96, 77),
+    RGBColor(244, 165, 130),
+    RGBColor(253, 219, 199),
+    RGBColor(247, 247, 247),
+    RGBColor(209, 229, 240),
+    RGBColor(146, 197, 222),
+    RGBColor(67, 147, 195),
+    RGBColor(33, 102, 172),
+];
+
+const COOL_WARM: [RGBColor; 9] = [
+    RGBColor(59, 76, 192),
+    RGBColor(98, 130, 234),
+    RGBColor(141, 176, 254),
+    RGBColor(184, 208, 249),
+    RGBColor(221, 221, 221),
+    RGBColor(245, 196, 173),
+    RGBColor(244, 154, 123),
+    RGBColor(222, 96, 77),
+    RGBColor(180, 4, 38),
+];
+
+/// A continuous color scale, for coloring points or cells by a value instead of by series.
+///
+/// The sequential maps are perceptually uniform, so equal steps in value look like equal
+/// steps in color; the diverging maps run through a light neutral color at their center,
+/// for values above and below a meaningful midpoint such as zero.
+#[derive(Debug, Clone, Copy)]
+pub struct Colormap {
+    stops: &'static [RGBColor],
+    reversed: bool,
+    start: f32,
+    end: f32,
+}
+
+impl Colormap {
+    /// Sequential, from dark purple through teal to yellow.
+    pub const VIRIDIS: Colormap = Colormap::new(&VIRIDIS);
+    /// Sequential, from dark blue through magenta to yellow.
+    pub const PLASMA: Colormap = Colormap::new(&PLASMA);
+    /// A rainbow from dark blue through green to dark red, for telling many levels apart.
+    pub const TURBO: Colormap = Colormap::new(&TURBO);
+    /// Diverging, from red through white to blue.
+    pub const RED_BLUE: Colormap = Colormap::new(&RED_BLUE);
+    /// Diverging, from blue through light grey to red.
+    pub const COOL_WARM: Colormap = Colormap::new(&COOL_WARM);
+
+    /// A map interpolating linearly between evenly spaced `stops`.
+    ///
+    /// # Panics
+    ///
+    /// If `stops` is empty, which fails to compile when the map is a constant.
+    pub const fn new(stops: &'static [RGBColor]) -> Self {
+        assert!(!stops.is_empty(), "a colormap needs at least one stop");
+        Self {
+            stops,
+            reversed: false,
+            start: 0.0,
+            end: 1.0,
+        }
+    }
+
+    /// The same map running from its end to its start.
+    pub fn reversed(mut self) -> Self {
+        self.reversed = !self.reversed;
+        self
+    }
+
+    /// Only the part of the map between `start` and `end`, both between 0 and 1, stretched
+    /// over the whole scale, e.g. to leave out the nearly black end of a map on a dark
+    /// background.
+    pub fn clipped(mut self, start: f32, end: f32) -> Self {
+        self.start = start.clamp(0.0, 1.0);
+        self.end = end.clamp(0.0, 1.0);
+        self
+    }
+
+    /// The color at `t` between 0 and 1; values outside are clamped to the ends.
+    pub fn sample(&self, t: f32) -> RGBColor {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        let t = if self.reversed { 1.0 - t } else { t };
+        let t = self.start + t * (self.end - self.start);
+
+        let last = self.stops.len() - 1;
+        let position = t * last as f32;
+        let index = (position.floor() as usize).min(last);
+        let RGBColor(r0, g0, b0) = self.stops[index];
+        let RGBColor(r1, g1, b1) = self.stops[(index + 1).min(last)];
+        let fraction = position - index as f32;
+        let lerp =
+            |a: u8, b: u8| (f32::from(a) + (f32::from(b) - f32::from(a)) * fraction).round() as u8;
+        RGBColor(lerp(r0, r1), lerp(g0, g1), lerp(b0, b1))
+    }
+
+    /// The color of `value` on a scale spanning `range`, whose start maps to the start of
+    /// the map.
+    pub fn map(&self, value: f64, range: Range<f64>) -> RGBColor {
+        let span = range.end - range.start;
+        let t = if span == 0.0 {
+            0.5
+        } else {
+            (value - range.start) / span
+        };
+        self.sample(t as f32)
+    }
+
+    /// `count` evenly spaced colors from the start to the end of the map, for discrete
+    /// levels or as a series palette.
+    pub fn steps(&self, count: usize) -> Vec<RGBColor> {
+        match count {
+            0 => Vec::new(),
+            1 => vec![self.sample(0.5)],
+            _ => (0..count)
+                .map(|i| self.sample(i as f32 / (count - 1) as f32))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RAMP: Colormap = Colormap::new(&[RGBColor(0, 0, 0), RGBColor(200, 100, 50)]);
+
+    // RGBColor implements neither PartialEq nor Clone.
+    fn rgb(color: &RGBColor) -> (u8, u8, u8) {
+        (color.0, color.1, color.2)
+    }
+
+    fn at(map: Colormap, t: f32) -> (u8, u8, u8) {
+        rgb(&map.sample(t))
+    }
+
+    #[test]
+    fn samples_between_the_ends() {
+        assert_eq!(at(RAMP, 0.0), (0, 0, 0));
+        assert_eq!(at(RAMP, 1.0), (200, 100, 50));
+        assert_eq!(at(RAMP, 0.5), (100, 50, 25));
+        assert_eq!(at(RAMP, -3.0), at(RAMP, 0.0));
+        assert_eq!(at(RAMP, 7.0), at(RAMP, 1.0));
+        assert_eq!(at(RAMP, f32::NAN), at(RAMP, 0.0));
+
+        assert_eq!(at(Colormap::VIRIDIS, 0.0), rgb(&VIRIDIS[0]));
+        assert_eq!(at(Colormap::VIRIDIS, 1.0), rgb(&VIRIDIS[8]));
+        assert_eq!(at(Colormap::VIRIDIS, 0.5), rgb(&VIRIDIS[4]));
+
+        let single = Colormap::new(&[RGBColor(1, 2, 3)]);
+        assert_eq!(at(single, 0.0), (1, 2, 3));
+        assert_eq!(at(single, 1.0), (1, 2, 3));
+    }
+
+    #[test]
+    fn reverses_and_clips() {
+        let reversed = RAMP.reversed();
+        assert_eq!(at(reversed, 0.0), at(RAMP, 1.0));
+        assert_eq!(at(reversed, 0.25), at(RAMP, 0.75));
+        assert_eq!(at(reversed.reversed(), 0.25), at(RAMP, 0.25));
+
+        let clipped = RAMP.clipped(0.5, 1.0);
+        assert_eq!(at(clipped, 0.0), at(RAMP, 0.5));
+        assert_eq!(at(clipped, 1.0), at(RAMP, 1.0));
+        assert_eq!(at(clipped, 0.5), at(RAMP, 0.75));
+        assert_eq!(at(clipped.reversed(), 0.0), at(RAMP, 1.0));
+        assert_eq!(at(RAMP.clipped(-1.0, 2.0), 1.0), at(RAMP, 1.0));
+    }
+
+    #[test]
+    fn maps_values_onto_a_range() {
+        let map = |value, range| rgb(&RAMP.map(value, range));
+        assert_eq!(map(10.0, 10.0..20.0), at(RAMP, 0.0));
+        assert_eq!(map(15.0, 10.0..20.0), at(RAMP, 0.5));
+        assert_eq!(map(25.0, 10.0..20.0), at(RAMP, 1.0));
+        assert_eq!(map(12.0, 20.0..10.0), at(RAMP, 0.8));
+        assert_eq!(map(3.0, 5.0..5.0), at(RAMP, 0.5));
+        assert_eq!(map(f64::NAN, 0.0..1.0), at(RAMP, 0.0));
+    }
+
+    #[test]
+    fn steps_span_the_map() {
+        let steps = |map: Colormap, count| map.steps(count).iter().map(rgb).collect::<Vec<_>>();
+        assert!(steps(RAMP, 0).is_empty());
+        assert_eq!(steps(RAMP, 1), vec![at(RAMP, 0.5)]);
+        assert_eq!(
+            steps(RAMP, 3),
+            vec![at(RAMP, 0.0), at(RAMP, 0.5), at(RAMP, 1.0)]
+        );
+        assert_eq!(
+            steps(Colormap::TURBO, 9),
+            TURBO.iter().map(rgb).collect::<Vec<_>>()
+        );
+    }
+}
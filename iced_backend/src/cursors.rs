@@ -0,0 +1,182 @@
+use crate::hit::HitRegistry;
+use crate::{Axis, Corner, ReferenceLine, SeriesId, ViewState};
+use iced::canvas::{Frame, Text};
+use iced::{Color, Point, Rectangle, Size};
+use std::collections::HashMap;
+
+/// The distance between the table and the edges of the plotting area.
+const INSET: f32 = 4.0;
+/// The padding between the table's box and its text, and between its columns.
+const PADDING: f32 = 6.0;
+/// A rough average glyph width relative to the text size, used to size the columns.
+const GLYPH_WIDTH: f32 = 0.6;
+
+/// Two draggable vertical cursors, A and B, with a table of the registered series' values
+/// at both of them and the difference B − A.
+///
+/// Values are interpolated along the lines registered with
+/// [`BuildContext::register_line`](crate::BuildContext::register_line) and read on the
+/// primary y axis.
+#[derive(Debug, Clone)]
+pub struct DualCursors {
+    pub(crate) lines: [ReferenceLine; 2],
+    names: HashMap<SeriesId, String>,
+    corner: Corner,
+    text_size: f32,
+}
+
+impl DualCursors {
+    /// Places cursor A at `a` and cursor B at `b` on the x axis.
+    pub fn new(a: f64, b: f64) -> Self {
+        Self {
+            lines: [
+                ReferenceLine::new(Axis::X, a)
+                    .color(Color::from_rgb(0.0, 0.45, 0.7))
+                    .label("A"),
+                ReferenceLine::new(Axis::X, b)
+                    .color(Color::from_rgb(0.8, 0.4, 0.0))
+                    .label("B"),
+            ],
+            names: HashMap::new(),
+            corner: Corner::TopLeft,
+            text_size: 14.0,
+        }
+    }
+
+    /// Names `series` in the table; unnamed series are listed by their id.
+    pub fn series_name(mut self, series: SeriesId, name: impl Into<String>) -> Self {
+        self.names.insert(series, name.into());
+        self
+    }
+
+    /// Snaps both cursors to multiples of `step`, e.g. to sample times.
+    pub fn snap(mut self, step: f64) -> Self {
+        let [a, b] = self.lines.clone();
+        self.lines = [a.snap(step), b.snap(step)];
+        self
+    }
+
+    pub fn corner(mut self, corner: Corner) -> Self {
+        self.corner = corner;
+        self
+    }
+
+    pub fn text_size(mut self, size: f32) -> Self {
+        self.text_size = size;
+        self
+    }
+
+    pub fn a(&self) -> f64 {
+        self.lines[0].value()
+    }
+
+    pub fn b(&self) -> f64 {
+        self.lines[1].value()
+    }
+
+    /// Moves both cursors, e.g. to sync them with another chart.
+    pub fn set(&mut self, a: f64, b: f64) {
+        self.lines[0].set_value(a);
+        self.lines[1].set_value(b);
+    }
+
+    /// The values of every registered line series at both cursors, in series order.
+    pub(crate) fn values(
+        &self,
+        view: &ViewState,
+        plot: Rectangle,
+        hits: &HitRegistry,
+    ) -> Vec<(SeriesId, Option<f64>, Option<f64>)> {
+        let value_at = |series, x: f64| {
+            let pixel = plot.x + view.x.fraction(x) as f32 * plot.width;
+            hits.y_at(series, pixel)
+                .map(|y| view.to_data(plot, Point::new(pixel, y)).1)
+        };
+        hits.series()
+            .into_iter()
+            .filter(|series| hits.segments(*series).next().is_some())
+            .map(|series| {
+                (
+                    series,
+                    value_at(series, self.a()),
+                    value_at(series, self.b()),
+                )
+            })
+            .collect()
+    }
+
+    /// Draws the table; the cursor lines themselves are drawn with the reference lines.
+    pub(crate) fn draw_table(
+        &self,
+        frame: &mut Frame,
+        view: &ViewState,
+        plot: Rectangle,
+        hits: &HitRegistry,
+    ) {
+        let format =
+            |value: Option<f64>| value.map_or_else(|| "-".to_owned(), |v| format!("{:.3}", v));
+        let mut rows = vec![
+            [
+                String::new(),
+                "A".to_owned(),
+                "B".to_owned(),
+                "B − A".to_owned(),
+            ],
+            [
+                "x".to_owned(),
+                format(Some(self.a())),
+                format(Some(self.b())),
+                format(Some(self.b() - self.a())),
+            ],
+        ];
+        for (series, a, b) in self.values(view, plot, hits) {
+            let name = match self.names.get(&series) {
+                Some(name) => name.clone(),
+                None => format!("#{}", series.0),
+            };
+            let delta = a.and_then(|a| b.map(|b| b - a));
+            rows.push([name, format(a), format(b), format(delta)]);
+        }
+
+        let widths: Vec<f32> = (0..4)
+            .map(|column| {
+                let chars = rows
+                    .iter()
+                    .map(|row| row[column].chars().count())
+                    .max()
+                    .unwrap_or(0);
+                chars as f32 * self.text_size * GLYPH_WIDTH
+            })
+            .collect();
+        let size = Size::new(
+            widths.iter().sum::<f32>() + 5.0 * PADDING,
+            rows.len() as f32 * self.text_size + 2.0 * PADDING,
+        );
+        let x = match self.corner {
+            Corner::TopLeft | Corner::BottomLeft => plot.x + INSET,
+            Corner::TopRight | Corner::BottomRight => plot.x + plot.width - INSET - size.width,
+        };
+        let y = match self.corner {
+            Corner::TopLeft | Corner::TopRight => plot.y + INSET,
+            Corner::BottomLeft | Corner::BottomRight => plot.y + plot.height - INSET - size.height,
+        };
+
+        frame.fill_rectangle(
+            Point::new(x, y),
+            size,
+            Color::from_rgba(1.0, 1.0, 1.0, 0.85),
+        );
+        let mut left = x + PADDING;
+        for (column, width) in widths.iter().enumerate() {
+            for (row, cells) in rows.iter().enumerate() {
+                frame.fill_text(Text {
+                    content: cells[column].clone(),
+                    position: Point::new(left, y + PADDING + row as f32 * self.text_size),
+                    size: self.text_size,
+                    ..Text::default()
+                });
+            }
+            left += width + PADDING;
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use std::ops::Range;
+
+/// Points a chart can draw straight from the application's storage, without collecting
+/// them into a `Vec<(f64, f64)>` on every redraw.
+///
+/// It is implemented for slices and vectors of `(f64, f64)` and `(f32, f32)` pairs, and
+/// for [`Columns`] and [`Indexed`] over separate buffers. Implement [`SeriesData::len`]
+/// and [`SeriesData::point`] for other storage.
+///
+/// Everything taking points as an iterator, such as [`BuildContext::register_line`],
+/// [`BuildContext::track_stats`] or [`Indicator::extend`], accepts
+/// [`SeriesData::iter`]:
+///
+/// ```ignore
+/// let visible = data.visible_range(ctx.x_range());
+/// let line: Vec<_> = data.iter_range(visible.clone()).collect();
+/// ctx.track_stats(SeriesId(0), "price", data.iter_range(visible));
+/// ```
+///
+/// [`BuildContext::register_line`]: crate::BuildContext::register_line
+/// [`BuildContext::track_stats`]: crate::BuildContext::track_stats
+/// [`Indicator::extend`]: crate::Indicator::extend
+pub trait SeriesData {
+    fn len(&self) -> usize;
+
+    /// The point at `index`, or `None` past the end.
+    fn point(&self, index: usize) -> Option<(f64, f64)>;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The points as one slice, if they are stored that way, so they can be used without
+    /// going through [`SeriesData::point`].
+    fn as_slice(&self) -> Option<&[(f64, f64)]> {
+        None
+    }
+
+    fn iter(&self) -> SeriesIter<'_, Self> {
+        self.iter_range(0..self.len())
+    }
+
+    /// The points at the indices in `range`.
+    fn iter_range(&self, range: Range<usize>) -> SeriesIter<'_, Self> {
+        SeriesIter {
+            data: self,
+            range: range.start.min(self.len())..range.end.min(self.len()),
+        }
+    }
+
+    /// The indices of the points inside `x`, plus the nearest point on either side so a
+    /// line through them reaches the edges of the plot. The points must be sorted by x.
+    fn visible_range(&self, x: Range<f64>) -> Range<usize> {
+        let first_at_least = |bound: f64, inclusive: bool| {
+            let (mut low, mut high) = (0, self.len());
+            while low < high {
+                let middle = (low + high) / 2;
+                let at = self.point(middle).map_or(f64::INFINITY, |point| point.0);
+                if at < bound || (inclusive && at == bound) {
+                    low = middle + 1;
+                } else {
+                    high = middle;
+                }
+            }
+            low
+        };
+        let start = first_at_least(x.start, false);
+        let end = first_at_least(x.end, true);
+        start.saturating_sub(1)..(end + 1).min(self.len())
+    }
+}
+
+/// An iterator over the points of a [`SeriesData`].
+#[derive(Debug)]
+pub struct SeriesIter<'a, D: ?Sized> {
+    data: &'a D,
+    range: Range<usize>,
+}
+
+impl<D: SeriesData + ?Sized> Iterator for SeriesIter<'_, D> {
+    type Item = (f64, f64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.range.next()?;
+        self.data.point(index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<D: SeriesData + ?Sized> DoubleEndedIterator for SeriesIter<'_, D> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let index = self.range.next_back()?;
+        self.data.point(index)
+    }
+}
+
+impl<D: SeriesData + ?Sized> ExactSizeIterator for SeriesIter<'_, D> {}
+
+impl SeriesData for [(f64, f64)] {
+    fn len(&self) -> usize {
+        <[_]>::len(self)
+    }
+
+    fn point(&self, index: usize) -> Option<(f64, f64)> {
+        self.get(index).copied()
+    }
+
+    fn as_slice(&self) -> Option<&[(f64, f64)]> {
+        Some(self)
+    }
+}
+
+impl SeriesData for [(f32, f32)] {
+    fn len(&self) -> usize {
+        <[_]>::len(self)
+    }
+
+    fn point(&self, index: usize) -> Option<(f64, f64)> {
+        self.get(index).map(|&(x, y)| (f64::from(x), f64::from(y)))
+    }
+}
+
+impl<T> SeriesData for Vec<T>
+where
+    [T]: SeriesData,
+{
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn point(&self, index: usize) -> Option<(f64, f64)> {
+        self[..].point(index)
+    }
+
+    fn as_slice(&self) -> Option<&[(f64, f64)]> {
+        SeriesData::as_slice(&self[..])
+    }
+}
+
+impl<D: SeriesData + ?Sized> SeriesData for &D {
+    fn len(&self) -> usize {
+        (**self).len()
+    }
+
+    fn point(&self, index: usize) -> Option<(f64, f64)> {
+        (**self).point(index)
+    }
+
+    fn as_slice(&self) -> Option<&[(f64, f64)]> {
+        (**self).as_slice()
+    }
+}
+
+/// Points stored as separate x and y buffers, as in a columnar table. Extra values in the
+/// longer buffer are ignored.
+#[derive(Debug, Clone, Copy)]
+pub struct Columns<'a> {
+    pub xs: &'a [f64],
+    pub ys: &'a [f64],
+}
+
+impl<'a> Columns<'a> {
+    pub fn new(xs: &'a [f64], ys: &'a [f64]) -> Self {
+        Self { xs, ys }
+    }
+}
+
+impl SeriesData for Columns<'_> {
+    fn len(&self) -> usize {
+        self.xs.len().min(self.ys.len())
+    }
+
+    fn point(&self, index: usize) -> Option<(f64, f64)> {
+        Some((*self.xs.get(index)?, *self.ys.get(index)?))
+    }
+}
+
+/// Values plotted against their index, e.g. samples taken at a fixed rate, with x running
+/// from `start` in steps of `step`.
+#[derive(Debug, Clone, Copy)]
+pub struct Indexed<'a> {
+    pub values: &'a [f64],
+    pub start: f64,
+    pub step: f64,
+}
+
+impl<'a> Indexed<'a> {
+    /// The values at x = 0, 1, 2, ...
+    pub fn new(values: &'a [f64]) -> Self {
+        Self {
+            values,
+            start: 0.0,
+            step: 1.0,
+        }
+    }
+
+    pub fn x_axis(mut self, start: f64, step: f64) -> Self {
+        self.start = start;
+        self.step = step;
+        self
+    }
+}
+
+impl SeriesData for Indexed<'_> {
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    fn point(&self, index: usize) -> Option<(f64, f64)> {
+        let value = *self.values.get(index)?;
+        Some((self.start + index as f64 * self.step, value))
+    }
+
+    /// Computed from the step instead of searched, as the x values are evenly spaced.
+    fn visible_range(&self, x: Range<f64>) -> Range<usize> {
+        if self.step <= 0.0 || self.values.is_empty() {
+            return 0..self.values.len();
+        }
+        // The points at or just outside the edges.
+        let index = |x: f64| ((x - self.start) / self.step).max(0.0);
+        let start = index(x.start).floor() as usize;
+        let end = index(x.end).ceil() as usize + 1;
+        start.min(self.values.len())..end.min(self.values.len())
+    }
+}
@@ -0,0 +1,476 @@
+use std::ops::Range;
+
+/// The minimum horizontal distance between two tick labels, in pixels.
+const MIN_LABEL_SPACING: f64 = 80.0;
+
+const MONTH_NAMES: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// The calendar field a tick step is counted in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TimeUnit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Month,
+    Year,
+}
+
+impl TimeUnit {
+    /// The (average) length of the unit in seconds.
+    fn seconds(self) -> f64 {
+        match self {
+            TimeUnit::Second => 1.0,
+            TimeUnit::Minute => 60.0,
+            TimeUnit::Hour => 3_600.0,
+            TimeUnit::Day => 86_400.0,
+            TimeUnit::Month => 2_629_746.0,
+            TimeUnit::Year => 31_556_952.0,
+        }
+    }
+}
+
+/// The zone timestamps are displayed in. Timestamps themselves stay in UTC.
+///
+/// The system's local zone and named zones such as `Europe/Berlin`, which follow daylight
+/// saving time, need the `time-zones` feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimeZone {
+    #[default]
+    Utc,
+    /// A fixed offset in seconds east of UTC.
+    Fixed(i32),
+    /// The zone of the system.
+    #[cfg(feature = "time-zones")]
+    Local,
+    #[cfg(feature = "time-zones")]
+    Named(chrono_tz::Tz),
+}
+
+impl TimeZone {
+    /// The offset from UTC in seconds at the Unix timestamp `time`.
+    #[cfg_attr(not(feature = "time-zones"), allow(unused_variables))]
+    pub fn offset_at(&self, time: f64) -> i64 {
+        match self {
+            TimeZone::Utc => 0,
+            TimeZone::Fixed(offset) => i64::from(*offset),
+            #[cfg(feature = "time-zones")]
+            TimeZone::Local => local_offset(&chrono::Local, time),
+            #[cfg(feature = "time-zones")]
+            TimeZone::Named(zone) => local_offset(zone, time),
+        }
+    }
+
+    /// Shifts the Unix timestamp `time` to the wall-clock time of the zone, counted in
+    /// seconds as if it were UTC.
+    pub(crate) fn to_local(self, time: f64) -> f64 {
+        time + self.offset_at(time) as f64
+    }
+
+    /// Shifts a wall-clock time of the zone back to a Unix timestamp. Times skipped or
+    /// repeated by a daylight saving change map to one of their neighbours.
+    pub(crate) fn to_utc(self, local: f64) -> f64 {
+        let guess = local - self.offset_at(local) as f64;
+        local - self.offset_at(guess) as f64
+    }
+}
+
+#[cfg(feature = "time-zones")]
+fn local_offset<Z: chrono::TimeZone>(zone: &Z, time: f64) -> i64 {
+    use chrono::Offset;
+
+    chrono::DateTime::from_timestamp(time.floor() as i64, 0).map_or(0, |time| {
+        let offset = zone.offset_from_utc_datetime(&time.naive_utc());
+        i64::from(offset.fix().local_minus_utc())
+    })
+}
+
+/// Formats the Unix timestamp `time` as `YYYY-MM-DD hh:mm:ss` in `zone`, e.g. for cursor
+/// readouts.
+pub fn format_timestamp(time: f64, zone: TimeZone) -> String {
+    let seconds = zone.to_local(time).floor() as i64;
+    let (year, month, day) = civil_from_days(seconds.div_euclid(86_400));
+    let time = seconds.rem_euclid(86_400);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        time / 3_600,
+        time / 60 % 60,
+        time % 60
+    )
+}
+
+/// The candidate steps below one year, finest first.
+const STEPS: [(TimeUnit, u32); 23] = [
+    (TimeUnit::Second, 1),
+    (TimeUnit::Second, 2),
+    (TimeUnit::Second, 5),
+    (TimeUnit::Second, 10),
+    (TimeUnit::Second, 15),
+    (TimeUnit::Second, 30),
+    (TimeUnit::Minute, 1),
+    (TimeUnit::Minute, 2),
+    (TimeUnit::Minute, 5),
+    (TimeUnit::Minute, 10),
+    (TimeUnit::Minute, 15),
+    (TimeUnit::Minute, 30),
+    (TimeUnit::Hour, 1),
+    (TimeUnit::Hour, 2),
+    (TimeUnit::Hour, 3),
+    (TimeUnit::Hour, 6),
+    (TimeUnit::Hour, 12),
+    (TimeUnit::Day, 1),
+    (TimeUnit::Day, 2),
+    (TimeUnit::Day, 7),
+    (TimeUnit::Month, 1),
+    (TimeUnit::Month, 3),
+    (TimeUnit::Month, 6),
+];
+
+/// Tick positions and labels for an axis holding Unix timestamps (seconds, UTC).
+///
+/// Ticks are aligned to and labelled in the wall-clock time of a [`TimeZone`], UTC
+/// unless set with [`DateTimeTicks::in_time_zone`].
+///
+/// The granularity follows the visible span and the pixel width of the axis, so the labels
+/// stay readable from a range of centuries down to a few seconds. Labels are made of a
+/// primary line and, at the first tick and whenever the next coarser field rolls over, a
+/// second "context" line (e.g. the date below an hour label at midnight).
+///
+/// Use it from [`Chart::build_chart`](crate::Chart::build_chart) by binding the key points
+/// to the range with `BindKeyPoints::with_key_points` and passing [`DateTimeTicks::label`]
+/// to the mesh's label formatter.
+#[derive(Debug, Clone)]
+pub struct DateTimeTicks {
+    range: Range<f64>,
+    unit: TimeUnit,
+    count: u32,
+    time_zone: TimeZone,
+}
+
+impl DateTimeTicks {
+    /// Picks the tick step for `range` drawn across `pixel_width` pixels.
+    pub fn new(range: Range<f64>, pixel_width: f64) -> Self {
+        let span = (range.end - range.start).abs();
+        let max_labels = (pixel_width / MIN_LABEL_SPACING).floor().max(1.0);
+        let target = span / max_labels;
+        let (unit, count) = STEPS
+            .iter()
+            .copied()
+            .find(|&(unit, count)| unit.seconds() * f64::from(count) >= target)
+            .unwrap_or_else(|| {
+                (
+                    TimeUnit::Year,
+                    nice_years(target / TimeUnit::Year.seconds()),
+                )
+            });
+        let range = if range.start <= range.end {
+            range
+        } else {
+            range.end..range.start
+        };
+        Self {
+            range,
+            unit,
+            count,
+            time_zone: TimeZone::Utc,
+        }
+    }
+
+    /// Aligns and labels the ticks in `zone`, e.g. [`BuildContext::time_zone`].
+    ///
+    /// [`BuildContext::time_zone`]: crate::BuildContext::time_zone
+    pub fn in_time_zone(mut self, zone: TimeZone) -> Self {
+        self.time_zone = zone;
+        self
+    }
+
+    pub fn time_zone(&self) -> TimeZone {
+        self.time_zone
+    }
+
+    /// The unit ticks are counted in.
+    pub fn unit(&self) -> TimeUnit {
+        self.unit
+    }
+
+    /// How many units lie between two ticks.
+    pub fn step(&self) -> u32 {
+        self.count
+    }
+
+    /// The tick positions inside the range, aligned to calendar boundaries of the time
+    /// zone.
+    pub fn key_points(&self) -> Vec<f64> {
+        self.iter_key_points().collect()
+    }
+
+    /// The tick positions inside the range, computed as they are taken.
+    fn iter_key_points(&self) -> impl Iterator<Item = f64> + '_ {
+        let Range { start, end } = self.range;
+        let zone = self.time_zone;
+        let finite = start.is_finite() && end.is_finite();
+        finite
+            .then(|| self.local_key_points(zone.to_local(start), zone.to_local(end)))
+            .into_iter()
+            .flatten()
+            .map(move |local| zone.to_utc(local))
+            .filter(move |time| (start..=end).contains(time))
+    }
+
+    /// The tick positions between the wall-clock times `start` and `end`.
+    fn local_key_points(&self, start: f64, end: f64) -> Box<dyn Iterator<Item = f64>> {
+        let count = i64::from(self.count);
+        match self.unit {
+            TimeUnit::Month | TimeUnit::Year => {
+                let months = if self.unit == TimeUnit::Year {
+                    12 * count
+                } else {
+                    count
+                };
+                let (year, month, _) = civil_from_days(start.div_euclid(86_400.0) as i64);
+                // Months since year 0, rounded up to the step.
+                let first = div_ceil(year * 12 + i64::from(month) - 1, months) * months;
+                Box::new(
+                    (0..)
+                        .map(move |step| {
+                            let index = first + step * months;
+                            days_from_civil(index.div_euclid(12), index.rem_euclid(12) as u32 + 1)
+                                as f64
+                                * 86_400.0
+                        })
+                        .take_while(move |&time| time <= end)
+                        .filter(move |&time| time >= start),
+                )
+            }
+            unit => {
+                let step = unit.seconds() * f64::from(self.count);
+                let first = (start / step).ceil() * step;
+                Box::new(
+                    std::iter::successors(Some(first), move |time| Some(time + step))
+                        .take_while(move |&time| time <= end),
+                )
+            }
+        }
+    }
+
+    /// Formats the tick at `value`, adding a context line where the coarser field changes.
+    pub fn label(&self, value: f64) -> String {
+        let seconds = self.time_zone.to_local(value).floor() as i64;
+        let days = seconds.div_euclid(86_400);
+        let time = seconds.rem_euclid(86_400);
+        let (year, month, day) = civil_from_days(days);
+        let (hour, minute, second) = (time / 3_600, time / 60 % 60, time % 60);
+        let month_name = MONTH_NAMES[month as usize - 1];
+
+        // Only the first tick is needed, not all of them for every label.
+        let first = !matches!(self.iter_key_points().next(), Some(first) if value > first);
+        let (primary, context, rollover) = match self.unit {
+            TimeUnit::Second => (
+                format!("{:02}:{:02}:{:02}", hour, minute, second),
+                format!("{} {} {}", month_name, day, year),
+                time == 0,
+            ),
+            TimeUnit::Minute | TimeUnit::Hour => (
+                format!("{:02}:{:02}", hour, minute),
+                format!("{} {} {}", month_name, day, year),
+                time == 0,
+            ),
+            TimeUnit::Day => (
+                format!("{} {}", month_name, day),
+                year.to_string(),
+                month == 1 && day == 1,
+            ),
+            TimeUnit::Month => (month_name.to_string(), year.to_string(), month == 1),
+            TimeUnit::Year => return year.to_string(),
+        };
+
+        if first || rollover {
+            format!("{}\n{}", primary, context)
+        } else {
+            primary
+        }
+    }
+}
+
+/// Formats a signed span of seconds with its two most significant fields, e.g. `-2d 3h`.
+pub(crate) fn format_duration(seconds: f64) -> String {
+    // Rounded to the milliseconds shown for short spans first, so 59.9999s becomes 1m 0s
+    // rather than 60.000s.
+    let mut rest = (seconds.abs() * 1000.0).round() / 1000.0;
+    let sign = if seconds < 0.0 && rest > 0.0 { "-" } else { "" };
+    if rest < 60.0 {
+        return format!("{}{:.3}s", sign, rest);
+    }
+    let mut fields = vec![];
+    for &(unit, suffix) in &[
+        (TimeUnit::Day, "d"),
+        (TimeUnit::Hour, "h"),
+        (TimeUnit::Minute, "m"),
+        (TimeUnit::Second, "s"),
+    ] {
+        let count = (rest / unit.seconds()).floor();
+        if count > 0.0 || !fields.is_empty() {
+            fields.push(format!("{}{}", count, suffix));
+            rest -= count * unit.seconds();
+        }
+    }
+    fields.truncate(2);
+    format!("{}{}", sign, fields.join(" "))
+}
+
+/// Rounds a number of years up to 1, 2 or 5 times a power of ten.
+fn nice_years(years: f64) -> u32 {
+    let magnitude = 10f64.powf(years.max(1.0).log10().floor());
+    let nice = [1.0, 2.0, 5.0, 10.0]
+        .iter()
+        .map(|m| m * magnitude)
+        .find(|&step| step >= years)
+        .unwrap_or(10.0 * magnitude);
+    nice.min(f64::from(u32::MAX)) as u32
+}
+
+fn div_ceil(a: i64, b: i64) -> i64 {
+    -(-a).div_euclid(b)
+}
+
+/// Converts days since the Unix epoch into a (year, month, day) civil date.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Converts the first day of the given month into days since the Unix epoch.
+fn days_from_civil(year: i64, month: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let month = i64::from(month);
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_durations() {
+        let cases = [
+            (0.0, "0.000s"),
+            (0.25, "0.250s"),
+            (59.5, "59.500s"),
+            (-0.5, "-0.500s"),
+            (-0.0001, "0.000s"),
+            (59.9999, "1m 0s"),
+            (60.0, "1m 0s"),
+            (3599.9999, "1h 0m"),
+            (3600.0, "1h 0m"),
+            (-3661.0, "-1h 1m"),
+            (86399.0, "23h 59m"),
+            (2.0 * 86400.0 + 3.0 * 3600.0 + 59.0, "2d 3h"),
+            (-(2.0 * 86400.0 + 0.5), "-2d 0h"),
+        ];
+        for &(seconds, formatted) in &cases {
+            assert_eq!(format_duration(seconds), formatted, "{}", seconds);
+        }
+    }
+
+    const DAY: f64 = 86_400.0;
+
+    fn ticks(start: f64, end: f64, unit: TimeUnit, count: u32) -> DateTimeTicks {
+        DateTimeTicks {
+            range: start * DAY..end * DAY,
+            unit,
+            count,
+            time_zone: TimeZone::Utc,
+        }
+    }
+
+    #[test]
+    fn converts_civil_dates() {
+        let dates = [
+            (0, (1970, 1, 1)),
+            (-1, (1969, 12, 31)),
+            (59, (1970, 3, 1)),
+            (11_016, (2000, 2, 29)),
+            (-25_508, (1900, 3, 1)),
+            (-135_081, (1600, 2, 29)),
+            (-719_468, (0, 3, 1)),
+        ];
+        for &(days, date) in &dates {
+            assert_eq!(civil_from_days(days), date, "{}", days);
+        }
+        for days in (-1_000_000..1_000_000).step_by(97) {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month) + i64::from(day) - 1, days);
+        }
+        for month in -24_000i64..24_000 {
+            let (year, month) = (month.div_euclid(12), month.rem_euclid(12) as u32 + 1);
+            assert_eq!(
+                civil_from_days(days_from_civil(year, month)),
+                (year, month, 1)
+            );
+        }
+    }
+
+    #[test]
+    fn rounds_division_up() {
+        let cases = [(7, 3, 3), (6, 3, 2), (0, 5, 0), (-6, 3, -2), (-7, 3, -2)];
+        for &(a, b, quotient) in &cases {
+            assert_eq!(div_ceil(a, b), quotient, "{} / {}", a, b);
+        }
+    }
+
+    #[test]
+    fn steps_by_months_and_years() {
+        let quarters = ticks(14.0, 379.0, TimeUnit::Month, 3);
+        let days: Vec<_> = quarters.key_points().iter().map(|t| t / DAY).collect();
+        assert_eq!(days, vec![90.0, 181.0, 273.0, 365.0]);
+        let labels: Vec<_> = quarters
+            .key_points()
+            .iter()
+            .map(|&t| quarters.label(t))
+            .collect();
+        assert_eq!(labels, vec!["Apr\n1970", "Jul", "Oct", "Jan\n1971"]);
+
+        // Across the epoch and the leap years 1968 and 1972.
+        let years = ticks(-1_600.0, 1_500.0, TimeUnit::Year, 2);
+        let days: Vec<_> = years.key_points().iter().map(|t| t / DAY).collect();
+        assert_eq!(days, vec![-1_461.0, -731.0, 0.0, 730.0, 1_461.0]);
+        assert_eq!(years.label(-731.0 * DAY), "1968");
+
+        // A range starting on a key point includes it.
+        let months = ticks(-31.0, 0.0, TimeUnit::Month, 1);
+        assert_eq!(months.key_points(), vec![-31.0 * DAY, 0.0]);
+    }
+
+    #[test]
+    fn adds_context_to_the_first_tick_and_rollovers() {
+        let hours = ticks(-0.25, 0.25, TimeUnit::Hour, 3);
+        let labels: Vec<_> = hours.key_points().iter().map(|&t| hours.label(t)).collect();
+        assert_eq!(
+            labels,
+            vec![
+                "18:00\nDec 31 1969",
+                "21:00",
+                "00:00\nJan 1 1970",
+                "03:00",
+                "06:00"
+            ]
+        );
+    }
+}
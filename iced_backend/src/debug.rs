@@ -0,0 +1,73 @@
+use crate::ViewState;
+use iced::canvas::{Frame, Text};
+use iced::{Color, Point, Size};
+use std::time::Duration;
+
+/// The distance between the debug overlay and the top left corner of the canvas.
+const INSET: f32 = 4.0;
+/// The padding between the overlay box and its text.
+const PADDING: f32 = 4.0;
+const TEXT_SIZE: f32 = 12.0;
+/// A rough average glyph width relative to the text size, used to size the box.
+const GLYPH_WIDTH: f32 = 0.6;
+
+/// What the last draw of a [`ChartWidget`](crate::ChartWidget) cost, as shown by
+/// [`ChartWidget::debug_overlay`](crate::ChartWidget::debug_overlay).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FrameStats {
+    /// The duration of the last chart build, which may be older than the last draw.
+    pub build: Duration,
+    /// Whether the last draw reused the cached chart instead of building it.
+    pub cache_hit: bool,
+    /// The duration of [`Chart::build_overlay`](crate::Chart::build_overlay).
+    pub overlay: Duration,
+    /// The duration of drawing the widget's own layers, such as the legend and tooltips.
+    pub layers: Duration,
+    /// The points the chart reported through
+    /// [`BuildContext::report_decimation`](crate::BuildContext::report_decimation) before and
+    /// after decimating them.
+    pub points: usize,
+    pub drawn_points: usize,
+    /// The elements registered for hit testing.
+    pub registered: usize,
+}
+
+impl FrameStats {
+    /// Draws the stats and the ranges of `view` into the top left corner of the canvas.
+    pub(crate) fn draw(&self, frame: &mut Frame, view: &ViewState) {
+        let millis = |duration: Duration| duration.as_secs_f64() * 1000.0;
+        let mut lines = vec![
+            format!(
+                "build   {:>7.2} ms{}",
+                millis(self.build),
+                if self.cache_hit { " (cached)" } else { "" }
+            ),
+            format!("overlay {:>7.2} ms", millis(self.overlay)),
+            format!("layers  {:>7.2} ms", millis(self.layers)),
+            format!("points  {} -> {}", self.points, self.drawn_points),
+            format!("hits    {}", self.registered),
+            format!("x       {} .. {}", view.x.start, view.x.end),
+            format!("y       {} .. {}", view.y.start, view.y.end),
+        ];
+        if let Some(y) = &view.secondary_y {
+            lines.push(format!("y2      {} .. {}", y.start, y.end));
+        }
+        let columns = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0) as f32;
+        let size = Size::new(
+            columns * TEXT_SIZE * GLYPH_WIDTH + 2.0 * PADDING,
+            lines.len() as f32 * TEXT_SIZE + 2.0 * PADDING,
+        );
+        frame.fill_rectangle(
+            Point::new(INSET, INSET),
+            size,
+            Color::from_rgba(0.0, 0.0, 0.0, 0.75),
+        );
+        frame.fill_text(Text {
+            content: lines.join("\n"),
+            position: Point::new(INSET + PADDING, INSET + PADDING),
+            color: Color::WHITE,
+            size: TEXT_SIZE,
+            ..Text::default()
+        });
+    }
+}
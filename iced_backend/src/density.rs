@@ -0,0 +1,57 @@
+/// How much room tick labels need, used to derive label counts from the plot size.
+///
+/// A chart at 300 pixels wide should not ask for as many labels as one at 1600 pixels;
+/// [`BuildContext::x_labels`](crate::BuildContext::x_labels) and friends turn the pixel
+/// size of the plotting area into a count that keeps labels apart.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LabelDensity {
+    /// The estimated width of an x label plus the gap to its neighbour, in pixels.
+    pub x_label_width: f32,
+    /// The estimated height of a y label plus the gap to its neighbour, in pixels.
+    pub y_label_height: f32,
+    /// The fewest labels suggested along an axis.
+    pub min_labels: usize,
+    /// The most labels suggested along an axis.
+    pub max_labels: usize,
+}
+
+impl LabelDensity {
+    /// Sizes the label estimates for labels of `chars` characters at `font_size` pixels.
+    pub fn for_labels(chars: usize, font_size: f32) -> Self {
+        Self {
+            x_label_width: chars as f32 * font_size * 0.6 + 2.0 * font_size,
+            y_label_height: 2.5 * font_size,
+            ..Self::default()
+        }
+    }
+
+    /// The suggested number of x labels for a plotting area `width` pixels wide.
+    pub fn x_labels(&self, width: f32) -> usize {
+        self.count(width, self.x_label_width)
+    }
+
+    /// The suggested number of y labels for a plotting area `height` pixels high.
+    pub fn y_labels(&self, height: f32) -> usize {
+        self.count(height, self.y_label_height)
+    }
+
+    fn count(&self, length: f32, extent: f32) -> usize {
+        let count = if extent > 0.0 && length.is_finite() {
+            (length / extent).floor().max(0.0) as usize
+        } else {
+            0
+        };
+        count.max(self.min_labels).min(self.max_labels)
+    }
+}
+
+impl Default for LabelDensity {
+    fn default() -> Self {
+        Self {
+            x_label_width: 80.0,
+            y_label_height: 40.0,
+            min_labels: 2,
+            max_labels: 20,
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use std::fmt;
+
+/// The examples kept of every kind of problem, so a chart drawing millions of points off
+/// the frame doesn't fill the memory with diagnostics.
+const MAX_EXAMPLES: usize = 3;
+
+/// A reason a drawing call leaves nothing visible, or less than asked for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiagnosticKind {
+    /// A coordinate too far from the frame to be drawn, usually mapped from an infinite or
+    /// huge data value. The call fails with
+    /// [`IcedError::CoordinateOutOfRange`](crate::IcedError::CoordinateOutOfRange).
+    CoordinateOutOfRange,
+    /// Everything drawn lies outside the frame.
+    OutsideFrame,
+    /// The color is fully transparent.
+    Transparent,
+    /// A line or outline is zero pixels wide.
+    ZeroWidth,
+    /// The font could not lay out or draw the text, e.g. because it is not available.
+    Font,
+}
+
+impl fmt::Display for DiagnosticKind {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let description = match self {
+            DiagnosticKind::CoordinateOutOfRange => "used a coordinate out of range",
+            DiagnosticKind::OutsideFrame => "drew outside the frame",
+            DiagnosticKind::Transparent => "used a transparent color",
+            DiagnosticKind::ZeroWidth => "used a zero width stroke",
+            DiagnosticKind::Font => "failed to render text",
+        };
+        fmt.write_str(description)
+    }
+}
+
+/// The calls of one backend method that ran into one kind of problem.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub kind: DiagnosticKind,
+    /// The backend method, such as `draw_line`.
+    pub call: &'static str,
+    pub count: usize,
+    /// Details of the first few calls, such as their coordinates.
+    pub examples: Vec<String>,
+}
+
+/// What an [`IcedBackend`](crate::IcedBackend) found wrong with the calls it was handed,
+/// see [`IcedBackend::with_diagnostics`](crate::IcedBackend::with_diagnostics) and
+/// [`ChartWidget::validate_drawing`](crate::ChartWidget::validate_drawing).
+///
+/// It helps to find out why parts of a chart are invisible. Its `Display` implementation
+/// lists one line per method and problem.
+///
+/// Coordinates reach the backend as integer pixels, so NaN data values can't be told
+/// apart from 0; infinite ones show up as [`DiagnosticKind::CoordinateOutOfRange`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Diagnostics {
+    entries: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn entries(&self) -> &[Diagnostic] {
+        &self.entries
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The number of calls that ran into `kind`.
+    pub fn count(&self, kind: DiagnosticKind) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| entry.kind == kind)
+            .map(|entry| entry.count)
+            .sum()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Counts a `call` that ran into `kind`, described by `example` if it is one of the
+    /// first.
+    pub(crate) fn record(
+        &mut self,
+        kind: DiagnosticKind,
+        call: &'static str,
+        example: impl FnOnce() -> String,
+    ) {
+        match self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.kind == kind && entry.call == call)
+        {
+            Some(entry) => {
+                entry.count += 1;
+                if entry.examples.len() < MAX_EXAMPLES {
+                    entry.examples.push(example());
+                }
+            }
+            None => self.entries.push(Diagnostic {
+                kind,
+                call,
+                count: 1,
+                examples: vec![example()],
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostics {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        for entry in &self.entries {
+            writeln!(
+                fmt,
+                "{}: {} call(s) {}, e.g. {}",
+                entry.call,
+                entry.count,
+                entry.kind,
+                entry.examples.join("; ")
+            )?;
+        }
+        Ok(())
+    }
+}
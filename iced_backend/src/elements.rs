@@ -0,0 +1,335 @@
+use iced::{Color, Point};
+use plotters::element::{Drawable, PointCollection};
+use plotters::style::ShapeStyle;
+use plotters_backend::{BackendCoord, DrawingBackend, DrawingErrorKind};
+
+/// The outline of a point marker.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MarkerShape {
+    Circle,
+    Square,
+    Diamond,
+    Triangle,
+    Cross,
+    Plus,
+    /// A closed polygon through these vertices, in multiples of the marker size relative to
+    /// its center, with y pointing down.
+    Custom(&'static [(f32, f32)]),
+}
+
+impl MarkerShape {
+    /// All shapes, in the order palettes hand them out.
+    pub const ALL: [MarkerShape; 6] = [
+        MarkerShape::Circle,
+        MarkerShape::Square,
+        MarkerShape::Triangle,
+        MarkerShape::Diamond,
+        MarkerShape::Cross,
+        MarkerShape::Plus,
+    ];
+
+    /// The filled outline of a marker reaching `size` from `center`, or `None` for the
+    /// circle and the shapes made of lines.
+    pub(crate) fn outline(self, (x, y): (f32, f32), size: f32) -> Option<Vec<(f32, f32)>> {
+        let s = size;
+        match self {
+            MarkerShape::Square => Some(vec![
+                (x - s, y - s),
+                (x + s, y - s),
+                (x + s, y + s),
+                (x - s, y + s),
+            ]),
+            MarkerShape::Diamond => Some(vec![(x, y - s), (x + s, y), (x, y + s), (x - s, y)]),
+            MarkerShape::Triangle => {
+                let half = s * 3f32.sqrt() / 2.0;
+                Some(vec![
+                    (x, y - s),
+                    (x + half, y + s / 2.0),
+                    (x - half, y + s / 2.0),
+                ])
+            }
+            MarkerShape::Custom(vertices) => Some(
+                vertices
+                    .iter()
+                    .map(|(dx, dy)| (x + dx * s, y + dy * s))
+                    .collect(),
+            ),
+            MarkerShape::Circle | MarkerShape::Cross | MarkerShape::Plus => None,
+        }
+    }
+
+    /// Appends the triangles of a marker reaching `size` from `center` in `color` to a
+    /// mesh, or returns `false` for [`MarkerShape::Custom`], whose outline need not be
+    /// convex. The lines of [`MarkerShape::Cross`] and [`MarkerShape::Plus`] become bars
+    /// 1.5 pixels wide.
+    pub(crate) fn triangulate(
+        self,
+        center: (f32, f32),
+        size: f32,
+        color: Color,
+        vertices: &mut Vec<(Point, Color)>,
+        indices: &mut Vec<u32>,
+    ) -> bool {
+        let mut fan = |corners: &mut dyn Iterator<Item = (f32, f32)>| {
+            let first = vertices.len() as u32;
+            vertices.extend(corners.map(|(x, y)| (Point::new(x, y), color)));
+            let last = vertices.len() as u32;
+            for corner in first + 1..last.saturating_sub(1) {
+                indices.extend_from_slice(&[first, corner, corner + 1]);
+            }
+        };
+        if let MarkerShape::Custom(_) = self {
+            return false;
+        }
+        if let Some(outline) = self.outline(center, size) {
+            fan(&mut outline.into_iter());
+        } else if let Some(lines) = self.strokes(center, size) {
+            for [(x0, y0), (x1, y1)] in lines.iter().copied() {
+                let length = ((x1 - x0).powi(2) + (y1 - y0).powi(2))
+                    .sqrt()
+                    .max(f32::EPSILON);
+                let (nx, ny) = ((y0 - y1) / length * 0.75, (x1 - x0) / length * 0.75);
+                fan(&mut [
+                    (x0 + nx, y0 + ny),
+                    (x1 + nx, y1 + ny),
+                    (x1 - nx, y1 - ny),
+                    (x0 - nx, y0 - ny),
+                ]
+                .iter()
+                .copied());
+            }
+        } else {
+            // Enough sides that they stay within a quarter pixel of the circle.
+            let sides = (std::f32::consts::PI / (1.0 - 0.25 / size.max(0.5)).acos())
+                .ceil()
+                .clamp(8.0, 64.0) as u32;
+            let (x, y) = center;
+            fan(&mut (0..sides).map(|side| {
+                let angle = side as f32 / sides as f32 * std::f32::consts::TAU;
+                (x + size * angle.cos(), y + size * angle.sin())
+            }));
+        }
+        true
+    }
+
+    /// The lines of a marker made of lines, or `None` for the filled shapes.
+    pub(crate) fn strokes(self, (x, y): (f32, f32), size: f32) -> Option<[[(f32, f32); 2]; 2]> {
+        let s = size;
+        match self {
+            MarkerShape::Cross => Some([
+                [(x - s, y - s), (x + s, y + s)],
+                [(x - s, y + s), (x + s, y - s)],
+            ]),
+            MarkerShape::Plus => Some([[(x - s, y), (x + s, y)], [(x, y - s), (x, y + s)]]),
+            _ => None,
+        }
+    }
+}
+
+/// A point marker of any [`MarkerShape`], usable with `PointSeries::of_element`.
+pub struct Marker<Coord> {
+    center: Coord,
+    size: i32,
+    style: ShapeStyle,
+    shape: MarkerShape,
+}
+
+impl<Coord> Marker<Coord> {
+    /// A marker reaching `size` pixels from its center.
+    pub fn new<S: Into<ShapeStyle>>(
+        center: Coord,
+        size: i32,
+        style: S,
+        shape: MarkerShape,
+    ) -> Self {
+        Self {
+            center,
+            size,
+            style: style.into(),
+            shape,
+        }
+    }
+}
+
+impl<'a, Coord: 'a> PointCollection<'a, Coord> for &'a Marker<Coord> {
+    type Point = &'a Coord;
+    type IntoIter = std::iter::Once<&'a Coord>;
+    fn point_iter(self) -> std::iter::Once<&'a Coord> {
+        std::iter::once(&self.center)
+    }
+}
+
+impl<Coord, DB: DrawingBackend> Drawable<DB> for Marker<Coord> {
+    fn draw<I: Iterator<Item = BackendCoord>>(
+        &self,
+        mut points: I,
+        backend: &mut DB,
+        _: (u32, u32),
+    ) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+        let (x, y) = match points.next() {
+            Some(point) => point,
+            None => return Ok(()),
+        };
+        let s = self.size;
+        let outline = |vertices: Vec<BackendCoord>, backend: &mut DB| {
+            if self.style.filled {
+                backend.fill_polygon(vertices, &self.style)
+            } else {
+                let mut closed = vertices.clone();
+                closed.extend(vertices.first().copied());
+                backend.draw_path(closed, &self.style)
+            }
+        };
+        match self.shape {
+            MarkerShape::Circle => {
+                backend.draw_circle((x, y), s as u32, &self.style, self.style.filled)
+            }
+            MarkerShape::Square => outline(
+                vec![
+                    (x - s, y - s),
+                    (x + s, y - s),
+                    (x + s, y + s),
+                    (x - s, y + s),
+                ],
+                backend,
+            ),
+            MarkerShape::Diamond => outline(
+                vec![(x, y - s), (x + s, y), (x, y + s), (x - s, y)],
+                backend,
+            ),
+            MarkerShape::Triangle => {
+                // The vertices lie on the circle of radius `s`, which centers the triangle.
+                let half = (f64::from(s) * 3f64.sqrt() / 2.0).round() as i32;
+                outline(
+                    vec![(x, y - s), (x + half, y + s / 2), (x - half, y + s / 2)],
+                    backend,
+                )
+            }
+            MarkerShape::Cross => {
+                backend.draw_line((x - s, y - s), (x + s, y + s), &self.style)?;
+                backend.draw_line((x - s, y + s), (x + s, y - s), &self.style)
+            }
+            MarkerShape::Plus => {
+                backend.draw_line((x - s, y), (x + s, y), &self.style)?;
+                backend.draw_line((x, y - s), (x, y + s), &self.style)
+            }
+            MarkerShape::Custom(vertices) => outline(
+                vertices
+                    .iter()
+                    .map(|(dx, dy)| {
+                        (
+                            x + (dx * s as f32).round() as i32,
+                            y + (dy * s as f32).round() as i32,
+                        )
+                    })
+                    .collect(),
+                backend,
+            ),
+        }
+    }
+}
+
+/// The on/off rhythm of a stroked line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DashPattern {
+    Solid,
+    Dashed,
+    Dotted,
+    DashDot,
+}
+
+impl DashPattern {
+    /// All patterns, in the order palettes hand them out.
+    pub const ALL: [DashPattern; 4] = [
+        DashPattern::Solid,
+        DashPattern::Dashed,
+        DashPattern::Dotted,
+        DashPattern::DashDot,
+    ];
+
+    /// Alternating drawn and skipped lengths in pixels, starting with a drawn one.
+    fn lengths(self) -> &'static [f64] {
+        match self {
+            DashPattern::Solid => &[],
+            DashPattern::Dashed => &[8.0, 5.0],
+            DashPattern::Dotted => &[2.0, 4.0],
+            DashPattern::DashDot => &[8.0, 4.0, 2.0, 4.0],
+        }
+    }
+}
+
+/// A polyline stroked with a [`DashPattern`]; the pattern runs on across vertices.
+pub struct DashedPath<Coord> {
+    points: Vec<Coord>,
+    style: ShapeStyle,
+    pattern: DashPattern,
+}
+
+impl<Coord> DashedPath<Coord> {
+    pub fn new<P: Into<Vec<Coord>>, S: Into<ShapeStyle>>(
+        points: P,
+        style: S,
+        pattern: DashPattern,
+    ) -> Self {
+        Self {
+            points: points.into(),
+            style: style.into(),
+            pattern,
+        }
+    }
+}
+
+impl<'a, Coord: 'a> PointCollection<'a, Coord> for &'a DashedPath<Coord> {
+    type Point = &'a Coord;
+    type IntoIter = &'a [Coord];
+    fn point_iter(self) -> &'a [Coord] {
+        &self.points
+    }
+}
+
+impl<Coord, DB: DrawingBackend> Drawable<DB> for DashedPath<Coord> {
+    fn draw<I: Iterator<Item = BackendCoord>>(
+        &self,
+        points: I,
+        backend: &mut DB,
+        _: (u32, u32),
+    ) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+        let lengths = self.pattern.lengths();
+        if lengths.is_empty() {
+            return backend.draw_path(points, &self.style);
+        }
+
+        let mut index = 0;
+        let mut left = lengths[0];
+        let mut points = points.map(|(x, y)| (f64::from(x), f64::from(y)));
+        let mut from = match points.next() {
+            Some(point) => point,
+            None => return Ok(()),
+        };
+        for to in points {
+            let length = (to.0 - from.0).hypot(to.1 - from.1);
+            let mut done = 0.0;
+            while done < length {
+                let step = left.min(length - done);
+                if index % 2 == 0 {
+                    let at = |t: f64| {
+                        let t = t / length;
+                        (
+                            (from.0 + (to.0 - from.0) * t).round() as i32,
+                            (from.1 + (to.1 - from.1) * t).round() as i32,
+                        )
+                    };
+                    backend.draw_line(at(done), at(done + step), &self.style)?;
+                }
+                done += step;
+                left -= step;
+                if left <= 0.0 {
+                    index = (index + 1) % lengths.len();
+                    left = lengths[index];
+                }
+            }
+            from = to;
+        }
+        Ok(())
+    }
+}
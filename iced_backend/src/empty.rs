@@ -0,0 +1,105 @@
+use iced::canvas::{Frame, Text};
+use iced::{Color, HorizontalAlignment, Point, Rectangle, VerticalAlignment};
+
+type Draw = Box<dyn Fn(&mut Frame, Rectangle)>;
+
+/// What the widget shows over the plotting area when its series have no point in the
+/// visible ranges, instead of a blank grid, see
+/// [`ChartWidget::empty_state`](crate::ChartWidget::empty_state).
+///
+/// The plot counts as empty when the chart registered series for hit testing (see
+/// [`BuildContext::register_points`](crate::BuildContext::register_points) and
+/// [`BuildContext::set_series_color`](crate::BuildContext::set_series_color)) but none of
+/// their elements lies within the plotting area. Charts that register nothing never show
+/// it.
+pub struct EmptyState {
+    message: String,
+    hint: Option<String>,
+    color: Color,
+    text_size: f32,
+    custom: Option<Draw>,
+}
+
+impl EmptyState {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            hint: None,
+            color: Color::from_rgb(0.4, 0.4, 0.4),
+            text_size: 18.0,
+            custom: None,
+        }
+    }
+
+    /// Content drawn by `draw` onto the plotting area, whose bounds it is given.
+    pub fn custom<F>(draw: F) -> Self
+    where
+        F: 'static + Fn(&mut Frame, Rectangle),
+    {
+        Self {
+            custom: Some(Box::new(draw)),
+            ..Self::default()
+        }
+    }
+
+    /// Adds a smaller line below the message suggesting what to do, such as "Zoom out or
+    /// pick another time range".
+    pub fn hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Sets the size of the message; the hint is drawn at three quarters of it.
+    pub fn text_size(mut self, size: f32) -> Self {
+        self.text_size = size;
+        self
+    }
+
+    pub(crate) fn draw(&self, frame: &mut Frame, plot: Rectangle) {
+        if let Some(custom) = &self.custom {
+            return custom(frame, plot);
+        }
+        let center = plot.center();
+        let text = |content: &str, y: f32, size: f32, color: Color| Text {
+            content: content.to_owned(),
+            position: Point::new(center.x, y),
+            color,
+            size,
+            horizontal_alignment: HorizontalAlignment::Center,
+            vertical_alignment: VerticalAlignment::Center,
+            ..Text::default()
+        };
+        match &self.hint {
+            Some(hint) => {
+                let hint_size = self.text_size * 0.75;
+                frame.fill_text(text(
+                    &self.message,
+                    center.y - hint_size / 2.0,
+                    self.text_size,
+                    self.color,
+                ));
+                frame.fill_text(text(
+                    hint,
+                    center.y + self.text_size / 2.0 + 2.0,
+                    hint_size,
+                    Color {
+                        a: self.color.a * 0.7,
+                        ..self.color
+                    },
+                ));
+            }
+            None => frame.fill_text(text(&self.message, center.y, self.text_size, self.color)),
+        }
+    }
+}
+
+impl Default for EmptyState {
+    fn default() -> Self {
+        Self::new("No data in range")
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{BuildError, IcedError};
+use plotters::drawing::DrawingAreaErrorKind;
+use plotters_backend::DrawingErrorKind;
+use std::error::Error;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Why a chart could not be drawn, as reported by
+/// [`ChartWidget::on_error`](crate::ChartWidget::on_error).
+///
+/// Errors of plotters' drawing areas and chart contexts convert into it, as do the errors
+/// returned by [`Chart::build_chart`](crate::Chart::build_chart), which keep their kind if
+/// they came from plotters.
+#[derive(Debug, Clone)]
+pub enum ChartError {
+    /// The backend could not draw, e.g. into a collapsed frame.
+    Backend(IcedError),
+    /// Text could not be laid out in its font.
+    Font(String),
+    /// The drawing areas could not be laid out, e.g. because the margins or label areas
+    /// leave no room for the plotting area.
+    Layout(String),
+    /// The chart's own build code failed.
+    Build(Arc<dyn Error + Send + Sync>),
+    /// A file dropped onto the chart could not be read or parsed, at `line` if known.
+    File {
+        path: PathBuf,
+        line: Option<usize>,
+        message: String,
+    },
+}
+
+impl fmt::Display for ChartError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ChartError::Backend(error) => write!(fmt, "{}", error),
+            ChartError::Font(error) => write!(fmt, "font error: {}", error),
+            ChartError::Layout(error) => write!(fmt, "layout error: {}", error),
+            ChartError::Build(error) => write!(fmt, "{}", error),
+            ChartError::File {
+                path,
+                line: Some(line),
+                message,
+            } => write!(fmt, "{}:{}: {}", path.display(), line, message),
+            ChartError::File {
+                path,
+                line: None,
+                message,
+            } => write!(fmt, "{}: {}", path.display(), message),
+        }
+    }
+}
+
+impl Error for ChartError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ChartError::Backend(error) => Some(error),
+            ChartError::Build(error) => Some(&**error),
+            ChartError::Font(_) | ChartError::Layout(_) | ChartError::File { .. } => None,
+        }
+    }
+}
+
+impl From<IcedError> for ChartError {
+    fn from(error: IcedError) -> Self {
+        ChartError::Backend(error)
+    }
+}
+
+impl From<DrawingErrorKind<IcedError>> for ChartError {
+    fn from(error: DrawingErrorKind<IcedError>) -> Self {
+        match error {
+            DrawingErrorKind::DrawingError(error) => ChartError::Backend(error),
+            DrawingErrorKind::FontError(error) => ChartError::Font(error.to_string()),
+        }
+    }
+}
+
+impl From<DrawingAreaErrorKind<IcedError>> for ChartError {
+    fn from(error: DrawingAreaErrorKind<IcedError>) -> Self {
+        match error {
+            DrawingAreaErrorKind::BackendError(error) => error.into(),
+            error => ChartError::Layout(error.to_string()),
+        }
+    }
+}
+
+impl From<BuildError> for ChartError {
+    fn from(error: BuildError) -> Self {
+        let error = match error.downcast::<DrawingAreaErrorKind<IcedError>>() {
+            Ok(error) => return (*error).into(),
+            Err(error) => error,
+        };
+        match error.downcast::<IcedError>() {
+            Ok(error) => ChartError::Backend(*error),
+            Err(error) => ChartError::Build(error.into()),
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use crate::hit::HitRegistry;
+use crate::legend::LegendEntry;
+use crate::{SeriesId, ViewState};
+use iced::Rectangle;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// The registered points inside the plotting area `plot` as comma-separated values: a
+/// header row, then one row per distinct x with a column per series, left empty where a
+/// series has no point at that x. [`CsvParser`](crate::CsvParser) reads it back.
+///
+/// Series are named after their `entries`, or `series <id>` if unnamed.
+pub(crate) fn visible_csv(
+    hits: &HitRegistry,
+    view: &ViewState,
+    plot: Rectangle,
+    entries: &[LegendEntry],
+) -> String {
+    let mut columns: Vec<SeriesId> = vec![];
+    let mut xs: Vec<f64> = vec![];
+    let mut values = HashMap::new();
+    for (series, _, at) in hits.all_points().filter(|(_, _, at)| plot.contains(*at)) {
+        let (x, y) = view.to_data(plot, at);
+        if !columns.contains(&series) {
+            columns.push(series);
+        }
+        if values.insert((series, x.to_bits()), y).is_none() {
+            xs.push(x);
+        }
+    }
+    columns.sort();
+    xs.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    xs.dedup();
+
+    let mut csv = String::from("x");
+    for series in &columns {
+        let name = entries
+            .iter()
+            .find(|entry| entry.series == *series)
+            .map_or_else(
+                || format!("series {}", series.0),
+                |entry| entry.name.clone(),
+            );
+        csv.push(',');
+        csv.push_str(&quote(&name));
+    }
+    csv.push('\n');
+    for x in xs {
+        csv.push_str(&x.to_string());
+        for series in &columns {
+            csv.push(',');
+            if let Some(y) = values.get(&(*series, x.to_bits())) {
+                csv.push_str(&y.to_string());
+            }
+        }
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Writes `bytes` of the media type `mime` to `path`.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn save(path: PathBuf, _mime: &str, bytes: &[u8]) -> Result<PathBuf, String> {
+    std::fs::write(&path, bytes)
+        .map(|()| path)
+        .map_err(|e| e.to_string())
+}
+
+/// Has the browser download `bytes` of the media type `mime` as a file named after the file
+/// name of `path`, since pages can't write to the file system.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn save(path: PathBuf, mime: &str, bytes: &[u8]) -> Result<PathBuf, String> {
+    use wasm_bindgen::JsCast;
+
+    let js_error = |error: wasm_bindgen::JsValue| format!("{:?}", error);
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or("the path has no file name")?;
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(bytes));
+    let mut options = web_sys::BlobPropertyBag::new();
+    options.type_(mime);
+    let blob = web_sys::Blob::new_with_u8_array_sequence_and_options(&parts, &options)
+        .map_err(js_error)?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob).map_err(js_error)?;
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or("there is no document to download from")?;
+    let link: web_sys::HtmlAnchorElement = document
+        .create_element("a")
+        .map_err(js_error)?
+        .dyn_into()
+        .map_err(|_| "could not create a link")?;
+    link.set_href(&url);
+    link.set_download(name);
+    link.click();
+    web_sys::Url::revoke_object_url(&url).map_err(js_error)?;
+    Ok(path)
+}
+
+/// Quotes `name` if it holds a separator or a quote.
+fn quote(name: &str) -> String {
+    if name.contains(&[',', '"', '\n'][..]) {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    } else {
+        name.to_string()
+    }
+}
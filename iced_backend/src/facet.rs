@@ -0,0 +1,248 @@
+use crate::{
+    BuildContext, BuildError, CellTemplate, Chart, IcedBackend, Palette, Scatter, SeriesId,
+    SmallMultiples, ViewState,
+};
+use plotters::chart::{ChartBuilder, ChartContext, SeriesLabelPosition};
+use plotters::coord::types::RangedCoordf64;
+use plotters::coord::Shift;
+use plotters::drawing::DrawingArea;
+use plotters::element::Circle;
+use plotters::prelude::Cartesian2d;
+use plotters::style::{Color, IntoFont, TextStyle, BLACK, WHITE};
+use std::fmt::Display;
+
+type Coords = Cartesian2d<RangedCoordf64, RangedCoordf64>;
+
+/// The share of the data's extent added on both sides of the facets' view.
+const PADDING: f64 = 0.05;
+
+struct Group<K> {
+    key: K,
+    points: Vec<(f64, f64)>,
+}
+
+/// Records split into groups by a key, for drawing one facet per group in a
+/// [`SmallMultiples`] grid ([`Facets::grid`]) or all groups in one chart told apart by
+/// color ([`Facets::overlay`]).
+///
+/// Every group keeps the same [`Palette`] style in both, and its index as [`SeriesId`], so
+/// hovers and selections name the group.
+pub struct Facets<K> {
+    groups: Vec<Group<K>>,
+    palette: Palette,
+    lines: bool,
+}
+
+impl<K: PartialEq + Display> Facets<K> {
+    /// Groups `records` by `key`, in the order the keys first appear, placing each record
+    /// at `x` and `y`.
+    pub fn new<R, G, X, Y>(records: impl IntoIterator<Item = R>, key: G, x: X, y: Y) -> Self
+    where
+        G: Fn(&R) -> K,
+        X: Fn(&R) -> f64,
+        Y: Fn(&R) -> f64,
+    {
+        let mut groups: Vec<Group<K>> = vec![];
+        for record in records {
+            let point = (x(&record), y(&record));
+            let key = key(&record);
+            match groups.iter_mut().find(|group| group.key == key) {
+                Some(group) => group.points.push(point),
+                None => groups.push(Group {
+                    key,
+                    points: vec![point],
+                }),
+            }
+        }
+        Self {
+            groups,
+            palette: Palette::default(),
+            lines: false,
+        }
+    }
+
+    pub fn palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    /// Connects the records of each group in their order, e.g. for time series.
+    pub fn lines(mut self, lines: bool) -> Self {
+        self.lines = lines;
+        self
+    }
+
+    /// The keys of the groups, in the order of their facets.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.groups.iter().map(|group| &group.key)
+    }
+
+    /// The ranges spanning all records with a small margin, shared by the facets.
+    pub fn extent(&self) -> ViewState {
+        let points = || self.groups.iter().flat_map(|group| group.points.iter());
+        let x = padded(points().map(|point| point.0));
+        let y = padded(points().map(|point| point.1));
+        ViewState::new(x.0..x.1, y.0..y.1)
+    }
+
+    /// A grid of one facet per group, `columns` facets wide, titled with the group's key
+    /// in its color and sharing the extent of all records.
+    pub fn grid<Message: 'static>(self, columns: usize) -> SmallMultiples<Message, FacetGrid<K>>
+    where
+        K: 'static,
+    {
+        let view = self.extent();
+        let keys = (0..self.groups.len()).collect();
+        SmallMultiples::new(FacetGrid { facets: self }, keys, columns).initial_view(view)
+    }
+
+    /// One chart drawing all groups, with a legend naming them. Show it with the view of
+    /// [`Facets::extent`].
+    pub fn overlay(self) -> FacetOverlay<K> {
+        FacetOverlay { facets: self }
+    }
+
+    fn draw_group(
+        &self,
+        chart: &mut ChartContext<'_, IcedBackend<'_>, Coords>,
+        ctx: &mut BuildContext<'_>,
+        index: usize,
+    ) -> Result<(), BuildError> {
+        let group = &self.groups[index];
+        let style = self.palette.series(index);
+        let (r, g, b) = style.rgb;
+        let series = SeriesId(index);
+        if self.lines {
+            chart.draw_series(std::iter::once(style.line(group.points.clone(), 2)))?;
+            ctx.register_line(series, chart.plotting_area(), group.points.iter());
+        }
+        let scatter = Scatter::uniform(
+            group.points.iter().copied(),
+            iced::Color::from_rgb8(r, g, b),
+            3.0,
+        )
+        .shape(style.marker);
+        chart.plotting_area().draw(&scatter)?;
+        ctx.register_points(series, chart.plotting_area(), group.points.iter());
+        ctx.set_series_color(series, &style.color());
+        Ok(())
+    }
+}
+
+/// The [`CellTemplate`] of a faceted grid, see [`Facets::grid`]; its keys are the indices
+/// of the groups.
+pub struct FacetGrid<K> {
+    facets: Facets<K>,
+}
+
+impl<K: PartialEq + Display> CellTemplate for FacetGrid<K> {
+    type Key = usize;
+
+    fn build_cell(
+        &self,
+        index: &usize,
+        ctx: &mut BuildContext<'_>,
+        root: DrawingArea<IcedBackend<'_>, Shift>,
+    ) -> Result<(), BuildError> {
+        ctx.fill_background(&root)?;
+        let color = self.facets.palette.series(*index).color();
+        let title = self.facets.groups[*index].key.to_string();
+        let mut chart = ChartBuilder::on(&root)
+            .margin(6)
+            .caption(title, ("sans-serif", 16).into_font().color(&color))
+            .x_label_area_size(20)
+            .y_label_area_size(40)
+            .build_cartesian_2d(ctx.x_range(), ctx.y_range())?;
+        ctx.set_plotting_area(chart.plotting_area());
+        ctx.fit_labels(&mut chart.configure_mesh())
+            .label_style(("sans-serif", 11))
+            .draw()?;
+        self.facets.draw_group(&mut chart, ctx, *index)
+    }
+}
+
+/// All groups of [`Facets`] in one chart, see [`Facets::overlay`].
+pub struct FacetOverlay<K> {
+    facets: Facets<K>,
+}
+
+impl<K: PartialEq + Display> Chart for FacetOverlay<K> {
+    fn build_chart(
+        &self,
+        ctx: &mut BuildContext<'_>,
+        root: DrawingArea<IcedBackend<'_>, Shift>,
+    ) -> Result<(), BuildError> {
+        ctx.fill_background(&root)?;
+        let font: TextStyle = ("sans-serif", 12).into_font().into();
+        let names: Vec<String> = self
+            .facets
+            .groups
+            .iter()
+            .map(|g| g.key.to_string())
+            .collect();
+        let labels: Vec<&str> = names.iter().map(String::as_str).collect();
+        let legend = ctx.legend_size(&root, &labels, &font)?;
+        let position = ctx.legend_position();
+        let (right, bottom) = position.margins(legend);
+        let x = RangedCoordf64::from(ctx.x_range());
+        let y = RangedCoordf64::from(ctx.y_range());
+        let x_label_area = ctx.x_label_area_size(&root, &x, &font, None, 30)?;
+        let y_label_area = ctx.y_label_area_size(&root, &y, &font, None, 50)?;
+        let margin = ctx.margin(10);
+        let mut chart = ChartBuilder::on(&root)
+            .margin(margin)
+            .margin_right(margin + right)
+            .margin_bottom(margin + bottom)
+            .x_label_area_size(x_label_area)
+            .y_label_area_size(y_label_area)
+            .build_cartesian_2d(x, y)?;
+        ctx.set_plotting_area(chart.plotting_area());
+        ctx.fit_labels(&mut chart.configure_mesh()).draw()?;
+
+        for (index, name) in names.iter().enumerate() {
+            let color = self.facets.palette.series(index).color();
+            if ctx.is_hidden(SeriesId(index)) {
+                ctx.set_series_color(SeriesId(index), &color);
+            } else {
+                self.facets.draw_group(&mut chart, ctx, index)?;
+            }
+            // An empty series only adds the group to the legend.
+            let annotation = chart.draw_series(std::iter::empty::<Circle<(f64, f64), i32>>())?;
+            ctx.label(SeriesId(index), annotation, name.as_str())
+                .legend(move |(x, y)| Circle::new((x + 5, y), 4, color.filled()));
+        }
+        if ctx.draws_legend() {
+            return Ok(());
+        }
+        let offset = position.place(
+            root.get_pixel_range(),
+            chart.plotting_area().get_pixel_range(),
+            legend,
+        );
+        ctx.set_legend_bounds(chart.plotting_area(), offset, legend);
+        chart
+            .configure_series_labels()
+            .position(SeriesLabelPosition::Coordinate(offset.0, offset.1))
+            .label_font(font)
+            .background_style(&WHITE.mix(0.8))
+            .border_style(&BLACK)
+            .draw()?;
+        Ok(())
+    }
+}
+
+/// The extent of `values` widened by [`PADDING`] on both sides.
+fn padded(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    let (min, max) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+        (min.min(value), max.max(value))
+    });
+    if min > max {
+        return (0.0, 1.0);
+    }
+    let pad = if max > min {
+        (max - min) * PADDING
+    } else {
+        0.5
+    };
+    (min - pad, max + pad)
+}
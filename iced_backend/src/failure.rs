@@ -0,0 +1,163 @@
+use iced::canvas::{Frame, Path, Stroke, Text};
+use iced::{Color, HorizontalAlignment, Point, Rectangle, Size, VerticalAlignment};
+
+/// A rough average glyph width relative to the text size, used to wrap the message.
+const GLYPH_WIDTH: f32 = 0.6;
+/// The padding between the panel and its content.
+const PADDING: f32 = 12.0;
+/// The widest the panel gets, in pixels.
+const MAX_WIDTH: f32 = 420.0;
+/// The most lines of the error message shown.
+const MAX_LINES: usize = 6;
+
+/// The panel the widget shows instead of the chart after building it failed, with the
+/// error message and a retry button, see
+/// [`ChartWidget::error_panel`](crate::ChartWidget::error_panel).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorPanel {
+    pub title: String,
+    pub retry_label: String,
+    pub background: Color,
+    pub border: Color,
+    pub text_color: Color,
+    pub text_size: f32,
+}
+
+impl ErrorPanel {
+    /// The bounds of the panel and of its retry button within a canvas of `size`, for
+    /// `message`.
+    fn layout(&self, size: Size, message: &str) -> (Rectangle, Rectangle, Vec<String>) {
+        let width = (size.width - 2.0 * PADDING).clamp(0.0, MAX_WIDTH);
+        let columns = ((width - 2.0 * PADDING) / (self.text_size * GLYPH_WIDTH)).max(1.0);
+        let lines = wrap(message, columns as usize);
+        let button = Size::new(
+            self.retry_label.chars().count() as f32 * self.text_size * GLYPH_WIDTH + PADDING * 2.0,
+            self.text_size + PADDING,
+        );
+        let height =
+            (self.text_size * 1.2) * (1 + lines.len()) as f32 + button.height + PADDING * 4.0;
+        let panel = Rectangle {
+            x: (size.width - width) / 2.0,
+            y: ((size.height - height) / 2.0).max(0.0),
+            width,
+            height,
+        };
+        let retry = Rectangle {
+            x: panel.center_x() - button.width / 2.0,
+            y: panel.y + panel.height - PADDING - button.height,
+            width: button.width,
+            height: button.height,
+        };
+        (panel, retry, lines)
+    }
+
+    /// The bounds of the retry button within a canvas of `size`.
+    pub(crate) fn retry_bounds(&self, size: Size, message: &str) -> Rectangle {
+        self.layout(size, message).1
+    }
+
+    pub(crate) fn draw(&self, frame: &mut Frame, message: &str, retry_hovered: bool) {
+        let (panel, retry, lines) = self.layout(frame.size(), message);
+        frame.fill_rectangle(panel.position(), panel.size(), self.background);
+        frame.stroke(
+            &Path::rectangle(panel.position(), panel.size()),
+            Stroke {
+                color: self.border,
+                width: 1.0,
+                ..Stroke::default()
+            },
+        );
+
+        let line_height = self.text_size * 1.2;
+        let mut y = panel.y + PADDING;
+        frame.fill_text(Text {
+            content: self.title.clone(),
+            position: Point::new(panel.center_x(), y),
+            color: self.border,
+            size: self.text_size * 1.1,
+            horizontal_alignment: HorizontalAlignment::Center,
+            ..Text::default()
+        });
+        y += line_height + PADDING;
+        for line in lines {
+            frame.fill_text(Text {
+                content: line,
+                position: Point::new(panel.x + PADDING, y),
+                color: self.text_color,
+                size: self.text_size,
+                ..Text::default()
+            });
+            y += line_height;
+        }
+
+        let button = if retry_hovered {
+            self.border
+        } else {
+            Color {
+                a: self.border.a * 0.8,
+                ..self.border
+            }
+        };
+        frame.fill_rectangle(retry.position(), retry.size(), button);
+        frame.fill_text(Text {
+            content: self.retry_label.clone(),
+            position: retry.center(),
+            color: Color::WHITE,
+            size: self.text_size,
+            horizontal_alignment: HorizontalAlignment::Center,
+            vertical_alignment: VerticalAlignment::Center,
+            ..Text::default()
+        });
+    }
+}
+
+impl Default for ErrorPanel {
+    fn default() -> Self {
+        Self {
+            title: String::from("The chart could not be drawn"),
+            retry_label: String::from("Retry"),
+            background: Color::from_rgb(1.0, 0.95, 0.95),
+            border: Color::from_rgb(0.75, 0.15, 0.15),
+            text_color: Color::from_rgb(0.3, 0.1, 0.1),
+            text_size: 14.0,
+        }
+    }
+}
+
+/// Breaks `text` into lines of at most `columns` characters at spaces, cutting words
+/// longer than a line, and keeps at most [`MAX_LINES`] lines.
+fn wrap(text: &str, columns: usize) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+        loop {
+            let used = line.chars().count();
+            let space = usize::from(used > 0);
+            if used + space + word.len() <= columns {
+                if space > 0 {
+                    line.push(' ');
+                }
+                line.extend(word.iter());
+                break;
+            }
+            if used > 0 {
+                lines.push(std::mem::take(&mut line));
+            } else {
+                let rest = word.split_off(columns);
+                lines.push(word.into_iter().collect());
+                word = rest;
+            }
+        }
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    if lines.len() > MAX_LINES {
+        lines.truncate(MAX_LINES);
+        if let Some(last) = lines.last_mut() {
+            last.push('…');
+        }
+    }
+    lines
+}
@@ -0,0 +1,68 @@
+use iced::canvas::{Frame, Path, Stroke};
+use iced::{Color, Point, Size};
+
+/// A change of the keyboard focus of a
+/// [`ChartWidget::focusable`](crate::ChartWidget::focusable) chart, reported through
+/// [`ChartWidget::on_focus_change`](crate::ChartWidget::on_focus_change).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusChange {
+    /// The chart was clicked.
+    Gained,
+    /// Something outside the chart was clicked.
+    Lost,
+    /// Tab was pressed while the chart had focus, which it gave up; the application should
+    /// focus the next widget.
+    Next,
+    /// Shift+Tab was pressed while the chart had focus, which it gave up; the application
+    /// should focus the previous widget.
+    Previous,
+}
+
+/// How the ring around a focused chart looks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FocusStyle {
+    color: Color,
+    width: f32,
+}
+
+impl FocusStyle {
+    pub fn new() -> Self {
+        Self {
+            color: Color::from_rgb(0.2, 0.4, 0.9),
+            width: 2.0,
+        }
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Sets the width of the ring in pixels, drawn inside the canvas edges.
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub(crate) fn draw(&self, frame: &mut Frame, size: Size) {
+        let inset = self.width / 2.0;
+        let path = Path::rectangle(
+            Point::new(inset, inset),
+            Size::new(size.width - self.width, size.height - self.width),
+        );
+        frame.stroke(
+            &path,
+            Stroke {
+                color: self.color,
+                width: self.width,
+                ..Stroke::default()
+            },
+        );
+    }
+}
+
+impl Default for FocusStyle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,84 @@
+use iced::canvas::{Frame, Path, Stroke, Text};
+use iced::{Color, Point, Size, Vector};
+
+/// The drawing operations [`IcedBackend`](crate::IcedBackend) needs from the surface it
+/// draws onto.
+///
+/// It is implemented for the canvas [`Frame`], which the backend draws onto by default.
+/// Implement it to draw charts elsewhere, such as into a mock recording the operations
+/// in a test.
+pub trait FrameLike {
+    /// The size of the surface in pixels.
+    fn size(&self) -> Size;
+
+    fn fill(&mut self, path: &Path, color: Color);
+
+    fn fill_rectangle(&mut self, top_left: Point, size: Size, color: Color);
+
+    fn stroke(&mut self, path: &Path, stroke: Stroke);
+
+    fn fill_text(&mut self, text: Text);
+
+    /// Fills triangles given by the indices of their corners in `vertices`, each corner
+    /// with its own color. Surfaces that can't take a mesh fill every triangle as a path
+    /// in the color of its first corner.
+    fn fill_triangles(&mut self, vertices: &[(Point, Color)], indices: &[u32]) {
+        for corners in indices.chunks_exact(3) {
+            let corner = |index: u32| vertices[index as usize];
+            let path = Path::new(|builder| {
+                builder.move_to(corner(corners[0]).0);
+                builder.line_to(corner(corners[1]).0);
+                builder.line_to(corner(corners[2]).0);
+                builder.close();
+            });
+            self.fill(&path, corner(corners[0]).1);
+        }
+    }
+
+    /// Runs `f`, restoring the transform of the surface afterwards.
+    fn with_save(&mut self, f: impl FnOnce(&mut Self));
+
+    /// Translates everything drawn from now on by `translation`.
+    fn translate(&mut self, translation: Vector);
+
+    /// Rotates everything drawn from now on by `angle`, in radians.
+    fn rotate(&mut self, angle: f32);
+}
+
+impl FrameLike for Frame {
+    fn size(&self) -> Size {
+        Frame::size(self)
+    }
+
+    fn fill(&mut self, path: &Path, color: Color) {
+        Frame::fill(self, path, color);
+    }
+
+    fn fill_rectangle(&mut self, top_left: Point, size: Size, color: Color) {
+        Frame::fill_rectangle(self, top_left, size, color);
+    }
+
+    fn stroke(&mut self, path: &Path, stroke: Stroke) {
+        Frame::stroke(self, path, stroke);
+    }
+
+    fn fill_text(&mut self, text: Text) {
+        Frame::fill_text(self, text);
+    }
+
+    fn fill_triangles(&mut self, vertices: &[(Point, Color)], indices: &[u32]) {
+        Frame::fill_triangles(self, vertices, indices);
+    }
+
+    fn with_save(&mut self, f: impl FnOnce(&mut Self)) {
+        Frame::with_save(self, f);
+    }
+
+    fn translate(&mut self, translation: Vector) {
+        Frame::translate(self, translation);
+    }
+
+    fn rotate(&mut self, angle: f32) {
+        Frame::rotate(self, angle);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{FrameLike, IcedBackend};
+use plotters::element::{Drawable, PointCollection};
+use plotters::style::{Color, RGBAColor};
+use plotters_backend::{BackendCoord, DrawingErrorKind};
+
+/// How a [`GradientFill`] varies its color across the shape, measured on the shape's
+/// bounding box.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Gradient {
+    /// From `top` at the top edge of the shape to `bottom` at its bottom edge, e.g. an area
+    /// under a curve fading to transparent towards the x axis.
+    Vertical { top: RGBAColor, bottom: RGBAColor },
+    /// From `inner` at the center of the shape to `outer` at its corners.
+    Radial { inner: RGBAColor, outer: RGBAColor },
+}
+
+impl Gradient {
+    /// A vertical gradient fading from `color` at the top to fully transparent.
+    pub fn fade<C: Color>(color: &C) -> Self {
+        Gradient::Vertical {
+            top: color.to_rgba(),
+            bottom: color.mix(0.0),
+        }
+    }
+
+    /// The color a fraction `t` of the way from the start to the end of the gradient.
+    pub(crate) fn color_at(&self, t: f32) -> iced::Color {
+        let (from, to) = match self {
+            Gradient::Vertical { top, bottom } => (top, bottom),
+            Gradient::Radial { inner, outer } => (inner, outer),
+        };
+        let ((r0, g0, b0), (r1, g1, b1)) = (from.rgb(), to.rgb());
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+        iced::Color::from_rgba(
+            lerp(f32::from(r0), f32::from(r1)) / 255.0,
+            lerp(f32::from(g0), f32::from(g1)) / 255.0,
+            lerp(f32::from(b0), f32::from(b1)) / 255.0,
+            lerp(from.alpha() as f32, to.alpha() as f32),
+        )
+    }
+}
+
+/// A filled polygon colored with a [`Gradient`], approximated by bands of solid color.
+///
+/// Like [`Scatter`](crate::Scatter), it only draws onto an [`IcedBackend`], through
+/// `chart.plotting_area().draw(&fill)`.
+pub struct GradientFill<Coord> {
+    points: Vec<Coord>,
+    gradient: Gradient,
+}
+
+impl<Coord> GradientFill<Coord> {
+    /// The polygon through `points`.
+    pub fn new<I: IntoIterator<Item = Coord>>(points: I, gradient: Gradient) -> Self {
+        Self {
+            points: points.into_iter().collect(),
+            gradient,
+        }
+    }
+}
+
+impl<X: Clone, Y: Clone> GradientFill<(X, Y)> {
+    /// The area between the line through `series` and the horizontal line at `baseline`,
+    /// like `AreaSeries`. `series` should be ordered by x.
+    pub fn area<I: IntoIterator<Item = (X, Y)>>(
+        series: I,
+        baseline: Y,
+        gradient: Gradient,
+    ) -> Self {
+        let mut points: Vec<_> = series.into_iter().collect();
+        let ends = match (points.first(), points.last()) {
+            (Some((first, _)), Some((last, _))) => Some((first.clone(), last.clone())),
+            _ => None,
+        };
+        if let Some((first, last)) = ends {
+            points.push((last, baseline.clone()));
+            points.push((first, baseline));
+        }
+        Self { points, gradient }
+    }
+}
+
+impl<'a, Coord: 'a> PointCollection<'a, Coord> for &'a GradientFill<Coord> {
+    type Point = &'a Coord;
+    type IntoIter = &'a [Coord];
+    fn point_iter(self) -> &'a [Coord] {
+        &self.points
+    }
+}
+
+impl<'b, F: FrameLike, Coord> Drawable<IcedBackend<'b, F>> for GradientFill<Coord> {
+    fn draw<I: Iterator<Item = BackendCoord>>(
+        &self,
+        points: I,
+        backend: &mut IcedBackend<'b, F>,
+        _: (u32, u32),
+    ) -> Result<(), DrawingErrorKind<crate::IcedError>> {
+        backend.fill_gradient(points, &self.gradient)
+    }
+}
@@ -0,0 +1,101 @@
+use crate::FrameLike;
+use iced::canvas::{Frame, Geometry};
+use iced::Size;
+use std::cell::{Cell, RefCell};
+
+/// Splits what one [`IcedBackend`](crate::IcedBackend) draws into named groups, each drawn
+/// onto a surface of its own, see [`IcedBackend::with_groups`](crate::IcedBackend::with_groups).
+///
+/// Plotters interleaves its drawing calls, e.g. the mesh is drawn while configuring it and
+/// the series after, so the parts of one chart can't be drawn by separate passes. Selecting
+/// a group before each part instead sorts the calls of a single pass, and the groups can
+/// then be cached apart. Groups whose geometry is still cached can be skipped, which drops
+/// their calls before anything is tessellated.
+pub struct GeometryGroups<F = Frame> {
+    size: Size,
+    blank: Box<dyn Fn(Size) -> F>,
+    selected: Cell<&'static str>,
+    skipped: Vec<&'static str>,
+    surfaces: RefCell<Vec<(&'static str, F)>>,
+}
+
+impl GeometryGroups {
+    /// Creates groups of canvas frames of `size`, drawing into `first` until another group
+    /// is selected.
+    pub fn new(size: Size, first: &'static str) -> Self {
+        Self::with_surfaces(size, first, Frame::new)
+    }
+
+    /// The geometry of every group drawn into, in the order they were first drawn into.
+    pub fn into_geometry(self) -> Vec<(&'static str, Geometry)> {
+        self.into_surfaces()
+            .into_iter()
+            .map(|(group, frame)| (group, frame.into_geometry()))
+            .collect()
+    }
+}
+
+impl<F: FrameLike> GeometryGroups<F> {
+    /// Creates groups of surfaces of `size` made by `blank`, e.g. canvas frames that are
+    /// scaled, drawing into `first` until another group is selected.
+    pub fn with_surfaces(
+        size: Size,
+        first: &'static str,
+        blank: impl 'static + Fn(Size) -> F,
+    ) -> Self {
+        Self {
+            size,
+            blank: Box::new(blank),
+            selected: Cell::new(first),
+            skipped: vec![],
+            surfaces: RefCell::new(vec![]),
+        }
+    }
+
+    /// Drops everything drawn into `group`, e.g. because its geometry is still cached.
+    pub fn skip(mut self, group: &'static str) -> Self {
+        self.skipped.push(group);
+        self
+    }
+
+    /// Draws the following calls into `group`.
+    pub fn select(&self, group: &'static str) {
+        self.selected.set(group);
+    }
+
+    pub fn selected(&self) -> &'static str {
+        self.selected.get()
+    }
+
+    pub fn is_skipped(&self, group: &'static str) -> bool {
+        self.skipped.contains(&group)
+    }
+
+    /// Runs `f` on the surface of `group`, unless the group is skipped.
+    pub fn draw<R>(&self, group: &'static str, f: impl FnOnce(&mut F) -> R) -> Option<R> {
+        if self.is_skipped(group) {
+            return None;
+        }
+        let mut surfaces = self.surfaces.borrow_mut();
+        let index = match surfaces.iter().position(|(name, _)| *name == group) {
+            Some(index) => index,
+            None => {
+                surfaces.push((group, (self.blank)(self.size)));
+                surfaces.len() - 1
+            }
+        };
+        Some(f(&mut surfaces[index].1))
+    }
+
+    /// Removes what was drawn into `group`, if anything.
+    pub fn take(&self, group: &'static str) -> Option<F> {
+        let mut surfaces = self.surfaces.borrow_mut();
+        let index = surfaces.iter().position(|(name, _)| *name == group)?;
+        Some(surfaces.remove(index).1)
+    }
+
+    /// The surface of every group drawn into, in the order they were first drawn into.
+    pub fn into_surfaces(self) -> Vec<(&'static str, F)> {
+        self.surfaces.into_inner()
+    }
+}
@@ -0,0 +1,63 @@
+/// The longest tick marks plotters draws, in pixels.
+pub(crate) const TICK_SIZE: u32 = 5;
+/// The space left between the tick labels and the axis description or the canvas edge.
+pub(crate) const LABEL_GAP: u32 = 5;
+
+/// The size of the area beside the plotting area that holds the tick labels and the
+/// description of an axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LabelArea {
+    /// A fixed size in pixels.
+    Fixed(u32),
+    /// Just large enough for the longest tick label of the visible range, measured with the
+    /// backend's text measurement, so labels are never clipped.
+    Auto,
+}
+
+/// The space a chart leaves around its plotting area, see
+/// [`ChartWidget::gutters`](crate::ChartWidget::gutters).
+///
+/// Charts read it through [`BuildContext::margin`](crate::BuildContext::margin),
+/// [`BuildContext::x_label_area_size`](crate::BuildContext::x_label_area_size) and
+/// [`BuildContext::y_label_area_size`](crate::BuildContext::y_label_area_size).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Gutters {
+    /// The margin around the chart, in pixels.
+    pub margin: u32,
+    pub x_label_area: LabelArea,
+    pub y_label_area: LabelArea,
+}
+
+impl Gutters {
+    /// A margin of 10 pixels and label areas fitted to the labels.
+    pub fn new() -> Self {
+        Self {
+            margin: 10,
+            x_label_area: LabelArea::Auto,
+            y_label_area: LabelArea::Auto,
+        }
+    }
+
+    pub fn margin(mut self, margin: u32) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    pub fn x_label_area(mut self, area: LabelArea) -> Self {
+        self.x_label_area = area;
+        self
+    }
+
+    pub fn y_label_area(mut self, area: LabelArea) -> Self {
+        self.y_label_area = area;
+        self
+    }
+}
+
+impl Default for Gutters {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,144 @@
+use crate::Colormap;
+use plotters::chart::ChartContext;
+use plotters::coord::types::RangedCoordf64;
+use plotters::drawing::DrawingAreaErrorKind;
+use plotters::element::Rectangle;
+use plotters::prelude::Cartesian2d;
+use plotters::style::Color;
+use plotters_backend::DrawingBackend;
+use std::ops::Range;
+
+type HeatmapResult<DB> = Result<(), DrawingAreaErrorKind<<DB as DrawingBackend>::ErrorType>>;
+type Coords = Cartesian2d<RangedCoordf64, RangedCoordf64>;
+
+/// A grid of values drawn as cells colored by a [`Colormap`].
+///
+/// The values are stored row by row. Row 0 is drawn at the start of the y extent and
+/// column 0 at the start of the x extent; by default the cells are one unit wide, so cell
+/// `(row, column)` covers `column..column + 1` along x. Non-finite values are left empty.
+#[derive(Debug, Clone)]
+pub struct Heatmap {
+    values: Vec<f64>,
+    columns: usize,
+    x: Range<f64>,
+    y: Range<f64>,
+    colormap: Colormap,
+    value_range: Option<Range<f64>>,
+}
+
+impl Heatmap {
+    /// A heatmap of `values`, `columns` values per row. A last incomplete row is dropped.
+    pub fn new(mut values: Vec<f64>, columns: usize) -> Self {
+        let columns = columns.max(1);
+        let rows = values.len() / columns;
+        values.truncate(rows * columns);
+        Self {
+            values,
+            columns,
+            x: 0.0..columns as f64,
+            y: 0.0..rows as f64,
+            colormap: Colormap::VIRIDIS,
+            value_range: None,
+        }
+    }
+
+    /// A heatmap of `rows`, each as long as the first one. Longer rows are cut, shorter
+    /// ones padded with empty cells.
+    pub fn from_rows<R>(rows: impl IntoIterator<Item = R>) -> Self
+    where
+        R: IntoIterator<Item = f64>,
+    {
+        let mut columns = None;
+        let mut values = vec![];
+        for row in rows {
+            let start = values.len();
+            values.extend(row);
+            let columns = *columns.get_or_insert(values.len() - start);
+            values.resize(start + columns, f64::NAN);
+        }
+        Self::new(values, columns.unwrap_or(1))
+    }
+
+    /// Spreads the cells over `x` and `y` in data coordinates.
+    pub fn extent(mut self, x: Range<f64>, y: Range<f64>) -> Self {
+        self.x = x;
+        self.y = y;
+        self
+    }
+
+    pub fn colormap(mut self, colormap: Colormap) -> Self {
+        self.colormap = colormap;
+        self
+    }
+
+    /// Maps `range` onto the colormap instead of the extent of the values, e.g. to keep
+    /// the colors of several heatmaps comparable.
+    pub fn value_range(mut self, range: Range<f64>) -> Self {
+        self.value_range = Some(range);
+        self
+    }
+
+    pub fn rows(&self) -> usize {
+        self.values.len() / self.columns
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> Option<f64> {
+        if column >= self.columns {
+            return None;
+        }
+        self.values.get(row * self.columns + column).copied()
+    }
+
+    /// The range mapped onto the colormap.
+    pub fn range(&self) -> Range<f64> {
+        if let Some(range) = &self.value_range {
+            return range.clone();
+        }
+        let (min, max) = self
+            .values
+            .iter()
+            .filter(|value| value.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &value| {
+                (min.min(value), max.max(value))
+            });
+        if min <= max {
+            min..max
+        } else {
+            0.0..1.0
+        }
+    }
+
+    /// The data coordinates of the corners of cell `(row, column)`.
+    pub fn cell(&self, row: usize, column: usize) -> [(f64, f64); 2] {
+        let width = (self.x.end - self.x.start) / self.columns as f64;
+        let height = (self.y.end - self.y.start) / self.rows().max(1) as f64;
+        let x = self.x.start + column as f64 * width;
+        let y = self.y.start + row as f64 * height;
+        [(x, y), (x + width, y + height)]
+    }
+
+    pub fn draw<DB: DrawingBackend>(
+        &self,
+        chart: &mut ChartContext<'_, DB, Coords>,
+    ) -> HeatmapResult<DB> {
+        let range = self.range();
+        let cells = self
+            .values
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| value.is_finite())
+            .map(|(index, &value)| {
+                let color = self.colormap.map(value, range.clone());
+                Rectangle::new(
+                    self.cell(index / self.columns, index % self.columns),
+                    color.filled(),
+                )
+            });
+        chart.draw_series(cells)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,70 @@
+use crate::hit::HitRegistry;
+use crate::SeriesId;
+use iced::canvas::{Frame, Path, Stroke};
+use iced::{Color, Rectangle};
+
+/// How the series under the cursor is emphasized.
+///
+/// The rest of the plot is dimmed with a translucent veil and the hovered series' registered
+/// lines and points are redrawn on top, thicker, in its registered color (see
+/// [`BuildContext::set_series_color`](crate::BuildContext::set_series_color)). Only this
+/// overlay is redrawn while hovering; the chart itself stays cached.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HoverHighlight {
+    /// The stroke width of the emphasized lines.
+    pub width: f32,
+    /// The radius of the emphasized points.
+    pub point_radius: f32,
+    /// How strongly everything else is faded, from `0.0` (not at all) to `1.0`.
+    pub dim: f32,
+    /// The color used for series without a registered color.
+    pub color: Color,
+    /// The color of the veil that dims the other series.
+    pub background: Color,
+}
+
+impl HoverHighlight {
+    pub(crate) fn draw(
+        &self,
+        frame: &mut Frame,
+        plot: Rectangle,
+        hits: &HitRegistry,
+        series: SeriesId,
+    ) {
+        if self.dim > 0.0 {
+            frame.fill_rectangle(
+                plot.position(),
+                plot.size(),
+                Color {
+                    a: self.dim,
+                    ..self.background
+                },
+            );
+        }
+
+        let color = hits.color(series).unwrap_or(self.color);
+        let stroke = Stroke {
+            color,
+            width: self.width,
+            ..Stroke::default()
+        };
+        for (from, to) in hits.segments(series) {
+            frame.stroke(&Path::line(from, to), stroke);
+        }
+        for point in hits.points(series) {
+            frame.fill(&Path::circle(point, self.point_radius), color);
+        }
+    }
+}
+
+impl Default for HoverHighlight {
+    fn default() -> Self {
+        Self {
+            width: 3.0,
+            point_radius: 4.0,
+            dim: 0.5,
+            color: Color::from_rgb(0.2, 0.2, 0.2),
+            background: Color::WHITE,
+        }
+    }
+}
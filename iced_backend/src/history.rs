@@ -0,0 +1,145 @@
+use crate::ViewState;
+use std::collections::VecDeque;
+
+/// Whether there are view changes to undo or redo, for enabling toolbar buttons.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HistoryStatus {
+    pub can_undo: bool,
+    pub can_redo: bool,
+}
+
+/// A bounded undo/redo stack of views.
+#[derive(Debug)]
+pub(crate) struct History {
+    past: VecDeque<ViewState>,
+    future: Vec<ViewState>,
+    limit: usize,
+}
+
+impl History {
+    pub(crate) fn new(limit: usize) -> Self {
+        Self {
+            past: VecDeque::new(),
+            future: vec![],
+            limit,
+        }
+    }
+
+    pub(crate) fn set_limit(&mut self, limit: usize) {
+        self.limit = limit;
+        self.past.truncate(limit);
+    }
+
+    /// Records `view` as the state before a change, dropping the redo stack.
+    pub(crate) fn record(&mut self, view: ViewState) {
+        if self.limit == 0 {
+            return;
+        }
+        if self.past.len() == self.limit {
+            self.past.pop_back();
+        }
+        self.past.push_front(view);
+        self.future.clear();
+    }
+
+    /// Returns the view to go back to, remembering `current` for redo.
+    pub(crate) fn undo(&mut self, current: &ViewState) -> Option<ViewState> {
+        let view = self.past.pop_front()?;
+        self.future.push(current.clone());
+        Some(view)
+    }
+
+    /// Returns the view to go forward to, remembering `current` for undo.
+    pub(crate) fn redo(&mut self, current: &ViewState) -> Option<ViewState> {
+        let view = self.future.pop()?;
+        self.past.push_front(current.clone());
+        Some(view)
+    }
+
+    pub(crate) fn status(&self) -> HistoryStatus {
+        HistoryStatus {
+            can_undo: !self.past.is_empty(),
+            can_redo: !self.future.is_empty(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn view(start: f64) -> ViewState {
+        ViewState::new(start..start + 1.0, 0.0..1.0)
+    }
+
+    #[test]
+    fn the_limit_evicts_the_oldest_view() {
+        let mut history = History::new(2);
+        history.record(view(0.0));
+        history.record(view(1.0));
+        history.record(view(2.0));
+        assert_eq!(history.undo(&view(3.0)), Some(view(2.0)));
+        assert_eq!(history.undo(&view(2.0)), Some(view(1.0)));
+        assert_eq!(history.undo(&view(1.0)), None);
+    }
+
+    #[test]
+    fn lowering_the_limit_drops_the_oldest_views() {
+        let mut history = History::new(3);
+        for start in 0..3 {
+            history.record(view(start as f64));
+        }
+        history.set_limit(1);
+        assert_eq!(history.undo(&view(3.0)), Some(view(2.0)));
+        assert_eq!(history.undo(&view(2.0)), None);
+    }
+
+    #[test]
+    fn a_zero_limit_records_nothing() {
+        let mut history = History::new(0);
+        history.record(view(0.0));
+        assert_eq!(history.status(), HistoryStatus::default());
+    }
+
+    #[test]
+    fn recording_clears_redo() {
+        let mut history = History::new(10);
+        history.record(view(0.0));
+        assert_eq!(history.undo(&view(1.0)), Some(view(0.0)));
+        assert!(history.status().can_redo);
+        history.record(view(0.0));
+        assert!(!history.status().can_redo);
+        assert_eq!(history.redo(&view(2.0)), None);
+    }
+
+    #[test]
+    fn undo_and_redo_walk_back_and_forth() {
+        let mut history = History::new(10);
+        history.record(view(0.0));
+        history.record(view(1.0));
+        assert_eq!(history.undo(&view(2.0)), Some(view(1.0)));
+        assert_eq!(history.undo(&view(1.0)), Some(view(0.0)));
+        assert_eq!(history.redo(&view(0.0)), Some(view(1.0)));
+        assert_eq!(history.redo(&view(1.0)), Some(view(2.0)));
+    }
+
+    #[test]
+    fn the_ends_return_none() {
+        let mut history = History::new(10);
+        assert_eq!(history.undo(&view(0.0)), None);
+        assert_eq!(history.redo(&view(0.0)), None);
+        assert_eq!(history.status(), HistoryStatus::default());
+
+        history.record(view(0.0));
+        assert_eq!(history.redo(&view(1.0)), None);
+        assert_eq!(history.undo(&view(1.0)), Some(view(0.0)));
+        assert_eq!(history.undo(&view(0.0)), None);
+        assert_eq!(
+            history.status(),
+            HistoryStatus {
+                can_undo: false,
+                can_redo: true
+            }
+        );
+    }
+}
@@ -0,0 +1,343 @@
+use iced::{Color, Point, Rectangle, Vector};
+use std::collections::HashMap;
+
+/// How far from a point or line, in pixels, the cursor still hits it.
+pub(crate) const HIT_RADIUS: f32 = 8.0;
+
+/// How much further from the cursor, in pixels, a data point may be than a line or area
+/// and still win, so a hover near a vertex reports the point rather than its line.
+const POINT_TIE_RADIUS: f32 = 3.0;
+
+/// Identifies a series registered through [`BuildContext`](crate::BuildContext).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SeriesId(pub usize);
+
+/// What kind of drawn element was hit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HitKind {
+    /// A data point; the index is the point's index in its series.
+    Point,
+    /// The line from point `index` to point `index + 1`.
+    Segment,
+    /// A filled area such as a bar; the index is the one it was registered with.
+    Area,
+}
+
+/// The element under a pixel, as returned by
+/// [`ChartWidget::hit_test`](crate::ChartWidget::hit_test).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hit {
+    pub series: SeriesId,
+    pub kind: HitKind,
+    pub index: usize,
+    /// The distance between the tested pixel and the element, `0.0` inside areas.
+    pub distance: f32,
+}
+
+/// Which element wins when several lie within the hit radius.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HitPriority {
+    /// The closest element. A data point also wins over a line or area up to 3 pixels
+    /// closer, so hovering near a vertex reports the point.
+    #[default]
+    Closest,
+    /// The closest element of the series registered last, which is drawn on top, so
+    /// the series the user sees under the cursor wins in dense plots.
+    Topmost,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Shape {
+    Point(Point),
+    Segment(Point, Point),
+    Area(Rectangle),
+}
+
+impl Shape {
+    fn distance(&self, to: Point) -> f32 {
+        self.closest(to).distance(to)
+    }
+
+    /// The point of the shape closest to `to`.
+    fn closest(&self, to: Point) -> Point {
+        match *self {
+            Shape::Point(point) => point,
+            Shape::Segment(from, end) => {
+                let line = end - from;
+                let length = line.x * line.x + line.y * line.y;
+                let t = if length > 0.0 {
+                    let offset = to - from;
+                    ((offset.x * line.x + offset.y * line.y) / length).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+                from + Vector::new(line.x * t, line.y * t)
+            }
+            Shape::Area(rect) => Point::new(
+                to.x.clamp(rect.x, rect.x + rect.width),
+                to.y.clamp(rect.y, rect.y + rect.height),
+            ),
+        }
+    }
+
+    fn kind(&self) -> HitKind {
+        match self {
+            Shape::Point(_) => HitKind::Point,
+            Shape::Segment(..) => HitKind::Segment,
+            Shape::Area(_) => HitKind::Area,
+        }
+    }
+}
+
+/// The elements drawn in the last chart build, in canvas pixels.
+#[derive(Debug, Default)]
+pub(crate) struct HitRegistry {
+    items: Vec<(SeriesId, usize, Shape)>,
+    colors: HashMap<SeriesId, Color>,
+}
+
+impl HitRegistry {
+    pub(crate) fn set_color(&mut self, series: SeriesId, color: Color) {
+        self.colors.insert(series, color);
+    }
+
+    pub(crate) fn color(&self, series: SeriesId) -> Option<Color> {
+        self.colors.get(&series).copied()
+    }
+
+    /// The number of registered elements.
+    pub(crate) fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// The registered points of `series`.
+    pub(crate) fn points(&self, series: SeriesId) -> impl Iterator<Item = Point> + '_ {
+        self.items
+            .iter()
+            .filter_map(move |(id, _, shape)| match shape {
+                Shape::Point(point) if *id == series => Some(*point),
+                _ => None,
+            })
+    }
+
+    /// The registered points of all series with their series and index.
+    pub(crate) fn all_points(&self) -> impl Iterator<Item = (SeriesId, usize, Point)> + '_ {
+        self.items
+            .iter()
+            .filter_map(|(series, index, shape)| match shape {
+                Shape::Point(point) => Some((*series, *index, *point)),
+                _ => None,
+            })
+    }
+
+    /// The registered line segments of `series`.
+    pub(crate) fn segments(&self, series: SeriesId) -> impl Iterator<Item = (Point, Point)> + '_ {
+        self.items
+            .iter()
+            .filter_map(move |(id, _, shape)| match shape {
+                Shape::Segment(from, to) if *id == series => Some((*from, *to)),
+                _ => None,
+            })
+    }
+
+    /// Where a tooltip for `hit` points to: the registered point itself, the point of a
+    /// segment closest to `near`, or the top center of an area.
+    pub(crate) fn anchor(&self, hit: &Hit, near: Point) -> Option<Point> {
+        self.items
+            .iter()
+            .filter(|(series, index, shape)| {
+                *series == hit.series && *index == hit.index && shape.kind() == hit.kind
+            })
+            .map(|(_, _, shape)| match *shape {
+                Shape::Area(rect) => Point::new(rect.x + rect.width / 2.0, rect.y),
+                _ => shape.closest(near),
+            })
+            .min_by(|a, b| {
+                a.distance(near)
+                    .partial_cmp(&b.distance(near))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+    }
+
+    /// The registered point of any series closest to `point`, within `radius`.
+    pub(crate) fn nearest_point(&self, point: Point, radius: f32) -> Option<Point> {
+        self.items
+            .iter()
+            .filter_map(|(_, _, shape)| match shape {
+                Shape::Point(at) => Some(*at),
+                _ => None,
+            })
+            .map(|at| (at, at.distance(point)))
+            .filter(|(_, distance)| *distance <= radius)
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(at, _)| at)
+    }
+
+    /// The ids of all registered series, in ascending order.
+    pub(crate) fn series(&self) -> Vec<SeriesId> {
+        let mut series: Vec<_> = self.items.iter().map(|(id, _, _)| *id).collect();
+        series.sort();
+        series.dedup();
+        series
+    }
+
+    /// The height of the registered line of `series` at the horizontal pixel `x`,
+    /// interpolated along the first segment spanning it.
+    pub(crate) fn y_at(&self, series: SeriesId, x: f32) -> Option<f32> {
+        self.segments(series).find_map(|(from, to)| {
+            let (left, right) = if from.x <= to.x {
+                (from, to)
+            } else {
+                (to, from)
+            };
+            if x < left.x || x > right.x {
+                return None;
+            }
+            if right.x == left.x {
+                return Some(left.y);
+            }
+            Some(left.y + (right.y - left.y) * (x - left.x) / (right.x - left.x))
+        })
+    }
+
+    /// Whether series were registered but none of their elements overlaps `plot`, so the
+    /// visible part of the chart holds no data.
+    pub(crate) fn is_empty_within(&self, plot: Rectangle) -> bool {
+        if self.items.is_empty() && self.colors.is_empty() {
+            return false;
+        }
+        let overlaps = |rect: Rectangle| {
+            rect.x <= plot.x + plot.width
+                && rect.x + rect.width >= plot.x
+                && rect.y <= plot.y + plot.height
+                && rect.y + rect.height >= plot.y
+        };
+        !self.items.iter().any(|(_, _, shape)| match *shape {
+            Shape::Point(point) => plot.contains(point),
+            // The bounding box stands in for the segment.
+            Shape::Segment(from, to) => overlaps(Rectangle {
+                x: from.x.min(to.x),
+                y: from.y.min(to.y),
+                width: (to.x - from.x).abs(),
+                height: (to.y - from.y).abs(),
+            }),
+            Shape::Area(rect) => overlaps(rect),
+        })
+    }
+
+    pub(crate) fn add_point(&mut self, series: SeriesId, index: usize, at: Point) {
+        self.items.push((series, index, Shape::Point(at)));
+    }
+
+    pub(crate) fn add_segment(&mut self, series: SeriesId, index: usize, from: Point, to: Point) {
+        self.items.push((series, index, Shape::Segment(from, to)));
+    }
+
+    pub(crate) fn add_area(&mut self, series: SeriesId, index: usize, rect: Rectangle) {
+        self.items.push((series, index, Shape::Area(rect)));
+    }
+
+    /// Finds the element within `radius` of `point` that wins by `priority`. Within a layer,
+    /// points win over lines and areas up to [`POINT_TIE_RADIUS`] closer, so a hover near a
+    /// vertex reports the data point rather than the line.
+    pub(crate) fn hit_test(&self, point: Point, radius: f32, priority: HitPriority) -> Option<Hit> {
+        // The position of a series' last element; later series are drawn on top.
+        let mut layers: HashMap<SeriesId, usize> = HashMap::new();
+        if priority == HitPriority::Topmost {
+            for (position, (series, _, _)) in self.items.iter().enumerate() {
+                layers.insert(*series, position);
+            }
+        }
+        self.items
+            .iter()
+            .map(|(series, index, shape)| Hit {
+                series: *series,
+                kind: shape.kind(),
+                index: *index,
+                distance: shape.distance(point),
+            })
+            .filter(|hit| hit.distance <= radius)
+            .min_by(|a, b| {
+                let rank = |hit: &Hit| {
+                    let layer = layers
+                        .get(&hit.series)
+                        .map_or(0, |position| usize::MAX - position);
+                    let point = hit.kind == HitKind::Point;
+                    let distance = hit.distance - if point { POINT_TIE_RADIUS } else { 0.0 };
+                    (layer, distance, !point)
+                };
+                rank(a)
+                    .partial_cmp(&rank(b))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hit(registry: &HitRegistry, x: f32, y: f32, priority: HitPriority) -> Option<Hit> {
+        registry.hit_test(Point::new(x, y), HIT_RADIUS, priority)
+    }
+
+    #[test]
+    fn projects_onto_segments() {
+        let segment = Shape::Segment(Point::new(0.0, 0.0), Point::new(10.0, 0.0));
+        assert_eq!(segment.closest(Point::new(4.0, 3.0)), Point::new(4.0, 0.0));
+        assert_eq!(segment.distance(Point::new(4.0, 3.0)), 3.0);
+        // Beyond the ends, the nearest end is closest.
+        assert_eq!(segment.closest(Point::new(-3.0, 4.0)), Point::ORIGIN);
+        assert_eq!(segment.distance(Point::new(13.0, 4.0)), 5.0);
+        let dot = Shape::Segment(Point::new(2.0, 2.0), Point::new(2.0, 2.0));
+        assert_eq!(dot.distance(Point::new(5.0, 6.0)), 5.0);
+    }
+
+    #[test]
+    fn clamps_into_areas() {
+        let area = Shape::Area(Rectangle::new(
+            Point::new(0.0, 0.0),
+            iced::Size::new(10.0, 20.0),
+        ));
+        assert_eq!(area.distance(Point::new(5.0, 5.0)), 0.0);
+        assert_eq!(area.closest(Point::new(15.0, 25.0)), Point::new(10.0, 20.0));
+        assert_eq!(area.distance(Point::new(-3.0, 10.0)), 3.0);
+    }
+
+    #[test]
+    fn prefers_close_points_over_lines() {
+        let mut registry = HitRegistry::default();
+        registry.add_segment(
+            SeriesId(0),
+            0,
+            Point::new(0.0, 50.0),
+            Point::new(100.0, 50.0),
+        );
+        registry.add_point(SeriesId(0), 1, Point::new(50.0, 52.0));
+        registry.add_point(SeriesId(0), 2, Point::new(80.0, 57.0));
+        let closest = |x, y| hit(&registry, x, y, HitPriority::Closest).map(|hit| hit.kind);
+        // The point 2 pixels away wins over the line under the cursor...
+        assert_eq!(closest(50.0, 50.0), Some(HitKind::Point));
+        // ...but one 7 pixels away doesn't.
+        assert_eq!(closest(80.0, 50.0), Some(HitKind::Segment));
+        assert_eq!(closest(50.0, 70.0), None);
+    }
+
+    #[test]
+    fn topmost_prefers_later_series() {
+        let mut registry = HitRegistry::default();
+        let bar = Rectangle::new(Point::new(0.0, 0.0), iced::Size::new(20.0, 20.0));
+        registry.add_point(SeriesId(0), 0, Point::new(10.0, 10.0));
+        registry.add_point(SeriesId(0), 1, Point::new(10.0, 30.0));
+        registry.add_area(SeriesId(1), 3, bar);
+        let series = |y, priority| hit(&registry, 10.0, y, priority).map(|hit| hit.series);
+        assert_eq!(series(10.0, HitPriority::Closest), Some(SeriesId(0)));
+        assert_eq!(series(10.0, HitPriority::Topmost), Some(SeriesId(1)));
+        // The top series wins while in reach, even if further away...
+        let top = hit(&registry, 10.0, 25.0, HitPriority::Topmost).unwrap();
+        assert_eq!((top.series, top.index, top.distance), (SeriesId(1), 3, 5.0));
+        // ...and the series below are hit beyond it.
+        assert_eq!(series(30.0, HitPriority::Topmost), Some(SeriesId(0)));
+    }
+}
@@ -0,0 +1,318 @@
+use crate::{shade_between, SeriesStyle};
+use plotters::chart::ChartContext;
+use plotters::coord::types::RangedCoordf64;
+use plotters::drawing::DrawingAreaErrorKind;
+use plotters::element::PathElement;
+use plotters::prelude::Cartesian2d;
+use plotters::style::Color;
+use plotters_backend::DrawingBackend;
+use std::collections::VecDeque;
+use std::ops::Range;
+
+type IndicatorResult<DB> = Result<(), DrawingAreaErrorKind<<DB as DrawingBackend>::ErrorType>>;
+type Coords = Cartesian2d<RangedCoordf64, RangedCoordf64>;
+
+/// What an [`Indicator`] computes from the last values of a series.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IndicatorKind {
+    /// The mean of the last `period` values.
+    MovingAverage(usize),
+    /// The exponential moving average with a smoothing factor of `2 / (period + 1)`,
+    /// starting from the mean of the first `period` values.
+    ExponentialAverage(usize),
+    /// The moving average of the last `period` values with bands `deviations` standard
+    /// deviations above and below it.
+    Bands { period: usize, deviations: f64 },
+    /// The change from the value `period` points earlier, in percent.
+    RateOfChange(usize),
+}
+
+impl IndicatorKind {
+    fn period(self) -> usize {
+        match self {
+            IndicatorKind::MovingAverage(period)
+            | IndicatorKind::ExponentialAverage(period)
+            | IndicatorKind::Bands { period, .. }
+            | IndicatorKind::RateOfChange(period) => period.max(1),
+        }
+    }
+}
+
+/// A value of an [`Indicator`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IndicatorPoint {
+    pub x: f64,
+    pub value: f64,
+    /// The lower and upper band, for [`IndicatorKind::Bands`].
+    pub band: Option<(f64, f64)>,
+}
+
+/// A moving average, band or rate of change following a series, drawn over the chart as
+/// a series of its own:
+///
+/// ```ignore
+/// let mut average = Indicator::new(IndicatorKind::MovingAverage(20)).name("MA 20");
+/// average.extend(prices.iter().copied());
+/// // As new prices stream in:
+/// average.push(time, price);
+/// ```
+///
+/// It is updated incrementally: every pushed point costs time in proportion to the period,
+/// however long the series grows. The points must be pushed in ascending x order; points with a non-finite value
+/// are skipped.
+#[derive(Debug, Clone)]
+pub struct Indicator {
+    kind: IndicatorKind,
+    name: Option<String>,
+    /// The last values, as many as the computation looks back.
+    window: VecDeque<f64>,
+    average: Option<f64>,
+    points: Vec<IndicatorPoint>,
+}
+
+impl Indicator {
+    pub fn new(kind: IndicatorKind) -> Self {
+        Self {
+            kind,
+            name: None,
+            window: VecDeque::new(),
+            average: None,
+            points: vec![],
+        }
+    }
+
+    /// Computes the indicator over all of `points`.
+    pub fn over(kind: IndicatorKind, points: impl IntoIterator<Item = (f64, f64)>) -> Self {
+        let mut indicator = Self::new(kind);
+        indicator.extend(points);
+        indicator
+    }
+
+    /// Lists the indicator as `name` in the series legend.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn kind(&self) -> IndicatorKind {
+        self.kind
+    }
+
+    /// Feeds the next point of the series.
+    pub fn push(&mut self, x: f64, y: f64) {
+        if !y.is_finite() {
+            return;
+        }
+        let period = self.kind.period();
+        // The rate of change compares with the value one period before the current one.
+        let length = match self.kind {
+            IndicatorKind::RateOfChange(_) => period + 1,
+            _ => period,
+        };
+        self.window.push_back(y);
+        if self.window.len() > length {
+            self.window.pop_front();
+        }
+        if self.window.len() < length {
+            return;
+        }
+
+        // Recomputed over the window rather than kept as running sums, which drift and
+        // lose every digit of the variance far from zero.
+        let mean = self.window.iter().sum::<f64>() / period as f64;
+        let (value, band) = match self.kind {
+            IndicatorKind::MovingAverage(_) => (mean, None),
+            IndicatorKind::ExponentialAverage(_) => {
+                let smoothing = 2.0 / (period as f64 + 1.0);
+                let average = self
+                    .average
+                    .map_or(mean, |average| average + smoothing * (y - average));
+                self.average = Some(average);
+                (average, None)
+            }
+            IndicatorKind::Bands { deviations, .. } => {
+                let variance = self
+                    .window
+                    .iter()
+                    .map(|y| (y - mean) * (y - mean))
+                    .sum::<f64>()
+                    / period as f64;
+                let spread = deviations * variance.sqrt();
+                (mean, Some((mean - spread, mean + spread)))
+            }
+            IndicatorKind::RateOfChange(_) => match self.window.front() {
+                Some(&old) if old != 0.0 => ((y - old) / old.abs() * 100.0, None),
+                _ => return,
+            },
+        };
+        self.points.push(IndicatorPoint { x, value, band });
+    }
+
+    pub fn extend(&mut self, points: impl IntoIterator<Item = (f64, f64)>) {
+        for (x, y) in points {
+            self.push(x, y);
+        }
+    }
+
+    /// Forgets all points, e.g. before feeding a different series.
+    pub fn clear(&mut self) {
+        *self = Self {
+            name: self.name.take(),
+            ..Self::new(self.kind)
+        };
+    }
+
+    pub fn points(&self) -> &[IndicatorPoint] {
+        &self.points
+    }
+
+    /// The points inside `range` of x, plus the nearest point on either side so a line
+    /// through them reaches the edges of the plot. Empty if `range` is reversed.
+    pub fn visible(&self, range: Range<f64>) -> &[IndicatorPoint] {
+        if range.start > range.end || range.start.is_nan() || range.end.is_nan() {
+            return &[];
+        }
+        let start = self.points.partition_point(|point| point.x < range.start);
+        let end = self.points.partition_point(|point| point.x <= range.end);
+        let start = start.saturating_sub(1);
+        let end = (end + 1).min(self.points.len());
+        self.points.get(start..end).unwrap_or(&[])
+    }
+
+    /// Draws the points visible in `chart` as a line in `style`, over the band (if any)
+    /// shaded in a tint of its color, and labels it for the series legend if named.
+    pub fn draw<DB: DrawingBackend>(
+        &self,
+        chart: &mut ChartContext<'_, DB, Coords>,
+        style: &SeriesStyle,
+        width: u32,
+    ) -> IndicatorResult<DB> {
+        let points = self.visible(chart.x_range());
+        let color = style.color();
+        let bands: Vec<_> = points
+            .iter()
+            .filter_map(|point| Some((point.x, point.band?)))
+            .collect();
+        if !bands.is_empty() {
+            let lower: Vec<_> = bands.iter().map(|&(x, (lower, _))| (x, lower)).collect();
+            let upper: Vec<_> = bands.iter().map(|&(x, (_, upper))| (x, upper)).collect();
+            shade_between(
+                chart.plotting_area(),
+                &upper,
+                &lower,
+                color.mix(0.15).filled(),
+            )?;
+            let edge = color.mix(0.5).stroke_width(1);
+            chart.draw_series(vec![
+                PathElement::new(upper, edge.clone()),
+                PathElement::new(lower, edge),
+            ])?;
+        }
+
+        let line = points.iter().map(|point| (point.x, point.value)).collect();
+        let annotation = chart.draw_series(std::iter::once(style.line(line, width)))?;
+        if let Some(name) = &self.name {
+            let style = *style;
+            annotation
+                .label(name.as_str())
+                .legend(move |(x, y)| style.line(vec![(x, y), (x + 20, y)], width));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(kind: IndicatorKind, ys: &[f64]) -> Vec<IndicatorPoint> {
+        let points = ys.iter().enumerate().map(|(x, &y)| (x as f64, y));
+        Indicator::over(kind, points).points().to_vec()
+    }
+
+    #[test]
+    fn computes_averages() {
+        let ys = [1.0, 2.0, 3.0, 4.0, 8.0];
+        let average: Vec<_> = values(IndicatorKind::MovingAverage(3), &ys)
+            .iter()
+            .map(|point| (point.x, point.value))
+            .collect();
+        assert_eq!(average, vec![(2.0, 2.0), (3.0, 3.0), (4.0, 5.0)]);
+        // Starts from the mean of 1, 2 and 3, then moves halfway to each value.
+        let exponential: Vec<_> = values(IndicatorKind::ExponentialAverage(3), &ys)
+            .iter()
+            .map(|point| point.value)
+            .collect();
+        assert_eq!(exponential, vec![2.0, 3.0, 5.5]);
+    }
+
+    #[test]
+    fn computes_bands() {
+        let kind = IndicatorKind::Bands {
+            period: 2,
+            deviations: 2.0,
+        };
+        let bands: Vec<_> = values(kind, &[1.0, 3.0, 3.0])
+            .iter()
+            .map(|point| (point.value, point.band))
+            .collect();
+        assert_eq!(
+            bands,
+            vec![(2.0, Some((0.0, 4.0))), (3.0, Some((3.0, 3.0)))]
+        );
+    }
+
+    #[test]
+    fn computes_rates_of_change() {
+        let rates: Vec<_> = values(IndicatorKind::RateOfChange(1), &[2.0, 3.0, 0.0, 6.0])
+            .iter()
+            .map(|point| (point.x, point.value))
+            .collect();
+        // There is no rate of change from zero.
+        assert_eq!(rates, vec![(1.0, 50.0), (2.0, -100.0)]);
+    }
+
+    #[test]
+    fn pushes_match_a_recompute_over_the_window() {
+        let period = 5;
+        let kind = IndicatorKind::Bands {
+            period,
+            deviations: 1.0,
+        };
+        // Far from zero, where the difference of sums of squares loses every digit.
+        let ys: Vec<_> = (0..200)
+            .map(|i| 1e9 + ((i * 7919) % 13) as f64 / 4.0)
+            .collect();
+        let points = values(kind, &ys);
+        assert_eq!(points.len(), ys.len() - period + 1);
+        for (point, window) in points.iter().zip(ys.windows(period)) {
+            let mean = window.iter().sum::<f64>() / period as f64;
+            let variance =
+                window.iter().map(|y| (y - mean) * (y - mean)).sum::<f64>() / period as f64;
+            let (lower, upper) = point.band.unwrap();
+            assert!((point.value - mean).abs() < 1e-6);
+            let spread = (upper - lower) / 2.0;
+            assert!((spread - variance.sqrt()).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn clamps_the_visible_points() {
+        let indicator = Indicator::over(
+            IndicatorKind::MovingAverage(1),
+            (0..5).map(|x| (x as f64, 0.0)),
+        );
+        let xs = |range| -> Vec<f64> {
+            indicator
+                .visible(range)
+                .iter()
+                .map(|point| point.x)
+                .collect()
+        };
+        assert_eq!(xs(1.5..2.5), vec![1.0, 2.0, 3.0]);
+        assert_eq!(xs(-10.0..10.0), vec![0.0, 1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(xs(10.0..20.0), vec![4.0]);
+        assert_eq!(xs(3.5..1.5), Vec::<f64>::new());
+        assert_eq!(xs(f64::NAN..1.0), Vec::<f64>::new());
+    }
+}
@@ -0,0 +1,414 @@
+use crate::{BuildError, ChartError};
+use std::error::Error;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// A named series of points read from a file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataSeries {
+    pub name: String,
+    pub points: Vec<(f64, f64)>,
+}
+
+/// The series read from one dropped file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dataset {
+    pub path: PathBuf,
+    pub series: Vec<DataSeries>,
+}
+
+/// Turns the contents of a file into series.
+pub trait DataParser {
+    /// Whether this parser handles `path`, usually decided by its extension.
+    fn accepts(&self, path: &Path) -> bool;
+
+    /// Parses `contents`, returning a [`ParseError`] to report the malformed line.
+    fn parse(&self, contents: &str) -> Result<Vec<DataSeries>, BuildError>;
+}
+
+/// A malformed line of a file, returned by a [`DataParser`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    /// The number of the line, counting from 1.
+    pub line: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses comma-separated values: the first column holds x, every further column is a
+/// series of y values. A non-numeric first row is taken as the series names. Cells may be
+/// quoted to hold commas, with doubled quotes standing for quotes; blank lines are skipped.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CsvParser;
+
+impl DataParser for CsvParser {
+    fn accepts(&self, path: &Path) -> bool {
+        has_extension(path, "csv")
+    }
+
+    fn parse(&self, contents: &str) -> Result<Vec<DataSeries>, BuildError> {
+        let mut rows = contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(number, line)| {
+                cells(line)
+                    .map(|cells| (number + 1, cells))
+                    .ok_or_else(|| ParseError::new(number + 1, "unterminated quote"))
+            });
+
+        let mut series: Vec<DataSeries> = vec![];
+        let mut pending = None;
+        if let Some(first) = rows.next() {
+            let (number, header) = first?;
+            if header.iter().all(|cell| cell.parse::<f64>().is_err()) {
+                series = header[1..]
+                    .iter()
+                    .map(|name| DataSeries {
+                        name: name.clone(),
+                        points: vec![],
+                    })
+                    .collect();
+            } else {
+                pending = Some(Ok((number, header)));
+            }
+        }
+
+        for row in pending.into_iter().chain(rows) {
+            let (number, row) = row?;
+            let values = row
+                .iter()
+                .map(|cell| {
+                    cell.parse::<f64>()
+                        .map_err(|_| ParseError::new(number, format!("`{}` is not a number", cell)))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if values.len() < 2 {
+                return Err(
+                    ParseError::new(number, "expected an x and at least one y column").into(),
+                );
+            }
+            while series.len() < values.len() - 1 {
+                series.push(DataSeries {
+                    name: format!("series {}", series.len() + 1),
+                    points: vec![],
+                });
+            }
+            for (series, &y) in series.iter_mut().zip(&values[1..]) {
+                series.points.push((values[0], y));
+            }
+        }
+        Ok(series)
+    }
+}
+
+/// Parses JSON holding either an array of `[x, y]` pairs or an object mapping series
+/// names to such arrays.
+#[cfg(feature = "json")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonParser;
+
+#[cfg(feature = "json")]
+impl DataParser for JsonParser {
+    fn accepts(&self, path: &Path) -> bool {
+        has_extension(path, "json")
+    }
+
+    fn parse(&self, contents: &str) -> Result<Vec<DataSeries>, BuildError> {
+        use serde_json::Value;
+
+        fn points(name: &str, value: &Value) -> Result<DataSeries, BuildError> {
+            let pairs = value
+                .as_array()
+                .ok_or_else(|| format!("series `{}` is not an array", name))?;
+            let points = pairs
+                .iter()
+                .map(|pair| match pair.as_array().map(Vec::as_slice) {
+                    Some([x, y]) => x.as_f64().zip(y.as_f64()),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| format!("series `{}` must hold [x, y] number pairs", name))?;
+            Ok(DataSeries {
+                name: name.to_string(),
+                points,
+            })
+        }
+
+        match serde_json::from_str(contents)? {
+            Value::Object(map) => map
+                .iter()
+                .map(|(name, value)| points(name, value))
+                .collect(),
+            value @ Value::Array(_) => Ok(vec![points("series 1", &value)?]),
+            _ => Err("expected an array of points or an object of series".into()),
+        }
+    }
+}
+
+/// Splits a CSV line into trimmed cells, or `None` if a quote is left open.
+fn cells(line: &str) -> Option<Vec<String>> {
+    let mut cells = vec![];
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                cell.push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => cells.push(std::mem::take(&mut cell).trim().to_string()),
+            c => cell.push(c),
+        }
+    }
+    if quoted {
+        return None;
+    }
+    cells.push(cell.trim().to_string());
+    Some(cells)
+}
+
+/// The contents of a dropped file, read on the application's executor by
+/// [`ChartWidget::file_dropped`] and carried by the application's message back to
+/// [`ChartWidget::finish_file_drop`].
+///
+/// [`ChartWidget::file_dropped`]: crate::ChartWidget::file_dropped
+/// [`ChartWidget::finish_file_drop`]: crate::ChartWidget::finish_file_drop
+#[derive(Debug, Clone)]
+pub struct DroppedFile {
+    pub(crate) path: PathBuf,
+    pub(crate) contents: Result<String, String>,
+}
+
+impl DroppedFile {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Loads files dropped onto a chart with the first parser that accepts them.
+///
+/// The canvas does not see window events, so the application forwards
+/// `window::Event::FileDropped` (e.g. from `iced_native::subscription::events`) to
+/// [`ChartWidget::file_dropped`](crate::ChartWidget::file_dropped).
+pub struct FileDrop {
+    parsers: Vec<Box<dyn DataParser>>,
+}
+
+impl FileDrop {
+    /// Handles CSV files, and JSON files with the `json` feature.
+    pub fn new() -> Self {
+        let drop = Self::empty().parser(CsvParser);
+        #[cfg(feature = "json")]
+        let drop = drop.parser(JsonParser);
+        drop
+    }
+
+    /// Accepts no files until parsers are added.
+    pub fn empty() -> Self {
+        Self { parsers: vec![] }
+    }
+
+    pub fn parser<P: 'static + DataParser>(mut self, parser: P) -> Self {
+        self.parsers.push(Box::new(parser));
+        self
+    }
+
+    /// Whether a parser accepts `path`.
+    pub fn accepts(&self, path: &Path) -> bool {
+        self.parsers.iter().any(|parser| parser.accepts(path))
+    }
+
+    /// Reads the file at `path`, unless no parser accepts it. This blocks, so it runs on
+    /// the application's executor.
+    pub(crate) fn read(&self, path: PathBuf) -> impl FnOnce() -> DroppedFile {
+        let accepted = self.accepts(&path);
+        move || {
+            let contents = if accepted {
+                std::fs::read_to_string(&path).map_err(|error| error.to_string())
+            } else {
+                Err("no parser accepts the file".to_string())
+            };
+            DroppedFile { path, contents }
+        }
+    }
+
+    /// Parses a file read by [`ChartWidget::file_dropped`](crate::ChartWidget::file_dropped)
+    /// with the first parser that accepts it.
+    pub fn parse(&self, file: DroppedFile) -> Result<Dataset, ChartError> {
+        let DroppedFile { path, contents } = file;
+        let fail = |line, message| ChartError::File {
+            path: path.clone(),
+            line,
+            message,
+        };
+        let contents = contents.map_err(|message| fail(None, message))?;
+        let parser = self
+            .parsers
+            .iter()
+            .find(|parser| parser.accepts(&path))
+            .ok_or_else(|| fail(None, "no parser accepts the file".to_string()))?;
+        let series = parser.parse(&contents).map_err(|error| {
+            let (line, message) = locate(error);
+            fail(line, message)
+        })?;
+        Ok(Dataset { path, series })
+    }
+}
+
+impl Default for FileDrop {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The line and message of a parse error.
+fn locate(error: BuildError) -> (Option<usize>, String) {
+    let error = match error.downcast::<ParseError>() {
+        Ok(error) => return (Some(error.line), error.message),
+        Err(error) => error,
+    };
+    #[cfg(feature = "json")]
+    if let Some(json) = error.downcast_ref::<serde_json::Error>() {
+        return (Some(json.line()), error.to_string());
+    }
+    (None, error.to_string())
+}
+
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(extension))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(contents: &str) -> Result<Vec<DataSeries>, ParseError> {
+        CsvParser
+            .parse(contents)
+            .map_err(|error| *error.downcast::<ParseError>().unwrap())
+    }
+
+    #[test]
+    fn names_series_from_the_header() {
+        let series = parse("time,a,b\n0,1,2\n1,3,4\n").unwrap();
+        assert_eq!(
+            series,
+            vec![
+                DataSeries {
+                    name: "a".into(),
+                    points: vec![(0.0, 1.0), (1.0, 3.0)],
+                },
+                DataSeries {
+                    name: "b".into(),
+                    points: vec![(0.0, 2.0), (1.0, 4.0)],
+                },
+            ]
+        );
+        let names: Vec<_> = parse("0,1,2")
+            .unwrap()
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        assert_eq!(names, vec!["series 1", "series 2"]);
+    }
+
+    #[test]
+    fn unquotes_cells() {
+        let series = parse("x,\"price, in \"\"$\"\"\"\n\"1\", \"2.5\" \n").unwrap();
+        assert_eq!(series[0].name, "price, in \"$\"");
+        assert_eq!(series[0].points, vec![(1.0, 2.5)]);
+    }
+
+    #[test]
+    fn skips_blank_lines() {
+        let series = parse("\nx,a\r\n\n  \n0,1\r\n\n2,3\n").unwrap();
+        assert_eq!(series[0].points, vec![(0.0, 1.0), (2.0, 3.0)]);
+    }
+
+    #[test]
+    fn reports_the_malformed_line() {
+        let error = |contents| parse(contents).unwrap_err();
+        assert_eq!(
+            error("x,a\n\n0,1\n1,two\n"),
+            ParseError::new(4, "`two` is not a number")
+        );
+        assert_eq!(
+            error("x,a\n0\n"),
+            ParseError::new(2, "expected an x and at least one y column")
+        );
+        assert_eq!(
+            error("x,\"a\n0,1\n"),
+            ParseError::new(1, "unterminated quote")
+        );
+    }
+
+    #[test]
+    fn reports_the_file_and_line() {
+        let file = |name: &str, contents: &str| DroppedFile {
+            path: PathBuf::from(name),
+            contents: Ok(contents.to_string()),
+        };
+        let drop = FileDrop::empty().parser(CsvParser);
+        let dataset = drop.parse(file("data.CSV", "x,a\n0,1\n")).unwrap();
+        assert_eq!(dataset.path, PathBuf::from("data.CSV"));
+        assert_eq!(dataset.series[0].points, vec![(0.0, 1.0)]);
+        match drop.parse(file("data.csv", "x,a\n0,?\n")) {
+            Err(ChartError::File {
+                path,
+                line: Some(2),
+                ..
+            }) => assert_eq!(path, PathBuf::from("data.csv")),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(!drop.accepts(Path::new("data.txt")));
+        assert!(matches!(
+            drop.parse(file("data.txt", "")),
+            Err(ChartError::File { line: None, .. })
+        ));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn parses_json() {
+        let series = JsonParser
+            .parse(r#"{"a": [[0, 1], [1, 2.5]], "b": []}"#)
+            .unwrap();
+        assert_eq!(series[0].name, "a");
+        assert_eq!(series[0].points, vec![(0.0, 1.0), (1.0, 2.5)]);
+        assert!(series[1].points.is_empty());
+        let series = JsonParser.parse("[[0, 1]]").unwrap();
+        assert_eq!(series[0].name, "series 1");
+        assert!(JsonParser.parse(r#"{"a": [[0, 1, 2]]}"#).is_err());
+        assert!(JsonParser.parse("3").is_err());
+
+        let drop = FileDrop::empty().parser(JsonParser);
+        let file = DroppedFile {
+            path: PathBuf::from("data.json"),
+            contents: Ok("[\n  [0, 1],\n  [1,\n]".to_string()),
+        };
+        assert!(matches!(
+            drop.parse(file),
+            Err(ChartError::File { line: Some(4), .. })
+        ));
+    }
+}
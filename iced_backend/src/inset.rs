@@ -0,0 +1,217 @@
+use crate::{BuildContext, BuildError, Corner, IcedBackend, LayerCoord, OverlayLayer};
+use plotters::chart::ChartBuilder;
+use plotters::coord::Shift;
+use plotters::drawing::DrawingArea;
+use plotters::element::{PathElement, Rectangle};
+use plotters::style::{Color, IntoFont, RGBAColor, ShapeStyle, BLACK, WHITE};
+use plotters_backend::BackendCoord;
+use std::ops::Range;
+
+/// The distance in pixels between an inset in a corner and the edges of the plotting area.
+const MARGIN: i32 = 10;
+
+/// Where an [`Inset`] is placed within the plotting area of the main chart. The inset is
+/// always kept inside the plotting area.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InsetAnchor {
+    /// In a corner, a small margin away from the edges.
+    Corner(Corner),
+    /// With its top left corner `x` and `y` pixels from the top left corner of the
+    /// plotting area.
+    Pixel { x: i32, y: i32 },
+    /// With its top left corner at a data point of the main chart, so it moves along when
+    /// the view is panned.
+    Data { x: f64, y: f64 },
+}
+
+/// A small secondary chart drawn inside the plotting area of the main chart, such as a
+/// zoomed view of a region of interest.
+///
+/// The inset has its own coordinate system spanning the data ranges it was created with,
+/// and draws its series through an [`OverlayLayer`]. It can mark its source region on the
+/// main chart and link that region to itself (see [`Inset::link`]).
+pub struct Inset<L> {
+    layer: L,
+    x: Range<f64>,
+    y: Range<f64>,
+    anchor: InsetAnchor,
+    size: (u32, u32),
+    caption: Option<String>,
+    background: RGBAColor,
+    border: ShapeStyle,
+    link: Option<ShapeStyle>,
+}
+
+impl<L: OverlayLayer> Inset<L> {
+    /// An inset drawing `layer` over the data ranges `x` and `y`, 240 by 160 pixels in the
+    /// top right corner.
+    pub fn new(layer: L, x: Range<f64>, y: Range<f64>) -> Self {
+        Self {
+            layer,
+            x,
+            y,
+            anchor: InsetAnchor::Corner(Corner::TopRight),
+            size: (240, 160),
+            caption: None,
+            background: WHITE.to_rgba(),
+            border: BLACK.mix(0.6).stroke_width(1),
+            link: None,
+        }
+    }
+
+    pub fn anchor(mut self, anchor: InsetAnchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// Sets the size of the inset in pixels, including its labels.
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.size = (width, height);
+        self
+    }
+
+    pub fn caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = Some(caption.into());
+        self
+    }
+
+    pub fn background(mut self, color: impl Color) -> Self {
+        self.background = color.to_rgba();
+        self
+    }
+
+    pub fn border(mut self, style: impl Into<ShapeStyle>) -> Self {
+        self.border = style.into();
+        self
+    }
+
+    /// Outlines the inset's data ranges on the main chart with `style` and connects the
+    /// outline to the inset.
+    pub fn link(mut self, style: impl Into<ShapeStyle>) -> Self {
+        self.link = Some(style.into());
+        self
+    }
+
+    pub fn layer(&self) -> &L {
+        &self.layer
+    }
+
+    pub fn layer_mut(&mut self) -> &mut L {
+        &mut self.layer
+    }
+
+    /// Draws the inset onto `area`, the plotting area of the main chart. Call it after
+    /// drawing the main chart's series, so the inset covers them.
+    pub fn draw(
+        &self,
+        ctx: &mut BuildContext<'_>,
+        area: &DrawingArea<IcedBackend<'_>, LayerCoord>,
+    ) -> Result<(), BuildError> {
+        let plot = area.strip_coord_spec();
+        let (width, height) = plot.dim_in_pixel();
+        let size = (
+            self.size.0.min(width) as i32,
+            self.size.1.min(height) as i32,
+        );
+        let origin = self.origin(area, (width as i32, height as i32), size);
+
+        if let Some(style) = &self.link {
+            self.draw_link(area, &plot, origin, size, style)?;
+        }
+
+        let inset = plot.shrink(origin, size);
+        inset.fill(&self.background)?;
+        let mut builder = ChartBuilder::on(&inset);
+        builder
+            .margin(4)
+            .x_label_area_size(18)
+            .y_label_area_size(36);
+        if let Some(caption) = &self.caption {
+            builder.caption(caption, ("sans-serif", 14).into_font());
+        }
+        let mut chart = builder.build_cartesian_2d(self.x.clone(), self.y.clone())?;
+        chart
+            .configure_mesh()
+            .x_labels(3)
+            .y_labels(3)
+            .label_style(("sans-serif", 10))
+            .draw()?;
+        self.layer.build_layer(ctx, chart.plotting_area())?;
+        inset.draw(&Rectangle::new(
+            [(0, 0), (size.0 - 1, size.1 - 1)],
+            self.border.clone(),
+        ))?;
+        Ok(())
+    }
+
+    /// The top left corner of the inset relative to the plotting area, which is `bounds`
+    /// pixels large.
+    fn origin(
+        &self,
+        area: &DrawingArea<IcedBackend<'_>, LayerCoord>,
+        bounds: (i32, i32),
+        size: (i32, i32),
+    ) -> BackendCoord {
+        let (x, y) = match self.anchor {
+            InsetAnchor::Corner(corner) => {
+                let x = match corner {
+                    Corner::TopLeft | Corner::BottomLeft => MARGIN,
+                    Corner::TopRight | Corner::BottomRight => bounds.0 - MARGIN - size.0,
+                };
+                let y = match corner {
+                    Corner::TopLeft | Corner::TopRight => MARGIN,
+                    Corner::BottomLeft | Corner::BottomRight => bounds.1 - MARGIN - size.1,
+                };
+                (x, y)
+            }
+            InsetAnchor::Pixel { x, y } => (x, y),
+            InsetAnchor::Data { x, y } => relative(area, (x, y)),
+        };
+        (x.clamp(0, bounds.0 - size.0), y.clamp(0, bounds.1 - size.1))
+    }
+
+    /// Outlines the source region and connects it to the inset at `origin` by the two
+    /// corners that keep the connectors outside both rectangles.
+    fn draw_link(
+        &self,
+        area: &DrawingArea<IcedBackend<'_>, LayerCoord>,
+        plot: &DrawingArea<IcedBackend<'_>, Shift>,
+        origin: BackendCoord,
+        size: (i32, i32),
+        style: &ShapeStyle,
+    ) -> Result<(), BuildError> {
+        area.draw(&Rectangle::new(
+            [(self.x.start, self.y.end), (self.x.end, self.y.start)],
+            style.clone(),
+        ))?;
+
+        let (left, top) = relative(area, (self.x.start, self.y.end));
+        let (right, bottom) = relative(area, (self.x.end, self.y.start));
+        let source = [(left, top), (right, top), (right, bottom), (left, bottom)];
+        let (x, y) = origin;
+        let target = [
+            (x, y),
+            (x + size.0, y),
+            (x + size.0, y + size.1),
+            (x, y + size.1),
+        ];
+
+        let dx = (x + size.0 / 2) - (left + right) / 2;
+        let dy = (y + size.1 / 2) - (top + bottom) / 2;
+        let corners = if (dx > 0) != (dy > 0) { [0, 2] } else { [1, 3] };
+        for corner in corners.iter() {
+            plot.draw(&PathElement::new(
+                vec![source[*corner], target[*corner]],
+                style.clone(),
+            ))?;
+        }
+        Ok(())
+    }
+}
+
+/// The pixel position of the data point `point` relative to the top left corner of `area`.
+fn relative(area: &DrawingArea<IcedBackend<'_>, LayerCoord>, point: (f64, f64)) -> BackendCoord {
+    let (x, y) = area.map_coordinate(&point);
+    let (base_x, base_y) = area.get_base_pixel();
+    (x - base_x, y - base_y)
+}
@@ -0,0 +1,706 @@
+use crate::{Axis, ViewState};
+use iced::canvas::{Cursor, Frame, Path, Stroke};
+use iced::keyboard::ModifiersState;
+use iced::{mouse, Color, Point, Rectangle, Size, Vector};
+use std::time::{Duration, Instant};
+
+/// How strongly a one-pixel drag along an axis gutter scales that axis.
+const AXIS_DRAG_SENSITIVITY: f64 = 0.01;
+/// How strongly a one-pixel drag in [`DragMode::Zoom`] zooms.
+const ZOOM_DRAG_SENSITIVITY: f64 = 0.01;
+/// The smallest box, in pixels along each side, a box zoom zooms to.
+const MIN_ZOOM_BOX: f32 = 4.0;
+/// The zoom factor applied per scrolled line.
+const WHEEL_ZOOM_STEP: f64 = 1.2;
+/// How many pixels of a pixel-based scroll delta make up one line.
+const PIXELS_PER_LINE: f32 = 20.0;
+/// How much of the pointer movement before a release counts towards the fling velocity.
+const FLING_WINDOW: Duration = Duration::from_millis(100);
+/// The slowest release, in pixels per second, that starts coasting.
+const MIN_FLING_SPEED: f32 = 300.0;
+/// The fraction of its velocity a coasting view keeps per second.
+const COAST_FRICTION: f32 = 0.02;
+/// Coasting stops once it slows below this many pixels per second.
+const MIN_COAST_SPEED: f32 = 10.0;
+
+/// The part of the canvas a point lies in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Region {
+    Plot,
+    XAxis,
+    YAxis,
+    SecondaryYAxis,
+    Outside,
+}
+
+impl Region {
+    /// Classifies `point` (relative to the canvas) against the plotting area. The axis
+    /// gutters are the strips below, left and right of the plotting area.
+    pub(crate) fn at(plot: Rectangle, bounds: Rectangle, point: Point) -> Self {
+        let within_x = point.x >= plot.x && point.x <= plot.x + plot.width;
+        let within_y = point.y >= plot.y && point.y <= plot.y + plot.height;
+        if within_x && within_y {
+            Region::Plot
+        } else if within_x && point.y > plot.y + plot.height && point.y <= bounds.height {
+            Region::XAxis
+        } else if within_y && point.x < plot.x && point.x >= 0.0 {
+            Region::YAxis
+        } else if within_y && point.x > plot.x + plot.width && point.x <= bounds.width {
+            Region::SecondaryYAxis
+        } else {
+            Region::Outside
+        }
+    }
+
+    /// The axis whose gutter this region is.
+    pub(crate) fn axis(self) -> Option<Axis> {
+        match self {
+            Region::XAxis => Some(Axis::X),
+            Region::YAxis => Some(Axis::Y),
+            Region::SecondaryYAxis => Some(Axis::SecondaryY),
+            Region::Plot | Region::Outside => None,
+        }
+    }
+}
+
+/// What scrolling over the plotting area does.
+///
+/// Mouse wheels scroll in lines and trackpads in pixels; by default the wheel zooms and
+/// the trackpad pans along both axes, following the fingers. Either can be changed with
+/// [`ChartWidget::scroll_actions`](crate::ChartWidget::scroll_actions).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScrollAction {
+    /// Zooms around the cursor; scrolling up zooms in.
+    Zoom,
+    /// Pans the view as if dragged by the scrolled distance.
+    Pan,
+}
+
+/// What dragging with a mouse button in the plotting area does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DragMode {
+    /// Moves the view along with the cursor.
+    Pan,
+    /// Draws a box and zooms to it when the button is released.
+    BoxZoom,
+    /// Zooms around the point the drag started at: dragging right zooms x in and dragging
+    /// up zooms y in.
+    Zoom,
+    /// Leaves the button to the widget's other tools, such as reference lines.
+    None,
+}
+
+/// Which mouse button does what, see
+/// [`ChartWidget::mouse_bindings`](crate::ChartWidget::mouse_bindings).
+///
+/// By default the left button pans and the other buttons do nothing. Buttons other than
+/// [`DragMode::None`] also scale an axis when dragged along its gutter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MouseBindings {
+    pub left: DragMode,
+    pub middle: DragMode,
+    pub right: DragMode,
+}
+
+impl MouseBindings {
+    pub fn new() -> Self {
+        Self {
+            left: DragMode::Pan,
+            middle: DragMode::None,
+            right: DragMode::None,
+        }
+    }
+
+    pub fn left(mut self, mode: DragMode) -> Self {
+        self.left = mode;
+        self
+    }
+
+    pub fn middle(mut self, mode: DragMode) -> Self {
+        self.middle = mode;
+        self
+    }
+
+    pub fn right(mut self, mode: DragMode) -> Self {
+        self.right = mode;
+        self
+    }
+
+    /// What dragging with `button` does. Other buttons do nothing.
+    pub fn mode(&self, button: mouse::Button) -> DragMode {
+        match button {
+            mouse::Button::Left => self.left,
+            mouse::Button::Middle => self.middle,
+            mouse::Button::Right => self.right,
+            mouse::Button::Other(_) => DragMode::None,
+        }
+    }
+}
+
+impl Default for MouseBindings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A modifier key, for [`ModifierBindings`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Modifier {
+    Shift,
+    Control,
+    Alt,
+    /// The Windows or Command key.
+    Logo,
+}
+
+impl Modifier {
+    fn is_held(self, modifiers: ModifiersState) -> bool {
+        match self {
+            Modifier::Shift => modifiers.shift,
+            Modifier::Control => modifiers.control,
+            Modifier::Alt => modifiers.alt,
+            Modifier::Logo => modifiers.logo,
+        }
+    }
+}
+
+/// How held modifier keys change panning and zooming, see
+/// [`ChartWidget::modifier_bindings`](crate::ChartWidget::modifier_bindings).
+///
+/// By default Shift restricts panning and zooming to x and Alt restricts them to y.
+/// [`ModifierBindings::standard`] instead follows common document viewers: the wheel only
+/// zooms with Ctrl held, Shift keeps a pan to one axis and Alt drags a zoom box.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModifierBindings {
+    /// The key the wheel and trackpad only act with. Without it scrolling is ignored, so
+    /// a chart inside a scrollable doesn't catch the scrolling of the page.
+    pub scroll: Option<Modifier>,
+    /// The key restricting panning and zooming to x.
+    pub x_only: Option<Modifier>,
+    /// The key restricting panning and zooming to y.
+    pub y_only: Option<Modifier>,
+    /// The key keeping a pan to the axis it has moved furthest along.
+    pub constrain_pan: Option<Modifier>,
+    /// The key turning any drag in the plotting area into a box zoom.
+    pub box_zoom: Option<Modifier>,
+}
+
+impl ModifierBindings {
+    pub fn new() -> Self {
+        Self {
+            scroll: None,
+            x_only: Some(Modifier::Shift),
+            y_only: Some(Modifier::Alt),
+            constrain_pan: None,
+            box_zoom: None,
+        }
+    }
+
+    /// Ctrl+wheel zooms, Shift+drag pans along one axis and Alt+drag zooms to a box.
+    pub fn standard() -> Self {
+        Self {
+            scroll: Some(Modifier::Control),
+            x_only: None,
+            y_only: None,
+            constrain_pan: Some(Modifier::Shift),
+            box_zoom: Some(Modifier::Alt),
+        }
+    }
+}
+
+impl Default for ModifierBindings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The axes panning and zooming in the plotting area act on.
+///
+/// Holding Shift restricts a gesture to x and holding Alt restricts it to y, whatever
+/// lock is set with [`ChartWidget::axis_lock`](crate::ChartWidget::axis_lock), unless
+/// [`ModifierBindings`] assign the keys otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AxisLock {
+    /// Both axes move.
+    #[default]
+    Free,
+    /// Only x moves, e.g. for time series whose y range follows the visible data.
+    XOnly,
+    /// Only y moves.
+    YOnly,
+}
+
+impl AxisLock {
+    fn moves_x(self) -> bool {
+        self != AxisLock::YOnly
+    }
+
+    fn moves_y(self) -> bool {
+        self != AxisLock::XOnly
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Drag {
+    Pan {
+        start: Point,
+    },
+    /// A box from `start` to the current point.
+    BoxZoom {
+        start: Point,
+    },
+    Zoom {
+        anchor: (f64, f64),
+    },
+    ScaleX {
+        anchor: f64,
+    },
+    ScaleY {
+        anchor: f64,
+    },
+    ScaleSecondaryY {
+        anchor: f64,
+    },
+}
+
+/// A view that keeps panning after a fling, slowing down with friction.
+#[derive(Debug, Clone, Copy)]
+struct Coast {
+    /// In pixels per second.
+    velocity: Vector,
+    last: Instant,
+}
+
+/// The pan/zoom state machine of the chart widget.
+#[derive(Debug)]
+pub(crate) struct Interaction {
+    drag: Option<(Drag, Point)>,
+    /// The button holding the drag.
+    button: Option<mouse::Button>,
+    pub(crate) bindings: MouseBindings,
+    /// Whether releasing a fast pan lets the view coast.
+    pub(crate) kinetic: bool,
+    /// The recent pointer positions of the current pan, for the fling velocity.
+    samples: Vec<(Instant, Point)>,
+    coast: Option<Coast>,
+    /// What a scroll in lines, as sent by mouse wheels, does.
+    pub(crate) line_scroll: ScrollAction,
+    /// What a scroll in pixels, as sent by trackpads, does.
+    pub(crate) pixel_scroll: ScrollAction,
+    pub(crate) lock: AxisLock,
+    /// The modifier keys currently held, which can override `lock`.
+    pub(crate) modifiers: ModifiersState,
+    pub(crate) modifier_bindings: ModifierBindings,
+}
+
+impl Default for Interaction {
+    fn default() -> Self {
+        Self {
+            drag: None,
+            button: None,
+            bindings: MouseBindings::default(),
+            kinetic: false,
+            samples: vec![],
+            coast: None,
+            line_scroll: ScrollAction::Zoom,
+            pixel_scroll: ScrollAction::Pan,
+            lock: AxisLock::Free,
+            modifiers: ModifiersState::default(),
+            modifier_bindings: ModifierBindings::default(),
+        }
+    }
+}
+
+impl Interaction {
+    /// Applies a mouse event to `view`, returning whether the view changed.
+    pub(crate) fn on_mouse(
+        &mut self,
+        event: mouse::Event,
+        view: &mut ViewState,
+        plot: Rectangle,
+        bounds: Rectangle,
+        cursor: Cursor,
+    ) -> bool {
+        // Any new input stops a coasting view.
+        if let mouse::Event::ButtonPressed(_) | mouse::Event::WheelScrolled { .. } = event {
+            self.coast = None;
+        }
+        match event {
+            mouse::Event::ButtonPressed(button) if self.drag.is_none() => {
+                let mode = match self.bindings.mode(button) {
+                    DragMode::None => DragMode::None,
+                    _ if self.holds(self.modifier_bindings.box_zoom) => DragMode::BoxZoom,
+                    mode => mode,
+                };
+                self.drag = cursor.position_in(&bounds).and_then(|point| {
+                    let drag = match Region::at(plot, bounds, point) {
+                        Region::Plot => match mode {
+                            DragMode::Pan => Drag::Pan { start: point },
+                            DragMode::BoxZoom => Drag::BoxZoom { start: point },
+                            DragMode::Zoom => Drag::Zoom {
+                                anchor: (fraction_x(plot, point), fraction_y(plot, point)),
+                            },
+                            DragMode::None => return None,
+                        },
+                        _ if mode == DragMode::None => return None,
+                        Region::XAxis => Drag::ScaleX {
+                            anchor: fraction_x(plot, point),
+                        },
+                        Region::YAxis => Drag::ScaleY {
+                            anchor: fraction_y(plot, point),
+                        },
+                        Region::SecondaryYAxis if view.secondary_y.is_some() => {
+                            Drag::ScaleSecondaryY {
+                                anchor: fraction_y(plot, point),
+                            }
+                        }
+                        Region::SecondaryYAxis | Region::Outside => return None,
+                    };
+                    Some((drag, point))
+                });
+                self.button = self.drag.map(|_| button);
+                self.samples.clear();
+                if let Some((Drag::Pan { .. }, point)) = self.drag {
+                    self.samples.push((Instant::now(), point));
+                }
+                false
+            }
+            mouse::Event::ButtonReleased(button) if self.button == Some(button) => {
+                if let (true, Some((Drag::Pan { .. }, _))) = (self.kinetic, self.drag) {
+                    self.coast = self.fling(Instant::now());
+                }
+                let zoomed = match self.drag {
+                    Some((Drag::BoxZoom { start }, end)) => {
+                        self.zoom_to_box(view, plot, start, end)
+                    }
+                    _ => false,
+                };
+                self.drag = None;
+                self.button = None;
+                zoomed
+            }
+            mouse::Event::CursorMoved { .. } => {
+                let (drag, last) = match self.drag {
+                    Some(drag) => drag,
+                    None => return false,
+                };
+                let point = match cursor.position_from(bounds.position()) {
+                    Some(point) => point,
+                    None => return false,
+                };
+                let (dx, dy) = (f64::from(point.x - last.x), f64::from(point.y - last.y));
+                self.drag = Some((drag, point));
+                match drag {
+                    Drag::Pan { start } => {
+                        let mut lock = self.lock();
+                        if self.holds(self.modifier_bindings.constrain_pan) {
+                            let moved = point - start;
+                            lock = if moved.x.abs() >= moved.y.abs() {
+                                AxisLock::XOnly
+                            } else {
+                                AxisLock::YOnly
+                            };
+                        }
+                        let dx = if lock.moves_x() { dx } else { 0.0 };
+                        let dy = if lock.moves_y() { dy } else { 0.0 };
+                        if self.kinetic {
+                            let now = Instant::now();
+                            self.samples.push((now, point));
+                            self.samples
+                                .retain(|(at, _)| now.duration_since(*at) <= FLING_WINDOW);
+                        }
+                        pan(view, plot, dx, dy)
+                    }
+                    Drag::BoxZoom { .. } => return false,
+                    Drag::Zoom { anchor } => {
+                        let lock = self.lock();
+                        if lock.moves_x() {
+                            view.x.zoom((-dx * ZOOM_DRAG_SENSITIVITY).exp(), anchor.0);
+                        }
+                        if lock.moves_y() {
+                            let factor = (dy * ZOOM_DRAG_SENSITIVITY).exp();
+                            view.y.zoom(factor, anchor.1);
+                            if let Some(y) = &mut view.secondary_y {
+                                y.zoom(factor, anchor.1);
+                            }
+                        }
+                    }
+                    // Dragging right along the x axis or up along the y axis zooms in.
+                    Drag::ScaleX { anchor } => {
+                        view.x.zoom((-dx * AXIS_DRAG_SENSITIVITY).exp(), anchor)
+                    }
+                    Drag::ScaleY { anchor } => {
+                        view.y.zoom((dy * AXIS_DRAG_SENSITIVITY).exp(), anchor)
+                    }
+                    Drag::ScaleSecondaryY { anchor } => {
+                        if let Some(y) = &mut view.secondary_y {
+                            y.zoom((dy * AXIS_DRAG_SENSITIVITY).exp(), anchor)
+                        }
+                    }
+                }
+                true
+            }
+            mouse::Event::WheelScrolled { delta } => {
+                let point = match cursor.position_in(&bounds) {
+                    Some(point) if Region::at(plot, bounds, point) == Region::Plot => point,
+                    _ => return false,
+                };
+                if let (Some(_), false) = (
+                    self.modifier_bindings.scroll,
+                    self.holds(self.modifier_bindings.scroll),
+                ) {
+                    return false;
+                }
+                let (action, (x, y)) = match delta {
+                    mouse::ScrollDelta::Lines { x, y } => (self.line_scroll, (x, y)),
+                    mouse::ScrollDelta::Pixels { x, y } => (
+                        self.pixel_scroll,
+                        (x / PIXELS_PER_LINE, y / PIXELS_PER_LINE),
+                    ),
+                };
+                let lock = self.lock();
+                match action {
+                    ScrollAction::Zoom => {
+                        let factor = WHEEL_ZOOM_STEP.powf(-f64::from(y));
+                        if lock.moves_x() {
+                            view.x.zoom(factor, fraction_x(plot, point));
+                        }
+                        if lock.moves_y() {
+                            view.y.zoom(factor, fraction_y(plot, point));
+                            if let Some(y) = &mut view.secondary_y {
+                                y.zoom(factor, fraction_y(plot, point));
+                            }
+                        }
+                    }
+                    ScrollAction::Pan => {
+                        let dx = f64::from(x * PIXELS_PER_LINE);
+                        let dy = f64::from(y * PIXELS_PER_LINE);
+                        pan(
+                            view,
+                            plot,
+                            if lock.moves_x() { dx } else { 0.0 },
+                            if lock.moves_y() { dy } else { 0.0 },
+                        );
+                    }
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// The lock in effect, taking held modifier keys into account.
+    fn lock(&self) -> AxisLock {
+        if self.holds(self.modifier_bindings.x_only) {
+            AxisLock::XOnly
+        } else if self.holds(self.modifier_bindings.y_only) {
+            AxisLock::YOnly
+        } else {
+            self.lock
+        }
+    }
+
+    /// Whether `modifier` is set and held.
+    fn holds(&self, modifier: Option<Modifier>) -> bool {
+        modifier.is_some_and(|modifier| modifier.is_held(self.modifiers))
+    }
+
+    /// The velocity of the pointer over the last moments of a pan released at `now`, if
+    /// it was fast enough to start coasting.
+    fn fling(&self, now: Instant) -> Option<Coast> {
+        let recent: Vec<_> = self
+            .samples
+            .iter()
+            .filter(|(at, _)| now.duration_since(*at) <= FLING_WINDOW)
+            .collect();
+        let (first, last) = (recent.first()?, recent.last()?);
+        let elapsed = last.0.duration_since(first.0).as_secs_f32();
+        if elapsed <= 0.0 {
+            return None;
+        }
+        let velocity = (last.1 - first.1) * (1.0 / elapsed);
+        let lock = self.lock();
+        let velocity = Vector::new(
+            if lock.moves_x() { velocity.x } else { 0.0 },
+            if lock.moves_y() { velocity.y } else { 0.0 },
+        );
+        if velocity.x.hypot(velocity.y) < MIN_FLING_SPEED {
+            return None;
+        }
+        Some(Coast {
+            velocity,
+            last: now,
+        })
+    }
+
+    pub(crate) fn stop_coasting(&mut self) {
+        self.coast = None;
+    }
+
+    pub(crate) fn is_coasting(&self) -> bool {
+        self.coast.is_some()
+    }
+
+    /// Advances a coasting view to `now`, returning whether it moved.
+    pub(crate) fn coast(&mut self, view: &mut ViewState, plot: Rectangle, now: Instant) -> bool {
+        let coast = match &mut self.coast {
+            Some(coast) => coast,
+            None => return false,
+        };
+        let elapsed = now.saturating_duration_since(coast.last).as_secs_f32();
+        coast.last = now;
+        // The distance travelled while the velocity decays exponentially over `elapsed`.
+        let decay = COAST_FRICTION.powf(elapsed);
+        let distance = (1.0 - decay) / -COAST_FRICTION.ln();
+        pan(
+            view,
+            plot,
+            f64::from(coast.velocity.x * distance),
+            f64::from(coast.velocity.y * distance),
+        );
+        coast.velocity = coast.velocity * decay;
+        if coast.velocity.x.hypot(coast.velocity.y) < MIN_COAST_SPEED {
+            self.coast = None;
+        }
+        true
+    }
+
+    pub(crate) fn is_dragging(&self) -> bool {
+        self.drag.is_some()
+    }
+
+    /// The box of a running box zoom, within the plotting area.
+    pub(crate) fn zoom_box(&self, plot: Rectangle) -> Option<Rectangle> {
+        match self.drag {
+            Some((Drag::BoxZoom { start }, end)) => Some(box_within(plot, start, end)),
+            _ => None,
+        }
+    }
+
+    /// Shows the data in the box from `start` to `end`, along the axes not locked, and
+    /// returns whether the view changed. Boxes too small to be meant are ignored.
+    fn zoom_to_box(&self, view: &mut ViewState, plot: Rectangle, start: Point, end: Point) -> bool {
+        let area = box_within(plot, start, end);
+        let lock = self.lock();
+        let wide = lock.moves_x() && area.width >= MIN_ZOOM_BOX;
+        let high = lock.moves_y() && area.height >= MIN_ZOOM_BOX;
+        if !(wide || high) {
+            return false;
+        }
+        let (left, top) = (
+            fraction_x(plot, Point::new(area.x, area.y)),
+            fraction_y(plot, Point::new(area.x, area.y)),
+        );
+        let (right, bottom) = (
+            fraction_x(plot, Point::new(area.x + area.width, area.y + area.height)),
+            fraction_y(plot, Point::new(area.x + area.width, area.y + area.height)),
+        );
+        if wide {
+            view.x.set(view.x.value_at(left)..view.x.value_at(right));
+        }
+        if high {
+            if let Some(y) = &mut view.secondary_y {
+                y.set(y.value_at(bottom)..y.value_at(top));
+            }
+            view.y.set(view.y.value_at(bottom)..view.y.value_at(top));
+        }
+        true
+    }
+
+    pub(crate) fn draw_zoom_box(&self, frame: &mut Frame, plot: Rectangle) {
+        let area = match self.zoom_box(plot) {
+            Some(area) => area,
+            None => return,
+        };
+        let path = Path::rectangle(
+            Point::new(area.x, area.y),
+            Size::new(area.width, area.height),
+        );
+        frame.fill(&path, Color::from_rgba(0.2, 0.4, 0.9, 0.1));
+        frame.stroke(
+            &path,
+            Stroke {
+                color: Color::from_rgb(0.2, 0.4, 0.9),
+                width: 1.0,
+                ..Stroke::default()
+            },
+        );
+    }
+
+    pub(crate) fn mouse_interaction(
+        &self,
+        view: &ViewState,
+        plot: Rectangle,
+        bounds: Rectangle,
+        cursor: Cursor,
+    ) -> mouse::Interaction {
+        match self.drag {
+            Some((Drag::Pan { .. }, _)) => mouse::Interaction::Grabbing,
+            Some((Drag::BoxZoom { .. }, _)) | Some((Drag::Zoom { .. }, _)) => {
+                mouse::Interaction::Crosshair
+            }
+            Some((Drag::ScaleX { .. }, _)) => mouse::Interaction::ResizingHorizontally,
+            Some((Drag::ScaleY { .. }, _)) | Some((Drag::ScaleSecondaryY { .. }, _)) => {
+                mouse::Interaction::ResizingVertically
+            }
+            None => match cursor
+                .position_in(&bounds)
+                .map(|point| Region::at(plot, bounds, point))
+            {
+                Some(Region::Plot) if self.bindings.left == DragMode::Pan => {
+                    mouse::Interaction::Grab
+                }
+                Some(Region::Plot) if self.bindings.left == DragMode::None => {
+                    mouse::Interaction::Idle
+                }
+                Some(Region::Plot) => mouse::Interaction::Crosshair,
+                Some(Region::XAxis) => mouse::Interaction::ResizingHorizontally,
+                Some(Region::YAxis) => mouse::Interaction::ResizingVertically,
+                Some(Region::SecondaryYAxis) if view.secondary_y.is_some() => {
+                    mouse::Interaction::ResizingVertically
+                }
+                _ => mouse::Interaction::Idle,
+            },
+        }
+    }
+}
+
+/// Moves the view so the content follows a drag by `dx`, `dy` pixels.
+fn pan(view: &mut ViewState, plot: Rectangle, dx: f64, dy: f64) {
+    view.x.pan(-dx / f64::from(plot.width));
+    view.y.pan(dy / f64::from(plot.height));
+    if let Some(y) = &mut view.secondary_y {
+        y.pan(dy / f64::from(plot.height));
+    }
+}
+
+/// The box spanned by `start` and `end`, clamped to `plot`.
+fn box_within(plot: Rectangle, start: Point, end: Point) -> Rectangle {
+    let clamp = |point: Point| {
+        Point::new(
+            point.x.max(plot.x).min(plot.x + plot.width),
+            point.y.max(plot.y).min(plot.y + plot.height),
+        )
+    };
+    let (start, end) = (clamp(start), clamp(end));
+    Rectangle {
+        x: start.x.min(end.x),
+        y: start.y.min(end.y),
+        width: (end.x - start.x).abs(),
+        height: (end.y - start.y).abs(),
+    }
+}
+
+fn fraction_x(plot: Rectangle, point: Point) -> f64 {
+    f64::from((point.x - plot.x) / plot.width)
+}
+
+fn fraction_y(plot: Rectangle, point: Point) -> f64 {
+    f64::from((plot.y + plot.height - point.y) / plot.height)
+}
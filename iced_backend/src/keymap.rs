@@ -0,0 +1,269 @@
+use iced::keyboard::{KeyCode, ModifiersState};
+
+/// What a key binding of a [`Keymap`] does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum KeyAction {
+    PanLeft,
+    PanRight,
+    PanUp,
+    PanDown,
+    ZoomIn,
+    ZoomOut,
+    /// Goes back to the view the widget was created with.
+    ResetView,
+    UndoView,
+    RedoView,
+    /// Reported through [`ChartWidget::on_key_action`](crate::ChartWidget::on_key_action)
+    /// for the application to capture the chart, e.g. with `ChartWidget::screenshot`.
+    Screenshot,
+    /// Drops the points picked while measuring.
+    CancelMeasurement,
+    /// Copies the hovered element, or else the selected points, as text, see
+    /// [`ChartWidget::on_copy`](crate::ChartWidget::on_copy).
+    CopyValue,
+    /// Shows or hides the
+    /// [`ChartWidget::debug_overlay`](crate::ChartWidget::debug_overlay).
+    ToggleDebugOverlay,
+}
+
+/// Groups of [`KeyAction`]s turned on and off together with [`Keymap::disable`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum KeyGroup {
+    /// Panning, zooming and resetting the view.
+    Navigation,
+    /// Undoing and redoing view changes.
+    History,
+    Screenshot,
+    Measurement,
+    Clipboard,
+    Debug,
+}
+
+impl KeyAction {
+    pub fn group(self) -> KeyGroup {
+        match self {
+            KeyAction::PanLeft
+            | KeyAction::PanRight
+            | KeyAction::PanUp
+            | KeyAction::PanDown
+            | KeyAction::ZoomIn
+            | KeyAction::ZoomOut
+            | KeyAction::ResetView => KeyGroup::Navigation,
+            KeyAction::UndoView | KeyAction::RedoView => KeyGroup::History,
+            KeyAction::Screenshot => KeyGroup::Screenshot,
+            KeyAction::CancelMeasurement => KeyGroup::Measurement,
+            KeyAction::CopyValue => KeyGroup::Clipboard,
+            KeyAction::ToggleDebugOverlay => KeyGroup::Debug,
+        }
+    }
+}
+
+/// The keys a [`ChartWidget`](crate::ChartWidget) reacts to while the cursor is over it,
+/// see [`ChartWidget::keymap`](crate::ChartWidget::keymap).
+///
+/// By default:
+///
+/// | Keys | Action |
+/// |---|---|
+/// | Arrows | Pan by a tenth of the view |
+/// | `+`, `=`, `-` | Zoom in and out |
+/// | Home, `0` | Reset the view |
+/// | Ctrl+Z | Undo a view change |
+/// | Ctrl+Shift+Z, Ctrl+Y | Redo a view change |
+/// | Print Screen | Screenshot |
+/// | Escape | Cancel measuring |
+/// | Ctrl+C | Copy the hovered or selected values |
+/// | F12 | Toggle the debug overlay |
+///
+/// A binding matches only with exactly its modifiers held.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keymap {
+    bindings: Vec<(KeyCode, ModifiersState, KeyAction)>,
+    disabled: Vec<KeyGroup>,
+}
+
+impl Keymap {
+    /// A keymap without bindings.
+    pub fn empty() -> Self {
+        Self {
+            bindings: vec![],
+            disabled: vec![],
+        }
+    }
+
+    /// Binds `key` with exactly `modifiers` held to `action`, replacing what it was bound to.
+    pub fn bind(mut self, key: KeyCode, modifiers: ModifiersState, action: KeyAction) -> Self {
+        self = self.unbind(key, modifiers);
+        self.bindings.push((key, modifiers, action));
+        self
+    }
+
+    pub fn unbind(mut self, key: KeyCode, modifiers: ModifiersState) -> Self {
+        self.bindings
+            .retain(|(bound, held, _)| (*bound, *held) != (key, modifiers));
+        self
+    }
+
+    /// Removes every binding of `action`, e.g. before binding it to other keys.
+    pub fn unbind_action(mut self, action: KeyAction) -> Self {
+        self.bindings.retain(|(_, _, bound)| *bound != action);
+        self
+    }
+
+    /// Ignores the bindings of the actions in `group`, keeping them for
+    /// [`Keymap::enable`].
+    pub fn disable(mut self, group: KeyGroup) -> Self {
+        if !self.disabled.contains(&group) {
+            self.disabled.push(group);
+        }
+        self
+    }
+
+    pub fn enable(mut self, group: KeyGroup) -> Self {
+        self.disabled.retain(|disabled| *disabled != group);
+        self
+    }
+
+    /// The action bound to `key` with `modifiers` held, unless its group is disabled.
+    pub fn action(&self, key: KeyCode, modifiers: ModifiersState) -> Option<KeyAction> {
+        self.bindings
+            .iter()
+            .find(|(bound, held, _)| (*bound, *held) == (key, modifiers))
+            .map(|(_, _, action)| *action)
+            .filter(|action| !self.disabled.contains(&action.group()))
+    }
+
+    /// The keys bound to `action`, with their modifiers.
+    pub fn keys(&self, action: KeyAction) -> impl Iterator<Item = (KeyCode, ModifiersState)> + '_ {
+        self.bindings
+            .iter()
+            .filter(move |(_, _, bound)| *bound == action)
+            .map(|(key, modifiers, _)| (*key, *modifiers))
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let none = ModifiersState::default();
+        let control = ModifiersState {
+            control: true,
+            ..none
+        };
+        let control_shift = ModifiersState {
+            shift: true,
+            ..control
+        };
+        let shift = ModifiersState {
+            shift: true,
+            ..none
+        };
+        Keymap::empty()
+            .bind(KeyCode::Left, none, KeyAction::PanLeft)
+            .bind(KeyCode::Right, none, KeyAction::PanRight)
+            .bind(KeyCode::Up, none, KeyAction::PanUp)
+            .bind(KeyCode::Down, none, KeyAction::PanDown)
+            .bind(KeyCode::Add, none, KeyAction::ZoomIn)
+            .bind(KeyCode::Equals, none, KeyAction::ZoomIn)
+            // `+` is Shift+`=` on many layouts.
+            .bind(KeyCode::Equals, shift, KeyAction::ZoomIn)
+            .bind(KeyCode::Subtract, none, KeyAction::ZoomOut)
+            .bind(KeyCode::Minus, none, KeyAction::ZoomOut)
+            .bind(KeyCode::Home, none, KeyAction::ResetView)
+            .bind(KeyCode::Key0, none, KeyAction::ResetView)
+            .bind(KeyCode::Z, control, KeyAction::UndoView)
+            .bind(KeyCode::Z, control_shift, KeyAction::RedoView)
+            .bind(KeyCode::Y, control, KeyAction::RedoView)
+            .bind(KeyCode::Snapshot, none, KeyAction::Screenshot)
+            .bind(KeyCode::Escape, none, KeyAction::CancelMeasurement)
+            .bind(KeyCode::C, control, KeyAction::CopyValue)
+            .bind(KeyCode::F12, none, KeyAction::ToggleDebugOverlay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NONE: ModifiersState = ModifiersState {
+        shift: false,
+        control: false,
+        alt: false,
+        logo: false,
+    };
+    const CONTROL: ModifiersState = ModifiersState {
+        control: true,
+        ..NONE
+    };
+    const CONTROL_SHIFT: ModifiersState = ModifiersState {
+        shift: true,
+        ..CONTROL
+    };
+
+    #[test]
+    fn default_bindings() {
+        let keymap = Keymap::default();
+        let cases = [
+            (KeyCode::C, CONTROL, Some(KeyAction::CopyValue)),
+            (KeyCode::Z, CONTROL, Some(KeyAction::UndoView)),
+            (KeyCode::Z, CONTROL_SHIFT, Some(KeyAction::RedoView)),
+            (KeyCode::Y, CONTROL, Some(KeyAction::RedoView)),
+            (KeyCode::Left, NONE, Some(KeyAction::PanLeft)),
+            (KeyCode::Equals, NONE, Some(KeyAction::ZoomIn)),
+            (KeyCode::Escape, NONE, Some(KeyAction::CancelMeasurement)),
+            // Only the exact modifiers match.
+            (KeyCode::C, NONE, None),
+            (KeyCode::Z, NONE, None),
+            (KeyCode::Left, CONTROL, None),
+            (KeyCode::Q, NONE, None),
+        ];
+        for (key, modifiers, action) in cases.iter() {
+            assert_eq!(keymap.action(*key, *modifiers), *action, "{:?}", key);
+        }
+    }
+
+    #[test]
+    fn binding_replaces_the_previous_action() {
+        let keymap = Keymap::default().bind(KeyCode::C, CONTROL, KeyAction::Screenshot);
+        assert_eq!(
+            keymap.action(KeyCode::C, CONTROL),
+            Some(KeyAction::Screenshot)
+        );
+        assert_eq!(keymap.keys(KeyAction::CopyValue).count(), 0);
+        let keymap = keymap.bind(KeyCode::Insert, CONTROL, KeyAction::CopyValue);
+        assert_eq!(
+            keymap.keys(KeyAction::CopyValue).collect::<Vec<_>>(),
+            vec![(KeyCode::Insert, CONTROL)]
+        );
+    }
+
+    #[test]
+    fn unbinding() {
+        let keymap = Keymap::default().unbind(KeyCode::Z, CONTROL);
+        assert_eq!(keymap.action(KeyCode::Z, CONTROL), None);
+        assert_eq!(
+            keymap.action(KeyCode::Z, CONTROL_SHIFT),
+            Some(KeyAction::RedoView)
+        );
+
+        let keymap = keymap.unbind_action(KeyAction::RedoView);
+        assert_eq!(keymap.action(KeyCode::Z, CONTROL_SHIFT), None);
+        assert_eq!(keymap.action(KeyCode::Y, CONTROL), None);
+    }
+
+    #[test]
+    fn disabled_groups_are_kept_for_enabling() {
+        let keymap = Keymap::default().disable(KeyGroup::History);
+        assert_eq!(keymap.action(KeyCode::Z, CONTROL), None);
+        assert_eq!(
+            keymap.action(KeyCode::C, CONTROL),
+            Some(KeyAction::CopyValue)
+        );
+        let keymap = keymap.enable(KeyGroup::History);
+        assert_eq!(
+            keymap.action(KeyCode::Z, CONTROL),
+            Some(KeyAction::UndoView)
+        );
+    }
+}
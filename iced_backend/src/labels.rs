@@ -0,0 +1,333 @@
+use plotters::coord::Shift;
+use plotters::drawing::{DrawingArea, DrawingAreaErrorKind};
+use plotters::element::PathElement;
+use plotters::style::{ShapeStyle, TextStyle};
+use plotters_backend::{BackendCoord, DrawingBackend};
+
+/// The directions tried around an anchor, in order of preference.
+const DIRECTIONS: [(i32, i32); 8] = [
+    (1, -1),
+    (-1, -1),
+    (1, 1),
+    (-1, 1),
+    (1, 0),
+    (-1, 0),
+    (0, -1),
+    (0, 1),
+];
+
+/// A value label attached to a data point.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataLabel {
+    /// The labelled point in backend pixels, e.g. from `ChartContext::backend_coord`.
+    pub anchor: BackendCoord,
+    pub text: String,
+}
+
+impl DataLabel {
+    pub fn new(anchor: BackendCoord, text: impl Into<String>) -> Self {
+        Self {
+            anchor,
+            text: text.into(),
+        }
+    }
+}
+
+/// Where a [`DataLabel`] ended up.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlacedLabel {
+    pub text: String,
+    /// The top-left corner of the label in backend pixels.
+    pub position: BackendCoord,
+    pub size: (u32, u32),
+    /// The line from the anchor to the label, if it was pushed away from its point.
+    pub leader: Option<(BackendCoord, BackendCoord)>,
+}
+
+/// Places data labels next to their points so they overlap neither each other nor the
+/// points.
+///
+/// Every label tries the eight positions around its anchor, preferring above-right. With
+/// leader lines enabled, positions further away are tried as well and connected to the
+/// anchor by a line. Labels that fit nowhere are dropped unless overlaps are allowed.
+#[derive(Debug, Clone)]
+pub struct LabelPlacer {
+    offset: i32,
+    marker_radius: i32,
+    leader_lines: bool,
+    keep_overlapping: bool,
+}
+
+impl LabelPlacer {
+    pub fn new() -> Self {
+        Self {
+            offset: 4,
+            marker_radius: 3,
+            leader_lines: false,
+            keep_overlapping: false,
+        }
+    }
+
+    /// The gap between a point and its label, in pixels.
+    pub fn offset(mut self, offset: i32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// The radius around each anchor that labels keep clear of, in pixels. Negative radii
+    /// count as zero.
+    pub fn marker_radius(mut self, radius: i32) -> Self {
+        self.marker_radius = radius.max(0);
+        self
+    }
+
+    /// Tries positions further from the point and draws a leader line to them.
+    pub fn leader_lines(mut self, enabled: bool) -> Self {
+        self.leader_lines = enabled;
+        self
+    }
+
+    /// Places labels that fit nowhere at their preferred position instead of dropping them.
+    pub fn keep_overlapping(mut self, keep: bool) -> Self {
+        self.keep_overlapping = keep;
+        self
+    }
+
+    /// Places `labels` inside `bounds` (in backend pixels), measuring them with `measure`.
+    pub fn place<E>(
+        &self,
+        labels: &[DataLabel],
+        bounds: (BackendCoord, BackendCoord),
+        mut measure: impl FnMut(&str) -> Result<(u32, u32), E>,
+    ) -> Result<Vec<PlacedLabel>, E> {
+        let markers: Vec<_> = labels
+            .iter()
+            .map(|label| {
+                let (x, y) = label.anchor;
+                let r = self.marker_radius.max(0);
+                Rect::new((x - r, y - r), ((2 * r) as u32, (2 * r) as u32))
+            })
+            .collect();
+        let rings = if self.leader_lines { 3 } else { 1 };
+
+        let mut taken: Vec<Rect> = vec![];
+        let mut placed = vec![];
+        for label in labels {
+            let size = measure(&label.text)?;
+            let candidates = (1..=rings).flat_map(|ring| {
+                DIRECTIONS.iter().map(move |&direction| {
+                    (ring, self.candidate(label.anchor, size, direction, ring))
+                })
+            });
+            let mut chosen = None;
+            for (ring, rect) in candidates {
+                let free = rect.inside(bounds)
+                    && !taken
+                        .iter()
+                        .chain(&markers)
+                        .any(|other| rect.intersects(other));
+                if free {
+                    chosen = Some((ring, rect));
+                    break;
+                }
+            }
+            let (ring, rect) = match chosen {
+                Some(chosen) => chosen,
+                None if self.keep_overlapping => {
+                    (1, self.candidate(label.anchor, size, DIRECTIONS[0], 1))
+                }
+                None => continue,
+            };
+            taken.push(rect);
+            placed.push(PlacedLabel {
+                text: label.text.clone(),
+                position: rect.origin,
+                size,
+                leader: if ring > 1 {
+                    Some((label.anchor, rect.closest_to(label.anchor)))
+                } else {
+                    None
+                },
+            });
+        }
+        Ok(placed)
+    }
+
+    /// Places `labels` within `area`, measuring them with the area's text measurement, and
+    /// draws them along with their leader lines.
+    pub fn draw<DB: DrawingBackend>(
+        &self,
+        area: &DrawingArea<DB, Shift>,
+        labels: &[DataLabel],
+        style: &TextStyle,
+        leader_style: impl Into<ShapeStyle>,
+    ) -> Result<Vec<PlacedLabel>, DrawingAreaErrorKind<DB::ErrorType>> {
+        let (x, y) = area.get_pixel_range();
+        let bounds = ((x.start, y.start), (x.end, y.end));
+        let placed = self.place(labels, bounds, |text| area.estimate_text_size(text, style))?;
+
+        // The area draws relative to its own origin.
+        let local = |(px, py): BackendCoord| (px - x.start, py - y.start);
+        let leader_style = leader_style.into();
+        for label in &placed {
+            if let Some((from, to)) = label.leader {
+                area.draw(&PathElement::new(
+                    vec![local(from), local(to)],
+                    leader_style.clone(),
+                ))?;
+            }
+            area.draw_text(&label.text, style, local(label.position))?;
+        }
+        Ok(placed)
+    }
+
+    fn candidate(
+        &self,
+        (x, y): BackendCoord,
+        (width, height): (u32, u32),
+        (dx, dy): (i32, i32),
+        ring: i32,
+    ) -> Rect {
+        let gap = self.marker_radius + self.offset * ring + (ring - 1) * height as i32;
+        let (width, height) = (width as i32, height as i32);
+        let left = match dx {
+            1 => x + gap,
+            -1 => x - gap - width,
+            _ => x - width / 2,
+        };
+        let top = match dy {
+            1 => y + gap,
+            -1 => y - gap - height,
+            _ => y - height / 2,
+        };
+        Rect::new((left, top), (width as u32, height as u32))
+    }
+}
+
+impl Default for LabelPlacer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Rect {
+    origin: BackendCoord,
+    end: BackendCoord,
+}
+
+impl Rect {
+    fn new(origin: BackendCoord, (width, height): (u32, u32)) -> Self {
+        Self {
+            origin,
+            end: (origin.0 + width as i32, origin.1 + height as i32),
+        }
+    }
+
+    fn intersects(&self, other: &Rect) -> bool {
+        self.origin.0 < other.end.0
+            && other.origin.0 < self.end.0
+            && self.origin.1 < other.end.1
+            && other.origin.1 < self.end.1
+    }
+
+    fn inside(&self, (min, max): (BackendCoord, BackendCoord)) -> bool {
+        self.origin.0 >= min.0
+            && self.origin.1 >= min.1
+            && self.end.0 <= max.0
+            && self.end.1 <= max.1
+    }
+
+    fn closest_to(&self, (x, y): BackendCoord) -> BackendCoord {
+        (
+            x.max(self.origin.0).min(self.end.0),
+            y.max(self.origin.1).min(self.end.1),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOUNDS: (BackendCoord, BackendCoord) = ((0, 0), (200, 100));
+
+    fn place(placer: &LabelPlacer, anchors: &[BackendCoord]) -> Vec<PlacedLabel> {
+        let labels: Vec<_> = anchors
+            .iter()
+            .map(|&anchor| DataLabel::new(anchor, "label"))
+            .collect();
+        placer
+            .place(&labels, BOUNDS, |_| Ok::<_, ()>((30, 10)))
+            .unwrap()
+    }
+
+    fn rect(label: &PlacedLabel) -> Rect {
+        Rect::new(label.position, label.size)
+    }
+
+    #[test]
+    fn keeps_labels_apart() {
+        let anchors = [(100, 50), (104, 50), (100, 54), (96, 46)];
+        let placer = LabelPlacer::new();
+        let placed = place(&placer, &anchors);
+        assert_eq!(placed.len(), anchors.len());
+        for (i, label) in placed.iter().enumerate() {
+            assert!(rect(label).inside(BOUNDS));
+            assert!(label.leader.is_none());
+            for other in &placed[i + 1..] {
+                assert!(!rect(label).intersects(&rect(other)));
+            }
+            for &(x, y) in &anchors {
+                assert!(!rect(label).intersects(&Rect::new((x - 3, y - 3), (6, 6))));
+            }
+        }
+        // The first label takes the preferred position, above and right of its point.
+        assert_eq!(placed[0].position, (107, 33));
+    }
+
+    #[test]
+    fn falls_back_to_leader_lines() {
+        // Nine points one label apart leave the middle one no position next to it.
+        let anchors: Vec<_> = (-1..=1)
+            .flat_map(|dy| (-1..=1).map(move |dx| (100 + 40 * dx, 50 + 18 * dy)))
+            .filter(|&anchor| anchor != (100, 50))
+            .chain(std::iter::once((100, 50)))
+            .collect();
+        let placer = LabelPlacer::new().marker_radius(12);
+        assert_eq!(place(&placer, &anchors).len(), anchors.len() - 1);
+        let placed = place(&placer.leader_lines(true), &anchors);
+        assert_eq!(placed.len(), anchors.len());
+        let last = placed.last().unwrap();
+        assert_eq!(
+            last.leader,
+            Some(((100, 50), rect(last).closest_to((100, 50))))
+        );
+    }
+
+    #[test]
+    fn keeps_labels_inside_the_bounds() {
+        let placer = LabelPlacer::new();
+        // Above-right would leave the area, so the label goes below and left.
+        let placed = place(&placer, &[(195, 5)]);
+        assert_eq!(placed[0].position, (158, 12));
+        // Nothing fits in an area smaller than the label.
+        let labels = [DataLabel::new((5, 5), "label")];
+        let tiny = ((0, 0), (20, 20));
+        let measure = |_: &str| Ok::<_, ()>((30, 10));
+        assert!(placer.place(&labels, tiny, measure).unwrap().is_empty());
+        let kept = placer
+            .clone()
+            .keep_overlapping(true)
+            .place(&labels, tiny, measure)
+            .unwrap();
+        assert_eq!(kept[0].position, (12, -12));
+    }
+
+    #[test]
+    fn ignores_negative_marker_radii() {
+        let placer = LabelPlacer::new().marker_radius(-5);
+        let placed = place(&placer, &[(100, 50)]);
+        assert_eq!(placed[0].position, (104, 36));
+    }
+}
@@ -0,0 +1,155 @@
+use crate::hit::HitRegistry;
+use crate::SeriesId;
+use iced::canvas::{Frame, Path, Stroke};
+use iced::{Color, Point};
+use std::collections::HashSet;
+
+/// The minimum distance between two recorded lasso points, in pixels.
+const MIN_STEP: f32 = 2.0;
+
+/// A freeform region being drawn with the lasso tool, in canvas pixels.
+#[derive(Debug, Default)]
+pub(crate) struct Lasso {
+    path: Vec<Point>,
+}
+
+impl Lasso {
+    pub(crate) fn start(point: Point) -> Self {
+        Self { path: vec![point] }
+    }
+
+    pub(crate) fn extend(&mut self, point: Point) {
+        match self.path.last() {
+            Some(last) if last.distance(point) < MIN_STEP => {}
+            _ => self.path.push(point),
+        }
+    }
+
+    /// Whether `point` lies inside the region closed by joining the last point to the first,
+    /// by the even-odd rule.
+    pub(crate) fn contains(&self, point: Point) -> bool {
+        if self.path.len() < 3 {
+            return false;
+        }
+        let mut inside = false;
+        let mut prev = self.path[self.path.len() - 1];
+        for &cur in &self.path {
+            if (cur.y > point.y) != (prev.y > point.y)
+                && point.x < prev.x + (point.y - prev.y) / (cur.y - prev.y) * (cur.x - prev.x)
+            {
+                inside = !inside;
+            }
+            prev = cur;
+        }
+        inside
+    }
+
+    /// The registered data points inside the region.
+    pub(crate) fn select(&self, hits: &HitRegistry) -> Vec<(SeriesId, usize)> {
+        hits.all_points()
+            .filter(|(_, _, point)| self.contains(*point))
+            .map(|(series, index, _)| (series, index))
+            .collect()
+    }
+
+    pub(crate) fn draw(&self, frame: &mut Frame) {
+        let path = Path::new(|builder| {
+            if let Some(first) = self.path.first() {
+                builder.move_to(*first);
+                for point in &self.path[1..] {
+                    builder.line_to(*point);
+                }
+                builder.close();
+            }
+        });
+        frame.fill(&path, Color::from_rgba(0.2, 0.4, 0.9, 0.1));
+        frame.stroke(
+            &path,
+            Stroke {
+                color: Color::from_rgb(0.2, 0.4, 0.9),
+                width: 1.0,
+                ..Stroke::default()
+            },
+        );
+    }
+}
+
+/// Rings the selected points, looked up in the current build so they follow the view.
+pub(crate) fn draw_selection(
+    frame: &mut Frame,
+    hits: &HitRegistry,
+    selection: &[(SeriesId, usize)],
+) {
+    let stroke = Stroke {
+        color: Color::from_rgb(0.9, 0.5, 0.0),
+        width: 2.0,
+        ..Stroke::default()
+    };
+    let selection: HashSet<_> = selection.iter().copied().collect();
+    for (series, index, point) in hits.all_points() {
+        if selection.contains(&(series, index)) {
+            frame.stroke(&Path::circle(point, 6.0), stroke);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lasso(points: &[(f32, f32)]) -> Lasso {
+        let mut lasso = Lasso::start(Point::new(points[0].0, points[0].1));
+        for &(x, y) in &points[1..] {
+            lasso.extend(Point::new(x, y));
+        }
+        lasso
+    }
+
+    /// A U open at the bottom, with a notch from y = 10 down between x = 10 and 20.
+    const U: [(f32, f32); 8] = [
+        (0.0, 0.0),
+        (30.0, 0.0),
+        (30.0, 30.0),
+        (20.0, 30.0),
+        (20.0, 10.0),
+        (10.0, 10.0),
+        (10.0, 30.0),
+        (0.0, 30.0),
+    ];
+
+    #[test]
+    fn fewer_than_three_points_contain_nothing() {
+        assert!(!lasso(&[(0.0, 0.0)]).contains(Point::new(0.0, 0.0)));
+        assert!(!lasso(&[(0.0, 0.0), (10.0, 10.0)]).contains(Point::new(5.0, 5.0)));
+        // Points closer than the minimum step are not recorded.
+        let close = lasso(&[(0.0, 0.0), (10.0, 0.0), (10.5, 0.5)]);
+        assert!(!close.contains(Point::new(5.0, 0.1)));
+    }
+
+    #[test]
+    fn concave_lasso() {
+        for points in [U.to_vec(), U.iter().rev().copied().collect()] {
+            let lasso = lasso(&points);
+            assert!(lasso.contains(Point::new(5.0, 20.0)));
+            assert!(lasso.contains(Point::new(25.0, 20.0)));
+            assert!(lasso.contains(Point::new(15.0, 5.0)));
+            assert!(!lasso.contains(Point::new(15.0, 20.0)));
+            assert!(!lasso.contains(Point::new(40.0, 5.0)));
+            assert!(!lasso.contains(Point::new(-5.0, 20.0)));
+        }
+    }
+
+    #[test]
+    fn points_at_the_height_of_a_horizontal_edge() {
+        let lasso = lasso(&U);
+        assert!(lasso.contains(Point::new(5.0, 10.0)));
+        assert!(lasso.contains(Point::new(25.0, 10.0)));
+        assert!(!lasso.contains(Point::new(-5.0, 10.0)));
+        assert!(!lasso.contains(Point::new(35.0, 10.0)));
+        // Edges are half open, so a point on a top edge is inside and one on a bottom
+        // edge is outside, and lassos sharing an edge never both contain a point.
+        assert!(lasso.contains(Point::new(15.0, 0.0)));
+        assert!(!lasso.contains(Point::new(15.0, 10.0)));
+        assert!(!lasso.contains(Point::new(5.0, 30.0)));
+    }
+}
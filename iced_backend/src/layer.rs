@@ -0,0 +1,153 @@
+use crate::GeometryGroups;
+use iced::canvas::{Cache, Frame, Geometry};
+use iced::Size;
+use std::cell::{Cell, RefCell};
+
+/// A part of a [`ChartWidget`](crate::ChartWidget) whose geometry is cached on its own, see
+/// [`ChartWidget::invalidate_layer`](crate::ChartWidget::invalidate_layer).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Layer {
+    /// The mesh, axes and labels [`Chart::build_chart`](crate::Chart::build_chart) draws.
+    Grid,
+    /// The series [`Chart::build_chart`](crate::Chart::build_chart) draws.
+    Data,
+    /// What [`Chart::build_overlay`](crate::Chart::build_overlay) draws.
+    Overlay,
+    /// The annotations of the widget.
+    Annotations,
+}
+
+impl Layer {
+    /// The name of the [`GeometryGroups`] group the layer is drawn into.
+    pub(crate) fn group(self) -> &'static str {
+        match self {
+            Layer::Grid => "grid",
+            Layer::Data => "data",
+            Layer::Overlay => "overlay",
+            Layer::Annotations => "annotations",
+        }
+    }
+}
+
+/// The cached [`Layer::Grid`] and [`Layer::Data`], which the chart draws in one build.
+pub(crate) struct ChartLayers {
+    grid: Cache,
+    data: Cache,
+    /// The sizes the grid and the data were last drawn at, until they are cleared.
+    drawn: Cell<(Option<Size>, Option<Size>)>,
+    /// Whether the chart drew into [`Layer::Data`] the last time it was built; if not,
+    /// everything is in the grid and neither layer can be cleared alone.
+    split: Cell<bool>,
+}
+
+impl ChartLayers {
+    pub(crate) fn new() -> Self {
+        Self {
+            grid: Cache::new(),
+            data: Cache::new(),
+            drawn: Cell::new((None, None)),
+            split: Cell::new(false),
+        }
+    }
+
+    /// The geometry of the grid and the data at `size`. If either isn't cached, `build`
+    /// draws into groups of frames scaled by `scale`, with the cached layers skipped.
+    pub(crate) fn draw(
+        &self,
+        size: Size,
+        scale: f32,
+        build: impl FnOnce(&GeometryGroups),
+    ) -> (Geometry, Geometry) {
+        let (grid, data) = self.drawn.get();
+        let (grid, data) = (grid == Some(size), data == Some(size));
+        let mut drawn = (None, None);
+        if !grid || !data {
+            let mut groups =
+                GeometryGroups::with_surfaces(size, Layer::Grid.group(), move |size| {
+                    let mut frame = Frame::new(size);
+                    frame.scale(scale);
+                    frame
+                });
+            if grid {
+                groups = groups.skip(Layer::Grid.group());
+            }
+            if data {
+                groups = groups.skip(Layer::Data.group());
+            }
+            build(&groups);
+            drawn = (
+                groups.take(Layer::Grid.group()),
+                groups.take(Layer::Data.group()),
+            );
+            if !data {
+                self.split.set(drawn.1.is_some());
+            }
+            self.drawn.set((Some(size), Some(size)));
+        }
+        let (grid, data) = (RefCell::new(drawn.0), RefCell::new(drawn.1));
+        (
+            self.grid.draw(size, |frame| {
+                if let Some(drawn) = grid.borrow_mut().take() {
+                    *frame = drawn;
+                }
+            }),
+            self.data.draw(size, |frame| {
+                if let Some(drawn) = data.borrow_mut().take() {
+                    *frame = drawn;
+                }
+            }),
+        )
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.grid.clear();
+        self.data.clear();
+        self.drawn.set((None, None));
+    }
+
+    /// Clears [`Layer::Grid`] or [`Layer::Data`] alone if the chart draws them apart, or
+    /// else both.
+    pub(crate) fn clear_layer(&mut self, layer: Layer) {
+        let (grid, data) = self.drawn.get();
+        match layer {
+            _ if !self.split.get() => self.clear(),
+            Layer::Grid => {
+                self.grid.clear();
+                self.drawn.set((None, data));
+            }
+            Layer::Data => {
+                self.data.clear();
+                self.drawn.set((grid, None));
+            }
+            Layer::Overlay | Layer::Annotations => {}
+        }
+    }
+}
+
+/// A canvas [`Cache`] that is also cleared when it is drawn for a different `K`, such as
+/// the view the geometry depends on, and can be cleared while drawing.
+pub(crate) struct LayerCache<K> {
+    cache: RefCell<Cache>,
+    key: RefCell<Option<K>>,
+}
+
+impl<K: PartialEq> LayerCache<K> {
+    pub(crate) fn new() -> Self {
+        Self {
+            cache: RefCell::new(Cache::new()),
+            key: RefCell::new(None),
+        }
+    }
+
+    pub(crate) fn draw(&self, size: Size, key: K, f: impl Fn(&mut Frame)) -> Geometry {
+        if self.key.borrow().as_ref() != Some(&key) {
+            self.cache.borrow_mut().clear();
+            *self.key.borrow_mut() = Some(key);
+        }
+        self.cache.borrow().draw(size, f)
+    }
+
+    pub(crate) fn clear(&self) {
+        self.cache.borrow_mut().clear();
+    }
+}
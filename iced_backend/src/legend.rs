@@ -0,0 +1,327 @@
+use crate::hit::HitRegistry;
+use crate::SeriesId;
+use iced::canvas::{Frame, Path, Stroke, Text};
+use iced::{Color, Point, Rectangle, Size};
+use plotters::coord::Shift;
+use plotters::drawing::{DrawingArea, DrawingAreaErrorKind};
+use plotters::style::TextStyle;
+use plotters_backend::DrawingBackend;
+use std::ops::Range;
+
+/// The width plotters reserves for the swatch in front of every legend entry.
+const SWATCH_WIDTH: u32 = 30;
+/// The margin plotters leaves around the legend entries, inside the border.
+const LEGEND_MARGIN: u32 = 10;
+/// The gap between the legend and the edge of the plotting area or the canvas.
+const LEGEND_GAP: u32 = 5;
+/// The padding between the border of a [`LegendStyle`] legend and its entries.
+const PADDING: f32 = 6.0;
+/// The width of the swatch in front of every entry of a [`LegendStyle`] legend.
+const SWATCH: f32 = 18.0;
+/// The gap between the swatch and the name of an entry.
+const SWATCH_GAP: f32 = 6.0;
+/// The height of an entry relative to the text size.
+const LINE_HEIGHT: f32 = 1.4;
+/// A rough average glyph width relative to the text size, used to size the legend.
+const GLYPH_WIDTH: f32 = 0.6;
+
+/// Where the series legend of a chart is drawn, see
+/// [`ChartWidget::legend_position`](crate::ChartWidget::legend_position).
+///
+/// Charts read it from [`BuildContext::legend_position`](crate::BuildContext::legend_position),
+/// reserve [`LegendPosition::margins`] beside the plotting area and place the legend at
+/// [`LegendPosition::place`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LegendPosition {
+    UpperLeft,
+    #[default]
+    UpperRight,
+    LowerLeft,
+    LowerRight,
+    /// Right of the plotting area, which shrinks to make room.
+    OutsideRight,
+    /// Below the x axis, with the plotting area shrunk to make room.
+    OutsideBottom,
+    /// With its top left corner at `x` and `y`, fractions of the width and height of the
+    /// plotting area from its top left corner, where the user dragged it.
+    Floating {
+        x: f32,
+        y: f32,
+    },
+}
+
+impl LegendPosition {
+    /// The extra margins right of and below the chart for a legend `size` pixels large.
+    pub fn margins(&self, size: (u32, u32)) -> (u32, u32) {
+        match self {
+            LegendPosition::OutsideRight => (size.0 + 2 * LEGEND_GAP, 0),
+            LegendPosition::OutsideBottom => (0, size.1 + 2 * LEGEND_GAP),
+            _ => (0, 0),
+        }
+    }
+
+    /// The top left corner of a legend `size` pixels large, relative to the top left corner
+    /// of the plotting area `plot` drawn on `root`, both given by their pixel ranges, e.g.
+    /// from `DrawingArea::get_pixel_range`. Pass it to plotters as
+    /// `SeriesLabelPosition::Coordinate`.
+    pub fn place(
+        &self,
+        root: (Range<i32>, Range<i32>),
+        plot: (Range<i32>, Range<i32>),
+        size: (u32, u32),
+    ) -> (i32, i32) {
+        let (x, y) = plot;
+        let (root_x, root_y) = root;
+        let (width, height) = (size.0 as i32, size.1 as i32);
+        let gap = LEGEND_GAP as i32;
+        let left = gap;
+        let right = x.end - x.start - width - gap;
+        let top = gap;
+        let bottom = y.end - y.start - height - gap;
+        let (at_x, at_y) = match *self {
+            LegendPosition::UpperLeft => (left, top),
+            LegendPosition::UpperRight => (right, top),
+            LegendPosition::LowerLeft => (left, bottom),
+            LegendPosition::LowerRight => (right, bottom),
+            LegendPosition::OutsideRight => (root_x.end - width - gap - x.start, 0),
+            LegendPosition::OutsideBottom => (
+                (x.end - x.start - width) / 2,
+                root_y.end - height - gap - y.start,
+            ),
+            LegendPosition::Floating { x: fx, y: fy } => {
+                let at_x = (fx * (x.end - x.start) as f32) as i32;
+                let at_y = (fy * (y.end - y.start) as f32) as i32;
+                // Keep the legend on the canvas.
+                let (min_x, min_y) = (root_x.start - x.start, root_y.start - y.start);
+                (
+                    at_x.clamp(min_x, (root_x.end - width - x.start).max(min_x)),
+                    at_y.clamp(min_y, (root_y.end - height - y.start).max(min_y)),
+                )
+            }
+        };
+        (at_x, at_y)
+    }
+
+    /// Whether the legend is beside the plotting area rather than over it.
+    pub fn is_outside(&self) -> bool {
+        matches!(
+            self,
+            LegendPosition::OutsideRight | LegendPosition::OutsideBottom
+        )
+    }
+}
+
+/// The size in pixels of a plotters series legend listing `names` in `font`, with the
+/// swatch width and margin plotters uses by default.
+pub fn legend_size<'a, DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    names: impl IntoIterator<Item = &'a str>,
+    font: &TextStyle<'_>,
+) -> Result<(u32, u32), DrawingAreaErrorKind<DB::ErrorType>> {
+    let mut width = 0;
+    let mut lines = 0;
+    for name in names {
+        width = width.max(root.estimate_text_size(name, font)?.0);
+        lines += 1;
+    }
+    // Plotters spaces the lines of a legend 1.25 font sizes apart.
+    let line_height = (font.font.get_size() * 1.25).round() as u32;
+    Ok((
+        width + SWATCH_WIDTH + 2 * LEGEND_MARGIN,
+        lines * line_height + 2 * LEGEND_MARGIN,
+    ))
+}
+
+/// A series named with [`BuildContext::label`](crate::BuildContext::label), listed in the
+/// legend the widget draws.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LegendEntry {
+    pub series: SeriesId,
+    pub name: String,
+}
+
+/// How the legend the widget draws looks, see
+/// [`ChartWidget::legend`](crate::ChartWidget::legend).
+///
+/// Every entry shows the color registered for its series with
+/// [`BuildContext::set_series_color`](crate::BuildContext::set_series_color). Hidden series
+/// are listed faded.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LegendStyle {
+    text_size: f32,
+    text_color: Color,
+    background: Color,
+    border: Color,
+    /// The opacity of the entries of hidden series.
+    hidden_alpha: f32,
+}
+
+impl LegendStyle {
+    pub fn new() -> Self {
+        Self {
+            text_size: 13.0,
+            text_color: Color::BLACK,
+            background: Color::from_rgba(1.0, 1.0, 1.0, 0.85),
+            border: Color::from_rgb(0.6, 0.6, 0.6),
+            hidden_alpha: 0.35,
+        }
+    }
+
+    pub fn text_size(mut self, size: f32) -> Self {
+        self.text_size = size;
+        self
+    }
+
+    pub fn text_color(mut self, color: Color) -> Self {
+        self.text_color = color;
+        self
+    }
+
+    pub fn background(mut self, color: Color) -> Self {
+        self.background = color;
+        self
+    }
+
+    /// Sets the color of the border, transparent for none.
+    pub fn border(mut self, color: Color) -> Self {
+        self.border = color;
+        self
+    }
+
+    /// Sets the opacity of the entries of hidden series, from `0.0` to `1.0`.
+    pub fn hidden_alpha(mut self, alpha: f32) -> Self {
+        self.hidden_alpha = alpha;
+        self
+    }
+
+    /// The size of a legend listing `names`, in canvas pixels.
+    pub(crate) fn size<'a>(&self, names: impl IntoIterator<Item = &'a str>) -> Size {
+        let mut columns = 0;
+        let mut lines = 0;
+        for name in names {
+            columns = columns.max(name.chars().count());
+            lines += 1;
+        }
+        Size::new(
+            columns as f32 * self.text_size * GLYPH_WIDTH + SWATCH + SWATCH_GAP + 2.0 * PADDING,
+            lines as f32 * self.line_height() + 2.0 * PADDING,
+        )
+    }
+
+    /// Where a legend listing `entries` goes at `position`, in the area `area` of the
+    /// canvas the chart is built on, with the plotting area `plot`.
+    pub(crate) fn bounds(
+        &self,
+        position: LegendPosition,
+        area: Rectangle,
+        plot: Rectangle,
+        entries: &[LegendEntry],
+    ) -> Rectangle {
+        let legend = self.size(entries.iter().map(|entry| entry.name.as_str()));
+        let (x, y) = position.place(
+            (
+                area.x as i32..(area.x + area.width) as i32,
+                area.y as i32..(area.y + area.height) as i32,
+            ),
+            (
+                plot.x as i32..(plot.x + plot.width) as i32,
+                plot.y as i32..(plot.y + plot.height) as i32,
+            ),
+            (legend.width as u32, legend.height as u32),
+        );
+        Rectangle::new(Point::new(plot.x + x as f32, plot.y + y as f32), legend)
+    }
+
+    /// The series of the entry under `point`, in a legend drawn at `legend`.
+    pub(crate) fn entry_at(
+        &self,
+        legend: Rectangle,
+        entries: &[LegendEntry],
+        point: Point,
+    ) -> Option<SeriesId> {
+        if !legend.contains(point) {
+            return None;
+        }
+        let row = ((point.y - legend.y - PADDING) / self.line_height()).floor();
+        if row < 0.0 {
+            return None;
+        }
+        entries.get(row as usize).map(|entry| entry.series)
+    }
+
+    pub(crate) fn draw(
+        &self,
+        frame: &mut Frame,
+        legend: Rectangle,
+        entries: &[LegendEntry],
+        hits: &HitRegistry,
+        hidden: &[SeriesId],
+        hovered: Option<SeriesId>,
+    ) {
+        if entries.is_empty() {
+            return;
+        }
+        frame.fill_rectangle(legend.position(), legend.size(), self.background);
+        frame.stroke(
+            &Path::rectangle(legend.position(), legend.size()),
+            Stroke {
+                color: self.border,
+                width: 1.0,
+                ..Stroke::default()
+            },
+        );
+        for (row, entry) in entries.iter().enumerate() {
+            let top = legend.y + PADDING + row as f32 * self.line_height();
+            let middle = top + self.line_height() / 2.0;
+            let alpha = if hidden.contains(&entry.series) {
+                self.hidden_alpha
+            } else {
+                1.0
+            };
+            let fade = |color: Color| Color {
+                a: color.a * alpha,
+                ..color
+            };
+            if hovered == Some(entry.series) {
+                frame.fill_rectangle(
+                    Point::new(legend.x + 1.0, top),
+                    Size::new(legend.width - 2.0, self.line_height()),
+                    Color {
+                        a: 0.1,
+                        ..self.text_color
+                    },
+                );
+            }
+            let swatch = hits.color(entry.series).unwrap_or(self.text_color);
+            frame.stroke(
+                &Path::line(
+                    Point::new(legend.x + PADDING, middle),
+                    Point::new(legend.x + PADDING + SWATCH, middle),
+                ),
+                Stroke {
+                    color: fade(swatch),
+                    width: 3.0,
+                    ..Stroke::default()
+                },
+            );
+            frame.fill_text(Text {
+                content: entry.name.clone(),
+                position: Point::new(legend.x + PADDING + SWATCH + SWATCH_GAP, top),
+                color: fade(self.text_color),
+                size: self.text_size,
+                ..Text::default()
+            });
+        }
+    }
+
+    fn line_height(&self) -> f32 {
+        self.text_size * LINE_HEIGHT
+    }
+}
+
+impl Default for LegendStyle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,3 +1,159 @@
+mod anchored;
+mod annotation;
 mod backend;
+mod binding;
+mod bubble;
+mod chart;
+mod clip;
+mod colormap;
+mod cursors;
+mod data;
+mod datetime;
+mod debug;
+mod density;
+mod diagnostics;
+mod elements;
+mod empty;
+mod error;
+#[cfg(feature = "export")]
+mod export;
+mod facet;
+mod failure;
+mod focus;
+mod frame;
+mod gradient;
+mod groups;
+mod gutters;
+mod heatmap;
+mod highlight;
+mod history;
+mod hit;
+mod indicator;
+mod ingest;
+mod inset;
+mod interaction;
+mod keymap;
+mod labels;
+mod lasso;
+mod layer;
+mod legend;
+mod loading;
+mod lod;
+mod measure;
+mod multichart;
+mod multiples;
+#[cfg(feature = "ndarray")]
+mod ndarray;
+mod notation;
+mod overlay;
+mod pattern;
+mod placeholder;
+#[cfg(feature = "polars")]
+mod polars;
+pub mod prelude;
+mod prepare;
+mod pyramid;
+#[cfg(feature = "export")]
+mod raster;
+mod readout;
+mod recording;
+mod reference;
+mod scatter;
+mod shading;
+mod shared;
+mod simple;
+mod spec;
+mod split;
+mod splom;
+mod stacked;
+mod stats;
+pub mod subscription;
+mod theme;
+mod tiles;
+#[cfg(feature = "chrono")]
+mod timerange;
+mod titles;
+mod tooltip;
+mod trace;
+mod units;
+mod view;
+mod widget;
 
-pub use backend::IcedBackend;
+pub use anchored::Anchored;
+pub use annotation::{Annotation, AnnotationChange, AnnotationId, AnnotationStore};
+pub use backend::{IcedBackend, IcedError, TextHalo};
+pub use binding::XRangeBinding;
+pub use bubble::{Bubble, BubbleSeries};
+pub use chart::{BuildContext, BuildError, Chart};
+pub use colormap::Colormap;
+pub use cursors::DualCursors;
+pub use data::{Columns, Indexed, SeriesData, SeriesIter};
+pub use datetime::{format_timestamp, DateTimeTicks, TimeUnit, TimeZone};
+pub use debug::FrameStats;
+pub use density::LabelDensity;
+pub use diagnostics::{Diagnostic, DiagnosticKind, Diagnostics};
+pub use elements::{DashPattern, DashedPath, Marker, MarkerShape};
+pub use empty::EmptyState;
+pub use error::ChartError;
+pub use facet::{FacetGrid, FacetOverlay, Facets};
+pub use failure::ErrorPanel;
+pub use focus::{FocusChange, FocusStyle};
+pub use frame::FrameLike;
+pub use gradient::{Gradient, GradientFill};
+pub use groups::GeometryGroups;
+pub use gutters::{Gutters, LabelArea};
+pub use heatmap::Heatmap;
+pub use highlight::HoverHighlight;
+pub use history::HistoryStatus;
+pub use hit::{Hit, HitKind, HitPriority, SeriesId};
+pub use indicator::{Indicator, IndicatorKind, IndicatorPoint};
+#[cfg(feature = "json")]
+pub use ingest::JsonParser;
+pub use ingest::{CsvParser, DataParser, DataSeries, Dataset, DroppedFile, FileDrop, ParseError};
+pub use inset::{Inset, InsetAnchor};
+pub use interaction::{
+    AxisLock, DragMode, Modifier, ModifierBindings, MouseBindings, ScrollAction,
+};
+pub use keymap::{KeyAction, KeyGroup, Keymap};
+pub use labels::{DataLabel, LabelPlacer, PlacedLabel};
+pub use layer::Layer;
+pub use legend::{legend_size, LegendPosition, LegendStyle};
+pub use loading::LoadingStyle;
+pub use lod::LevelOfDetail;
+pub use measure::{MeasureStyle, Measurement};
+pub use multichart::{MultiChart, Regions};
+pub use multiples::{CellTemplate, MultipleCell, SmallMultiples};
+pub use notation::{SharedExponent, SiFormat};
+pub use overlay::{LayerCoord, Overlay, OverlayLayer};
+pub use pattern::{fill_pattern, Hatch, Pattern};
+pub use placeholder::Placeholder;
+#[cfg(feature = "polars")]
+pub use polars::{ColumnAxis, FramePlot};
+pub use prepare::Prepared;
+pub use pyramid::{Bucket, Pyramid};
+pub use readout::{Corner, CursorPosition, Readout};
+pub use recording::{RecordedEvent, Recording};
+pub use reference::ReferenceLine;
+pub use scatter::{BlendMode, Scatter};
+pub use shading::{shade_between, shade_x_band, shade_y_band};
+pub use shared::SharedSeries;
+pub use simple::{
+    AreaChart, Areas, LineChart, Lines, Points, ScatterChart, SimpleChart, SimpleKind,
+};
+pub use spec::{AxisSpec, ChartSpec, InteractionSpec, SeriesKind, SeriesSpec};
+pub use split::{ChartSplit, PanelSide};
+pub use splom::{MatrixCell, ScatterMatrix};
+pub use stacked::Stacked;
+pub use stats::{SeriesStats, StatsBox};
+pub use theme::{Palette, SeriesStyle};
+pub use tiles::{TileFrame, TileSource, TILE_SIZE};
+#[cfg(feature = "chrono")]
+pub use timerange::{timestamp, to_datetime, Follow, TimeRange, TimeWindow};
+pub use titles::TitleStyle;
+pub use tooltip::{PinnedTooltip, Tooltip};
+pub use units::{Unit, UnitSet};
+pub use view::{
+    Axis, AxisLimits, AxisRange, AxisScale, SavedState, ViewChangeCause, ViewCommand, ViewLimits,
+    ViewState,
+};
+pub use widget::ChartWidget;
@@ -0,0 +1,104 @@
+use iced::canvas::path::Arc;
+use iced::canvas::{Frame, Path, Stroke, Text};
+use iced::{Color, HorizontalAlignment, Point, Rectangle, Size};
+use std::f32::consts::PI;
+use std::time::{Duration, Instant};
+
+/// The widest a progress bar gets, in pixels.
+const MAX_BAR_WIDTH: f32 = 240.0;
+
+/// A fetch or preparation in flight, see [`ChartWidget::set_loading`].
+///
+/// [`ChartWidget::set_loading`]: crate::ChartWidget::set_loading
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Loading {
+    pub(crate) since: Instant,
+    /// The share done between 0 and 1, or `None` if unknown.
+    pub(crate) progress: Option<f32>,
+}
+
+impl Loading {
+    pub(crate) fn new(progress: Option<f32>) -> Self {
+        Self {
+            since: Instant::now(),
+            progress,
+        }
+    }
+}
+
+/// How the widget shows that data is loading: a veil over the last known chart and a
+/// spinner, or a progress bar once progress is reported, see
+/// [`ChartWidget::set_loading`](crate::ChartWidget::set_loading).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LoadingStyle {
+    /// Laid over the chart, so it stays visible but reads as stale.
+    pub veil: Color,
+    pub color: Color,
+    /// The color of the unfilled part of the spinner ring or progress bar.
+    pub track: Color,
+    /// The radius of the spinner and the height of the progress bar, in pixels.
+    pub size: f32,
+    pub text_size: f32,
+}
+
+impl LoadingStyle {
+    pub(crate) fn draw(&self, frame: &mut Frame, area: Rectangle, loading: &Loading) {
+        frame.fill_rectangle(area.position(), area.size(), self.veil);
+        let center = area.center();
+        match loading.progress {
+            Some(progress) => self.draw_bar(frame, area, center, progress),
+            None => self.draw_spinner(frame, center, loading.since.elapsed()),
+        }
+    }
+
+    /// A three-quarter ring turning once a second.
+    fn draw_spinner(&self, frame: &mut Frame, center: Point, elapsed: Duration) {
+        let stroke = |color| Stroke {
+            color,
+            width: (self.size / 4.0).max(1.0),
+            ..Stroke::default()
+        };
+        frame.stroke(&Path::circle(center, self.size), stroke(self.track));
+        let start = elapsed.as_secs_f32().fract() * 2.0 * PI;
+        let arc = Path::new(|builder| {
+            builder.arc(Arc {
+                center,
+                radius: self.size,
+                start_angle: start,
+                end_angle: start + 1.5 * PI,
+            })
+        });
+        frame.stroke(&arc, stroke(self.color));
+    }
+
+    fn draw_bar(&self, frame: &mut Frame, area: Rectangle, center: Point, progress: f32) {
+        let width = (area.width / 2.0).min(MAX_BAR_WIDTH);
+        let origin = Point::new(center.x - width / 2.0, center.y - self.size / 2.0);
+        frame.fill_rectangle(origin, Size::new(width, self.size), self.track);
+        frame.fill_rectangle(
+            origin,
+            Size::new(width * progress.clamp(0.0, 1.0), self.size),
+            self.color,
+        );
+        frame.fill_text(Text {
+            content: format!("{:.0} %", progress.clamp(0.0, 1.0) * 100.0),
+            position: Point::new(center.x, origin.y + self.size + 4.0),
+            color: self.color,
+            size: self.text_size,
+            horizontal_alignment: HorizontalAlignment::Center,
+            ..Text::default()
+        });
+    }
+}
+
+impl Default for LoadingStyle {
+    fn default() -> Self {
+        Self {
+            veil: Color::from_rgba(1.0, 1.0, 1.0, 0.6),
+            color: Color::from_rgb(0.2, 0.4, 0.9),
+            track: Color::from_rgba(0.0, 0.0, 0.0, 0.1),
+            size: 16.0,
+            text_size: 14.0,
+        }
+    }
+}
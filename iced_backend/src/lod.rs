@@ -0,0 +1,155 @@
+use crate::trace;
+use crate::{SeriesData, ViewState};
+use std::ops::Range;
+
+/// A series kept at several resolutions, so drawing it costs about as much as the plot is
+/// wide however far the user zooms out, while zooming in shows every point.
+///
+/// Level 0 is the series itself. Every further level keeps the lowest and highest point of
+/// each run of `factor` pairs of points of the level before, so peaks survive however
+/// coarse the level. Pick the points to draw from the visible range:
+///
+/// ```ignore
+/// let points = self.lod.visible(ctx.x_range(), ctx.x_pixels());
+/// chart.draw_series(LineSeries::new(points.iter().copied(), &BLUE))?;
+/// ```
+///
+/// Building the levels of a large series takes a while; build them off the UI thread with
+/// [`ChartWidget::prepare`](crate::ChartWidget::prepare) when the data changes. For series
+/// that grow, use a [`Pyramid`](crate::Pyramid), which keeps its levels up to date as points
+/// are appended.
+#[derive(Debug, Clone, Default)]
+pub struct LevelOfDetail {
+    levels: Vec<Vec<(f64, f64)>>,
+}
+
+impl LevelOfDetail {
+    /// The levels of `points`, which must be sorted by x, each a quarter of the one before.
+    pub fn new(points: Vec<(f64, f64)>) -> Self {
+        Self::with_factor(points, 4)
+    }
+
+    /// The levels of `points`, each `factor` times coarser than the one before. Levels
+    /// are added until one has fewer than 1024 points.
+    pub fn with_factor(points: Vec<(f64, f64)>, factor: usize) -> Self {
+        let factor = factor.max(2);
+        trace::span!("decimate", points = points.len(), factor);
+        let mut levels = vec![points];
+        while let Some(finest) = levels.last().filter(|level| level.len() >= 1024) {
+            let coarser = decimate(finest, factor);
+            levels.push(coarser);
+        }
+        Self { levels }
+    }
+
+    /// The number of levels, including the series itself.
+    pub fn levels(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// The points of level `index`, or an empty slice past the coarsest level.
+    pub fn level(&self, index: usize) -> &[(f64, f64)] {
+        self.levels.get(index).map_or(&[], Vec::as_slice)
+    }
+
+    /// The finest level that shows at most two points per pixel of `pixels` across `x`.
+    pub fn level_for(&self, x: Range<f64>, pixels: f32) -> usize {
+        let budget = (2.0 * pixels.max(1.0)) as usize;
+        let fitting = self
+            .levels
+            .iter()
+            .position(|level| level.visible_range(x.clone()).len() <= budget);
+        fitting.unwrap_or(self.levels.len().saturating_sub(1))
+    }
+
+    /// The points to draw of the level picked by [`LevelOfDetail::level_for`], including
+    /// the nearest point on either side of `x`.
+    pub fn visible(&self, x: Range<f64>, pixels: f32) -> &[(f64, f64)] {
+        let index = self.level_for(x.clone(), pixels);
+        let level = self.level(index);
+        let visible = &level[level.visible_range(x)];
+        trace::event!(level = index, points = visible.len(), "level of detail");
+        visible
+    }
+
+    /// Like [`LevelOfDetail::visible`], for the x range of `view` across a plotting area
+    /// `width` pixels wide.
+    pub fn for_view(&self, view: &ViewState, width: f32) -> &[(f64, f64)] {
+        self.visible(view.x.as_range(), width)
+    }
+}
+
+/// Keeps the lowest and highest point of every run of `2 * factor` points, in their order.
+pub(crate) fn decimate(points: &[(f64, f64)], factor: usize) -> Vec<(f64, f64)> {
+    let mut decimated = Vec::with_capacity(points.len() / factor + 2);
+    for run in points.chunks(2 * factor) {
+        let lowest = run
+            .iter()
+            .enumerate()
+            .min_by(|a, b| a.1 .1.total_cmp(&b.1 .1))
+            .map(|(index, _)| index);
+        let highest = run
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1 .1.total_cmp(&b.1 .1))
+            .map(|(index, _)| index);
+        match (lowest, highest) {
+            (Some(lowest), Some(highest)) if lowest == highest => decimated.push(run[lowest]),
+            (Some(lowest), Some(highest)) => {
+                decimated.push(run[lowest.min(highest)]);
+                decimated.push(run[lowest.max(highest)]);
+            }
+            _ => {}
+        }
+    }
+    decimated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(len: usize) -> Vec<(f64, f64)> {
+        (0..len)
+            .map(|index| (index as f64, ((index * 7919) % 100_003) as f64))
+            .collect()
+    }
+
+    #[test]
+    fn decimate_keeps_the_extremes_in_order() {
+        let points = [(0.0, 3.0), (1.0, 9.0), (2.0, 1.0), (3.0, 4.0), (4.0, 2.0)];
+        assert_eq!(
+            decimate(&points, 2),
+            vec![(1.0, 9.0), (2.0, 1.0), (4.0, 2.0)]
+        );
+    }
+
+    #[test]
+    fn levels_shrink_by_the_factor() {
+        let lod = LevelOfDetail::new(series(100_000));
+        assert_eq!(lod.level(0).len(), 100_000);
+        for index in 1..lod.levels() {
+            assert!(lod.level(index).len() <= lod.level(index - 1).len() / 4 + 2);
+        }
+        assert!(lod.level(lod.levels() - 1).len() < 1024);
+        assert!(lod.level(lod.levels()).is_empty());
+    }
+
+    #[test]
+    fn level_for_picks_the_finest_level_within_the_budget() {
+        let lod = LevelOfDetail::new(series(100_000));
+        for pixels in [300.0, 800.0, 1920.0] {
+            let budget = 2 * pixels as usize;
+            for x in [0.0..100_000.0, 20_000.0..60_000.0, 500.0..900.0] {
+                let index = lod.level_for(x.clone(), pixels);
+                let fits = |index: usize| {
+                    let level = lod.level(index);
+                    level.visible_range(x.clone()).len() <= budget
+                };
+                assert!(fits(index), "{:?} at {}", x, pixels);
+                assert!(index == 0 || !fits(index - 1), "{:?} at {}", x, pixels);
+                assert!(lod.visible(x.clone(), pixels).len() <= budget);
+            }
+        }
+    }
+}
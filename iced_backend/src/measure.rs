@@ -0,0 +1,126 @@
+use crate::datetime::format_duration;
+use crate::ViewState;
+use iced::canvas::{Frame, Path, Stroke, Text};
+use iced::{Color, Point, Rectangle, Size};
+
+/// The padding between the measurement box and its text.
+const PADDING: f32 = 4.0;
+/// A rough average glyph width relative to the text size, used to size the box.
+const GLYPH_WIDTH: f32 = 0.6;
+
+/// A distance measured between two picked points, in data coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Measurement {
+    pub from: (f64, f64),
+    pub to: (f64, f64),
+}
+
+impl Measurement {
+    pub fn dx(&self) -> f64 {
+        self.to.0 - self.from.0
+    }
+
+    pub fn dy(&self) -> f64 {
+        self.to.1 - self.from.1
+    }
+
+    /// `Δy / Δx`, infinite for vertical measurements.
+    pub fn slope(&self) -> f64 {
+        self.dy() / self.dx()
+    }
+}
+
+/// How the measurement tool presents its result, see
+/// [`ChartWidget::set_measuring`](crate::ChartWidget::set_measuring).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeasureStyle {
+    /// Shows Δx as a duration, for x axes holding Unix timestamps in seconds.
+    pub time_x: bool,
+    pub color: Color,
+    pub text_size: f32,
+}
+
+impl MeasureStyle {
+    fn text(&self, measurement: &Measurement) -> String {
+        let dx = if self.time_x {
+            format!("Δt: {}", format_duration(measurement.dx()))
+        } else {
+            format!("Δx: {:.3}", measurement.dx())
+        };
+        let slope = if self.time_x {
+            format!("slope: {:.3}/s", measurement.slope())
+        } else {
+            format!("slope: {:.3}", measurement.slope())
+        };
+        format!("{}\nΔy: {:.3}\n{}", dx, measurement.dy(), slope)
+    }
+
+    /// Draws the connector from the first pick to the second, or to the cursor while the
+    /// second point has not been picked yet.
+    pub(crate) fn draw(
+        &self,
+        frame: &mut Frame,
+        view: &ViewState,
+        plot: Rectangle,
+        picks: &[(f64, f64)],
+        cursor: Option<(f64, f64)>,
+    ) {
+        let from = match picks.first() {
+            Some(from) => *from,
+            None => return,
+        };
+        let to = match picks.get(1).copied().or(cursor) {
+            Some(to) => to,
+            None => return,
+        };
+        let (a, b) = (view.to_pixel(plot, from), view.to_pixel(plot, to));
+        frame.stroke(
+            &Path::line(a, b),
+            Stroke {
+                color: self.color,
+                width: 1.5,
+                ..Stroke::default()
+            },
+        );
+        for point in picks.iter().map(|pick| view.to_pixel(plot, *pick)) {
+            frame.fill(&Path::circle(point, 4.0), self.color);
+        }
+
+        let content = self.text(&Measurement { from, to });
+        let lines = content.lines().count() as f32;
+        let columns = content
+            .lines()
+            .map(|l| l.chars().count())
+            .max()
+            .unwrap_or(0) as f32;
+        let size = Size::new(
+            columns * self.text_size * GLYPH_WIDTH + 2.0 * PADDING,
+            lines * self.text_size + 2.0 * PADDING,
+        );
+        let middle = Point::new((a.x + b.x) / 2.0, (a.y + b.y) / 2.0);
+        let x = (middle.x + 8.0)
+            .min(plot.x + plot.width - size.width)
+            .max(plot.x);
+        let y = (middle.y + 8.0)
+            .min(plot.y + plot.height - size.height)
+            .max(plot.y);
+        frame.fill_rectangle(Point::new(x, y), size, Color::from_rgba(1.0, 1.0, 1.0, 0.9));
+        frame.fill_text(Text {
+            content,
+            position: Point::new(x + PADDING, y + PADDING),
+            color: self.color,
+            size: self.text_size,
+            ..Text::default()
+        });
+    }
+}
+
+impl Default for MeasureStyle {
+    fn default() -> Self {
+        Self {
+            time_x: false,
+            color: Color::from_rgb(0.1, 0.3, 0.7),
+            text_size: 14.0,
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use crate::units::AxisUnits;
+use crate::{BuildContext, Chart, ChartError, IcedBackend, LabelDensity, ViewState};
+use iced::canvas::{self, Cache, Cursor, Event, Geometry};
+use iced::{Point, Rectangle, Size};
+use plotters::drawing::IntoDrawingArea;
+use plotters::style::{Color, RGBAColor, WHITE};
+use std::cell::RefCell;
+
+/// How a [`MultiChart`] divides its canvas between its charts.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Regions {
+    /// Cells of equal size filled row by row, `columns` cells wide and `spacing` pixels
+    /// apart.
+    Grid { columns: usize, spacing: f32 },
+    /// A rectangle per chart, in pixels from the top left corner of the canvas. Charts
+    /// without one are not drawn.
+    Custom(Vec<Rectangle>),
+}
+
+impl Regions {
+    /// The regions of `count` charts on a canvas of `size`.
+    fn layout(&self, count: usize, size: Size) -> Vec<Rectangle> {
+        match self {
+            Regions::Grid { columns, spacing } => {
+                let columns = (*columns).max(1);
+                let rows = count.div_ceil(columns).max(1);
+                let width = (size.width - spacing * (columns - 1) as f32) / columns as f32;
+                let height = (size.height - spacing * (rows - 1) as f32) / rows as f32;
+                (0..count)
+                    .map(|index| {
+                        let (row, column) = (index / columns, index % columns);
+                        Rectangle::new(
+                            Point::new(
+                                column as f32 * (width + spacing),
+                                row as f32 * (height + spacing),
+                            ),
+                            Size::new(width.max(0.0), height.max(0.0)),
+                        )
+                    })
+                    .collect()
+            }
+            Regions::Custom(regions) => regions.iter().copied().take(count).collect(),
+        }
+    }
+}
+
+/// Several charts drawn onto one canvas, each built into its own region with
+/// [`IcedBackend::sub_area`].
+///
+/// It is cheaper than a [`ChartWidget`](crate::ChartWidget) per chart and packs a dashboard
+/// as tightly as wanted, but only draws: the charts don't react to the mouse, and their
+/// views are set by the application with [`MultiChart::set_view`]. All charts are cached
+/// together, so changing one redraws them all.
+///
+/// Regions don't clip what a chart draws past their edges, such as points outside its
+/// view, so charts should keep to their plotting areas.
+pub struct MultiChart<Message> {
+    charts: Vec<(Box<dyn Chart>, ViewState)>,
+    regions: Regions,
+    background: Option<RGBAColor>,
+    density: LabelDensity,
+    units: AxisUnits,
+    cache: Cache,
+    /// The errors of the last build, with the index of the failing chart.
+    errors: RefCell<Vec<(usize, ChartError)>>,
+    on_error: Option<Box<dyn Fn(usize, ChartError) -> Message>>,
+}
+
+impl<Message> MultiChart<Message> {
+    pub fn new(regions: Regions) -> Self {
+        Self {
+            charts: vec![],
+            regions,
+            background: Some(WHITE.to_rgba()),
+            density: LabelDensity::default(),
+            units: AxisUnits::default(),
+            cache: Cache::new(),
+            errors: RefCell::new(vec![]),
+            on_error: None,
+        }
+    }
+
+    /// Adds `chart`, built with the ranges of `view`, in the next region.
+    pub fn push(mut self, chart: impl 'static + Chart, view: ViewState) -> Self {
+        self.charts.push((Box::new(chart), view));
+        self
+    }
+
+    /// Sets the color [`BuildContext::fill_background`] fills every region with.
+    pub fn background(mut self, color: impl Color) -> Self {
+        self.background = Some(color.to_rgba());
+        self
+    }
+
+    pub fn transparent_background(mut self) -> Self {
+        self.background = None;
+        self
+    }
+
+    pub fn label_density(mut self, density: LabelDensity) -> Self {
+        self.density = density;
+        self
+    }
+
+    /// Sets the message produced when a chart fails to build, given its index.
+    pub fn on_error<F>(mut self, f: F) -> Self
+    where
+        F: 'static + Fn(usize, ChartError) -> Message,
+    {
+        self.on_error = Some(Box::new(f));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.charts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.charts.is_empty()
+    }
+
+    pub fn view(&self, index: usize) -> Option<&ViewState> {
+        self.charts.get(index).map(|(_, view)| view)
+    }
+
+    /// Builds the chart at `index` with the ranges of `view` from the next draw on.
+    pub fn set_view(&mut self, index: usize, view: ViewState) {
+        if let Some((_, current)) = self.charts.get_mut(index) {
+            *current = view;
+            self.cache.clear();
+        }
+    }
+
+    pub fn set_regions(&mut self, regions: Regions) {
+        self.regions = regions;
+        self.cache.clear();
+    }
+
+    /// Discards the cached geometry so all charts are rebuilt on the next draw.
+    pub fn redraw(&mut self) {
+        self.cache.clear();
+    }
+}
+
+impl<Message> canvas::Program<Message> for MultiChart<Message> {
+    fn update(&mut self, _event: Event, _bounds: Rectangle, _cursor: Cursor) -> Option<Message> {
+        let errors = self.errors.get_mut();
+        if errors.is_empty() {
+            return None;
+        }
+        let (index, error) = errors.remove(0);
+        self.on_error.as_ref().map(|f| f(index, error))
+    }
+
+    fn draw(&self, bounds: Rectangle, _cursor: Cursor) -> Vec<Geometry> {
+        if IcedBackend::is_degenerate(bounds.size()) {
+            return vec![];
+        }
+        let geometry = self.cache.draw(bounds.size(), |frame| {
+            let regions = self.regions.layout(self.charts.len(), bounds.size());
+            let mut errors = self.errors.borrow_mut();
+            errors.clear();
+            for (index, ((chart, view), region)) in self.charts.iter().zip(regions).enumerate() {
+                // Collapsed regions are skipped like collapsed canvases.
+                if IcedBackend::is_degenerate(region.size()) {
+                    continue;
+                }
+                let mut ctx = BuildContext::new(
+                    view,
+                    &self.units,
+                    region.size(),
+                    self.density,
+                    self.background.clone(),
+                );
+                let result = IcedBackend::sub_area(frame, region)
+                    .map_err(ChartError::from)
+                    .and_then(|backend| {
+                        chart
+                            .build_chart(&mut ctx, backend.into_drawing_area())
+                            .map_err(ChartError::from)
+                    });
+                if let Err(error) = result {
+                    errors.push((index, error));
+                }
+            }
+        });
+        vec![geometry]
+    }
+}
@@ -0,0 +1,176 @@
+use crate::{
+    BuildContext, BuildError, Chart, ChartWidget, IcedBackend, ViewCommand, ViewLimits, ViewState,
+};
+use iced::canvas::Canvas;
+use iced::{Column, Element, Length, Row};
+use plotters::coord::Shift;
+use plotters::drawing::DrawingArea;
+use std::rc::Rc;
+
+/// The chart drawn in every cell of a [`SmallMultiples`] grid, once per key.
+pub trait CellTemplate {
+    /// What sets the cells apart, such as a region or a sensor name.
+    type Key;
+
+    /// Draws the cell for `key` onto `root`, like [`Chart::build_chart`]. The ranges in
+    /// `ctx` are shared by all cells.
+    fn build_cell(
+        &self,
+        key: &Self::Key,
+        ctx: &mut BuildContext<'_>,
+        root: DrawingArea<IcedBackend<'_>, Shift>,
+    ) -> Result<(), BuildError>;
+}
+
+/// One cell of a [`SmallMultiples`] grid.
+pub struct MultipleCell<T: CellTemplate> {
+    template: Rc<T>,
+    key: T::Key,
+}
+
+impl<T: CellTemplate> MultipleCell<T> {
+    pub fn key(&self) -> &T::Key {
+        &self.key
+    }
+}
+
+impl<T: CellTemplate> Chart for MultipleCell<T> {
+    fn build_chart(
+        &self,
+        ctx: &mut BuildContext<'_>,
+        root: DrawingArea<IcedBackend<'_>, Shift>,
+    ) -> Result<(), BuildError> {
+        self.template.build_cell(&self.key, ctx, root)
+    }
+}
+
+/// A grid of small charts drawn from one [`CellTemplate`] for a list of keys, sharing
+/// their styling and their visible ranges, so the cells compare at a glance.
+///
+/// Panning or zooming any cell moves all of them: route the message of
+/// [`SmallMultiples::on_view_change`] to [`SmallMultiples::set_view`]. Each cell caches
+/// its drawing, which is only rebuilt when the shared view or its data changes.
+pub struct SmallMultiples<Message, T: CellTemplate> {
+    template: Rc<T>,
+    cells: Vec<ChartWidget<Message, MultipleCell<T>>>,
+    columns: usize,
+    view: ViewState,
+    spacing: u16,
+}
+
+impl<Message: 'static, T: 'static + CellTemplate> SmallMultiples<Message, T> {
+    /// A grid of a cell for every key in `keys`, filled row by row, `columns` cells wide.
+    pub fn new(template: T, keys: Vec<T::Key>, columns: usize) -> Self {
+        let template = Rc::new(template);
+        let cells = keys
+            .into_iter()
+            .map(|key| {
+                ChartWidget::new(MultipleCell {
+                    template: Rc::clone(&template),
+                    key,
+                })
+            })
+            .collect();
+        Self {
+            template,
+            cells,
+            columns: columns.max(1),
+            view: ViewState::default(),
+            spacing: 4,
+        }
+    }
+
+    /// Sets the initially visible ranges of all cells.
+    pub fn initial_view(mut self, view: ViewState) -> Self {
+        self.cells = self
+            .cells
+            .into_iter()
+            .map(|cell| cell.view(view.clone()))
+            .collect();
+        self.view = view;
+        self
+    }
+
+    /// Limits the visible ranges of all cells.
+    pub fn limits(mut self, limits: ViewLimits) -> Self {
+        self.cells = self
+            .cells
+            .into_iter()
+            .map(|cell| cell.limits(limits))
+            .collect();
+        self
+    }
+
+    /// Sets the space in pixels between the cells.
+    pub fn spacing(mut self, spacing: u16) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Sets the message produced when the user pans or zooms any cell, carrying its new
+    /// view for [`SmallMultiples::set_view`].
+    pub fn on_view_change<F>(mut self, f: F) -> Self
+    where
+        F: 'static + Fn(ViewState) -> Message,
+    {
+        let f = Rc::new(f);
+        self.cells = self
+            .cells
+            .into_iter()
+            .map(|cell| {
+                let f = Rc::clone(&f);
+                cell.on_view_change(move |view, _| f(view))
+            })
+            .collect();
+        self
+    }
+
+    /// Shows `view` in all cells.
+    pub fn set_view(&mut self, view: ViewState) {
+        for cell in &mut self.cells {
+            cell.apply(ViewCommand::SetView(view.clone()));
+            // The cells follow the view they report; reporting it back is not needed.
+            let _ = cell.take_view_change();
+        }
+        self.view = view;
+    }
+
+    /// The visible ranges shared by the cells.
+    pub fn view_state(&self) -> &ViewState {
+        &self.view
+    }
+
+    pub fn template(&self) -> &T {
+        &self.template
+    }
+
+    /// The cell drawn for `key`.
+    pub fn cell_mut(&mut self, key: &T::Key) -> Option<&mut ChartWidget<Message, MultipleCell<T>>>
+    where
+        T::Key: PartialEq,
+    {
+        self.cells.iter_mut().find(|cell| cell.chart().key() == key)
+    }
+
+    /// Lays out the cells in a grid filling the available space.
+    pub fn view(&mut self) -> Element<'_, Message> {
+        let (spacing, columns) = (self.spacing, self.columns);
+        let mut rows = Column::new()
+            .spacing(spacing)
+            .width(Length::Fill)
+            .height(Length::Fill);
+        for chunk in self.cells.chunks_mut(columns) {
+            let mut row = Row::new().spacing(spacing).height(Length::Fill);
+            let empty = columns - chunk.len();
+            for cell in chunk {
+                row = row.push(Canvas::new(cell).width(Length::Fill).height(Length::Fill));
+            }
+            // Keeps the cells of a partly filled last row as wide as the others.
+            for _ in 0..empty {
+                row = row.push(Column::new().width(Length::Fill));
+            }
+            rows = rows.push(row);
+        }
+        rows.into()
+    }
+}
@@ -0,0 +1,48 @@
+//! Plotting `ndarray` arrays without converting them first, with the `ndarray` feature.
+//!
+//! One-dimensional arrays of `f64` are [`SeriesData`] of values plotted against their
+//! index, like [`Indexed`](crate::Indexed); two-dimensional arrays with two columns are
+//! points with x in the first column and y in the second. Two-dimensional arrays of any
+//! shape become [`Heatmap`]s:
+//!
+//! ```ignore
+//! let heatmap = Heatmap::from(temperatures.view()).extent(0.0..360.0, -90.0..90.0);
+//! ```
+
+use crate::{Heatmap, SeriesData};
+use ndarray::{ArrayBase, Data, Ix1, Ix2};
+
+impl<S: Data<Elem = f64>> SeriesData for ArrayBase<S, Ix1> {
+    fn len(&self) -> usize {
+        ArrayBase::len(self)
+    }
+
+    fn point(&self, index: usize) -> Option<(f64, f64)> {
+        Some((index as f64, *self.get(index)?))
+    }
+}
+
+/// Rows of `[x, y]`; arrays with fewer than two columns have no points.
+impl<S: Data<Elem = f64>> SeriesData for ArrayBase<S, Ix2> {
+    fn len(&self) -> usize {
+        if self.ncols() < 2 {
+            0
+        } else {
+            self.nrows()
+        }
+    }
+
+    fn point(&self, index: usize) -> Option<(f64, f64)> {
+        if self.ncols() < 2 {
+            return None;
+        }
+        Some((*self.get((index, 0))?, *self.get((index, 1))?))
+    }
+}
+
+/// Row `i` of the array becomes row `i` of the heatmap.
+impl<S: Data<Elem = f64>> From<ArrayBase<S, Ix2>> for Heatmap {
+    fn from(array: ArrayBase<S, Ix2>) -> Self {
+        Heatmap::new(array.iter().copied().collect(), array.ncols())
+    }
+}
@@ -0,0 +1,371 @@
+use crate::Axis;
+use iced::canvas::{Frame, Text};
+use iced::{Color, HorizontalAlignment, Point, Rectangle, VerticalAlignment};
+use std::ops::Range;
+
+const DECIMAL_PREFIXES: [&str; 17] = [
+    "y", "z", "a", "f", "p", "n", "µ", "m", "", "k", "M", "G", "T", "P", "E", "Z", "Y",
+];
+/// The index of the empty prefix in [`DECIMAL_PREFIXES`].
+const UNPREFIXED: i32 = 8;
+
+const BINARY_PREFIXES: [&str; 9] = ["", "Ki", "Mi", "Gi", "Ti", "Pi", "Ei", "Zi", "Yi"];
+
+const EXPONENT_SIZE: f32 = 14.0;
+const EXPONENT_PADDING: f32 = 2.0;
+
+/// Formats numbers with SI prefixes, such as `1.2k`, `3.4M` or `560µ`, or with binary
+/// prefixes such as `1.5 KiB` for sizes in bytes.
+///
+/// Use it in a mesh's label formatter or a [`Readout`](crate::Readout) formatter:
+///
+/// ```ignore
+/// let si = SiFormat::new().unit("Hz").for_range(ctx.x_range(), ctx.x_labels());
+/// chart.configure_mesh().x_label_formatter(&|x| si.format(*x)).draw()?;
+/// ```
+///
+/// Trailing zeros are dropped, so ticks read `1k`, `1.5k`, `2k`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SiFormat {
+    binary: bool,
+    unit: String,
+    precision: usize,
+    step: Option<f64>,
+}
+
+impl SiFormat {
+    /// Decimal prefixes with up to one decimal and no unit.
+    pub fn new() -> Self {
+        Self {
+            binary: false,
+            unit: String::new(),
+            precision: 1,
+            step: None,
+        }
+    }
+
+    /// Uses powers of 1024 with the prefixes `Ki`, `Mi`, `Gi`, ... Values below 1024 are
+    /// shown without prefix.
+    pub fn binary(mut self) -> Self {
+        self.binary = true;
+        self
+    }
+
+    /// Appends `unit` after the prefix, separated from the number by a space, e.g. `kHz`.
+    pub fn unit(mut self, unit: impl Into<String>) -> Self {
+        self.unit = unit.into();
+        self
+    }
+
+    /// Sets the most decimals shown.
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Shows as many decimals as ticks `step` apart need to stay distinct, instead of the
+    /// fixed precision, so labels stay readable at any zoom level.
+    pub fn for_step(mut self, step: f64) -> Self {
+        self.step = Some(step.abs()).filter(|step| step.is_finite() && *step > 0.0);
+        self
+    }
+
+    /// Like [`SiFormat::for_step`], for about `labels` ticks across `range`, e.g.
+    /// [`BuildContext::x_range`] and [`BuildContext::x_labels`].
+    ///
+    /// [`BuildContext::x_range`]: crate::BuildContext::x_range
+    /// [`BuildContext::x_labels`]: crate::BuildContext::x_labels
+    pub fn for_range(self, range: Range<f64>, labels: usize) -> Self {
+        let step = (range.end - range.start) / labels.max(1) as f64;
+        self.for_step(step)
+    }
+
+    pub fn format(&self, value: f64) -> String {
+        if !value.is_finite() {
+            return value.to_string();
+        }
+        let (base, prefixes, first): (f64, &[&str], i32) = if self.binary {
+            (1024.0, &BINARY_PREFIXES, 0)
+        } else {
+            (1000.0, &DECIMAL_PREFIXES, -UNPREFIXED)
+        };
+        let last = first + prefixes.len() as i32 - 1;
+        let power = if value == 0.0 {
+            0
+        } else {
+            (value.abs().log(base).floor() as i32).clamp(first, last)
+        };
+
+        let mut power = power;
+        let mut text;
+        loop {
+            let scale = base.powi(power);
+            let decimals = match self.step {
+                Some(step) => decimals_for(step / scale),
+                None => self.precision,
+            };
+            text = format!("{:.*}", decimals, value / scale);
+            // Rounding can carry into the next prefix, as in 999.96 -> 1000.0.
+            let rounded: f64 = text.parse().unwrap_or(0.0);
+            if rounded.abs() < base || power == last {
+                break;
+            }
+            power += 1;
+        }
+        let prefix = prefixes[(power - first) as usize];
+        let number = trim_zeros(&text);
+        match (prefix, self.unit.as_str()) {
+            ("", "") => number.to_string(),
+            (prefix, "") => format!("{}{}", number, prefix),
+            (prefix, unit) => format!("{} {}{}", number, prefix, unit),
+        }
+    }
+}
+
+impl Default for SiFormat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The decimals needed to tell values `step` apart.
+pub(crate) fn decimals_for(step: f64) -> usize {
+    if !(step.is_finite() && step > 0.0) {
+        return 0;
+    }
+    (-step.log10() - 1e-9).ceil().max(0.0) as usize
+}
+
+/// Drops trailing zeros after the decimal point, and the point itself if nothing follows.
+fn trim_zeros(text: &str) -> &str {
+    if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        text
+    }
+}
+
+/// Tick labels sharing one power of ten, which is shown once at the end of the axis, as in
+/// `×10⁻⁶`, instead of in every label.
+///
+/// ```ignore
+/// let exponent = SharedExponent::for_range(ctx.y_range(), ctx.y_labels());
+/// chart.configure_mesh().y_label_formatter(&|y| exponent.format(*y)).draw()?;
+/// ctx.show_exponent(Axis::Y, &exponent);
+/// ```
+///
+/// Ranges whose values read well as they are keep an exponent of 0, so the labels are
+/// unchanged and no exponent is shown.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SharedExponent {
+    exponent: i32,
+    decimals: usize,
+}
+
+impl SharedExponent {
+    /// The exponent for about `labels` ticks across `range`, if its largest magnitude is at
+    /// least 10⁴ or below 10⁻³.
+    pub fn for_range(range: Range<f64>, labels: usize) -> Self {
+        let magnitude = range.start.abs().max(range.end.abs());
+        let exponent = if magnitude.is_finite() && magnitude > 0.0 {
+            magnitude.log10().floor() as i32
+        } else {
+            0
+        };
+        let exponent = if (-3..4).contains(&exponent) {
+            0
+        } else {
+            exponent
+        };
+        Self::with_exponent(range, labels, exponent)
+    }
+
+    /// Uses `exponent` for about `labels` ticks across `range`, e.g. to keep the exponent
+    /// of several charts the same.
+    pub fn with_exponent(range: Range<f64>, labels: usize, exponent: i32) -> Self {
+        let step = (range.end - range.start).abs() / labels.max(1) as f64;
+        Self {
+            exponent,
+            decimals: decimals_for(step / 10f64.powi(exponent)),
+        }
+    }
+
+    pub fn exponent(&self) -> i32 {
+        self.exponent
+    }
+
+    /// The label of a tick at `value`, divided by the shared power of ten.
+    pub fn format(&self, value: f64) -> String {
+        let mantissa = value / 10f64.powi(self.exponent);
+        let text = format!("{:.*}", self.decimals, mantissa);
+        // Avoid "-0" for ticks at zero rounded from tiny negative values.
+        match trim_zeros(&text) {
+            "-0" => "0".to_string(),
+            text => text.to_string(),
+        }
+    }
+
+    /// The exponent as shown at the axis, such as `×10⁻⁶`, or `None` for an exponent of 0.
+    pub fn label(&self) -> Option<String> {
+        if self.exponent == 0 {
+            return None;
+        }
+        Some(format!("×10{}", superscript(self.exponent)))
+    }
+}
+
+fn superscript(value: i32) -> String {
+    const DIGITS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+    let digits = value.unsigned_abs().to_string();
+    let sign = if value < 0 { "⁻" } else { "" };
+    sign.chars()
+        .chain(digits.bytes().map(|digit| DIGITS[(digit - b'0') as usize]))
+        .collect()
+}
+
+/// Draws the exponent labels of `exponents` next to the axes of `plot`: the x exponent
+/// below the right end of the x axis, the y exponents above the top of their axis.
+pub(crate) fn draw_exponents(frame: &mut Frame, plot: Rectangle, exponents: &[(Axis, String)]) {
+    for (axis, label) in exponents {
+        let (position, horizontal_alignment, vertical_alignment) = match axis {
+            Axis::X => (
+                Point::new(plot.x + plot.width, frame.height() - EXPONENT_PADDING),
+                HorizontalAlignment::Right,
+                VerticalAlignment::Bottom,
+            ),
+            Axis::Y => (
+                Point::new(plot.x, plot.y - EXPONENT_PADDING),
+                HorizontalAlignment::Left,
+                VerticalAlignment::Bottom,
+            ),
+            Axis::SecondaryY => (
+                Point::new(plot.x + plot.width, plot.y - EXPONENT_PADDING),
+                HorizontalAlignment::Right,
+                VerticalAlignment::Bottom,
+            ),
+        };
+        frame.fill_text(Text {
+            content: label.clone(),
+            position,
+            color: Color::BLACK,
+            size: EXPONENT_SIZE,
+            horizontal_alignment,
+            vertical_alignment,
+            ..Text::default()
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn si_format() {
+        let decimal = SiFormat::new();
+        let binary = SiFormat::new().binary();
+        let cases = [
+            (&decimal, 0.0, "0"),
+            (&decimal, 1.2345, "1.2"),
+            (&decimal, 1500.0, "1.5k"),
+            (&decimal, -2500.0, "-2.5k"),
+            (&decimal, 0.00056, "560µ"),
+            (&decimal, 999.96, "1k"),
+            (&decimal, 999_960.0, "1M"),
+            (&decimal, 1e30, "1000000Y"),
+            (&decimal, f64::NAN, "NaN"),
+            (&decimal, f64::INFINITY, "inf"),
+            (&binary, 500.0, "500"),
+            (&binary, 1536.0, "1.5Ki"),
+            (&binary, 1023.99, "1Ki"),
+            (&binary, 3.0 * 1024.0 * 1024.0, "3Mi"),
+        ];
+        for (format, value, expected) in cases.iter() {
+            assert_eq!(format.format(*value), *expected, "{}", value);
+        }
+    }
+
+    #[test]
+    fn si_format_spaces_the_unit() {
+        let hertz = SiFormat::new().unit("Hz");
+        assert_eq!(hertz.format(1500.0), "1.5 kHz");
+        assert_eq!(hertz.format(12.0), "12 Hz");
+        assert_eq!(SiFormat::new().binary().unit("B").format(1536.0), "1.5 KiB");
+    }
+
+    #[test]
+    fn si_format_decimals() {
+        let cases = [
+            (SiFormat::new().precision(3), 1_234_567.0, "1.235M"),
+            (SiFormat::new().precision(0), 1_650.0, "2k"),
+            (SiFormat::new().for_step(100.0), 12_345.0, "12.3k"),
+            (SiFormat::new().for_step(1.0), 12_345.0, "12.345k"),
+            (SiFormat::new().for_range(0.0..5000.0, 10), 2500.0, "2.5k"),
+            // Invalid steps fall back to the precision.
+            (SiFormat::new().for_step(0.0), 1_234.0, "1.2k"),
+        ];
+        for (format, value, expected) in cases.iter() {
+            assert_eq!(format.format(*value), *expected, "{:?}", format);
+        }
+    }
+
+    #[test]
+    fn decimals_for_steps() {
+        let cases = [
+            (10.0, 0),
+            (1.0, 0),
+            (0.5, 1),
+            (0.1, 1),
+            (0.025, 2),
+            (0.01, 2),
+            (0.0, 0),
+            (-1.0, 0),
+            (f64::NAN, 0),
+        ];
+        for (step, expected) in cases.iter() {
+            assert_eq!(decimals_for(*step), *expected, "{}", step);
+        }
+    }
+
+    #[test]
+    fn shared_exponent_thresholds() {
+        let cases = [
+            (0.0..9_999.0, 0),
+            (0.0..10_000.0, 4),
+            (-25_000.0..0.0, 4),
+            (0.0..0.001, 0),
+            (0.0..0.000_99, -4),
+            (0.0..0.0, 0),
+            (0.0..f64::INFINITY, 0),
+        ];
+        for (range, expected) in cases.iter() {
+            let exponent = SharedExponent::for_range(range.clone(), 5);
+            assert_eq!(exponent.exponent(), *expected, "{:?}", range);
+        }
+    }
+
+    #[test]
+    fn shared_exponent_format() {
+        let exponent = SharedExponent::for_range(0.0..0.000_05, 10);
+        assert_eq!(exponent.exponent(), -5);
+        assert_eq!(exponent.format(0.000_02), "2");
+        assert_eq!(exponent.format(0.000_025), "2.5");
+        assert_eq!(exponent.format(-1e-12), "0");
+
+        let exponent = SharedExponent::with_exponent(0.0..1e7, 20, 6);
+        assert_eq!(exponent.format(2_500_000.0), "2.5");
+        assert_eq!(exponent.format(-1.0), "0");
+    }
+
+    #[test]
+    fn shared_exponent_label() {
+        assert_eq!(superscript(0), "⁰");
+        assert_eq!(superscript(12), "¹²");
+        assert_eq!(superscript(-6), "⁻⁶");
+        assert_eq!(superscript(i32::MIN), "⁻²¹⁴⁷⁴⁸³⁶⁴⁸");
+        let label = |exponent| SharedExponent::with_exponent(0.0..1.0, 5, exponent).label();
+        assert_eq!(label(0), None);
+        assert_eq!(label(-6).as_deref(), Some("×10⁻⁶"));
+        assert_eq!(label(9).as_deref(), Some("×10⁹"));
+    }
+}
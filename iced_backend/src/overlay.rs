@@ -0,0 +1,136 @@
+use crate::{Axis, BuildContext, BuildError, Chart, IcedBackend};
+use plotters::chart::ChartBuilder;
+use plotters::coord::types::RangedCoordf64;
+use plotters::coord::Shift;
+use plotters::drawing::DrawingArea;
+use plotters::prelude::Cartesian2d;
+use plotters::style::IntoFont;
+
+/// The coordinate system of an [`Overlay`] layer.
+pub type LayerCoord = Cartesian2d<RangedCoordf64, RangedCoordf64>;
+
+/// One of the two coordinate systems drawn by an [`Overlay`], or the contents of an
+/// [`Inset`](crate::Inset).
+pub trait OverlayLayer {
+    /// Draws the layer's series onto `area`, whose coordinates map the shared x range and
+    /// the layer's own y range onto the plotting area.
+    ///
+    /// Elements are drawn with `area.draw(&element)`; series can be registered for hit
+    /// testing through `ctx` with `area` as their drawing area.
+    fn build_layer(
+        &self,
+        ctx: &mut BuildContext<'_>,
+        area: &DrawingArea<IcedBackend<'_>, LayerCoord>,
+    ) -> Result<(), BuildError>;
+}
+
+/// Two charts sharing the x axis and plotting area, each with its own y axis: the primary
+/// one on the left and the secondary one on the right, such as temperature and humidity.
+///
+/// The widget's view must have a secondary y axis (see
+/// [`ViewState::with_secondary_y`](crate::ViewState::with_secondary_y)). Both y axes can
+/// then be scaled independently by dragging their gutters, and the readout reports the
+/// cursor's value on both.
+pub struct Overlay<P, S> {
+    primary: P,
+    secondary: S,
+    caption: Option<String>,
+    y_desc: Option<String>,
+    secondary_y_desc: Option<String>,
+}
+
+impl<P: OverlayLayer, S: OverlayLayer> Overlay<P, S> {
+    pub fn new(primary: P, secondary: S) -> Self {
+        Self {
+            primary,
+            secondary,
+            caption: None,
+            y_desc: None,
+            secondary_y_desc: None,
+        }
+    }
+
+    pub fn caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = Some(caption.into());
+        self
+    }
+
+    /// Labels the primary y axis on the left.
+    pub fn y_desc(mut self, desc: impl Into<String>) -> Self {
+        self.y_desc = Some(desc.into());
+        self
+    }
+
+    /// Labels the secondary y axis on the right.
+    pub fn secondary_y_desc(mut self, desc: impl Into<String>) -> Self {
+        self.secondary_y_desc = Some(desc.into());
+        self
+    }
+
+    pub fn primary(&self) -> &P {
+        &self.primary
+    }
+
+    pub fn primary_mut(&mut self) -> &mut P {
+        &mut self.primary
+    }
+
+    pub fn secondary(&self) -> &S {
+        &self.secondary
+    }
+
+    pub fn secondary_mut(&mut self) -> &mut S {
+        &mut self.secondary
+    }
+}
+
+impl<P: OverlayLayer, S: OverlayLayer> Chart for Overlay<P, S> {
+    fn build_chart(
+        &self,
+        ctx: &mut BuildContext<'_>,
+        root: DrawingArea<IcedBackend<'_>, Shift>,
+    ) -> Result<(), BuildError> {
+        let secondary_y = ctx
+            .secondary_y_range()
+            .ok_or("an overlay needs a view with a secondary y axis")?;
+        ctx.fill_background(&root)?;
+
+        let mut builder = ChartBuilder::on(&root);
+        builder
+            .margin(10)
+            .x_label_area_size(30)
+            .y_label_area_size(60)
+            .right_y_label_area_size(60);
+        if let Some(caption) = &self.caption {
+            builder.caption(caption, ("sans-serif", 24).into_font());
+        }
+        let mut chart = builder
+            .build_cartesian_2d(ctx.x_range(), ctx.y_range())?
+            .set_secondary_coord(ctx.x_range(), secondary_y);
+        ctx.set_plotting_area(chart.plotting_area());
+
+        {
+            let mut mesh = chart.configure_mesh();
+            ctx.fit_labels(&mut mesh);
+            if let Some(desc) = &self.y_desc {
+                mesh.y_desc(desc);
+            }
+            mesh.y_label_formatter(&|y| ctx.format(Axis::Y, *y))
+                .draw()?;
+        }
+        {
+            let mut axes = chart.configure_secondary_axes();
+            axes.y_labels(ctx.y_labels());
+            if let Some(desc) = &self.secondary_y_desc {
+                axes.y_desc(desc);
+            }
+            axes.y_label_formatter(&|y| ctx.format(Axis::SecondaryY, *y))
+                .draw()?;
+        }
+
+        self.primary.build_layer(ctx, chart.plotting_area())?;
+        self.secondary
+            .build_layer(ctx, chart.secondary_plotting_area())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,226 @@
+use crate::clip::Envelope;
+use crate::shading::{pixel_bounds, to_point};
+use iced::Point;
+use plotters::coord::CoordTranslate;
+use plotters::drawing::{DrawingArea, DrawingAreaErrorKind};
+use plotters::element::{Circle, PathElement, Polygon};
+use plotters::style::{Color, RGBAColor, ShapeStyle};
+use plotters_backend::DrawingBackend;
+use std::f32::consts::FRAC_PI_4;
+
+type PatternResult<DB> = Result<(), DrawingAreaErrorKind<<DB as DrawingBackend>::ErrorType>>;
+
+/// The strokes a [`Pattern`] tiles its shape with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hatch {
+    /// Lines rising to the right.
+    Diagonal,
+    /// Lines falling to the right.
+    BackDiagonal,
+    /// Both diagonals.
+    Cross,
+    Horizontal,
+    Vertical,
+    /// A grid of dots.
+    Dots,
+}
+
+impl Hatch {
+    /// All hatches, in the order [`Palette::distinct_shapes`](crate::Palette::distinct_shapes)
+    /// assigns them to series.
+    pub const ALL: [Hatch; 6] = [
+        Hatch::Diagonal,
+        Hatch::BackDiagonal,
+        Hatch::Cross,
+        Hatch::Dots,
+        Hatch::Horizontal,
+        Hatch::Vertical,
+    ];
+
+    /// The directions of the hatch lines, in radians from the x axis of the screen.
+    fn angles(self) -> &'static [f32] {
+        match self {
+            Hatch::Diagonal => &[-FRAC_PI_4],
+            Hatch::BackDiagonal => &[FRAC_PI_4],
+            Hatch::Cross => &[-FRAC_PI_4, FRAC_PI_4],
+            Hatch::Horizontal => &[0.0],
+            Hatch::Vertical => &[std::f32::consts::FRAC_PI_2],
+            Hatch::Dots => &[],
+        }
+    }
+}
+
+/// A fill of hatch lines or dots instead of a solid color, for charts that are printed in
+/// black and white or read by people with color blindness. Draw it with [`fill_pattern`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pattern {
+    pub hatch: Hatch,
+    pub color: RGBAColor,
+    /// The solid color under the hatch, if any.
+    pub background: Option<RGBAColor>,
+    /// The distance between the lines or dots in pixels.
+    pub spacing: f32,
+    /// The width of the lines or the radius of the dots in pixels.
+    pub width: u32,
+}
+
+impl Pattern {
+    /// A `hatch` of 1 pixel wide lines in `color`, 6 pixels apart, over nothing.
+    pub fn new<C: Color>(hatch: Hatch, color: &C) -> Self {
+        Self {
+            hatch,
+            color: color.to_rgba(),
+            background: None,
+            spacing: 6.0,
+            width: 1,
+        }
+    }
+
+    pub fn background<C: Color>(mut self, color: &C) -> Self {
+        self.background = Some(color.to_rgba());
+        self
+    }
+
+    pub fn spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing.max(2.0);
+        self
+    }
+
+    pub fn width(mut self, width: u32) -> Self {
+        self.width = width;
+        self
+    }
+}
+
+/// Fills the polygon through `outline` with `pattern`, clipped to the pixel range of
+/// `area` (usually `chart.plotting_area()`), e.g. a bar or the area under a curve.
+///
+/// The hatch is aligned to the backend's pixel grid rather than to the shape, so the
+/// patterns of adjacent shapes line up.
+pub fn fill_pattern<DB, CT>(
+    area: &DrawingArea<DB, CT>,
+    outline: &[CT::From],
+    pattern: &Pattern,
+) -> PatternResult<DB>
+where
+    DB: DrawingBackend,
+    CT: CoordTranslate,
+{
+    let outline: Vec<Point> = outline
+        .iter()
+        .map(|point| to_point(area.map_coordinate(point)))
+        .collect();
+    let vert = Envelope::of(pixel_bounds(area)).clip_polygon(&outline);
+    if vert.len() < 3 {
+        return Ok(());
+    }
+    let screen = area.use_screen_coord();
+    let pixel = |point: Point| (point.x.round() as i32, point.y.round() as i32);
+
+    if let Some(background) = &pattern.background {
+        let vertices: Vec<_> = vert.iter().copied().map(pixel).collect();
+        screen.draw(&Polygon::new(vertices, background.filled()))?;
+    }
+    let style = ShapeStyle {
+        color: pattern.color.clone(),
+        filled: true,
+        stroke_width: pattern.width,
+    };
+    if pattern.hatch == Hatch::Dots {
+        for dot in dots(&vert, pattern.spacing) {
+            screen.draw(&Circle::new(pixel(dot), pattern.width, style.clone()))?;
+        }
+    }
+    for &angle in pattern.hatch.angles() {
+        for (from, to) in hatch_lines(&vert, angle, pattern.spacing) {
+            screen.draw(&PathElement::new(
+                vec![pixel(from), pixel(to)],
+                style.clone(),
+            ))?;
+        }
+    }
+    Ok(())
+}
+
+/// The parts inside `vert` of the parallel lines at `angle`, `spacing` pixels apart.
+///
+/// Every line is cut at its crossings with the polygon's edges and the pieces between
+/// every other crossing are kept, so concave polygons are hatched correctly.
+fn hatch_lines(vert: &[Point], angle: f32, spacing: f32) -> Vec<(Point, Point)> {
+    let direction = (angle.cos(), angle.sin());
+    let normal = (-direction.1, direction.0);
+    let along = |point: Point, axis: (f32, f32)| point.x * axis.0 + point.y * axis.1;
+    let offsets = vert.iter().map(|point| along(*point, normal));
+    let low = offsets.clone().fold(f32::INFINITY, f32::min);
+    let high = offsets.fold(f32::NEG_INFINITY, f32::max);
+
+    let mut lines = vec![];
+    let mut offset = (low / spacing).ceil() * spacing;
+    while offset <= high {
+        let mut crossings = vec![];
+        let mut prev = vert[vert.len() - 1];
+        for &cur in vert {
+            let (a, b) = (along(prev, normal), along(cur, normal));
+            if (a < offset) != (b < offset) {
+                let t = (offset - a) / (b - a);
+                let crossing =
+                    Point::new(prev.x + t * (cur.x - prev.x), prev.y + t * (cur.y - prev.y));
+                crossings.push(along(crossing, direction));
+            }
+            prev = cur;
+        }
+        crossings.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let at = |position: f32| {
+            Point::new(
+                offset * normal.0 + position * direction.0,
+                offset * normal.1 + position * direction.1,
+            )
+        };
+        for pair in crossings.chunks_exact(2) {
+            lines.push((at(pair[0]), at(pair[1])));
+        }
+        offset += spacing;
+    }
+    lines
+}
+
+/// The points of a grid `spacing` pixels wide that lie inside `vert`, with every other
+/// row shifted by half the spacing.
+fn dots(vert: &[Point], spacing: f32) -> Vec<Point> {
+    let (mut min, mut max) = (vert[0], vert[0]);
+    for point in vert {
+        min = Point::new(min.x.min(point.x), min.y.min(point.y));
+        max = Point::new(max.x.max(point.x), max.y.max(point.y));
+    }
+    let mut dots = vec![];
+    let mut row = (min.y / spacing).ceil() as i64;
+    while row as f32 * spacing <= max.y {
+        let shift = if row % 2 == 0 { 0.0 } else { spacing / 2.0 };
+        let y = row as f32 * spacing;
+        let mut x = ((min.x - shift) / spacing).ceil() * spacing + shift;
+        while x <= max.x {
+            let dot = Point::new(x, y);
+            if contains(vert, dot) {
+                dots.push(dot);
+            }
+            x += spacing;
+        }
+        row += 1;
+    }
+    dots
+}
+
+/// Whether `point` lies inside `vert` by the even-odd rule.
+fn contains(vert: &[Point], point: Point) -> bool {
+    let mut inside = false;
+    let mut prev = vert[vert.len() - 1];
+    for &cur in vert {
+        if (cur.y > point.y) != (prev.y > point.y)
+            && point.x < prev.x + (point.y - prev.y) / (cur.y - prev.y) * (cur.x - prev.x)
+        {
+            inside = !inside;
+        }
+        prev = cur;
+    }
+    inside
+}
@@ -0,0 +1,122 @@
+use iced::canvas::{Frame, Path, Stroke};
+use iced::{Color, Point, Rectangle, Size};
+use std::time::{Duration, Instant};
+
+/// The time the shimmer of a skeleton takes to cross the plot area.
+const SHIMMER_PERIOD: Duration = Duration::from_millis(1500);
+/// The width of the shimmer relative to the plot area.
+const SHIMMER_WIDTH: f32 = 0.25;
+/// The space left around the skeleton's plot area for its axes, in pixels.
+const LEFT_MARGIN: f32 = 50.0;
+const BOTTOM_MARGIN: f32 = 30.0;
+const MARGIN: f32 = 10.0;
+
+type Draw = Box<dyn Fn(&mut Frame, Size)>;
+
+enum Kind {
+    Skeleton(Color),
+    Custom(Draw),
+}
+
+/// What the widget shows instead of the chart until its first data arrives, so a
+/// dashboard starting up shows the shape of its charts rather than empty rectangles, see
+/// [`ChartWidget::placeholder`](crate::ChartWidget::placeholder).
+pub struct Placeholder {
+    kind: Kind,
+    since: Instant,
+}
+
+impl Placeholder {
+    /// Greyed axes around a plot area with a light shimmer passing over it.
+    pub fn skeleton() -> Self {
+        Self {
+            kind: Kind::Skeleton(Color::from_rgb(0.85, 0.85, 0.85)),
+            since: Instant::now(),
+        }
+    }
+
+    /// A skeleton drawn in `color` instead of light grey.
+    pub fn skeleton_color(color: Color) -> Self {
+        Self {
+            kind: Kind::Skeleton(color),
+            since: Instant::now(),
+        }
+    }
+
+    /// Content drawn by `draw` onto a frame the size of the canvas.
+    pub fn custom<F>(draw: F) -> Self
+    where
+        F: 'static + Fn(&mut Frame, Size),
+    {
+        Self {
+            kind: Kind::Custom(Box::new(draw)),
+            since: Instant::now(),
+        }
+    }
+
+    /// Whether the placeholder moves and needs the widget to be ticked.
+    pub(crate) fn is_animated(&self) -> bool {
+        matches!(self.kind, Kind::Skeleton(_))
+    }
+
+    pub(crate) fn draw(&self, frame: &mut Frame, size: Size) {
+        match &self.kind {
+            Kind::Skeleton(color) => self.draw_skeleton(frame, size, *color),
+            Kind::Custom(draw) => draw(frame, size),
+        }
+    }
+
+    fn draw_skeleton(&self, frame: &mut Frame, size: Size, color: Color) {
+        let plot = Rectangle {
+            x: LEFT_MARGIN,
+            y: MARGIN,
+            width: (size.width - LEFT_MARGIN - MARGIN).max(0.0),
+            height: (size.height - BOTTOM_MARGIN - MARGIN).max(0.0),
+        };
+        let faint = Color { a: 0.4, ..color };
+        frame.fill_rectangle(plot.position(), plot.size(), faint);
+
+        let axes = Path::new(|builder| {
+            builder.move_to(plot.position());
+            builder.line_to(Point::new(plot.x, plot.y + plot.height));
+            builder.line_to(Point::new(plot.x + plot.width, plot.y + plot.height));
+        });
+        frame.stroke(
+            &axes,
+            Stroke {
+                color,
+                width: 2.0,
+                ..Stroke::default()
+            },
+        );
+        // Bars where the tick labels will be.
+        for i in 0..5 {
+            let t = i as f32 / 4.0;
+            let y = plot.y + plot.height * t - 3.0;
+            frame.fill_rectangle(Point::new(plot.x - 36.0, y), Size::new(28.0, 6.0), color);
+            let x = plot.x + plot.width * t - 14.0;
+            frame.fill_rectangle(
+                Point::new(x, plot.y + plot.height + 10.0),
+                Size::new(28.0, 6.0),
+                color,
+            );
+        }
+
+        // The shimmer, as a few bands fading out from its center.
+        let phase = self.since.elapsed().as_secs_f32() / SHIMMER_PERIOD.as_secs_f32();
+        let width = plot.width * SHIMMER_WIDTH;
+        let center = plot.x - width + phase.fract() * (plot.width + 2.0 * width);
+        for band in 1..=3 {
+            let half = width / 2.0 * band as f32 / 3.0;
+            let left = (center - half).max(plot.x);
+            let right = (center + half).min(plot.x + plot.width);
+            if right > left {
+                frame.fill_rectangle(
+                    Point::new(left, plot.y),
+                    Size::new(right - left, plot.height),
+                    Color::from_rgba(1.0, 1.0, 1.0, 0.15),
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,198 @@
+//! Plotting columns of a polars `DataFrame`, with the `polars` feature.
+
+use crate::{AxisSpec, ChartSpec, Facets, SeriesKind, SeriesSpec};
+use polars::prelude::{DataFrame, DataType, PolarsResult, Series, TimeUnit};
+
+/// How the values of a column are placed along an axis, decided by its dtype.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnAxis {
+    /// Numbers and booleans, as they are.
+    Numeric,
+    /// Dates, datetimes and durations, as seconds since the Unix epoch or in the duration;
+    /// label the axis with [`DateTimeTicks`](crate::DateTimeTicks).
+    Time,
+    /// Strings and categories, at the positions 0, 1, 2, ... of the categories in the order
+    /// they first appear.
+    Categories(Vec<String>),
+}
+
+/// Columns of a `DataFrame` picked for a chart: one x column, one or more y columns and
+/// optionally a column to group the rows by.
+///
+/// ```ignore
+/// let plot = FramePlot::new(&frame, "time").y("temperature").group_by("station");
+/// let chart = ChartWidget::new(plot.spec()?);
+/// let grid = plot.facets()?.grid(3);
+/// ```
+///
+/// Rows with a null x or y are skipped.
+#[derive(Debug, Clone)]
+pub struct FramePlot<'a> {
+    frame: &'a DataFrame,
+    x: String,
+    ys: Vec<String>,
+    group_by: Option<String>,
+}
+
+impl<'a> FramePlot<'a> {
+    pub fn new(frame: &'a DataFrame, x: impl Into<String>) -> Self {
+        Self {
+            frame,
+            x: x.into(),
+            ys: vec![],
+            group_by: None,
+        }
+    }
+
+    /// Adds a y column, drawn as its own series.
+    pub fn y(mut self, column: impl Into<String>) -> Self {
+        self.ys.push(column.into());
+        self
+    }
+
+    /// Splits every y column into one series per distinct value of `column`.
+    pub fn group_by(mut self, column: impl Into<String>) -> Self {
+        self.group_by = Some(column.into());
+        self
+    }
+
+    /// How the x column is placed along the x axis.
+    pub fn x_axis(&self) -> PolarsResult<ColumnAxis> {
+        Ok(values(self.frame.column(&self.x)?)?.1)
+    }
+
+    /// A line chart of the y columns, one series per column and group, named after them.
+    pub fn spec(&self) -> PolarsResult<ChartSpec> {
+        let (xs, _) = values(self.frame.column(&self.x)?)?;
+        let groups = self.groups()?;
+        let mut series = vec![];
+        for y in &self.ys {
+            let (ys, _) = values(self.frame.column(y)?)?;
+            let rows = xs.iter().zip(&ys).zip(&groups);
+            for key in distinct(&groups) {
+                let data = rows
+                    .clone()
+                    .filter(|(_, group)| **group == key)
+                    .filter_map(|((x, y), _)| Some(((*x)?, (*y)?)))
+                    .collect();
+                let name = match &key {
+                    Some(key) => format!("{} ({})", y, key),
+                    None => y.clone(),
+                };
+                series.push(SeriesSpec {
+                    name: Some(name),
+                    kind: SeriesKind::Line,
+                    data,
+                    ..SeriesSpec::default()
+                });
+            }
+        }
+        let y_label = match self.ys.as_slice() {
+            [y] => Some(y.clone()),
+            _ => None,
+        };
+        Ok(ChartSpec {
+            x_axis: AxisSpec {
+                label: Some(self.x.clone()),
+                ..AxisSpec::default()
+            },
+            y_axis: AxisSpec {
+                label: y_label,
+                ..AxisSpec::default()
+            },
+            legend: series.len() > 1,
+            series,
+            ..ChartSpec::default()
+        })
+    }
+
+    /// The rows of the first y column split into one facet per group, or a single facet
+    /// without [`FramePlot::group_by`].
+    pub fn facets(&self) -> PolarsResult<Facets<String>> {
+        let (xs, _) = values(self.frame.column(&self.x)?)?;
+        let ys = match self.ys.first() {
+            Some(y) => values(self.frame.column(y)?)?.0,
+            None => vec![],
+        };
+        let groups = self.groups()?;
+        let records = xs
+            .into_iter()
+            .zip(ys)
+            .zip(groups)
+            .filter_map(|((x, y), group)| Some((x?, y?, group.unwrap_or_default())));
+        Ok(Facets::new(records, |r| r.2.clone(), |r| r.0, |r| r.1).lines(true))
+    }
+
+    /// The group of every row, or `None` for all rows without grouping.
+    fn groups(&self) -> PolarsResult<Vec<Option<String>>> {
+        let column = match &self.group_by {
+            Some(column) => self.frame.column(column)?,
+            None => return Ok(vec![None; self.frame.height()]),
+        };
+        let keys = column.cast(&DataType::Utf8)?;
+        Ok(keys
+            .utf8()?
+            .into_iter()
+            .map(|key| Some(key.unwrap_or("null").to_string()))
+            .collect())
+    }
+}
+
+/// The distinct values of `groups`, in the order they first appear.
+fn distinct(groups: &[Option<String>]) -> Vec<Option<String>> {
+    let mut keys: Vec<Option<String>> = vec![];
+    for group in groups {
+        if !keys.contains(group) {
+            keys.push(group.clone());
+        }
+    }
+    keys
+}
+
+/// The values of `column` as positions along an axis, and how they were placed.
+fn values(column: &Series) -> PolarsResult<(Vec<Option<f64>>, ColumnAxis)> {
+    let seconds = |column: &Series, per_second: f64| -> PolarsResult<Vec<Option<f64>>> {
+        let column = column.cast(&DataType::Int64)?;
+        Ok(column
+            .i64()?
+            .into_iter()
+            .map(|value| value.map(|value| value as f64 / per_second))
+            .collect())
+    };
+    let per_second = |unit: &TimeUnit| match unit {
+        TimeUnit::Nanoseconds => 1e9,
+        TimeUnit::Microseconds => 1e6,
+        TimeUnit::Milliseconds => 1e3,
+    };
+    match column.dtype() {
+        DataType::Date => Ok((seconds(column, 1.0 / 86_400.0)?, ColumnAxis::Time)),
+        DataType::Datetime(unit, _) | DataType::Duration(unit) => {
+            Ok((seconds(column, per_second(unit))?, ColumnAxis::Time))
+        }
+        DataType::Utf8 | DataType::Categorical(_) => {
+            let names = column.cast(&DataType::Utf8)?;
+            let mut categories: Vec<String> = vec![];
+            let positions = names
+                .utf8()?
+                .into_iter()
+                .map(|name| {
+                    let name = name?;
+                    let position = match categories.iter().position(|known| known == name) {
+                        Some(position) => position,
+                        None => {
+                            categories.push(name.to_string());
+                            categories.len() - 1
+                        }
+                    };
+                    Some(position as f64)
+                })
+                .collect();
+            Ok((positions, ColumnAxis::Categories(categories)))
+        }
+        _ => {
+            let numbers = column.cast(&DataType::Float64)?;
+            let values = numbers.f64()?.into_iter().collect();
+            Ok((values, ColumnAxis::Numeric))
+        }
+    }
+}
@@ -0,0 +1,19 @@
+//! The items most charts need, in one import:
+//!
+//! ```ignore
+//! use iced_backend::prelude::*;
+//! ```
+//!
+//! Besides the backend, the widget and its helpers, it re-exports the plotters prelude,
+//! so `ChartBuilder`, the elements, styles and colors come along. [`Palette`] is this
+//! crate's series palette; the plotters trait of the same name stays at
+//! `plotters::style::Palette`.
+
+pub use crate::{AreaChart, LineChart, ScatterChart};
+pub use crate::{
+    BuildContext, BuildError, Chart, ChartError, ChartSpec, ChartWidget, IcedBackend, IcedError,
+    Palette, SeriesData, SeriesId, SeriesStyle, ViewState,
+};
+pub use iced::Length;
+pub use plotters::coord::Shift;
+pub use plotters::prelude::*;
@@ -0,0 +1,16 @@
+/// The result of a preparation started with [`ChartWidget::prepare`], carried by the
+/// application's message back to [`ChartWidget::finish_preparation`].
+///
+/// [`ChartWidget::prepare`]: crate::ChartWidget::prepare
+/// [`ChartWidget::finish_preparation`]: crate::ChartWidget::finish_preparation
+#[derive(Debug, Clone)]
+pub struct Prepared<T> {
+    pub(crate) generation: u64,
+    pub(crate) value: T,
+}
+
+impl<T> Prepared<T> {
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+}
@@ -0,0 +1,244 @@
+use crate::{SeriesData, ViewState};
+use std::ops::Range;
+
+/// The lowest and highest point of a run of consecutive points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bucket {
+    pub min: (f64, f64),
+    pub max: (f64, f64),
+}
+
+impl Bucket {
+    fn new(point: (f64, f64)) -> Self {
+        Self {
+            min: point,
+            max: point,
+        }
+    }
+
+    fn merge(&mut self, other: &Bucket) {
+        if other.min.1 < self.min.1 {
+            self.min = other.min;
+        }
+        if other.max.1 > self.max.1 {
+            self.max = other.max;
+        }
+    }
+
+    /// The lowest and highest point in the order of their x, or the one point if they are
+    /// the same.
+    fn points(&self) -> impl Iterator<Item = (f64, f64)> {
+        let (first, second) = if self.min.0 <= self.max.0 {
+            (self.min, self.max)
+        } else {
+            (self.max, self.min)
+        };
+        std::iter::once(first).chain(Some(second).filter(|second| *second != first))
+    }
+}
+
+/// A growing time series with the lowest and highest point of every run of 2, 4, 8, ...
+/// points kept up to date as points are appended, so any visible window is drawn at about
+/// pixel resolution in time proportional to the width of the plot, not the points in view.
+///
+/// ```ignore
+/// self.pyramid.extend(new_points);
+/// // In build_chart:
+/// let points = self.pyramid.visible(ctx.x_range(), ctx.x_pixels());
+/// ```
+///
+/// Points must be appended in order of x. Use [`LevelOfDetail`](crate::LevelOfDetail)
+/// for series that don't grow.
+#[derive(Debug, Clone, Default)]
+pub struct Pyramid {
+    points: Vec<(f64, f64)>,
+    /// Level `k` holds a bucket for every `2^(k + 1)` points; the last may be incomplete.
+    levels: Vec<Vec<Bucket>>,
+}
+
+impl Pyramid {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_points(points: impl IntoIterator<Item = (f64, f64)>) -> Self {
+        let mut pyramid = Self::new();
+        pyramid.extend(points);
+        pyramid
+    }
+
+    /// Appends `point`, updating one bucket per level.
+    pub fn push(&mut self, point: (f64, f64)) {
+        let index = self.points.len();
+        self.points.push(point);
+        for (level, buckets) in self.levels.iter_mut().enumerate() {
+            if index >> (level + 1) < buckets.len() {
+                if let Some(last) = buckets.last_mut() {
+                    last.merge(&Bucket::new(point));
+                }
+            } else {
+                buckets.push(Bucket::new(point));
+            }
+        }
+        // Add a level once its buckets would hold more than one point of the one below.
+        while self.points.len() >= 2 << self.levels.len() {
+            let buckets = match self.levels.last() {
+                Some(below) => below
+                    .chunks(2)
+                    .map(|pair| {
+                        let mut bucket = pair[0];
+                        if let Some(second) = pair.get(1) {
+                            bucket.merge(second);
+                        }
+                        bucket
+                    })
+                    .collect(),
+                None => self
+                    .points
+                    .chunks(2)
+                    .map(|pair| {
+                        let mut bucket = Bucket::new(pair[0]);
+                        if let Some(second) = pair.get(1) {
+                            bucket.merge(&Bucket::new(*second));
+                        }
+                        bucket
+                    })
+                    .collect(),
+            };
+            self.levels.push(buckets);
+        }
+    }
+
+    pub fn extend(&mut self, points: impl IntoIterator<Item = (f64, f64)>) {
+        for point in points {
+            self.push(point);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.points.clear();
+        self.levels.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// All points, as appended.
+    pub fn points(&self) -> &[(f64, f64)] {
+        &self.points
+    }
+
+    /// The buckets of level `level`, each covering `2^(level + 1)` points.
+    pub fn buckets(&self, level: usize) -> &[Bucket] {
+        self.levels.get(level).map_or(&[], Vec::as_slice)
+    }
+
+    /// The points to draw across `x`, at most about two per pixel of `pixels`: the points
+    /// themselves if few enough are visible, else the lowest and highest point of each
+    /// bucket of the finest level that is coarse enough. The nearest point on either side
+    /// of `x` is included.
+    pub fn visible(&self, x: Range<f64>, pixels: f32) -> Vec<(f64, f64)> {
+        let visible = self.points.visible_range(x);
+        let budget = (2.0 * pixels.max(1.0)) as usize;
+        if visible.len() <= budget {
+            return self.points[visible].to_vec();
+        }
+        let per_pixel = visible.len() / pixels.max(1.0) as usize;
+        let level = (usize::BITS - per_pixel.leading_zeros()).saturating_sub(1) as usize;
+        let level = level.min(self.levels.len().saturating_sub(1));
+        let size = 2 << level;
+        let buckets = visible.start / size..visible.end.div_ceil(size);
+        self.buckets(level)[buckets]
+            .iter()
+            .flat_map(Bucket::points)
+            .collect()
+    }
+
+    /// Like [`Pyramid::visible`], for the x range of `view` across a plotting area `width`
+    /// pixels wide.
+    pub fn for_view(&self, view: &ViewState, width: f32) -> Vec<(f64, f64)> {
+        self.visible(view.x.as_range(), width)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LevelOfDetail;
+
+    /// Points with distinct y values, so no bucket depends on how ties are broken.
+    fn series(len: usize) -> Vec<(f64, f64)> {
+        (0..len)
+            .map(|index| (index as f64, ((index * 7919) % 3001) as f64))
+            .collect()
+    }
+
+    fn flatten(buckets: &[Bucket]) -> Vec<(f64, f64)> {
+        buckets.iter().flat_map(Bucket::points).collect()
+    }
+
+    #[test]
+    fn chunked_appends_match_one_append() {
+        let points = series(3000);
+        let whole = Pyramid::from_points(points.clone());
+        let mut chunked = Pyramid::new();
+        let mut rest = points.as_slice();
+        for size in [1, 2, 5, 64, 100, 1000].iter().copied().cycle() {
+            if rest.is_empty() {
+                break;
+            }
+            let (chunk, tail) = rest.split_at(size.min(rest.len()));
+            chunked.extend(chunk.iter().copied());
+            rest = tail;
+        }
+        assert_eq!(chunked.points(), whole.points());
+        assert_eq!(chunked.levels, whole.levels);
+    }
+
+    #[test]
+    fn levels_match_level_of_detail() {
+        let points = series(3000);
+        let mut pyramid = Pyramid::new();
+        for chunk in points.chunks(333) {
+            pyramid.extend(chunk.iter().copied());
+        }
+        let lod = LevelOfDetail::with_factor(points, 2);
+        assert!(lod.levels() > 2);
+        for level in 1..lod.levels() {
+            assert_eq!(
+                flatten(pyramid.buckets(level)),
+                lod.level(level),
+                "level {}",
+                level
+            );
+        }
+    }
+
+    #[test]
+    fn visible_stays_near_the_budget() {
+        let pyramid = Pyramid::from_points(series(3000));
+        for pixels in [10.0, 100.0, 500.0] {
+            for x in [0.0..3000.0, 100.0..2100.0, 1000.0..1300.0] {
+                let visible = pyramid.visible(x.clone(), pixels);
+                // The nearest point on either side may add a bucket at each end.
+                assert!(
+                    visible.len() <= 2 * pixels as usize + 4,
+                    "{:?} at {}",
+                    x,
+                    pixels
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn visible_returns_few_points_unchanged() {
+        let pyramid = Pyramid::from_points(series(100));
+        assert_eq!(pyramid.visible(10.0..20.0, 100.0), &pyramid.points()[9..22]);
+    }
+}
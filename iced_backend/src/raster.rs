@@ -0,0 +1,412 @@
+use ab_glyph::{point, Font as _, FontRef, PxScale, ScaleFont};
+use iced::canvas::Geometry;
+use iced::{Font, HorizontalAlignment, Rectangle, Size, VerticalAlignment};
+use iced_graphics::layer::{Mesh, Quad, Text};
+use iced_graphics::{font, Layer, Primitive, Viewport};
+
+/// The samples taken per pixel along each axis, so edges are smoothed with this many
+/// squared samples, like the multisampling of the renderer.
+const SAMPLES: u32 = 4;
+/// The pixel rows rendered at once, which bounds the memory the samples take.
+const BAND: u32 = 32;
+
+/// Renders `geometry` as a canvas of `size` shows it and encodes the pixels as a PNG.
+pub(crate) fn png(geometry: Vec<Geometry>, size: Size) -> Result<Vec<u8>, String> {
+    let (width, height) = (size.width.ceil() as u32, size.height.ceil() as u32);
+    let pixels = render(geometry, width, height)?;
+    let mut bytes = vec![];
+    let mut encoder = png::Encoder::new(&mut bytes, width, height);
+    encoder.set_color(png::ColorType::RGBA);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&pixels))
+        .map_err(|e| e.to_string())?;
+    Ok(bytes)
+}
+
+/// Renders `geometry` into `width` by `height` pixels of sRGB colors with straight alpha,
+/// four bytes a pixel, row by row.
+///
+/// Layers are drawn like the renderer draws them: the quads, meshes and text of a layer in
+/// that order, so text lies above everything drawn before the next clip. Text without a
+/// font of its own is set in iced's built-in fallback font, which may differ from the
+/// system font on screen; images and borders of quads are left out.
+pub(crate) fn render(geometry: Vec<Geometry>, width: u32, height: u32) -> Result<Vec<u8>, String> {
+    let primitive = Primitive::Group {
+        primitives: geometry.into_iter().map(Geometry::into_primitive).collect(),
+    };
+    let viewport = Viewport::with_physical_size(iced::Size::new(width, height), 1.0);
+    let layers = Layer::generate(&primitive, &viewport);
+    let fallback = FontRef::try_from_slice(font::FALLBACK).map_err(|e| e.to_string())?;
+
+    let mut pixels = vec![0; (width * height * 4) as usize];
+    let row_bytes = (width * 4) as usize;
+    for top in (0..height).step_by(BAND as usize) {
+        let mut band = Band::new(width, top, BAND.min(height - top));
+        for layer in &layers {
+            let clip = band.clip(layer.bounds);
+            for quad in &layer.quads {
+                band.fill_quad(quad, clip);
+            }
+            for mesh in &layer.meshes {
+                band.fill_mesh(mesh, band.clip(mesh.clip_bounds).intersect(clip));
+            }
+            for text in &layer.text {
+                let font = match text.font {
+                    Font::External { bytes, .. } => FontRef::try_from_slice(bytes).ok(),
+                    Font::Default => None,
+                };
+                band.fill_text(text, font.as_ref().unwrap_or(&fallback), clip);
+            }
+        }
+        let start = top as usize * row_bytes;
+        band.resolve(&mut pixels[start..start + band.rows as usize * row_bytes]);
+    }
+    Ok(pixels)
+}
+
+/// A range of samples, in the samples of a [`Band`].
+#[derive(Debug, Clone, Copy)]
+struct SampleRect {
+    x: (i32, i32),
+    y: (i32, i32),
+}
+
+impl SampleRect {
+    fn intersect(self, other: SampleRect) -> SampleRect {
+        SampleRect {
+            x: (self.x.0.max(other.x.0), self.x.1.min(other.x.1)),
+            y: (self.y.0.max(other.y.0), self.y.1.min(other.y.1)),
+        }
+    }
+
+    /// The part of the rectangle within `low..high` along x and y.
+    fn within(self, low: (f32, f32), high: (f32, f32)) -> SampleRect {
+        self.intersect(SampleRect {
+            x: (low.0.floor() as i32, high.0.ceil() as i32),
+            y: (low.1.floor() as i32, high.1.ceil() as i32),
+        })
+    }
+}
+
+/// A band of pixel rows, sampled [`SAMPLES`] times along each axis, in premultiplied
+/// linear RGBA.
+struct Band {
+    /// The first pixel row.
+    top: u32,
+    rows: u32,
+    /// The width in samples.
+    width: u32,
+    samples: Vec<[f32; 4]>,
+}
+
+impl Band {
+    fn new(width: u32, top: u32, rows: u32) -> Self {
+        Self {
+            top,
+            rows,
+            width: width * SAMPLES,
+            samples: vec![[0.0; 4]; (width * SAMPLES * rows * SAMPLES) as usize],
+        }
+    }
+
+    /// `point` in pixels of the canvas, in samples of the band.
+    fn to_samples(&self, x: f32, y: f32) -> (f32, f32) {
+        let scale = SAMPLES as f32;
+        (x * scale, (y - self.top as f32) * scale)
+    }
+
+    /// The samples within `bounds`, in pixels of the canvas, rounded to whole pixels like
+    /// the scissor rectangles of the renderer.
+    fn clip(&self, bounds: Rectangle) -> SampleRect {
+        let (left, top) = self.to_samples(bounds.x.round(), bounds.y.round());
+        let (right, bottom) = self.to_samples(
+            (bounds.x + bounds.width).round(),
+            (bounds.y + bounds.height).round(),
+        );
+        SampleRect {
+            x: (0, self.width as i32),
+            y: (0, (self.rows * SAMPLES) as i32),
+        }
+        .within((left, top), (right, bottom))
+    }
+
+    fn blend(&mut self, x: i32, y: i32, color: [f32; 4]) {
+        let sample = &mut self.samples[y as usize * self.width as usize + x as usize];
+        let alpha = color[3];
+        for channel in 0..3 {
+            sample[channel] = color[channel] * alpha + sample[channel] * (1.0 - alpha);
+        }
+        sample[3] = alpha + sample[3] * (1.0 - alpha);
+    }
+
+    fn fill_quad(&mut self, quad: &Quad, clip: SampleRect) {
+        let low = self.to_samples(quad.position[0], quad.position[1]);
+        let high = self.to_samples(
+            quad.position[0] + quad.size[0],
+            quad.position[1] + quad.size[1],
+        );
+        let area = clip.within(low, high);
+        for y in area.y.0..area.y.1 {
+            for x in area.x.0..area.x.1 {
+                self.blend(x, y, quad.color);
+            }
+        }
+    }
+
+    fn fill_mesh(&mut self, mesh: &Mesh<'_>, clip: SampleRect) {
+        let vertices = &mesh.buffers.vertices;
+        for triangle in mesh.buffers.indices.chunks_exact(3) {
+            let corner = |index: u32| {
+                let vertex = &vertices[index as usize];
+                let position = self.to_samples(
+                    vertex.position[0] + mesh.origin.x,
+                    vertex.position[1] + mesh.origin.y,
+                );
+                (position, vertex.color)
+            };
+            self.fill_triangle(
+                [
+                    corner(triangle[0]),
+                    corner(triangle[1]),
+                    corner(triangle[2]),
+                ],
+                clip,
+            );
+        }
+    }
+
+    /// Fills the samples whose centers lie inside the triangle, blending the colors of its
+    /// corners. Samples on an edge shared by two triangles are filled by one of them only.
+    fn fill_triangle(&mut self, corners: [((f32, f32), [f32; 4]); 3], clip: SampleRect) {
+        let [(a, color_a), (mut b, mut color_b), (mut c, mut color_c)] = corners;
+        let mut area = edge(a, b, c);
+        if area < 0.0 {
+            std::mem::swap(&mut b, &mut c);
+            std::mem::swap(&mut color_b, &mut color_c);
+            area = -area;
+        }
+        if !(area.is_finite() && area > 0.0) {
+            return;
+        }
+        let low = (a.0.min(b.0).min(c.0), a.1.min(b.1).min(c.1));
+        let high = (a.0.max(b.0).max(c.0), a.1.max(b.1).max(c.1));
+        let bounds = clip.within(low, high);
+        for y in bounds.y.0..bounds.y.1 {
+            for x in bounds.x.0..bounds.x.1 {
+                let center = (x as f32 + 0.5, y as f32 + 0.5);
+                let weights = [edge(b, c, center), edge(c, a, center), edge(a, b, center)];
+                if !(covers(weights[0], b, c)
+                    && covers(weights[1], c, a)
+                    && covers(weights[2], a, b))
+                {
+                    continue;
+                }
+                let mut color = [0.0; 4];
+                for (channel, value) in color.iter_mut().enumerate() {
+                    *value = (color_a[channel] * weights[0]
+                        + color_b[channel] * weights[1]
+                        + color_c[channel] * weights[2])
+                        / area;
+                }
+                self.blend(x, y, color);
+            }
+        }
+    }
+
+    /// Sets `text` in `font` and blends the coverage of its glyphs, laid out line by line
+    /// around its position like the renderer aligns it.
+    fn fill_text(&mut self, text: &Text<'_>, font: &FontRef<'_>, clip: SampleRect) {
+        let font = font.as_scaled(PxScale::from(text.size * SAMPLES as f32));
+        let line_height = font.height() + font.line_gap();
+        let lines: Vec<&str> = text.content.split('\n').collect();
+        let (x, y) = self.to_samples(text.bounds.x, text.bounds.y);
+        let block = line_height * lines.len() as f32;
+        let top = match text.vertical_alignment {
+            VerticalAlignment::Top => y,
+            VerticalAlignment::Center => y - block / 2.0,
+            VerticalAlignment::Bottom => y - block,
+        };
+        for (index, line) in lines.iter().enumerate() {
+            let mut glyphs = vec![];
+            let mut caret = 0.0;
+            let mut previous = None;
+            for character in line.chars() {
+                let id = font.glyph_id(character);
+                if let Some(previous) = previous {
+                    caret += font.kern(previous, id);
+                }
+                glyphs.push(id.with_scale_and_position(font.scale(), point(caret, 0.0)));
+                caret += font.h_advance(id);
+                previous = Some(id);
+            }
+            let left = match text.horizontal_alignment {
+                HorizontalAlignment::Left => x,
+                HorizontalAlignment::Center => x - caret / 2.0,
+                HorizontalAlignment::Right => x - caret,
+            };
+            let baseline = top + line_height * index as f32 + font.ascent();
+            for mut glyph in glyphs {
+                glyph.position = point(glyph.position.x + left, baseline);
+                let outline = match font.outline_glyph(glyph) {
+                    Some(outline) => outline,
+                    None => continue,
+                };
+                let origin = outline.px_bounds().min;
+                outline.draw(|column, row, coverage| {
+                    let x = origin.x as i32 + column as i32;
+                    let y = origin.y as i32 + row as i32;
+                    if (clip.x.0..clip.x.1).contains(&x) && (clip.y.0..clip.y.1).contains(&y) {
+                        let [r, g, b, a] = text.color;
+                        self.blend(x, y, [r, g, b, a * coverage.min(1.0)]);
+                    }
+                });
+            }
+        }
+    }
+
+    /// Averages the samples of every pixel into `pixels`, four sRGB bytes a pixel.
+    fn resolve(&self, pixels: &mut [u8]) {
+        let width = self.width / SAMPLES;
+        let count = (SAMPLES * SAMPLES) as f32;
+        for (index, pixel) in pixels.chunks_exact_mut(4).enumerate() {
+            let (column, row) = (index as u32 % width, index as u32 / width);
+            let mut sum = [0.0; 4];
+            for y in row * SAMPLES..(row + 1) * SAMPLES {
+                let start = (y * self.width + column * SAMPLES) as usize;
+                for sample in &self.samples[start..start + SAMPLES as usize] {
+                    for (total, value) in sum.iter_mut().zip(sample) {
+                        *total += value;
+                    }
+                }
+            }
+            let alpha = sum[3] / count;
+            for channel in 0..3 {
+                let linear = if alpha > 0.0 {
+                    sum[channel] / count / alpha
+                } else {
+                    0.0
+                };
+                pixel[channel] = to_byte(to_srgb(linear));
+            }
+            pixel[3] = to_byte(alpha);
+        }
+    }
+}
+
+/// Twice the signed area of the triangle `a`, `b`, `p`; positive if `p` lies to the right
+/// of the edge from `a` to `b` in canvas coordinates, whose y axis points down.
+fn edge(a: (f32, f32), b: (f32, f32), p: (f32, f32)) -> f32 {
+    (b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0)
+}
+
+/// Whether a sample with the edge function `weight` for the edge from `a` to `b` lies on
+/// the inner side. Samples on the edge count for only one of the two directions it can be
+/// walked in, so neighbouring triangles don't both fill them.
+fn covers(weight: f32, a: (f32, f32), b: (f32, f32)) -> bool {
+    weight > 0.0 || (weight == 0.0 && (b.1 > a.1 || (b.1 == a.1 && b.0 > a.0)))
+}
+
+fn to_srgb(linear: f32) -> f32 {
+    if linear <= 0.003_130_8 {
+        linear * 12.92
+    } else {
+        1.055 * linear.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+fn to_byte(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iced::canvas::{Frame, Path};
+    use iced::{Color, Point};
+
+    fn pixel(pixels: &[u8], width: u32, x: u32, y: u32) -> [u8; 4] {
+        let start = ((y * width + x) * 4) as usize;
+        [
+            pixels[start],
+            pixels[start + 1],
+            pixels[start + 2],
+            pixels[start + 3],
+        ]
+    }
+
+    #[test]
+    fn fills_meshes_without_seams() {
+        let mut frame = Frame::new(Size::new(40.0, 40.0));
+        frame.fill_rectangle(Point::new(10.0, 10.0), Size::new(20.0, 20.0), Color::BLACK);
+        let pixels = render(vec![frame.into_geometry()], 40, 40).unwrap();
+        assert_eq!(pixel(&pixels, 40, 5, 5), [0, 0, 0, 0]);
+        // The rectangle is two triangles; their shared diagonal is filled exactly once.
+        for y in 10..30 {
+            for x in 10..30 {
+                assert_eq!(pixel(&pixels, 40, x, y), [0, 0, 0, 255]);
+            }
+        }
+        assert_eq!(pixel(&pixels, 40, 35, 20), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn smooths_edges_and_blends_in_linear_light() {
+        let mut frame = Frame::new(Size::new(10.0, 10.0));
+        frame.fill_rectangle(Point::ORIGIN, Size::new(10.0, 10.0), Color::WHITE);
+        // Covers the left half of the pixel column at x = 5.
+        frame.fill_rectangle(Point::new(0.0, 0.0), Size::new(5.5, 10.0), Color::BLACK);
+        let pixels = render(vec![frame.into_geometry()], 10, 10).unwrap();
+        assert_eq!(pixel(&pixels, 10, 2, 5), [0, 0, 0, 255]);
+        assert_eq!(pixel(&pixels, 10, 8, 5), [255, 255, 255, 255]);
+        // Half of the white light is left, which is brighter than half the sRGB value.
+        assert_eq!(pixel(&pixels, 10, 5, 5), [188, 188, 188, 255]);
+    }
+
+    #[test]
+    fn later_geometry_lies_on_top() {
+        let size = Size::new(10.0, 10.0);
+        let mut below = Frame::new(size);
+        below.fill(&Path::circle(Point::new(5.0, 5.0), 4.0), Color::BLACK);
+        let mut above = Frame::new(size);
+        above.fill_rectangle(Point::ORIGIN, size, Color::from_rgb(1.0, 0.0, 0.0));
+        let pixels = render(vec![below.into_geometry(), above.into_geometry()], 10, 10).unwrap();
+        assert_eq!(pixel(&pixels, 10, 5, 5), [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn draws_text() {
+        let mut frame = Frame::new(Size::new(60.0, 30.0));
+        frame.fill_text(iced::canvas::Text {
+            content: String::from("Hi"),
+            position: Point::new(30.0, 15.0),
+            color: Color::BLACK,
+            size: 20.0,
+            horizontal_alignment: HorizontalAlignment::Center,
+            vertical_alignment: VerticalAlignment::Center,
+            ..iced::canvas::Text::default()
+        });
+        let pixels = render(vec![frame.into_geometry()], 60, 30).unwrap();
+        let inked = |columns: std::ops::Range<u32>| {
+            columns
+                .flat_map(|x| (0..30).map(move |y| (x, y)))
+                .filter(|(x, y)| pixel(&pixels, 60, *x, *y)[3] > 0)
+                .count()
+        };
+        // Centered on the canvas: ink on both sides of the middle, none at the borders.
+        assert!(inked(18..30) > 0);
+        assert!(inked(30..42) > 0);
+        assert_eq!(inked(0..10) + inked(50..60), 0);
+    }
+
+    #[test]
+    fn encodes_a_png() {
+        let mut frame = Frame::new(Size::new(3.0, 2.0));
+        frame.fill_rectangle(Point::ORIGIN, Size::new(3.0, 2.0), Color::BLACK);
+        let bytes = png(vec![frame.into_geometry()], Size::new(3.0, 2.0)).unwrap();
+        assert_eq!(&bytes[..8], b"\x89PNG\r\n\x1a\n");
+        // The width and height of the header chunk.
+        assert_eq!(&bytes[16..24], &[0, 0, 0, 3, 0, 0, 0, 2]);
+    }
+}
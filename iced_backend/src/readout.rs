@@ -0,0 +1,182 @@
+use crate::units::AxisUnits;
+use crate::{format_timestamp, Axis, TimeZone, ViewState};
+use iced::canvas::{Frame, Text};
+use iced::{Color, Point, Rectangle, Size};
+
+/// The distance between the readout box and the edges of the plotting area.
+const INSET: f32 = 4.0;
+/// The padding between the readout box and its text.
+const PADDING: f32 = 4.0;
+/// A rough average glyph width relative to the text size, used to size the box.
+const GLYPH_WIDTH: f32 = 0.6;
+
+/// The data coordinates under the cursor.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CursorPosition {
+    pub x: f64,
+    pub y: f64,
+    /// The value on the secondary y axis, if the view has one.
+    pub secondary_y: Option<f64>,
+}
+
+impl CursorPosition {
+    /// Locates `point` (relative to the canvas), or returns `None` outside the plotting area.
+    pub(crate) fn locate(view: &ViewState, plot: Rectangle, point: Point) -> Option<Self> {
+        if !plot.contains(point) {
+            return None;
+        }
+        let (x, y) = view.to_data(plot, point);
+        Some(Self {
+            x,
+            y,
+            secondary_y: view.to_secondary_y(plot, point),
+        })
+    }
+}
+
+/// The corner of the plotting area a readout or an inset is drawn in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Corner {
+    TopLeft,
+    #[default]
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// A live display of the cursor's data coordinates, drawn in a corner of the plot.
+pub struct Readout {
+    corner: Corner,
+    text_size: f32,
+    x_formatter: Box<dyn Fn(f64) -> String>,
+    y_formatter: Box<dyn Fn(f64) -> String>,
+    secondary_y_formatter: Box<dyn Fn(f64) -> String>,
+    time_x: bool,
+}
+
+impl Readout {
+    pub fn new() -> Self {
+        Self {
+            corner: Corner::default(),
+            text_size: 14.0,
+            x_formatter: Box::new(default_format),
+            y_formatter: Box::new(default_format),
+            secondary_y_formatter: Box::new(default_format),
+            time_x: false,
+        }
+    }
+
+    pub fn corner(mut self, corner: Corner) -> Self {
+        self.corner = corner;
+        self
+    }
+
+    pub fn text_size(mut self, size: f32) -> Self {
+        self.text_size = size;
+        self
+    }
+
+    pub fn x_formatter<F: 'static + Fn(f64) -> String>(mut self, f: F) -> Self {
+        self.x_formatter = Box::new(f);
+        self
+    }
+
+    pub fn y_formatter<F: 'static + Fn(f64) -> String>(mut self, f: F) -> Self {
+        self.y_formatter = Box::new(f);
+        self
+    }
+
+    pub fn secondary_y_formatter<F: 'static + Fn(f64) -> String>(mut self, f: F) -> Self {
+        self.secondary_y_formatter = Box::new(f);
+        self
+    }
+
+    /// Shows x as a Unix timestamp in the time zone of the widget, see
+    /// [`ChartWidget::time_zone`](crate::ChartWidget::time_zone), instead of formatting it
+    /// with the x formatter.
+    pub fn time_x(mut self) -> Self {
+        self.time_x = true;
+        self
+    }
+
+    /// Formats `position` with the per-axis formatters, one axis per line.
+    pub fn text(&self, position: &CursorPosition) -> String {
+        self.text_in(position, &AxisUnits::default(), TimeZone::Utc)
+    }
+
+    /// Like [`Readout::text`], but axes with units are shown in their active unit and
+    /// timestamps in `zone`.
+    pub(crate) fn text_in(
+        &self,
+        position: &CursorPosition,
+        units: &AxisUnits,
+        zone: TimeZone,
+    ) -> String {
+        let format = |axis, value, formatter: &dyn Fn(f64) -> String| match units.get(axis) {
+            Some(units) => units.format(value),
+            None => formatter(value),
+        };
+        let x = if self.time_x {
+            format_timestamp(position.x, zone)
+        } else {
+            format(Axis::X, position.x, &self.x_formatter)
+        };
+        let mut text = format!(
+            "x: {}\ny: {}",
+            x,
+            format(Axis::Y, position.y, &self.y_formatter)
+        );
+        if let Some(y) = position.secondary_y {
+            text.push_str("\ny2: ");
+            text.push_str(&format(Axis::SecondaryY, y, &self.secondary_y_formatter));
+        }
+        text
+    }
+
+    pub(crate) fn draw(
+        &self,
+        frame: &mut Frame,
+        plot: Rectangle,
+        position: &CursorPosition,
+        units: &AxisUnits,
+        zone: TimeZone,
+    ) {
+        let content = self.text_in(position, units, zone);
+        let lines = content.lines().count() as f32;
+        let columns = content
+            .lines()
+            .map(|l| l.chars().count())
+            .max()
+            .unwrap_or(0) as f32;
+        let size = Size::new(
+            columns * self.text_size * GLYPH_WIDTH + 2.0 * PADDING,
+            lines * self.text_size + 2.0 * PADDING,
+        );
+        let x = match self.corner {
+            Corner::TopLeft | Corner::BottomLeft => plot.x + INSET,
+            Corner::TopRight | Corner::BottomRight => plot.x + plot.width - INSET - size.width,
+        };
+        let y = match self.corner {
+            Corner::TopLeft | Corner::TopRight => plot.y + INSET,
+            Corner::BottomLeft | Corner::BottomRight => plot.y + plot.height - INSET - size.height,
+        };
+
+        frame.fill_rectangle(Point::new(x, y), size, Color::from_rgba(1.0, 1.0, 1.0, 0.8));
+        frame.fill_text(Text {
+            content,
+            position: Point::new(x + PADDING, y + PADDING),
+            size: self.text_size,
+            ..Text::default()
+        });
+    }
+}
+
+impl Default for Readout {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn default_format(value: f64) -> String {
+    format!("{:.3}", value)
+}
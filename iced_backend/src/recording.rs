@@ -0,0 +1,169 @@
+use crate::{Chart, ChartWidget};
+use iced::canvas::{Cursor, Event, Program};
+use iced::Rectangle;
+use std::time::{Duration, Instant};
+
+/// A canvas event delivered to a [`ChartWidget`] while recording.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RecordedEvent {
+    /// The time since the recording started.
+    pub at: Duration,
+    pub event: Event,
+    pub bounds: Rectangle,
+    pub cursor: Cursor,
+}
+
+/// The events a [`ChartWidget`] received between [`ChartWidget::start_recording`] and
+/// [`ChartWidget::stop_recording`], for reproducing interaction bugs and testing
+/// interactions:
+///
+/// ```ignore
+/// let messages = recording.replay(&mut widget);
+/// assert_eq!(widget.view_state().x.as_range(), 2.0..4.0);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Recording {
+    events: Vec<RecordedEvent>,
+}
+
+impl Recording {
+    pub fn new(events: Vec<RecordedEvent>) -> Self {
+        Self { events }
+    }
+
+    pub fn events(&self) -> &[RecordedEvent] {
+        &self.events
+    }
+
+    /// The time from the start of the recording to its last event.
+    pub fn duration(&self) -> Duration {
+        self.events.last().map_or(Duration::ZERO, |event| event.at)
+    }
+
+    /// Feeds the events to `widget` one after another and returns the messages it
+    /// produced.
+    ///
+    /// The widget draws before every event, as it would in an application, so the regions
+    /// its interactions depend on are laid out. The events arrive without delay, so
+    /// interactions timed by the clock, such as kinetic panning and double clicks, can
+    /// behave differently; use [`Recording::replay_in_real_time`] for those.
+    pub fn replay<Message, C: Chart>(&self, widget: &mut ChartWidget<Message, C>) -> Vec<Message> {
+        self.replay_with(widget, |_| ())
+    }
+
+    /// Like [`Recording::replay`], but waits between the events as long as they were apart
+    /// when recorded.
+    pub fn replay_in_real_time<Message, C: Chart>(
+        &self,
+        widget: &mut ChartWidget<Message, C>,
+    ) -> Vec<Message> {
+        let start = Instant::now();
+        self.replay_with(widget, |at| {
+            if let Some(wait) = at.checked_sub(start.elapsed()) {
+                std::thread::sleep(wait);
+            }
+        })
+    }
+
+    fn replay_with<Message, C: Chart>(
+        &self,
+        widget: &mut ChartWidget<Message, C>,
+        mut wait: impl FnMut(Duration),
+    ) -> Vec<Message> {
+        let mut messages = vec![];
+        for recorded in &self.events {
+            wait(recorded.at);
+            widget.draw(recorded.bounds, recorded.cursor);
+            messages.extend(widget.update(recorded.event, recorded.bounds, recorded.cursor));
+        }
+        messages
+    }
+}
+
+/// Collects the events of a recording in progress.
+#[derive(Debug)]
+pub(crate) struct Recorder {
+    started: Instant,
+    events: Vec<RecordedEvent>,
+}
+
+impl Recorder {
+    pub(crate) fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            events: vec![],
+        }
+    }
+
+    pub(crate) fn record(&mut self, event: Event, bounds: Rectangle, cursor: Cursor) {
+        self.events.push(RecordedEvent {
+            at: self.started.elapsed(),
+            event,
+            bounds,
+            cursor,
+        });
+    }
+
+    pub(crate) fn finish(self) -> Recording {
+        Recording::new(self.events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BuildContext, BuildError, IcedBackend, ViewChangeCause, ViewState};
+    use iced::{mouse, Point, Size};
+    use plotters::coord::Shift;
+    use plotters::drawing::DrawingArea;
+    use std::ops::Range;
+
+    /// A chart plotting nothing over the whole canvas.
+    struct Blank;
+
+    impl Chart for Blank {
+        fn build_chart(
+            &self,
+            ctx: &mut BuildContext<'_>,
+            root: DrawingArea<IcedBackend<'_>, Shift>,
+        ) -> Result<(), BuildError> {
+            ctx.set_plotting_area(&root);
+            Ok(())
+        }
+    }
+
+    /// A mouse event with the cursor at `x` on a 200x100 canvas.
+    fn mouse_at(millis: u64, event: mouse::Event, x: f32) -> RecordedEvent {
+        RecordedEvent {
+            at: Duration::from_millis(millis),
+            event: Event::Mouse(event),
+            bounds: Rectangle::new(Point::ORIGIN, Size::new(200.0, 100.0)),
+            cursor: Cursor::Available(Point::new(x, 50.0)),
+        }
+    }
+
+    fn moved(millis: u64, x: f32) -> RecordedEvent {
+        mouse_at(millis, mouse::Event::CursorMoved { x, y: 50.0 }, x)
+    }
+
+    #[test]
+    fn replays_a_drag_pan() {
+        let mut widget = ChartWidget::new(Blank)
+            .view(ViewState::new(0.0..10.0, 0.0..10.0))
+            .on_view_change(|view: ViewState, cause| (view.x.as_range(), cause));
+        let left = mouse::Button::Left;
+        let recording = Recording::new(vec![
+            moved(0, 100.0),
+            mouse_at(10, mouse::Event::ButtonPressed(left), 100.0),
+            moved(20, 75.0),
+            moved(30, 50.0),
+            mouse_at(40, mouse::Event::ButtonReleased(left), 50.0),
+        ]);
+        let messages: Vec<(Range<f64>, ViewChangeCause)> = recording.replay(&mut widget);
+        // Dragging a quarter of the width to the left shows a quarter of the range further
+        // right.
+        assert_eq!(widget.view_state().x.as_range(), 2.5..12.5);
+        assert_eq!(widget.view_state().y.as_range(), 0.0..10.0);
+        assert_eq!(messages.last(), Some(&(2.5..12.5, ViewChangeCause::User)));
+    }
+}
@@ -0,0 +1,189 @@
+use crate::{Axis, ViewState};
+use iced::canvas::{Frame, Path, Stroke, Text};
+use iced::{Color, HorizontalAlignment, Point, Rectangle, VerticalAlignment};
+
+/// A line marking a fixed value on one axis, such as an alarm threshold.
+///
+/// Lines on [`Axis::X`] are vertical, lines on the y axes horizontal. Draggable lines can be
+/// grabbed with the left mouse button; see [`ChartWidget::on_reference_move`] for the
+/// resulting messages.
+///
+/// [`ChartWidget::on_reference_move`]: crate::ChartWidget::on_reference_move
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReferenceLine {
+    axis: Axis,
+    value: f64,
+    min: f64,
+    max: f64,
+    snap: Option<f64>,
+    draggable: bool,
+    color: Color,
+    width: f32,
+    label: Option<String>,
+}
+
+impl ReferenceLine {
+    pub fn new(axis: Axis, value: f64) -> Self {
+        Self {
+            axis,
+            value,
+            min: f64::NEG_INFINITY,
+            max: f64::INFINITY,
+            snap: None,
+            draggable: true,
+            color: Color::from_rgb(0.8, 0.1, 0.1),
+            width: 1.5,
+            label: None,
+        }
+    }
+
+    /// Keeps the value within `min..=max` while dragging.
+    pub fn bounds(mut self, min: f64, max: f64) -> Self {
+        self.min = min;
+        self.max = max;
+        self.value = self.value.clamp(min, max);
+        self
+    }
+
+    /// Snaps dragged values to multiples of `step`.
+    pub fn snap(mut self, step: f64) -> Self {
+        self.snap = Some(step).filter(|step| *step > 0.0);
+        self
+    }
+
+    pub fn draggable(mut self, draggable: bool) -> Self {
+        self.draggable = draggable;
+        self
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Draws `label` at the end of the line.
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn axis(&self) -> Axis {
+        self.axis
+    }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn is_draggable(&self) -> bool {
+        self.draggable
+    }
+
+    /// Moves the line to `value`, snapped and kept within its bounds, returning whether the
+    /// value changed.
+    pub fn set_value(&mut self, value: f64) -> bool {
+        let value = match self.snap {
+            Some(step) => (value / step).round() * step,
+            None => value,
+        };
+        let value = value.clamp(self.min, self.max);
+        if value == self.value {
+            return false;
+        }
+        self.value = value;
+        true
+    }
+
+    /// The position of the line in canvas pixels: an x coordinate for vertical lines, a y
+    /// coordinate otherwise. `None` if the line's axis is not part of the view.
+    pub(crate) fn pixel(&self, view: &ViewState, plot: Rectangle) -> Option<f32> {
+        match self.axis {
+            Axis::X => Some(plot.x + view.x.fraction(self.value) as f32 * plot.width),
+            Axis::Y => {
+                Some(plot.y + plot.height - view.y.fraction(self.value) as f32 * plot.height)
+            }
+            Axis::SecondaryY => view
+                .secondary_y
+                .map(|y| plot.y + plot.height - y.fraction(self.value) as f32 * plot.height),
+        }
+    }
+
+    /// The value under `point` along the line's axis.
+    pub(crate) fn value_at(&self, view: &ViewState, plot: Rectangle, point: Point) -> Option<f64> {
+        match self.axis {
+            Axis::X => Some(view.x.value_at(f64::from((point.x - plot.x) / plot.width))),
+            Axis::Y => Some(view.to_data(plot, point).1),
+            Axis::SecondaryY => view.to_secondary_y(plot, point),
+        }
+    }
+
+    /// The distance in pixels between `point` and the line, if the line is visible.
+    pub(crate) fn distance(&self, view: &ViewState, plot: Rectangle, point: Point) -> Option<f32> {
+        let at = self.pixel(view, plot)?;
+        match self.axis {
+            Axis::X if at >= plot.x && at <= plot.x + plot.width => Some((point.x - at).abs()),
+            Axis::Y | Axis::SecondaryY if at >= plot.y && at <= plot.y + plot.height => {
+                Some((point.y - at).abs())
+            }
+            _ => None,
+        }
+    }
+
+    pub(crate) fn draw(&self, frame: &mut Frame, view: &ViewState, plot: Rectangle, active: bool) {
+        let at = match self.pixel(view, plot) {
+            Some(at) => at,
+            None => return,
+        };
+        let (from, to) = match self.axis {
+            Axis::X if at >= plot.x && at <= plot.x + plot.width => {
+                (Point::new(at, plot.y), Point::new(at, plot.y + plot.height))
+            }
+            Axis::Y | Axis::SecondaryY if at >= plot.y && at <= plot.y + plot.height => {
+                (Point::new(plot.x, at), Point::new(plot.x + plot.width, at))
+            }
+            _ => return,
+        };
+        let width = if active { self.width * 2.0 } else { self.width };
+        frame.stroke(
+            &Path::line(from, to),
+            Stroke {
+                color: self.color,
+                width,
+                ..Stroke::default()
+            },
+        );
+        if let Some(label) = &self.label {
+            let (position, horizontal_alignment, vertical_alignment) = match self.axis {
+                Axis::X => (
+                    Point::new(at + 4.0, plot.y + 4.0),
+                    HorizontalAlignment::Left,
+                    VerticalAlignment::Top,
+                ),
+                Axis::Y => (
+                    Point::new(plot.x + 4.0, at - 2.0),
+                    HorizontalAlignment::Left,
+                    VerticalAlignment::Bottom,
+                ),
+                Axis::SecondaryY => (
+                    Point::new(plot.x + plot.width - 4.0, at - 2.0),
+                    HorizontalAlignment::Right,
+                    VerticalAlignment::Bottom,
+                ),
+            };
+            frame.fill_text(Text {
+                content: label.clone(),
+                position,
+                color: self.color,
+                size: 14.0,
+                horizontal_alignment,
+                vertical_alignment,
+                ..Text::default()
+            });
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use crate::{Colormap, FrameLike, IcedBackend, MarkerShape};
+use iced::Color;
+use plotters::element::{Drawable, PointCollection};
+use plotters::style::RGBColor;
+use plotters_backend::{BackendCoord, DrawingErrorKind};
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// How overlapping points of a [`Scatter`] combine.
+///
+/// The canvas can only blend by painting over, so [`BlendMode::Additive`] and
+/// [`BlendMode::Multiply`] are emulated: points are gathered into cells one marker wide,
+/// the colors of the points in a cell are combined, and every cell is drawn as a single
+/// marker over what lies below. Dense regions thus show up at the cost of snapping
+/// points to the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlendMode {
+    /// Later points are painted over earlier ones.
+    #[default]
+    Normal,
+    /// Overlapping points add up their light, so dense regions grow brighter.
+    Additive,
+    /// Overlapping points filter each other, so dense regions grow darker.
+    Multiply,
+}
+
+/// A scatter plot of many points, each with its own color and size, drawn as one
+/// [`MarkerShape`] into a single mesh rather than one path per point.
+///
+/// It only draws onto an [`IcedBackend`], through `chart.plotting_area().draw(&scatter)`.
+/// Use `PointSeries` for small series that need a different backend.
+///
+/// The corners of every marker are written straight into one vertex buffer with their
+/// color, without tessellating paths, so millions of points stay interactive. Circles
+/// take up to 64 vertices each, so prefer squares for the largest plots.
+/// [`MarkerShape::Custom`] outlines are still filled as one path per color.
+pub struct Scatter<Coord> {
+    positions: Vec<Coord>,
+    styles: Vec<(Color, f32)>,
+    shape: MarkerShape,
+    blend: BlendMode,
+}
+
+impl<Coord> Scatter<Coord> {
+    /// Square points of individual colors and half widths in pixels.
+    pub fn new<I: IntoIterator<Item = (Coord, Color, f32)>>(points: I) -> Self {
+        let (positions, styles) = points
+            .into_iter()
+            .map(|(position, color, size)| (position, (color, size)))
+            .unzip();
+        Self {
+            positions,
+            styles,
+            shape: MarkerShape::Square,
+            blend: BlendMode::Normal,
+        }
+    }
+
+    /// Square points that all share `color` and the half width `size`.
+    pub fn uniform<I: IntoIterator<Item = Coord>>(positions: I, color: Color, size: f32) -> Self {
+        let positions: Vec<_> = positions.into_iter().collect();
+        let styles = vec![(color, size); positions.len()];
+        Self {
+            positions,
+            styles,
+            shape: MarkerShape::Square,
+            blend: BlendMode::Normal,
+        }
+    }
+
+    /// Points of the half width `size` colored by their value on `colormap`, scaled so
+    /// `range` spans the whole map.
+    pub fn by_value<I>(points: I, colormap: &Colormap, range: Range<f64>, size: f32) -> Self
+    where
+        I: IntoIterator<Item = (Coord, f64)>,
+    {
+        Self::new(points.into_iter().map(|(position, value)| {
+            let RGBColor(r, g, b) = colormap.map(value, range.clone());
+            (position, Color::from_rgb8(r, g, b), size)
+        }))
+    }
+
+    /// Draws every point as `shape`, e.g. to tell several scatter series apart.
+    pub fn shape(mut self, shape: MarkerShape) -> Self {
+        self.shape = shape;
+        self
+    }
+
+    /// Sets how overlapping points combine, e.g. [`BlendMode::Additive`] to reveal the
+    /// density of heavily overplotted data.
+    pub fn blend(mut self, mode: BlendMode) -> Self {
+        self.blend = mode;
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+}
+
+impl<'a, Coord: 'a> PointCollection<'a, Coord> for &'a Scatter<Coord> {
+    type Point = &'a Coord;
+    type IntoIter = &'a [Coord];
+    fn point_iter(self) -> &'a [Coord] {
+        &self.positions
+    }
+}
+
+impl<'b, F: FrameLike, Coord> Drawable<IcedBackend<'b, F>> for Scatter<Coord> {
+    fn draw<I: Iterator<Item = BackendCoord>>(
+        &self,
+        points: I,
+        backend: &mut IcedBackend<'b, F>,
+        _: (u32, u32),
+    ) -> Result<(), DrawingErrorKind<crate::IcedError>> {
+        let markers = points
+            .zip(&self.styles)
+            .map(|(center, &(color, size))| (center, color, size));
+        match self.blend {
+            BlendMode::Normal => backend.draw_markers(self.shape, markers),
+            mode => backend.draw_markers(self.shape, combine(mode, markers)),
+        }
+        Ok(())
+    }
+}
+
+/// The color accumulated in one cell: the premultiplied sum or product of the colors,
+/// and the alpha of all points in the cell painted over each other.
+struct Cell {
+    color: [f32; 3],
+    transparency: f32,
+    size: f32,
+}
+
+/// Merges the markers in every cell one marker wide into one marker colored by `mode`.
+fn combine<I>(mode: BlendMode, markers: I) -> Vec<(BackendCoord, Color, f32)>
+where
+    I: Iterator<Item = (BackendCoord, Color, f32)>,
+{
+    let mut cells: HashMap<(i32, i32), Cell> = HashMap::new();
+    for ((x, y), color, size) in markers {
+        let width = (2.0 * size).max(1.0);
+        let key = (
+            (x as f32 / width).floor() as i32,
+            (y as f32 / width).floor() as i32,
+        );
+        let start = match mode {
+            BlendMode::Multiply => [1.0; 3],
+            _ => [0.0; 3],
+        };
+        let cell = cells.entry(key).or_insert(Cell {
+            color: start,
+            transparency: 1.0,
+            size,
+        });
+        let channels = [color.r, color.g, color.b];
+        for (value, channel) in cell.color.iter_mut().zip(&channels) {
+            *value = match mode {
+                BlendMode::Multiply => *value * (1.0 - color.a + color.a * channel),
+                _ => *value + color.a * channel,
+            };
+        }
+        cell.transparency *= 1.0 - color.a;
+        cell.size = cell.size.max(size);
+    }
+
+    cells
+        .into_iter()
+        .map(|((column, row), cell)| {
+            let width = (2.0 * cell.size).max(1.0);
+            let center = (
+                ((column as f32 + 0.5) * width) as i32,
+                ((row as f32 + 0.5) * width) as i32,
+            );
+            let alpha = 1.0 - cell.transparency;
+            let [r, g, b] = match mode {
+                // The summed light is premultiplied; dividing by the alpha it is drawn
+                // with keeps its brightness.
+                BlendMode::Additive if alpha > 0.0 => cell.color.map(|c| (c / alpha).min(1.0)),
+                _ => cell.color.map(|c| c.min(1.0)),
+            };
+            (center, Color::from_rgba(r, g, b, alpha), cell.size)
+        })
+        .collect()
+}
@@ -0,0 +1,134 @@
+use crate::clip::Envelope;
+use iced::{Point, Rectangle as Bounds};
+use plotters::coord::ranged1d::Ranged;
+use plotters::coord::CoordTranslate;
+use plotters::drawing::{DrawingArea, DrawingAreaErrorKind};
+use plotters::element::{Polygon, Rectangle};
+use plotters::prelude::Cartesian2d;
+use plotters::style::ShapeStyle;
+use plotters_backend::{BackendCoord, DrawingBackend};
+use std::ops::Range;
+
+type ShadingResult<DB> = Result<(), DrawingAreaErrorKind<<DB as DrawingBackend>::ErrorType>>;
+
+/// Shades the vertical band `x` of `area` (usually `chart.plotting_area()`) across its
+/// full height, e.g. to mark night hours on a time axis.
+///
+/// Call it before drawing the series so the band lies under the data. The part of the band
+/// outside the plotting area is cut off.
+pub fn shade_x_band<DB, X, Y, S>(
+    area: &DrawingArea<DB, Cartesian2d<X, Y>>,
+    x: Range<X::ValueType>,
+    style: S,
+) -> ShadingResult<DB>
+where
+    DB: DrawingBackend,
+    X: Ranged,
+    Y: Ranged,
+    S: Into<ShapeStyle>,
+{
+    let from = area.map_coordinate(&(x.start, area.get_y_range().start)).0;
+    let to = area.map_coordinate(&(x.end, area.get_y_range().start)).0;
+    let (_, ys) = area.get_pixel_range();
+    fill_rect(area, (from, ys.start), (to, ys.end), style)
+}
+
+/// Shades the horizontal band `y` of `area` across its full width, e.g. a tolerance band
+/// of ±σ around a target value.
+pub fn shade_y_band<DB, X, Y, S>(
+    area: &DrawingArea<DB, Cartesian2d<X, Y>>,
+    y: Range<Y::ValueType>,
+    style: S,
+) -> ShadingResult<DB>
+where
+    DB: DrawingBackend,
+    X: Ranged,
+    Y: Ranged,
+    S: Into<ShapeStyle>,
+{
+    let from = area.map_coordinate(&(area.get_x_range().start, y.start)).1;
+    let to = area.map_coordinate(&(area.get_x_range().start, y.end)).1;
+    let (xs, _) = area.get_pixel_range();
+    fill_rect(area, (xs.start, from), (xs.end, to), style)
+}
+
+/// Shades the area between two series, such as the lower and upper bounds of a confidence
+/// interval, clipped to the plotting area.
+///
+/// The region is the polygon running along `upper` and back along `lower`, so both series
+/// should be ordered by x.
+pub fn shade_between<DB, CT, S>(
+    area: &DrawingArea<DB, CT>,
+    upper: &[CT::From],
+    lower: &[CT::From],
+    style: S,
+) -> ShadingResult<DB>
+where
+    DB: DrawingBackend,
+    CT: CoordTranslate,
+    S: Into<ShapeStyle>,
+{
+    let outline: Vec<Point> = upper
+        .iter()
+        .chain(lower.iter().rev())
+        .map(|point| to_point(area.map_coordinate(point)))
+        .collect();
+    let clipped = Envelope::of(pixel_bounds(area)).clip_polygon(&outline);
+    if clipped.len() < 3 {
+        return Ok(());
+    }
+    let vertices = clipped
+        .into_iter()
+        .map(|point| (point.x.round() as i32, point.y.round() as i32))
+        .collect::<Vec<_>>();
+    area.use_screen_coord().draw(&Polygon::new(vertices, style))
+}
+
+/// Fills the rectangle spanned by two backend corners, cut to the pixel range of `area`.
+///
+/// `area` draws in its own coordinates, so the rectangle goes through a view of it that
+/// takes backend coordinates as they are.
+fn fill_rect<DB, CT, S>(
+    area: &DrawingArea<DB, CT>,
+    a: BackendCoord,
+    b: BackendCoord,
+    style: S,
+) -> ShadingResult<DB>
+where
+    DB: DrawingBackend,
+    CT: CoordTranslate,
+    S: Into<ShapeStyle>,
+{
+    let (xs, ys) = area.get_pixel_range();
+    let left = a.0.min(b.0).max(xs.start);
+    let right = a.0.max(b.0).min(xs.end);
+    let top = a.1.min(b.1).max(ys.start);
+    let bottom = a.1.max(b.1).min(ys.end);
+    if left >= right || top >= bottom {
+        return Ok(());
+    }
+    let style = style.into();
+    area.use_screen_coord().draw(&Rectangle::new(
+        [(left, top), (right, bottom)],
+        ShapeStyle {
+            filled: true,
+            ..style
+        },
+    ))
+}
+
+pub(crate) fn pixel_bounds<DB: DrawingBackend, CT: CoordTranslate>(
+    area: &DrawingArea<DB, CT>,
+) -> Bounds {
+    let (x, y) = area.get_pixel_range();
+    Bounds {
+        x: x.start as f32,
+        y: y.start as f32,
+        width: (x.end - x.start) as f32,
+        height: (y.end - y.start) as f32,
+    }
+}
+
+pub(crate) fn to_point((x, y): BackendCoord) -> Point {
+    Point::new(x as f32, y as f32)
+}
@@ -0,0 +1,84 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock, RwLockReadGuard};
+
+/// Points shared between the threads producing them and the chart drawing them.
+///
+/// Clones are handles to the same points. Producers append to their handle from any thread;
+/// every change bumps a version counter, which a [`ChartWidget::watch`]ing widget compares
+/// to redraw the chart only when the points changed, without copying them into messages:
+///
+/// ```ignore
+/// let series = SharedSeries::new();
+/// let producer = series.clone();
+/// std::thread::spawn(move || loop {
+///     producer.push(read_sensor());
+/// });
+/// let widget = ChartWidget::new(SensorChart { series: series.clone() }).watch(&series);
+/// ```
+///
+/// The chart reads the points with [`SharedSeries::read`] while it is built.
+///
+/// [`ChartWidget::watch`]: crate::ChartWidget::watch
+#[derive(Debug, Clone, Default)]
+pub struct SharedSeries {
+    points: Arc<RwLock<Vec<(f64, f64)>>>,
+    version: Arc<AtomicU64>,
+}
+
+impl SharedSeries {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_points(points: Vec<(f64, f64)>) -> Self {
+        Self {
+            points: Arc::new(RwLock::new(points)),
+            version: Arc::default(),
+        }
+    }
+
+    pub fn push(&self, point: (f64, f64)) {
+        self.write(|points| points.push(point));
+    }
+
+    pub fn extend(&self, points: impl IntoIterator<Item = (f64, f64)>) {
+        self.write(|all| all.extend(points));
+    }
+
+    /// Replaces all points.
+    pub fn replace(&self, points: Vec<(f64, f64)>) {
+        self.write(|all| *all = points);
+    }
+
+    /// Drops the oldest points beyond the last `count`, e.g. to keep a rolling window.
+    pub fn retain_last(&self, count: usize) {
+        self.write(|points| {
+            let excess = points.len().saturating_sub(count);
+            points.drain(..excess);
+        });
+    }
+
+    /// Changes the points in place and bumps the version.
+    pub fn write<R>(&self, f: impl FnOnce(&mut Vec<(f64, f64)>) -> R) -> R {
+        let mut points = self
+            .points
+            .write()
+            .unwrap_or_else(|error| error.into_inner());
+        let result = f(&mut points);
+        self.version.fetch_add(1, Ordering::Release);
+        result
+    }
+
+    /// Locks the points for reading. Producers wait until the guard is dropped, so hold it
+    /// only while drawing.
+    pub fn read(&self) -> RwLockReadGuard<'_, Vec<(f64, f64)>> {
+        self.points
+            .read()
+            .unwrap_or_else(|error| error.into_inner())
+    }
+
+    /// A counter bumped by every change.
+    pub fn version(&self) -> u64 {
+        self.version.load(Ordering::Acquire)
+    }
+}
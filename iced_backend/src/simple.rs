@@ -0,0 +1,175 @@
+use crate::{
+    AxisScale, BuildContext, BuildError, Chart, ChartSpec, ChartWidget, IcedBackend,
+    InteractionSpec, SeriesKind, SeriesSpec,
+};
+use plotters::coord::Shift;
+use plotters::drawing::DrawingArea;
+use std::marker::PhantomData;
+
+/// The kind of series a [`SimpleChart`] draws.
+pub trait SimpleKind {
+    const KIND: SeriesKind;
+}
+
+/// Marks a [`LineChart`].
+pub enum Lines {}
+
+/// Marks a [`ScatterChart`].
+pub enum Points {}
+
+/// Marks an [`AreaChart`].
+pub enum Areas {}
+
+impl SimpleKind for Lines {
+    const KIND: SeriesKind = SeriesKind::Line;
+}
+
+impl SimpleKind for Points {
+    const KIND: SeriesKind = SeriesKind::Points;
+}
+
+impl SimpleKind for Areas {
+    const KIND: SeriesKind = SeriesKind::Area;
+}
+
+/// A line chart, see [`SimpleChart`].
+pub type LineChart = SimpleChart<Lines>;
+
+/// A scatter plot, see [`SimpleChart`].
+pub type ScatterChart = SimpleChart<Points>;
+
+/// An area chart, see [`SimpleChart`].
+pub type AreaChart = SimpleChart<Areas>;
+
+/// A chart of one kind of series, set up in a few calls without the plotters
+/// `ChartBuilder`:
+///
+/// ```ignore
+/// let chart = LineChart::new()
+///     .series("temp", readings)
+///     .x_label("t")
+///     .with_legend()
+///     .into_widget();
+/// ```
+///
+/// Every series takes its color from the default [`Palette`](crate::Palette) unless set with
+/// [`SimpleChart::color`]. The visible ranges span the data unless set. It is drawn from
+/// a [`ChartSpec`], which [`SimpleChart::into_spec`] hands out for anything the builder
+/// does not cover.
+pub struct SimpleChart<K> {
+    spec: ChartSpec,
+    kind: PhantomData<K>,
+}
+
+impl<K: SimpleKind> SimpleChart<K> {
+    pub fn new() -> Self {
+        Self {
+            spec: ChartSpec::default(),
+            kind: PhantomData,
+        }
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.spec.title = Some(title.into());
+        self
+    }
+
+    /// Adds a series of `(x, y)` points named `name` in the legend.
+    pub fn series(
+        mut self,
+        name: impl Into<String>,
+        data: impl IntoIterator<Item = (f64, f64)>,
+    ) -> Self {
+        self.spec.series.push(SeriesSpec {
+            name: Some(name.into()),
+            kind: K::KIND,
+            data: data.into_iter().collect(),
+            ..SeriesSpec::default()
+        });
+        self
+    }
+
+    /// Sets the color of the last added series, as `#rrggbb`.
+    pub fn color(mut self, color: impl Into<String>) -> Self {
+        if let Some(series) = self.spec.series.last_mut() {
+            series.color = Some(color.into());
+        }
+        self
+    }
+
+    pub fn x_label(mut self, label: impl Into<String>) -> Self {
+        self.spec.x_axis.label = Some(label.into());
+        self
+    }
+
+    pub fn y_label(mut self, label: impl Into<String>) -> Self {
+        self.spec.y_axis.label = Some(label.into());
+        self
+    }
+
+    /// Sets the initially visible x range.
+    pub fn x_range(mut self, start: f64, end: f64) -> Self {
+        self.spec.x_axis.range = Some((start, end));
+        self
+    }
+
+    /// Sets the initially visible y range.
+    pub fn y_range(mut self, start: f64, end: f64) -> Self {
+        self.spec.y_axis.range = Some((start, end));
+        self
+    }
+
+    /// Draws x on a logarithmic scale.
+    pub fn log_x(mut self) -> Self {
+        self.spec.x_axis.scale = AxisScale::Log;
+        self
+    }
+
+    /// Draws y on a logarithmic scale.
+    pub fn log_y(mut self) -> Self {
+        self.spec.y_axis.scale = AxisScale::Log;
+        self
+    }
+
+    /// Lists the series in the top right corner of the plotting area.
+    pub fn with_legend(mut self) -> Self {
+        self.spec.legend = true;
+        self
+    }
+
+    pub fn interactions(mut self, interactions: InteractionSpec) -> Self {
+        self.spec.interactions = interactions;
+        self
+    }
+
+    pub fn spec(&self) -> &ChartSpec {
+        &self.spec
+    }
+
+    pub fn into_spec(self) -> ChartSpec {
+        self.spec
+    }
+
+    /// A widget drawing the chart, showing the ranges set or the extent of the data.
+    pub fn into_widget<Message>(self) -> ChartWidget<Message, Self> {
+        let view = self.spec.view();
+        let interactions = self.spec.interactions;
+        interactions.apply(ChartWidget::new(self).view(view))
+    }
+}
+
+impl<K: SimpleKind> Default for SimpleChart<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K> Chart for SimpleChart<K> {
+    fn build_chart(
+        &self,
+        ctx: &mut BuildContext<'_>,
+        root: DrawingArea<IcedBackend<'_>, Shift>,
+    ) -> Result<(), BuildError> {
+        self.spec.build_chart(ctx, root)
+    }
+}
@@ -0,0 +1,377 @@
+use crate::{
+    AxisLock, AxisScale, BuildContext, BuildError, Chart, ChartWidget, IcedBackend, Palette,
+    Readout, ScrollAction, SeriesId, SeriesStyle, ViewState,
+};
+use plotters::chart::{ChartBuilder, SeriesLabelPosition};
+use plotters::coord::combinators::LogRange;
+use plotters::coord::ranged1d::{AsRangedCoord, ValueFormatter};
+use plotters::coord::Shift;
+use plotters::drawing::DrawingArea;
+use plotters::element::Polygon;
+use plotters::style::{Color, IntoFont, BLACK, WHITE};
+use std::ops::Range;
+
+/// The share of the data's extent added on both sides of an axis without a range.
+const PADDING: f64 = 0.05;
+
+/// A chart described by data rather than code, e.g. loaded from a config file or sent by
+/// a server. With the `serde` feature it can be deserialized from JSON, TOML or any other
+/// serde format; every field but `series` may be left out.
+///
+/// The spec is itself the [`Chart`] drawing it: [`ChartSpec::into_widget`] wraps it in a
+/// widget showing its axes and interactions.
+///
+/// ```json
+/// {
+///     "title": "Temperature",
+///     "x_axis": { "label": "hour" },
+///     "y_axis": { "label": "°C", "range": [-10, 40] },
+///     "series": [{ "name": "Berlin", "kind": "Line", "data": [[0, 3.5], [1, 4.0]] }],
+///     "interactions": { "axis_lock": "XOnly" }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ChartSpec {
+    pub title: Option<String>,
+    pub x_axis: AxisSpec,
+    pub y_axis: AxisSpec,
+    pub series: Vec<SeriesSpec>,
+    /// Lists the named series in the top right corner of the plotting area.
+    pub legend: bool,
+    pub interactions: InteractionSpec,
+}
+
+/// One axis of a [`ChartSpec`].
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct AxisSpec {
+    pub label: Option<String>,
+    /// The initially visible range, or the extent of the data if not set.
+    pub range: Option<(f64, f64)>,
+    /// Linear or logarithmic; symlog axes cannot be drawn from a spec.
+    pub scale: AxisScale,
+}
+
+/// How a [`SeriesSpec`] is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SeriesKind {
+    /// A line through the points in their order.
+    #[default]
+    Line,
+    /// A marker at every point.
+    Points,
+    /// A line with the area between it and zero, or the bottom of a logarithmic axis,
+    /// filled.
+    Area,
+}
+
+/// One series of a [`ChartSpec`].
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct SeriesSpec {
+    /// The name shown in the legend; unnamed series are left out of it.
+    pub name: Option<String>,
+    pub kind: SeriesKind,
+    /// The `[x, y]` points of the series.
+    pub data: Vec<(f64, f64)>,
+    /// The color as `#rrggbb`, or the series' color in the default [`Palette`].
+    pub color: Option<String>,
+    /// The line width or marker size in pixels, or 2 and 4.
+    pub width: Option<u32>,
+}
+
+/// The interactions of a chart drawn from a [`ChartSpec`], mapped onto the builder
+/// methods of [`ChartWidget`] of the same names. The defaults are the widget's.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct InteractionSpec {
+    pub axis_lock: AxisLock,
+    pub kinetic_panning: bool,
+    pub snap_to_ticks: bool,
+    /// Shows the cursor's data coordinates in a corner of the plotting area.
+    pub readout: bool,
+    /// What scrolling a mouse wheel does.
+    pub wheel: ScrollAction,
+    /// What scrolling a trackpad does.
+    pub trackpad: ScrollAction,
+}
+
+impl Default for InteractionSpec {
+    fn default() -> Self {
+        Self {
+            axis_lock: AxisLock::Free,
+            kinetic_panning: false,
+            snap_to_ticks: false,
+            readout: false,
+            wheel: ScrollAction::Zoom,
+            trackpad: ScrollAction::Pan,
+        }
+    }
+}
+
+impl InteractionSpec {
+    /// Sets up `widget` with these interactions.
+    pub(crate) fn apply<Message, C: Chart>(
+        &self,
+        widget: ChartWidget<Message, C>,
+    ) -> ChartWidget<Message, C> {
+        let widget = widget
+            .axis_lock(self.axis_lock)
+            .kinetic_panning(self.kinetic_panning)
+            .snap_to_ticks(self.snap_to_ticks)
+            .scroll_actions(self.wheel, self.trackpad);
+        if self.readout {
+            widget.readout(Readout::new())
+        } else {
+            widget
+        }
+    }
+}
+
+impl ChartSpec {
+    /// Parses a spec from JSON.
+    #[cfg(all(feature = "serde", feature = "json"))]
+    pub fn from_json(json: &str) -> Result<Self, BuildError> {
+        serde_json::from_str(json).map_err(Into::into)
+    }
+
+    /// The ranges and scales of the axes, spanning the data along axes without a range.
+    pub fn view(&self) -> ViewState {
+        let x = self
+            .x_axis
+            .range_or(self.series.iter().flat_map(|s| &s.data).map(|p| p.0));
+        let y = self
+            .y_axis
+            .range_or(self.series.iter().flat_map(|s| &s.data).map(|p| p.1));
+        ViewState::new(x, y)
+            .with_x_scale(self.x_axis.scale)
+            .with_y_scale(self.y_axis.scale)
+    }
+
+    /// A widget drawing the spec, showing its [`ChartSpec::view`] and set up with its
+    /// interactions.
+    pub fn into_widget<Message>(self) -> ChartWidget<Message, ChartSpec> {
+        let view = self.view();
+        let interactions = self.interactions;
+        interactions.apply(ChartWidget::new(self).view(view))
+    }
+
+    fn draw<X, Y>(
+        &self,
+        ctx: &mut BuildContext<'_>,
+        root: &DrawingArea<IcedBackend<'_>, Shift>,
+        x: X,
+        y: Y,
+    ) -> Result<(), BuildError>
+    where
+        X: AsRangedCoord<Value = f64>,
+        Y: AsRangedCoord<Value = f64>,
+        X::CoordDescType: ValueFormatter<f64>,
+        Y::CoordDescType: ValueFormatter<f64>,
+    {
+        // Pixel sizes grow with the supersampling factor, so the chart looks the same.
+        let px = ctx.supersampling();
+        let font = |size: u32| ("sans-serif", f64::from(size * px)).into_font();
+        let names: Vec<&str> = self
+            .series
+            .iter()
+            .filter_map(|s| s.name.as_deref())
+            .collect();
+        // The widget's own legend lists the series whatever `self.legend` says.
+        let legend = if (self.legend || ctx.draws_legend()) && !names.is_empty() {
+            Some(ctx.legend_size(root, &names, &font(12).into())?)
+        } else {
+            None
+        };
+        let position = ctx.legend_position();
+        let (right, bottom) = legend.map_or((0, 0), |size| position.margins(size));
+        let (x, y): (X::CoordDescType, Y::CoordDescType) = (x.into(), y.into());
+        let (x_desc, y_desc) = (self.x_axis.label.as_deref(), self.y_axis.label.as_deref());
+        let label_font = font(12).into();
+        let x_label_area = ctx.x_label_area_size(
+            root,
+            &x,
+            &label_font,
+            x_desc,
+            if x_desc.is_some() { 40 } else { 30 },
+        )?;
+        let y_label_area = ctx.y_label_area_size(
+            root,
+            &y,
+            &label_font,
+            y_desc,
+            if y_desc.is_some() { 70 } else { 50 },
+        )?;
+        let margin = ctx.margin(10);
+        let mut builder = ChartBuilder::on(root);
+        builder
+            .margin(margin)
+            .margin_right(margin + right)
+            .margin_bottom(margin + bottom)
+            .x_label_area_size(x_label_area)
+            .y_label_area_size(y_label_area);
+        if let Some(title) = &self.title {
+            builder.caption(title, font(20));
+        }
+        let mut chart = builder.build_cartesian_2d(x, y)?;
+        ctx.set_plotting_area(chart.plotting_area());
+        {
+            let mut mesh = chart.configure_mesh();
+            ctx.fit_labels(&mut mesh)
+                .label_style(font(12))
+                .axis_desc_style(font(12));
+            if let Some(label) = &self.x_axis.label {
+                mesh.x_desc(label);
+            }
+            if let Some(label) = &self.y_axis.label {
+                mesh.y_desc(label);
+            }
+            mesh.draw()?;
+        }
+
+        let y_range = ctx.y_range();
+        let baseline = match ctx.view().y.scale {
+            AxisScale::Linear => 0.0,
+            _ => y_range.start.min(y_range.end),
+        };
+        let palette = Palette::default();
+        for (index, series) in self.series.iter().enumerate() {
+            let style = series.style(palette.series(index))?;
+            let id = SeriesId(index);
+            ctx.set_series_color(id, &style.color());
+            if ctx.is_hidden(id) {
+                // An empty series keeps the hidden series in the legend.
+                let annotation = chart.draw_series(std::iter::empty::<Polygon<(f64, f64)>>())?;
+                if let Some(name) = &series.name {
+                    ctx.label(id, annotation, name.as_str());
+                }
+                continue;
+            }
+            let annotation = match series.kind {
+                SeriesKind::Line | SeriesKind::Area => {
+                    if let (SeriesKind::Area, Some(first), Some(last)) =
+                        (series.kind, series.data.first(), series.data.last())
+                    {
+                        let mut outline = series.data.clone();
+                        outline.push((last.0, baseline));
+                        outline.push((first.0, baseline));
+                        chart.draw_series(std::iter::once(Polygon::new(
+                            outline,
+                            style.color().mix(0.3).filled(),
+                        )))?;
+                    }
+                    ctx.register_line(id, chart.plotting_area(), series.data.iter());
+                    let width = series.width.unwrap_or(2) * px;
+                    chart.draw_series(std::iter::once(style.line(series.data.clone(), width)))?
+                }
+                SeriesKind::Points => {
+                    ctx.register_points(id, chart.plotting_area(), series.data.iter());
+                    let size = (series.width.unwrap_or(4) * px) as i32;
+                    chart.draw_series(series.data.iter().map(|&point| style.marker(point, size)))?
+                }
+            };
+            if let Some(name) = &series.name {
+                ctx.label(id, annotation, name.as_str())
+                    .legend(move |(x, y)| {
+                        style.line(vec![(x, y), (x + 20 * px as i32, y)], 2 * px)
+                    });
+            }
+        }
+
+        if let (Some(size), false) = (legend, ctx.draws_legend()) {
+            let offset = position.place(
+                root.get_pixel_range(),
+                chart.plotting_area().get_pixel_range(),
+                size,
+            );
+            ctx.set_legend_bounds(chart.plotting_area(), offset, size);
+            chart
+                .configure_series_labels()
+                .position(SeriesLabelPosition::Coordinate(offset.0, offset.1))
+                .label_font(font(12))
+                .background_style(&WHITE.mix(0.8))
+                .border_style(&BLACK)
+                .draw()?;
+        }
+        Ok(())
+    }
+}
+
+impl AxisSpec {
+    /// The axis' range, or the extent of `values` with a small margin; positive values
+    /// only on a logarithmic axis.
+    fn range_or(&self, values: impl Iterator<Item = f64>) -> Range<f64> {
+        if let Some((start, end)) = self.range {
+            return start..end;
+        }
+        let log = self.scale == AxisScale::Log;
+        let (min, max) = values
+            .filter(|value| value.is_finite() && (!log || *value > 0.0))
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+                (min.min(value), max.max(value))
+            });
+        if min > max {
+            return if log { 1.0..10.0 } else { 0.0..1.0 };
+        }
+        if log {
+            // Pads by the same share of decades as a linear axis pads of its span.
+            let pad = ((max / min).ln() * PADDING).exp().max(1.1);
+            return min / pad..max * pad;
+        }
+        let pad = if max > min {
+            (max - min) * PADDING
+        } else {
+            0.5
+        };
+        min - pad..max + pad
+    }
+}
+
+impl SeriesSpec {
+    /// `default` with the color of the spec, if it sets one.
+    fn style(&self, default: SeriesStyle) -> Result<SeriesStyle, BuildError> {
+        match &self.color {
+            Some(color) => Ok(SeriesStyle {
+                rgb: parse_color(color)?,
+                ..default
+            }),
+            None => Ok(default),
+        }
+    }
+}
+
+impl Chart for ChartSpec {
+    fn build_chart(
+        &self,
+        ctx: &mut BuildContext<'_>,
+        root: DrawingArea<IcedBackend<'_>, Shift>,
+    ) -> Result<(), BuildError> {
+        ctx.fill_background(&root)?;
+        let (x, y) = (ctx.x_range(), ctx.y_range());
+        let view = ctx.view();
+        match (view.x.scale, view.y.scale) {
+            (AxisScale::Linear, AxisScale::Linear) => self.draw(ctx, &root, x, y),
+            (AxisScale::Linear, AxisScale::Log) => self.draw(ctx, &root, x, LogRange(y)),
+            (AxisScale::Log, AxisScale::Linear) => self.draw(ctx, &root, LogRange(x), y),
+            (AxisScale::Log, AxisScale::Log) => self.draw(ctx, &root, LogRange(x), LogRange(y)),
+            _ => Err("chart specs cannot draw symlog axes".into()),
+        }
+    }
+}
+
+/// Parses a `#rrggbb` color.
+fn parse_color(color: &str) -> Result<(u8, u8, u8), BuildError> {
+    let invalid = || format!("`{}` is not a color of the form #rrggbb", color);
+    let hex = color.strip_prefix('#').ok_or_else(invalid)?;
+    if hex.len() != 6 || !hex.is_ascii() {
+        return Err(invalid().into());
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| invalid());
+    Ok((channel(0)?, channel(2)?, channel(4)?))
+}
@@ -0,0 +1,138 @@
+use crate::{Chart, ChartWidget};
+use iced::canvas::Canvas;
+use iced::pane_grid::{self, Axis, Content, Pane, ResizeEvent, Split};
+use iced::{Element, Length, PaneGrid};
+use std::cell::Cell;
+
+/// The smallest share of the space either side of a [`ChartSplit`] can be dragged to.
+const MIN_SHARE: f32 = 0.1;
+
+/// The side of the chart a [`ChartSplit`] places its panel on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PanelSide {
+    Left,
+    #[default]
+    Right,
+    Top,
+    Bottom,
+}
+
+enum SplitPane<Message, C> {
+    Chart(Box<ChartWidget<Message, C>>),
+    Panel,
+}
+
+/// A chart next to a side panel, such as a legend or statistics, with a divider the user
+/// can drag to share the space between them.
+///
+/// It wraps a `PaneGrid` of two panes. Feed the messages of the `on_resize` function given
+/// to [`ChartSplit::view`] back to [`ChartSplit::resize`]. The chart's cache is drawn
+/// again at its new size whenever the divider moves.
+pub struct ChartSplit<Message, C> {
+    panes: pane_grid::State<SplitPane<Message, C>>,
+    chart: Pane,
+    split: Split,
+    side: PanelSide,
+    spacing: u16,
+}
+
+impl<Message: 'static, C: Chart + 'static> ChartSplit<Message, C> {
+    /// Places `chart` next to a panel on `side`, giving the chart three quarters of the
+    /// space.
+    pub fn new(chart: ChartWidget<Message, C>, side: PanelSide) -> Self {
+        let (mut panes, chart) = pane_grid::State::new(SplitPane::Chart(Box::new(chart)));
+        let axis = match side {
+            PanelSide::Left | PanelSide::Right => Axis::Vertical,
+            PanelSide::Top | PanelSide::Bottom => Axis::Horizontal,
+        };
+        let (panel, split) = panes
+            .split(axis, &chart, SplitPane::Panel)
+            .expect("a new pane grid has room for a second pane");
+        if let PanelSide::Left | PanelSide::Top = side {
+            panes.swap(&chart, &panel);
+        }
+        let mut split = Self {
+            panes,
+            chart,
+            split,
+            side,
+            spacing: 6,
+        };
+        split.set_chart_share(0.75);
+        split
+    }
+
+    /// Sets the share of the space given to the chart, between 0 and 1.
+    pub fn chart_share(mut self, share: f32) -> Self {
+        self.set_chart_share(share);
+        self
+    }
+
+    /// Sets the width in pixels of the divider.
+    pub fn spacing(mut self, spacing: u16) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    pub fn chart(&self) -> &ChartWidget<Message, C> {
+        match self.panes.get(&self.chart) {
+            Some(SplitPane::Chart(chart)) => chart,
+            _ => unreachable!("the chart pane holds the chart"),
+        }
+    }
+
+    pub fn chart_mut(&mut self) -> &mut ChartWidget<Message, C> {
+        match self.panes.get_mut(&self.chart) {
+            Some(SplitPane::Chart(chart)) => chart,
+            _ => unreachable!("the chart pane holds the chart"),
+        }
+    }
+
+    /// Moves the divider as the user drags it, keeping both sides at least a tenth of the
+    /// space.
+    pub fn resize(&mut self, event: ResizeEvent) {
+        if event.split == self.split {
+            let ratio = event.ratio.clamp(MIN_SHARE, 1.0 - MIN_SHARE);
+            self.panes.resize(&self.split, ratio);
+        }
+    }
+
+    fn set_chart_share(&mut self, share: f32) {
+        let share = share.clamp(MIN_SHARE, 1.0 - MIN_SHARE);
+        // The ratio is the share of the left or top pane.
+        let ratio = match self.side {
+            PanelSide::Right | PanelSide::Bottom => share,
+            PanelSide::Left | PanelSide::Top => 1.0 - share,
+        };
+        self.panes.resize(&self.split, ratio);
+    }
+
+    /// Lays out the chart and `panel`, reporting divider drags through `on_resize`.
+    pub fn view<'a, F>(
+        &'a mut self,
+        panel: impl Into<Element<'a, Message>>,
+        on_resize: F,
+    ) -> Element<'a, Message>
+    where
+        F: 'a + Fn(ResizeEvent) -> Message,
+    {
+        // The view function is called once per pane, so the panel is handed out once.
+        let panel = Cell::new(Some(panel.into()));
+        let spacing = self.spacing;
+        PaneGrid::new(&mut self.panes, move |_, pane, _| match pane {
+            SplitPane::Chart(chart) => Content::new(
+                Canvas::new(&mut **chart)
+                    .width(Length::Fill)
+                    .height(Length::Fill),
+            ),
+            SplitPane::Panel => Content::new(
+                panel
+                    .take()
+                    .expect("the pane grid shows the panel pane once"),
+            ),
+        })
+        .spacing(spacing)
+        .on_resize(spacing, on_resize)
+        .into()
+    }
+}
@@ -0,0 +1,249 @@
+use crate::{
+    BuildContext, BuildError, Chart, ChartWidget, IcedBackend, MarkerShape, Scatter, SeriesId,
+    ViewState,
+};
+use iced::canvas::Canvas;
+use iced::{Color, Column, Element, Length, Row};
+use plotters::chart::ChartBuilder;
+use plotters::coord::Shift;
+use plotters::drawing::DrawingArea;
+use plotters::element::Text;
+use plotters::style::text_anchor::{HPos, Pos, VPos};
+use plotters::style::{IntoFont, TextStyle};
+use std::ops::Range;
+use std::rc::Rc;
+
+/// The share of a variable's extent added on both sides of a cell's initial view.
+const PADDING: f64 = 0.05;
+
+/// The variables of a [`ScatterMatrix`], shared by all of its cells.
+struct Variables {
+    names: Vec<String>,
+    columns: Vec<Vec<f64>>,
+}
+
+/// One cell of a [`ScatterMatrix`]: a scatter plot of the variable of its column along x
+/// against the variable of its row along y, or the name of the variable on the diagonal.
+///
+/// Every record is registered as point `index` of [`SeriesId`]`(0)`, so a selection made in
+/// one cell names the same records in all others.
+pub struct MatrixCell {
+    variables: Rc<Variables>,
+    row: usize,
+    column: usize,
+    color: Color,
+}
+
+impl MatrixCell {
+    /// The index of the variable along y.
+    pub fn row(&self) -> usize {
+        self.row
+    }
+
+    /// The index of the variable along x.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+impl Chart for MatrixCell {
+    fn build_chart(
+        &self,
+        ctx: &mut BuildContext<'_>,
+        root: DrawingArea<IcedBackend<'_>, Shift>,
+    ) -> Result<(), BuildError> {
+        ctx.fill_background(&root)?;
+        let mut chart = ChartBuilder::on(&root)
+            .margin(4)
+            .x_label_area_size(20)
+            .y_label_area_size(36)
+            .build_cartesian_2d(ctx.x_range(), ctx.y_range())?;
+        ctx.set_plotting_area(chart.plotting_area());
+        chart
+            .configure_mesh()
+            .x_labels(3)
+            .y_labels(3)
+            .label_style(("sans-serif", 10))
+            .draw()?;
+
+        let area = chart.plotting_area();
+        if self.row == self.column {
+            let (width, height) = area.dim_in_pixel();
+            let style = TextStyle::from(("sans-serif", 16).into_font())
+                .pos(Pos::new(HPos::Center, VPos::Center));
+            area.strip_coord_spec().draw(&Text::new(
+                self.variables.names[self.row].as_str(),
+                (width as i32 / 2, height as i32 / 2),
+                style,
+            ))?;
+            return Ok(());
+        }
+
+        let xs = &self.variables.columns[self.column];
+        let ys = &self.variables.columns[self.row];
+        let points = || xs.iter().copied().zip(ys.iter().copied());
+        area.draw(&Scatter::uniform(points(), self.color, 2.5).shape(MarkerShape::Circle))?;
+        ctx.register_points(SeriesId(0), area, points());
+        Ok(())
+    }
+}
+
+/// A scatter-plot matrix (SPLOM): a grid with a cell for every pair of variables, for
+/// spotting relations between many variables of the same records at once.
+///
+/// Selections are linked: a lasso drawn in any cell reports the records inside it through
+/// [`ScatterMatrix::on_select`], and handing them to [`ScatterMatrix::select`] rings the
+/// same records in every cell. Only the selection layer of the cells is redrawn for that;
+/// their cached scatter plots are kept.
+///
+/// The cells start in lasso mode and can be switched to panning and zooming with
+/// [`ScatterMatrix::set_lasso_mode`].
+pub struct ScatterMatrix<Message> {
+    cells: Vec<ChartWidget<Message, MatrixCell>>,
+    count: usize,
+    selection: Vec<usize>,
+    spacing: u16,
+}
+
+impl<Message: 'static> ScatterMatrix<Message> {
+    /// A matrix of `variables`, each a name and one value per record. All variables must
+    /// hold the same number of records.
+    pub fn new(variables: Vec<(String, Vec<f64>)>) -> Self {
+        let (names, columns): (Vec<_>, Vec<_>) = variables.into_iter().unzip();
+        let extents: Vec<_> = columns.iter().map(|column| padded(column)).collect();
+        let variables = Rc::new(Variables { names, columns });
+        let count = variables.names.len();
+
+        let cells = (0..count * count)
+            .map(|i| {
+                let (row, column) = (i / count, i % count);
+                let cell = MatrixCell {
+                    variables: Rc::clone(&variables),
+                    row,
+                    column,
+                    color: Color::from_rgba(0.2, 0.4, 0.8, 0.6),
+                };
+                let view = ViewState::new(extents[column].clone(), extents[row].clone());
+                let mut widget = ChartWidget::new(cell).view(view);
+                widget.set_lasso_mode(true);
+                widget
+            })
+            .collect();
+        Self {
+            cells,
+            count,
+            selection: vec![],
+            spacing: 4,
+        }
+    }
+
+    /// Sets the color of the points in all cells.
+    pub fn point_color(mut self, color: Color) -> Self {
+        for cell in &mut self.cells {
+            cell.chart_mut().color = color;
+        }
+        self
+    }
+
+    /// Sets the space in pixels between the cells.
+    pub fn spacing(mut self, spacing: u16) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Sets the message produced when a lasso selection is completed in any cell, carrying
+    /// the indices of the selected records.
+    pub fn on_select<F>(mut self, f: F) -> Self
+    where
+        F: 'static + Fn(Vec<usize>) -> Message,
+    {
+        let f = Rc::new(f);
+        self.cells = self
+            .cells
+            .into_iter()
+            .map(|cell| {
+                let f = Rc::clone(&f);
+                cell.on_select(move |selection| {
+                    f(selection.into_iter().map(|(_, index)| index).collect())
+                })
+            })
+            .collect();
+        self
+    }
+
+    /// Rings the records at `indices` in every cell, e.g. the selection reported by
+    /// [`ScatterMatrix::on_select`].
+    pub fn select(&mut self, indices: Vec<usize>) {
+        let selection: Vec<_> = indices.iter().map(|&index| (SeriesId(0), index)).collect();
+        for cell in &mut self.cells {
+            cell.set_selection(selection.clone());
+        }
+        self.selection = indices;
+    }
+
+    /// The indices of the selected records.
+    pub fn selection(&self) -> &[usize] {
+        &self.selection
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.select(vec![]);
+    }
+
+    /// Switches the lasso in all cells on, or off for panning and zooming them.
+    pub fn set_lasso_mode(&mut self, enabled: bool) {
+        for cell in &mut self.cells {
+            cell.set_lasso_mode(enabled);
+        }
+    }
+
+    /// The cell plotting the variable `column` along x against the variable `row` along
+    /// y, e.g. to change its view.
+    pub fn cell_mut(
+        &mut self,
+        row: usize,
+        column: usize,
+    ) -> Option<&mut ChartWidget<Message, MatrixCell>> {
+        if row < self.count && column < self.count {
+            self.cells.get_mut(row * self.count + column)
+        } else {
+            None
+        }
+    }
+
+    /// Lays out the cells in a grid filling the available space.
+    pub fn view(&mut self) -> Element<'_, Message> {
+        let spacing = self.spacing;
+        let mut rows = Column::new()
+            .spacing(spacing)
+            .width(Length::Fill)
+            .height(Length::Fill);
+        let mut cells = self.cells.iter_mut();
+        for _ in 0..self.count {
+            let mut row = Row::new().spacing(spacing).height(Length::Fill);
+            for cell in cells.by_ref().take(self.count) {
+                row = row.push(Canvas::new(cell).width(Length::Fill).height(Length::Fill));
+            }
+            rows = rows.push(row);
+        }
+        rows.into()
+    }
+}
+
+/// The extent of `values` widened by [`PADDING`] on both sides.
+fn padded(values: &[f64]) -> Range<f64> {
+    let (min, max) = values
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &value| {
+            (min.min(value), max.max(value))
+        });
+    if min > max {
+        return 0.0..1.0;
+    }
+    let pad = if max > min {
+        (max - min) * PADDING
+    } else {
+        0.5
+    };
+    min - pad..max + pad
+}
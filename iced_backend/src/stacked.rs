@@ -0,0 +1,382 @@
+use crate::{fill_pattern, BuildContext, Palette, SeriesId};
+use plotters::chart::{ChartContext, SeriesAnno};
+use plotters::coord::types::RangedCoordf64;
+use plotters::drawing::DrawingAreaErrorKind;
+use plotters::element::{Polygon, Rectangle};
+use plotters::prelude::Cartesian2d;
+use plotters::style::{Color, RGBColor};
+use plotters_backend::DrawingBackend;
+use std::collections::HashMap;
+
+type StackedResult<DB> = Result<(), DrawingAreaErrorKind<<DB as DrawingBackend>::ErrorType>>;
+type Coords = Cartesian2d<RangedCoordf64, RangedCoordf64>;
+
+/// Series stacked on top of each other, drawn as areas or bars.
+///
+/// Layers share the x values given to [`Stacked::new`]. Positive values are stacked upwards
+/// from zero and negative values downwards, so layers never overlap. A missing value is a
+/// gap: the layer is interrupted there and contributes nothing to the layers above it.
+///
+/// Layer `i` is registered as series `first + i` (see [`Stacked::draw_areas`]), with one
+/// area per x index, so [`ChartWidget::on_hover_element`] reports the hovered layer and
+/// index and [`Stacked::contributions`] breaks the stack down at that index.
+///
+/// [`ChartWidget::on_hover_element`]: crate::ChartWidget::on_hover_element
+#[derive(Debug, Clone, Default)]
+pub struct Stacked {
+    xs: Vec<f64>,
+    layers: Vec<(String, Vec<Option<f64>>)>,
+    hatched: bool,
+}
+
+impl Stacked {
+    pub fn new(xs: impl Into<Vec<f64>>) -> Self {
+        Self {
+            xs: xs.into(),
+            layers: vec![],
+            hatched: false,
+        }
+    }
+
+    /// Fills the layers with the hatch patterns of their [`SeriesStyle`] instead of solid
+    /// colors, see [`SeriesStyle::pattern`].
+    ///
+    /// [`SeriesStyle`]: crate::SeriesStyle
+    /// [`SeriesStyle::pattern`]: crate::SeriesStyle::pattern
+    pub fn hatched(mut self, enabled: bool) -> Self {
+        self.hatched = enabled;
+        self
+    }
+
+    /// Builds a stack from `(x, y)` series, aligned on the sorted union of their x values.
+    /// An x value missing from a series becomes a gap in its layer.
+    pub fn from_series<I, S>(series: I) -> Self
+    where
+        I: IntoIterator<Item = (S, Vec<(f64, f64)>)>,
+        S: Into<String>,
+    {
+        let series: Vec<_> = series.into_iter().collect();
+        let mut xs: Vec<f64> = series
+            .iter()
+            .flat_map(|(_, points)| points.iter().map(|(x, _)| *x))
+            .collect();
+        xs.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        xs.dedup();
+
+        let mut stacked = Self::new(xs);
+        for (name, points) in series {
+            // The first point wins when a series repeats an x value.
+            let mut by_x = HashMap::with_capacity(points.len());
+            for (x, y) in points {
+                by_x.entry(x_key(x)).or_insert(y);
+            }
+            let values: Vec<_> = stacked
+                .xs
+                .iter()
+                .map(|x| by_x.get(&x_key(*x)).copied())
+                .collect();
+            stacked = stacked.layer(name, values);
+        }
+        stacked
+    }
+
+    /// Adds a layer on top of the previous ones. `values` are matched with the x values by
+    /// index; `None` and missing trailing values are gaps.
+    pub fn layer<I>(mut self, name: impl Into<String>, values: I) -> Self
+    where
+        I: IntoIterator<Item = Option<f64>>,
+    {
+        let mut values: Vec<_> = values.into_iter().take(self.xs.len()).collect();
+        values.resize(self.xs.len(), None);
+        self.layers.push((name.into(), values));
+        self
+    }
+
+    pub fn xs(&self) -> &[f64] {
+        &self.xs
+    }
+
+    /// The number of layers.
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// The bottom and top of every layer at x index `index`, or `None` for gaps.
+    pub fn extents(&self, index: usize) -> Vec<Option<(f64, f64)>> {
+        let (mut positive, mut negative) = (0.0, 0.0);
+        self.layers
+            .iter()
+            .map(|(_, values)| {
+                let value = values.get(index).copied().flatten()?;
+                Some(if value >= 0.0 {
+                    positive += value;
+                    (positive - value, positive)
+                } else {
+                    negative += value;
+                    (negative, negative - value)
+                })
+            })
+            .collect()
+    }
+
+    /// Like [`Stacked::extents`], but with every value clamped to the sign of `positive`, so
+    /// the other values are empty extents at the edge of the stack of that sign.
+    fn signed_extents(&self, index: usize, positive: bool) -> Vec<Option<(f64, f64)>> {
+        let mut edge = 0.0;
+        self.layers
+            .iter()
+            .map(|(_, values)| {
+                let value = values.get(index).copied().flatten()?;
+                let value = if positive {
+                    value.max(0.0)
+                } else {
+                    value.min(0.0)
+                };
+                edge += value;
+                Some(if positive {
+                    (edge - value, edge)
+                } else {
+                    (edge, edge - value)
+                })
+            })
+            .collect()
+    }
+
+    /// The name and value of every layer at x index `index`, bottom layer first.
+    pub fn contributions(&self, index: usize) -> Vec<(&str, Option<f64>)> {
+        self.layers
+            .iter()
+            .map(|(name, values)| (name.as_str(), values.get(index).copied().flatten()))
+            .collect()
+    }
+
+    /// Draws the layers as filled areas in the colors of `palette`, labelled for the
+    /// series legend, and registers them as series `first`, `first + 1`, ...
+    pub fn draw_areas<DB: DrawingBackend>(
+        &self,
+        chart: &mut ChartContext<'_, DB, Coords>,
+        ctx: &mut BuildContext<'_>,
+        palette: &Palette,
+        first: SeriesId,
+    ) -> StackedResult<DB> {
+        let extents: Vec<_> = (0..self.xs.len()).map(|i| self.extents(i)).collect();
+        // A layer that changes sign is drawn as a polygon in the positive stack and one in
+        // the negative stack, so it never crosses its neighbours.
+        let signed: Vec<_> = [true, false]
+            .iter()
+            .map(|&positive| {
+                (0..self.xs.len())
+                    .map(|i| self.signed_extents(i, positive))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        for (layer, (name, _)) in self.layers.iter().enumerate() {
+            // Every run of consecutive values becomes one polygon per sign.
+            let mut runs: Vec<Vec<(f64, (f64, f64))>> = vec![];
+            for extents in &signed {
+                let mut run = vec![];
+                for (x, extent) in self.xs.iter().zip(extents) {
+                    match extent[layer] {
+                        Some(extent) => run.push((*x, extent)),
+                        None if !run.is_empty() => runs.push(std::mem::take(&mut run)),
+                        None => {}
+                    }
+                }
+                if !run.is_empty() {
+                    runs.push(run);
+                }
+            }
+            // Runs without any value of their sign would be drawn as flat outlines.
+            runs.retain(|run| run.iter().any(|(_, (bottom, top))| bottom != top));
+
+            let color = palette.color(layer);
+            let outlines = runs.into_iter().map(|run| {
+                let top = run.iter().map(|(x, (_, top))| (*x, *top));
+                let bottom = run.iter().rev().map(|(x, (bottom, _))| (*x, *bottom));
+                top.chain(bottom).collect::<Vec<_>>()
+            });
+            let anno = if self.hatched {
+                let pattern = palette.series(layer).pattern();
+                for outline in outlines {
+                    fill_pattern(chart.plotting_area(), &outline, &pattern)?;
+                }
+                // An empty series only adds the layer to the legend.
+                chart.draw_series(std::iter::empty::<Polygon<(f64, f64)>>())?
+            } else {
+                let style = color.mix(0.8).filled();
+                chart.draw_series(outlines.map(|outline| Polygon::new(outline, style.clone())))?
+            };
+            label(anno, name, &color);
+
+            let series = SeriesId(first.0 + layer);
+            ctx.set_series_color(series, &color);
+            self.register(ctx, chart, series, layer, &extents);
+        }
+        Ok(())
+    }
+
+    /// Draws the layers as bars `width` wide (in x units), labelled for the series legend,
+    /// and registers them as series `first`, `first + 1`, ...
+    pub fn draw_bars<DB: DrawingBackend>(
+        &self,
+        chart: &mut ChartContext<'_, DB, Coords>,
+        ctx: &mut BuildContext<'_>,
+        palette: &Palette,
+        first: SeriesId,
+        width: f64,
+    ) -> StackedResult<DB> {
+        let extents: Vec<_> = (0..self.xs.len()).map(|i| self.extents(i)).collect();
+        for (layer, (name, _)) in self.layers.iter().enumerate() {
+            let color = palette.color(layer);
+            let corners = self.xs.iter().zip(&extents).filter_map(|(x, extent)| {
+                let (bottom, top) = extent[layer]?;
+                Some([(x - width / 2.0, bottom), (x + width / 2.0, top)])
+            });
+            let anno = if self.hatched {
+                let pattern = palette.series(layer).pattern();
+                let corners: Vec<_> = corners.collect();
+                for &[(left, bottom), (right, top)] in &corners {
+                    let outline = [(left, bottom), (right, bottom), (right, top), (left, top)];
+                    fill_pattern(chart.plotting_area(), &outline, &pattern)?;
+                }
+                // The outlines keep adjacent bars of the same hatch apart.
+                chart.draw_series(
+                    corners
+                        .into_iter()
+                        .map(|corners| Rectangle::new(corners, color.stroke_width(1))),
+                )?
+            } else {
+                chart.draw_series(corners.map(|corners| Rectangle::new(corners, color.filled())))?
+            };
+            label(anno, name, &color);
+
+            let series = SeriesId(first.0 + layer);
+            ctx.set_series_color(series, &color);
+            for (index, (x, extent)) in self.xs.iter().zip(&extents).enumerate() {
+                if let Some((bottom, top)) = extent[layer] {
+                    ctx.register_rect(
+                        series,
+                        index,
+                        chart.plotting_area(),
+                        ((x - width / 2.0, bottom), (x + width / 2.0, top)),
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Registers an area layer with one rectangle per x index, reaching halfway to the
+    /// neighbouring x values.
+    fn register<DB: DrawingBackend>(
+        &self,
+        ctx: &mut BuildContext<'_>,
+        chart: &ChartContext<'_, DB, Coords>,
+        series: SeriesId,
+        layer: usize,
+        extents: &[Vec<Option<(f64, f64)>>],
+    ) {
+        for (index, x) in self.xs.iter().enumerate() {
+            let (bottom, top) = match extents[index][layer] {
+                Some(extent) => extent,
+                None => continue,
+            };
+            let left = index
+                .checked_sub(1)
+                .map_or(*x, |prev| (self.xs[prev] + x) / 2.0);
+            let right = self.xs.get(index + 1).map_or(*x, |next| (x + next) / 2.0);
+            ctx.register_rect(
+                series,
+                index,
+                chart.plotting_area(),
+                ((left, bottom), (right, top)),
+            );
+        }
+    }
+}
+
+/// A hashable key for an x value, equal for equal values.
+fn x_key(x: f64) -> u64 {
+    // `0.0 == -0.0`, but their bits differ.
+    if x == 0.0 {
+        0
+    } else {
+        x.to_bits()
+    }
+}
+
+/// Names a drawn layer in the series legend, with a swatch of its color.
+fn label<DB: DrawingBackend>(anno: &mut SeriesAnno<'_, DB>, name: &str, color: &RGBColor) {
+    let RGBColor(r, g, b) = *color;
+    anno.label(name).legend(move |(x, y)| {
+        Rectangle::new([(x, y - 5), (x + 10, y + 5)], RGBColor(r, g, b).filled())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stacks_each_sign_from_zero() {
+        let stacked = Stacked::new(vec![0.0, 1.0])
+            .layer("a", vec![Some(1.0), Some(-1.0)])
+            .layer("b", vec![Some(-2.0), None])
+            .layer("c", vec![Some(3.0), Some(-4.0)]);
+        assert_eq!(
+            stacked.extents(0),
+            vec![Some((0.0, 1.0)), Some((-2.0, 0.0)), Some((1.0, 4.0))]
+        );
+        assert_eq!(
+            stacked.extents(1),
+            vec![Some((-1.0, 0.0)), None, Some((-5.0, -1.0))]
+        );
+        assert_eq!(stacked.extents(2), vec![None, None, None]);
+    }
+
+    #[test]
+    fn splits_layers_that_change_sign() {
+        let stacked = Stacked::new(vec![0.0, 1.0])
+            .layer("a", vec![Some(1.0), Some(-1.0)])
+            .layer("b", vec![Some(2.0), Some(2.0)]);
+        // "a" is empty in the positive stack at x = 1, so "b" stays on zero there.
+        assert_eq!(
+            stacked.signed_extents(1, true),
+            vec![Some((0.0, 0.0)), Some((0.0, 2.0))]
+        );
+        assert_eq!(
+            stacked.signed_extents(1, false),
+            vec![Some((-1.0, 0.0)), Some((-1.0, -1.0))]
+        );
+        assert_eq!(
+            stacked.signed_extents(0, false),
+            vec![Some((0.0, 0.0)), Some((0.0, 0.0))]
+        );
+    }
+
+    #[test]
+    fn aligns_series_on_their_x_values() {
+        let stacked = Stacked::from_series(vec![
+            ("a", vec![(2.0, 1.0), (0.0, -1.0), (2.0, 5.0)]),
+            ("b", vec![(1.0, 3.0), (-0.0, 4.0)]),
+        ]);
+        assert_eq!(stacked.xs(), &[0.0, 1.0, 2.0]);
+        assert_eq!(
+            stacked.contributions(0),
+            vec![("a", Some(-1.0)), ("b", Some(4.0))]
+        );
+        assert_eq!(
+            stacked.contributions(1),
+            vec![("a", None), ("b", Some(3.0))]
+        );
+        assert_eq!(
+            stacked.contributions(2),
+            vec![("a", Some(1.0)), ("b", None)]
+        );
+        assert_eq!(stacked.extents(1), vec![None, Some((0.0, 3.0))]);
+    }
+}
@@ -0,0 +1,162 @@
+use crate::{AxisRange, Corner, SeriesId, ViewState};
+use iced::canvas::{Frame, Text};
+use iced::{Color, Point, Rectangle, Size};
+
+/// The distance between the stats box and the edges of the plotting area.
+const INSET: f32 = 4.0;
+/// The padding between the stats box and its text.
+const PADDING: f32 = 4.0;
+/// A rough average glyph width relative to the text size, used to size the box.
+const GLYPH_WIDTH: f32 = 0.6;
+
+/// Summary statistics of the y values of a series within the visible window.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeriesStats {
+    pub series: SeriesId,
+    pub name: String,
+    /// The visible y values, e.g. for computing custom metrics in a
+    /// [`StatsBox::formatter`].
+    pub values: Vec<f64>,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    /// The population standard deviation.
+    pub std_dev: f64,
+}
+
+impl SeriesStats {
+    /// Summarizes the points of `points` that lie within the view's x and y ranges.
+    pub(crate) fn visible(
+        series: SeriesId,
+        name: String,
+        view: &ViewState,
+        points: impl Iterator<Item = (f64, f64)>,
+    ) -> Self {
+        let within = |range: &AxisRange, value: f64| {
+            value >= range.start.min(range.end) && value <= range.start.max(range.end)
+        };
+        let values: Vec<f64> = points
+            .filter(|(x, y)| within(&view.x, *x) && within(&view.y, *y))
+            .map(|(_, y)| y)
+            .collect();
+        let count = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / count;
+        let variance = values.iter().map(|y| (y - mean).powi(2)).sum::<f64>() / count;
+        Self {
+            series,
+            name,
+            min: values.iter().copied().fold(f64::NAN, f64::min),
+            max: values.iter().copied().fold(f64::NAN, f64::max),
+            mean,
+            std_dev: variance.sqrt(),
+            values,
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.values.len()
+    }
+}
+
+/// A box listing [`SeriesStats`] for every series tracked with
+/// [`BuildContext::track_stats`](crate::BuildContext::track_stats).
+///
+/// The statistics are computed while the chart is built, so they are only recalculated
+/// when the view or the chart changes.
+pub struct StatsBox {
+    corner: Corner,
+    text_size: f32,
+    formatter: Box<dyn Fn(&SeriesStats) -> String>,
+}
+
+impl StatsBox {
+    pub fn new() -> Self {
+        Self {
+            corner: Corner::BottomRight,
+            text_size: 13.0,
+            formatter: Box::new(default_format),
+        }
+    }
+
+    pub fn corner(mut self, corner: Corner) -> Self {
+        self.corner = corner;
+        self
+    }
+
+    pub fn text_size(mut self, size: f32) -> Self {
+        self.text_size = size;
+        self
+    }
+
+    /// Sets how the statistics of one series are presented, e.g. to add a median
+    /// computed from [`SeriesStats::values`]. The result may span several lines.
+    pub fn formatter<F>(mut self, f: F) -> Self
+    where
+        F: 'static + Fn(&SeriesStats) -> String,
+    {
+        self.formatter = Box::new(f);
+        self
+    }
+
+    pub(crate) fn draw(&self, frame: &mut Frame, plot: Rectangle, stats: &[SeriesStats]) {
+        if stats.is_empty() {
+            return;
+        }
+        let content = stats
+            .iter()
+            .map(|stats| (self.formatter)(stats))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let lines = content.lines().count() as f32;
+        let columns = content
+            .lines()
+            .map(|l| l.chars().count())
+            .max()
+            .unwrap_or(0) as f32;
+        let size = Size::new(
+            columns * self.text_size * GLYPH_WIDTH + 2.0 * PADDING,
+            lines * self.text_size + 2.0 * PADDING,
+        );
+        let x = match self.corner {
+            Corner::TopLeft | Corner::BottomLeft => plot.x + INSET,
+            Corner::TopRight | Corner::BottomRight => plot.x + plot.width - INSET - size.width,
+        };
+        let y = match self.corner {
+            Corner::TopLeft | Corner::TopRight => plot.y + INSET,
+            Corner::BottomLeft | Corner::BottomRight => plot.y + plot.height - INSET - size.height,
+        };
+
+        frame.fill_rectangle(
+            Point::new(x, y),
+            size,
+            Color::from_rgba(1.0, 1.0, 1.0, 0.85),
+        );
+        frame.fill_text(Text {
+            content,
+            position: Point::new(x + PADDING, y + PADDING),
+            size: self.text_size,
+            ..Text::default()
+        });
+    }
+}
+
+impl Default for StatsBox {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn default_format(stats: &SeriesStats) -> String {
+    if stats.values.is_empty() {
+        return format!("{}: no visible points", stats.name);
+    }
+    format!(
+        "{}: n={} min={:.3} max={:.3} mean={:.3} sd={:.3}",
+        stats.name,
+        stats.count(),
+        stats.min,
+        stats.max,
+        stats.mean,
+        stats.std_dev
+    )
+}
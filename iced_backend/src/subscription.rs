@@ -0,0 +1,77 @@
+//! Feeding a chart from a stream of points, such as a channel, a websocket or a serial
+//! port:
+//!
+//! ```ignore
+//! fn subscription(&self) -> Subscription<Message> {
+//!     subscription::from_stream(receiver_stream()).map(Message::Points)
+//! }
+//!
+//! fn update(&mut self, message: Message) -> Command<Message> {
+//!     match message {
+//!         Message::Points(points) => self.average.extend(points),
+//!     }
+//!     Command::none()
+//! }
+//! ```
+
+use iced::futures::stream::{Stream, StreamExt};
+use iced::Subscription;
+use iced_futures::subscription::Recipe;
+use iced_futures::BoxStream;
+use std::any::TypeId;
+use std::hash::{Hash, Hasher};
+
+/// The largest batch delivered by [`from_stream`].
+pub const MAX_BATCH: usize = 1024;
+
+/// Turns `stream` into a subscription delivering its items in batches.
+///
+/// The items that arrive while the application is busy are collected into one batch of
+/// up to [`MAX_BATCH`] items, so a burst produces a few messages instead of one per
+/// point. A batch is delivered as soon as the stream has nothing more ready, so single
+/// points are not delayed.
+///
+/// The subscription is identified by the type of the stream; use [`from_stream_with_id`]
+/// to run several streams of the same type at once.
+pub fn from_stream<S>(stream: S) -> Subscription<Vec<S::Item>>
+where
+    S: Stream + Send + 'static,
+    S::Item: Send + 'static,
+{
+    from_stream_with_id((), stream)
+}
+
+/// Like [`from_stream`], but identified by `id` as well, e.g. the address of the
+/// websocket. Changing the id restarts the subscription with the new stream.
+pub fn from_stream_with_id<I, S>(id: I, stream: S) -> Subscription<Vec<S::Item>>
+where
+    I: Hash + 'static,
+    S: Stream + Send + 'static,
+    S::Item: Send + 'static,
+{
+    Subscription::from_recipe(FromStream { id, stream })
+}
+
+struct FromStream<I, S> {
+    id: I,
+    stream: S,
+}
+
+impl<H, E, I, S> Recipe<H, E> for FromStream<I, S>
+where
+    H: Hasher,
+    I: Hash + 'static,
+    S: Stream + Send + 'static,
+    S::Item: Send + 'static,
+{
+    type Output = Vec<S::Item>;
+
+    fn hash(&self, state: &mut H) {
+        TypeId::of::<Self>().hash(state);
+        self.id.hash(state);
+    }
+
+    fn stream(self: Box<Self>, _input: BoxStream<E>) -> BoxStream<Self::Output> {
+        Box::pin(self.stream.ready_chunks(MAX_BATCH))
+    }
+}
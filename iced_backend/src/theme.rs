@@ -0,0 +1,138 @@
+use crate::{DashPattern, DashedPath, Hatch, Marker, MarkerShape, Pattern};
+use plotters::style::{Color, RGBColor, ShapeStyle};
+
+const OKABE_ITO: [RGBColor; 8] = [
+    RGBColor(230, 159, 0),
+    RGBColor(86, 180, 233),
+    RGBColor(0, 158, 115),
+    RGBColor(240, 228, 66),
+    RGBColor(0, 114, 178),
+    RGBColor(213, 94, 0),
+    RGBColor(204, 121, 167),
+    RGBColor(0, 0, 0),
+];
+
+const HIGH_CONTRAST_LIGHT: [RGBColor; 5] = [
+    RGBColor(0, 0, 0),
+    RGBColor(0, 68, 136),
+    RGBColor(187, 85, 102),
+    RGBColor(17, 119, 51),
+    RGBColor(136, 34, 85),
+];
+
+const HIGH_CONTRAST_DARK: [RGBColor; 5] = [
+    RGBColor(255, 255, 255),
+    RGBColor(255, 221, 0),
+    RGBColor(102, 204, 238),
+    RGBColor(238, 102, 119),
+    RGBColor(170, 238, 85),
+];
+
+/// An ordered set of series colors.
+///
+/// With [`Palette::distinct_shapes`], every series also gets its own marker shape, dash
+/// pattern and hatch, so series stay distinguishable without relying on color.
+#[derive(Debug, Clone, Copy)]
+pub struct Palette {
+    colors: &'static [RGBColor],
+    distinct_shapes: bool,
+}
+
+impl Palette {
+    /// The Okabe-Ito palette, distinguishable with all common forms of color blindness.
+    pub const OKABE_ITO: Palette = Palette::new(&OKABE_ITO);
+    /// Strongly contrasting colors for light backgrounds.
+    pub const HIGH_CONTRAST_LIGHT: Palette = Palette::new(&HIGH_CONTRAST_LIGHT);
+    /// Strongly contrasting colors for dark backgrounds.
+    pub const HIGH_CONTRAST_DARK: Palette = Palette::new(&HIGH_CONTRAST_DARK);
+
+    /// A palette of custom colors; `colors` must not be empty.
+    pub const fn new(colors: &'static [RGBColor]) -> Self {
+        Self {
+            colors,
+            distinct_shapes: false,
+        }
+    }
+
+    /// Pairs every color with a distinct marker shape, dash pattern and hatch.
+    pub fn distinct_shapes(mut self, enabled: bool) -> Self {
+        self.distinct_shapes = enabled;
+        self
+    }
+
+    pub fn colors(&self) -> &'static [RGBColor] {
+        self.colors
+    }
+
+    /// The color of series `index`, wrapping around after the last color.
+    pub fn color(&self, index: usize) -> RGBColor {
+        let RGBColor(r, g, b) = self.colors[index % self.colors.len()];
+        RGBColor(r, g, b)
+    }
+
+    /// The style of series `index`.
+    pub fn series(&self, index: usize) -> SeriesStyle {
+        let (marker, dash, hatch) = if self.distinct_shapes {
+            (
+                MarkerShape::ALL[index % MarkerShape::ALL.len()],
+                DashPattern::ALL[index % DashPattern::ALL.len()],
+                Hatch::ALL[index % Hatch::ALL.len()],
+            )
+        } else {
+            (MarkerShape::Circle, DashPattern::Solid, Hatch::Diagonal)
+        };
+        let RGBColor(r, g, b) = self.color(index);
+        SeriesStyle {
+            rgb: (r, g, b),
+            marker,
+            dash,
+            hatch,
+        }
+    }
+
+    /// The styles of series `0`, `1`, `2`, ... without end.
+    pub fn cycle(self) -> impl Iterator<Item = SeriesStyle> {
+        (0..).map(move |index| self.series(index))
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::OKABE_ITO
+    }
+}
+
+/// How one series is drawn: its color, marker shape, line dash pattern and the hatch of
+/// its fills.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SeriesStyle {
+    pub rgb: (u8, u8, u8),
+    pub marker: MarkerShape,
+    pub dash: DashPattern,
+    pub hatch: Hatch,
+}
+
+impl SeriesStyle {
+    pub fn color(&self) -> RGBColor {
+        RGBColor(self.rgb.0, self.rgb.1, self.rgb.2)
+    }
+
+    /// A line through `points` in this series' color and dash pattern.
+    pub fn line<Coord>(&self, points: Vec<Coord>, width: u32) -> DashedPath<Coord> {
+        DashedPath::new(points, self.stroke(width), self.dash)
+    }
+
+    /// A filled marker of this series' shape at `center`.
+    pub fn marker<Coord>(&self, center: Coord, size: i32) -> Marker<Coord> {
+        Marker::new(center, size, self.color().filled(), self.marker)
+    }
+
+    /// A fill of this series' hatch in its color, over a light tint of it.
+    pub fn pattern(&self) -> Pattern {
+        Pattern::new(self.hatch, &self.color()).background(&self.color().mix(0.15))
+    }
+
+    pub fn stroke(&self, width: u32) -> ShapeStyle {
+        self.color().stroke_width(width)
+    }
+}
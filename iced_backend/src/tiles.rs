@@ -0,0 +1,271 @@
+use crate::{AxisScale, BuildError, FrameLike, IcedBackend, ViewState};
+use iced::canvas::{Frame, Path, Stroke, Text};
+use iced::{Color, Point, Rectangle, Size, Vector};
+use plotters::coord::Shift;
+use plotters::drawing::{DrawingArea, IntoDrawingArea};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// The width and height of a tile in pixels.
+pub const TILE_SIZE: f32 = 256.0;
+
+/// How many zoom levels there are per doubling of the scale. Scales are rounded to a level
+/// so tiles are found again although panning changes the visible range by rounding.
+const LEVELS_PER_OCTAVE: f64 = 4096.0;
+
+/// Draws the data of a [`ChartWidget`](crate::ChartWidget) tile by tile, see
+/// [`ChartWidget::tiles`](crate::ChartWidget::tiles).
+///
+/// It is implemented for closures taking the same arguments as [`TileSource::draw_tile`].
+pub trait TileSource {
+    /// Draws the data within `x` and `y` onto `root`, a tile [`TILE_SIZE`] pixels wide and
+    /// high. Build a coordinate system without margins or labels over the ranges:
+    ///
+    /// ```ignore
+    /// let mut chart = ChartBuilder::on(&root).build_cartesian_2d(x.clone(), y)?;
+    /// chart.draw_series(LineSeries::new(data.iter_range(x), &BLUE))?;
+    /// ```
+    ///
+    /// Draw the points just outside the tile too, so lines run on into the next tile.
+    fn draw_tile(
+        &self,
+        x: Range<f64>,
+        y: Range<f64>,
+        root: DrawingArea<IcedBackend<'_, TileFrame>, Shift>,
+    ) -> Result<(), BuildError>;
+}
+
+impl<F> TileSource for F
+where
+    F: Fn(
+        Range<f64>,
+        Range<f64>,
+        DrawingArea<IcedBackend<'_, TileFrame>, Shift>,
+    ) -> Result<(), BuildError>,
+{
+    fn draw_tile(
+        &self,
+        x: Range<f64>,
+        y: Range<f64>,
+        root: DrawingArea<IcedBackend<'_, TileFrame>, Shift>,
+    ) -> Result<(), BuildError> {
+        self(x, y, root)
+    }
+}
+
+/// The drawing operations of a tile, recorded once and replayed wherever the tile shows.
+#[derive(Debug, Clone)]
+pub struct TileFrame {
+    size: Size,
+    operations: Vec<Operation>,
+}
+
+#[derive(Debug, Clone)]
+enum Operation {
+    Fill(Path, Color),
+    FillRectangle(Point, Size, Color),
+    Stroke(Path, Stroke),
+    FillText(Text),
+    FillTriangles(Vec<(Point, Color)>, Vec<u32>),
+    Save,
+    Restore,
+    Translate(Vector),
+    Rotate(f32),
+}
+
+impl TileFrame {
+    fn new(size: Size) -> Self {
+        Self {
+            size,
+            operations: vec![],
+        }
+    }
+
+    /// Draws the recorded operations onto `frame`.
+    fn replay(&self, frame: &mut Frame) {
+        replay(&self.operations, frame);
+    }
+}
+
+/// Replays `operations` up to the `Restore` closing the current save, and returns how many
+/// were consumed, including that `Restore`.
+fn replay(operations: &[Operation], frame: &mut Frame) -> usize {
+    let mut index = 0;
+    while let Some(operation) = operations.get(index) {
+        index += 1;
+        match operation {
+            Operation::Fill(path, color) => frame.fill(path, *color),
+            Operation::FillRectangle(top_left, size, color) => {
+                frame.fill_rectangle(*top_left, *size, *color)
+            }
+            Operation::Stroke(path, stroke) => frame.stroke(path, *stroke),
+            Operation::FillText(text) => frame.fill_text(text.clone()),
+            Operation::FillTriangles(vertices, indices) => frame.fill_triangles(vertices, indices),
+            Operation::Save => {
+                let rest = &operations[index..];
+                frame.with_save(|frame| index += replay(rest, frame));
+            }
+            Operation::Restore => break,
+            Operation::Translate(translation) => frame.translate(*translation),
+            Operation::Rotate(angle) => frame.rotate(*angle),
+        }
+    }
+    index
+}
+
+impl FrameLike for TileFrame {
+    fn size(&self) -> Size {
+        self.size
+    }
+
+    fn fill(&mut self, path: &Path, color: Color) {
+        self.operations.push(Operation::Fill(path.clone(), color));
+    }
+
+    fn fill_rectangle(&mut self, top_left: Point, size: Size, color: Color) {
+        self.operations
+            .push(Operation::FillRectangle(top_left, size, color));
+    }
+
+    fn stroke(&mut self, path: &Path, stroke: Stroke) {
+        self.operations
+            .push(Operation::Stroke(path.clone(), stroke));
+    }
+
+    fn fill_text(&mut self, text: Text) {
+        self.operations.push(Operation::FillText(text));
+    }
+
+    fn fill_triangles(&mut self, vertices: &[(Point, Color)], indices: &[u32]) {
+        self.operations.push(Operation::FillTriangles(
+            vertices.to_vec(),
+            indices.to_vec(),
+        ));
+    }
+
+    fn with_save(&mut self, f: impl FnOnce(&mut Self)) {
+        self.operations.push(Operation::Save);
+        f(self);
+        self.operations.push(Operation::Restore);
+    }
+
+    fn translate(&mut self, translation: Vector) {
+        self.operations.push(Operation::Translate(translation));
+    }
+
+    fn rotate(&mut self, angle: f32) {
+        self.operations.push(Operation::Rotate(angle));
+    }
+}
+
+/// A tile at one zoom level: the levels of the x and y scale, and the column and row of
+/// the tile counted from the data origin.
+type TileKey = (i64, i64, i64, i64);
+
+/// The tiles of a widget, drawn by a [`TileSource`] and cached per tile and zoom level.
+pub(crate) struct Tiles {
+    source: Box<dyn TileSource>,
+    capacity: usize,
+    cache: RefCell<HashMap<TileKey, (TileFrame, u64)>>,
+    /// Counts the draws, to find the tiles used least recently.
+    clock: Cell<u64>,
+}
+
+impl Tiles {
+    pub(crate) fn new(source: impl TileSource + 'static) -> Self {
+        Self {
+            source: Box::new(source),
+            capacity: 256,
+            cache: RefCell::new(HashMap::new()),
+            clock: Cell::new(0),
+        }
+    }
+
+    pub(crate) fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.cache.get_mut().clear();
+    }
+
+    /// Draws the tiles covering `plot` onto `frame`, drawing those not cached yet.
+    ///
+    /// Tiles are only drawn for linear axes. Tiles extending past `plot` are drawn whole;
+    /// the chart covers them with its background around the plotting area.
+    pub(crate) fn draw(
+        &self,
+        frame: &mut Frame,
+        view: &ViewState,
+        plot: Rectangle,
+    ) -> Result<(), BuildError> {
+        if view.x.scale != AxisScale::Linear || view.y.scale != AxisScale::Linear {
+            return Ok(());
+        }
+        let (x_level, x_scale) = level(f64::from(plot.width) / view.x.span());
+        let (y_level, y_scale) = level(f64::from(plot.height) / view.y.span());
+        if !(x_scale.is_finite() && y_scale.is_finite()) {
+            return Ok(());
+        }
+        let width = f64::from(TILE_SIZE) / x_scale;
+        let height = f64::from(TILE_SIZE) / y_scale;
+        let x = view.x.as_range();
+        let y = view.y.as_range();
+        let columns = (x.start / width).floor() as i64..(x.end / width).ceil() as i64;
+        let rows = (y.start / height).floor() as i64..(y.end / height).ceil() as i64;
+
+        let now = self.clock.get() + 1;
+        self.clock.set(now);
+        let mut cache = self.cache.borrow_mut();
+        for column in columns {
+            for row in rows.clone() {
+                let key = (x_level, y_level, column, row);
+                let tile_x = column as f64 * width..(column + 1) as f64 * width;
+                let tile_y = row as f64 * height..(row + 1) as f64 * height;
+                let left = plot.x + ((tile_x.start - x.start) * x_scale) as f32;
+                let top = plot.y + ((y.end - tile_y.end) * y_scale) as f32;
+                let tile = match cache.get_mut(&key) {
+                    Some(entry) => {
+                        entry.1 = now;
+                        &entry.0
+                    }
+                    None => {
+                        let tile = self.draw_tile(tile_x, tile_y)?;
+                        &cache.entry(key).or_insert((tile, now)).0
+                    }
+                };
+                frame.with_save(|frame| {
+                    frame.translate(Vector::new(left, top));
+                    tile.replay(frame);
+                });
+            }
+        }
+        // Drop the tiles used least recently, keeping at least those just drawn.
+        while cache.len() > self.capacity {
+            let oldest = cache
+                .iter()
+                .filter(|(_, (_, used))| *used < now)
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(key, _)| *key);
+            match oldest {
+                Some(key) => cache.remove(&key),
+                None => break,
+            };
+        }
+        Ok(())
+    }
+
+    fn draw_tile(&self, x: Range<f64>, y: Range<f64>) -> Result<TileFrame, BuildError> {
+        let mut tile = TileFrame::new(Size::new(TILE_SIZE, TILE_SIZE));
+        let backend = IcedBackend::new(&mut tile)?;
+        self.source.draw_tile(x, y, backend.into_drawing_area())?;
+        Ok(tile)
+    }
+}
+
+/// The zoom level of `scale`, in pixels per data unit, and the scale of that level.
+fn level(scale: f64) -> (i64, f64) {
+    let level = (scale.log2() * LEVELS_PER_OCTAVE).round() as i64;
+    (level, (level as f64 / LEVELS_PER_OCTAVE).exp2())
+}
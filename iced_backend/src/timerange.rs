@@ -0,0 +1,137 @@
+//! Time ranges for x axes holding Unix timestamps, built from chrono types, with the
+//! `chrono` feature.
+
+use crate::{Chart, ChartWidget, ViewState};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use std::ops::Range;
+
+/// The Unix timestamp of `time` in seconds, as time axes hold it.
+pub fn timestamp<Tz: TimeZone>(time: &DateTime<Tz>) -> f64 {
+    time.timestamp() as f64 + f64::from(time.timestamp_subsec_nanos()) * 1e-9
+}
+
+/// The UTC date and time of the Unix timestamp `value`, e.g. to format a tick or a value
+/// under the cursor, or `None` outside chrono's range.
+pub fn to_datetime(value: f64) -> Option<DateTime<Utc>> {
+    if !value.is_finite() {
+        return None;
+    }
+    let seconds = value.floor();
+    let nanos = ((value - seconds) * 1e9) as u32;
+    DateTime::from_timestamp(seconds as i64, nanos.min(999_999_999))
+}
+
+/// Ranges of Unix timestamps for [`ViewState`]s and [`ChartWidget::set_x_range`]:
+///
+/// ```ignore
+/// let view = TimeRange::last_n_minutes(15).view(0.0..100.0);
+/// widget.set_x_range(TimeRange::between(&start, &end).range());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeRange(Range<f64>);
+
+impl TimeRange {
+    /// The range from `start` to `end`.
+    pub fn between<Tz: TimeZone>(start: &DateTime<Tz>, end: &DateTime<Tz>) -> Self {
+        Self(timestamp(start)..timestamp(end))
+    }
+
+    /// The `duration` up to now.
+    pub fn last(duration: Duration) -> Self {
+        let now = Utc::now();
+        Self::between(&(now - duration), &now)
+    }
+
+    pub fn last_n_seconds(seconds: i64) -> Self {
+        Self::last(Duration::seconds(seconds))
+    }
+
+    pub fn last_n_minutes(minutes: i64) -> Self {
+        Self::last(Duration::minutes(minutes))
+    }
+
+    pub fn last_n_hours(hours: i64) -> Self {
+        Self::last(Duration::hours(hours))
+    }
+
+    pub fn last_n_days(days: i64) -> Self {
+        Self::last(Duration::days(days))
+    }
+
+    pub fn range(&self) -> Range<f64> {
+        self.0.clone()
+    }
+
+    /// A view showing this range along x and `y` along y.
+    pub fn view(&self, y: Range<f64>) -> ViewState {
+        ViewState::new(self.range(), y)
+    }
+}
+
+impl From<TimeRange> for Range<f64> {
+    fn from(range: TimeRange) -> Self {
+        range.0
+    }
+}
+
+/// How a [`TimeWindow`] moves along with new data.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Follow {
+    /// Keeps the length of the visible range, sliding it to end at the newest point.
+    Sliding,
+    /// Keeps the start of the visible range and extends its end to the newest point.
+    Growing,
+}
+
+/// Keeps the x range of a [`ChartWidget`] at the newest point of streaming data:
+///
+/// ```ignore
+/// // In update, after new points arrived:
+/// self.window.follow(&mut self.chart, newest_time);
+/// ```
+///
+/// It only follows while the view shows the newest point seen so far; after the user pans
+/// back to older data it leaves the view alone until they pan forward again.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeWindow {
+    mode: Follow,
+    /// The newest point seen, as a Unix timestamp.
+    newest: Option<f64>,
+}
+
+impl TimeWindow {
+    pub fn new(mode: Follow) -> Self {
+        Self { mode, newest: None }
+    }
+
+    /// The x range after the data reached `newest`, if the view showing `current` should
+    /// follow it.
+    pub fn next_range(&mut self, current: Range<f64>, newest: f64) -> Option<Range<f64>> {
+        let previous = self.newest.replace(newest);
+        let following = !matches!(previous, Some(previous) if current.end < previous);
+        if !following || newest <= current.end {
+            return None;
+        }
+        Some(match self.mode {
+            Follow::Sliding => newest - (current.end - current.start)..newest,
+            Follow::Growing => current.start..newest,
+        })
+    }
+
+    /// Moves the x range of `widget` to `newest`, a Unix timestamp, if it follows the data.
+    pub fn follow<Message, C: Chart>(&mut self, widget: &mut ChartWidget<Message, C>, newest: f64) {
+        let current = widget.view_state().x.as_range();
+        if let Some(range) = self.next_range(current, newest) {
+            widget.set_x_range(range);
+        }
+    }
+
+    /// Like [`TimeWindow::follow`], taking the newest point as a date and time.
+    pub fn follow_datetime<Message, C: Chart, Tz: TimeZone>(
+        &mut self,
+        widget: &mut ChartWidget<Message, C>,
+        newest: &DateTime<Tz>,
+    ) {
+        self.follow(widget, timestamp(newest));
+    }
+}
@@ -0,0 +1,154 @@
+use iced::canvas::{Frame, Text};
+use iced::{Color, Font, HorizontalAlignment, Point, Size, VerticalAlignment};
+
+/// The space between the slots and around them.
+const SPACING: f32 = 6.0;
+
+/// How the title, subtitle and caption of a [`ChartWidget`](crate::ChartWidget) look,
+/// see [`ChartWidget::title`](crate::ChartWidget::title).
+#[derive(Debug, Clone, Copy)]
+pub struct TitleStyle {
+    font: Font,
+    title_size: f32,
+    subtitle_size: f32,
+    caption_size: f32,
+    color: Color,
+    /// The color of the subtitle and the caption.
+    secondary_color: Color,
+}
+
+impl TitleStyle {
+    pub fn new() -> Self {
+        Self {
+            font: Font::Default,
+            title_size: 20.0,
+            subtitle_size: 14.0,
+            caption_size: 12.0,
+            color: Color::BLACK,
+            secondary_color: Color::from_rgb(0.4, 0.4, 0.4),
+        }
+    }
+
+    /// Sets the font of all slots, e.g. one loaded by the application.
+    pub fn font(mut self, font: Font) -> Self {
+        self.font = font;
+        self
+    }
+
+    pub fn title_size(mut self, size: f32) -> Self {
+        self.title_size = size;
+        self
+    }
+
+    pub fn subtitle_size(mut self, size: f32) -> Self {
+        self.subtitle_size = size;
+        self
+    }
+
+    pub fn caption_size(mut self, size: f32) -> Self {
+        self.caption_size = size;
+        self
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Sets the color of the subtitle and the caption.
+    pub fn secondary_color(mut self, color: Color) -> Self {
+        self.secondary_color = color;
+        self
+    }
+}
+
+impl Default for TitleStyle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The texts the widget lays out above and below the chart.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Titles {
+    pub title: Option<String>,
+    pub subtitle: Option<String>,
+    pub caption: Option<String>,
+    pub style: TitleStyle,
+}
+
+impl Titles {
+    /// The height of the band above the chart, holding the title and the subtitle.
+    pub fn above(&self) -> f32 {
+        let title = self.title.as_ref().map_or(0.0, |_| self.style.title_size);
+        let subtitle = self
+            .subtitle
+            .as_ref()
+            .map_or(0.0, |_| self.style.subtitle_size);
+        match (self.title.is_some(), self.subtitle.is_some()) {
+            (false, false) => 0.0,
+            (true, true) => title + subtitle + 3.0 * SPACING,
+            _ => title + subtitle + 2.0 * SPACING,
+        }
+    }
+
+    /// The height of the band below the chart, holding the caption.
+    pub fn below(&self) -> f32 {
+        self.caption
+            .as_ref()
+            .map_or(0.0, |_| self.style.caption_size + 2.0 * SPACING)
+    }
+
+    /// Draws the slots onto the bands of a canvas of `size`, filling them with
+    /// `background` first.
+    pub fn draw(&self, frame: &mut Frame, size: Size, background: Option<Color>) {
+        let (above, below) = (self.above(), self.below());
+        if let Some(background) = background {
+            if above > 0.0 {
+                frame.fill_rectangle(Point::ORIGIN, Size::new(size.width, above), background);
+            }
+            if below > 0.0 {
+                frame.fill_rectangle(
+                    Point::new(0.0, size.height - below),
+                    Size::new(size.width, below),
+                    background,
+                );
+            }
+        }
+        let centered = |content: &str, y: f32, text_size: f32, color: Color| Text {
+            content: content.to_string(),
+            position: Point::new(size.width / 2.0, y),
+            color,
+            size: text_size,
+            font: self.style.font,
+            horizontal_alignment: HorizontalAlignment::Center,
+            vertical_alignment: VerticalAlignment::Top,
+        };
+        let mut y = SPACING;
+        if let Some(title) = &self.title {
+            let text = centered(title, y, self.style.title_size, self.style.color);
+            frame.fill_text(text);
+            y += self.style.title_size + SPACING;
+        }
+        if let Some(subtitle) = &self.subtitle {
+            let text = centered(
+                subtitle,
+                y,
+                self.style.subtitle_size,
+                self.style.secondary_color,
+            );
+            frame.fill_text(text);
+        }
+        if let Some(caption) = &self.caption {
+            // Captions read like footnotes, from the left edge.
+            frame.fill_text(Text {
+                content: caption.clone(),
+                position: Point::new(SPACING, size.height - below + SPACING),
+                color: self.style.secondary_color,
+                size: self.style.caption_size,
+                font: self.style.font,
+                ..Text::default()
+            });
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use crate::SeriesId;
+use iced::canvas::{Frame, Path, Stroke, Text};
+use iced::{Color, Point, Rectangle, Size};
+
+/// The distance between a tooltip and the point it belongs to.
+const OFFSET: f32 = 8.0;
+/// The padding between the tooltip box and its text.
+const PADDING: f32 = 4.0;
+/// A rough average glyph width relative to the text size, used to size the box.
+const GLYPH_WIDTH: f32 = 0.6;
+
+/// A tooltip the user pinned by clicking a hovered element. It stays at its data point as
+/// the view is panned and zoomed, and is kept by
+/// [`ChartWidget::save_state`](crate::ChartWidget::save_state).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PinnedTooltip {
+    pub series: SeriesId,
+    /// The index the element was registered with.
+    pub index: usize,
+    pub x: f64,
+    pub y: f64,
+}
+
+/// A box describing the element under the cursor, see
+/// [`ChartWidget::tooltip`](crate::ChartWidget::tooltip).
+///
+/// Clicking a hovered element pins its tooltip, unless [`Tooltip::pinnable`] is turned
+/// off; a pinned tooltip is dismissed with the × in its corner.
+pub struct Tooltip {
+    text_size: f32,
+    pinnable: bool,
+    formatter: Box<dyn Fn(&PinnedTooltip) -> String>,
+}
+
+impl Tooltip {
+    pub fn new() -> Self {
+        Self {
+            text_size: 13.0,
+            pinnable: true,
+            formatter: Box::new(default_format),
+        }
+    }
+
+    pub fn text_size(mut self, size: f32) -> Self {
+        self.text_size = size;
+        self
+    }
+
+    pub fn pinnable(mut self, pinnable: bool) -> Self {
+        self.pinnable = pinnable;
+        self
+    }
+
+    /// Sets the text shown for an element, given its series, index and data point. The
+    /// result may span several lines.
+    pub fn formatter<F>(mut self, f: F) -> Self
+    where
+        F: 'static + Fn(&PinnedTooltip) -> String,
+    {
+        self.formatter = Box::new(f);
+        self
+    }
+
+    pub(crate) fn is_pinnable(&self) -> bool {
+        self.pinnable
+    }
+
+    /// The box of the tooltip of `element` at `at`, above and right of it unless that
+    /// leaves `canvas`.
+    pub(crate) fn bounds(&self, element: &PinnedTooltip, at: Point, canvas: Size) -> Rectangle {
+        let content = (self.formatter)(element);
+        let lines = content.lines().count().max(1) as f32;
+        let columns = content
+            .lines()
+            .map(|l| l.chars().count())
+            .max()
+            .unwrap_or(0) as f32;
+        // Leave room for the close button of a pinned tooltip.
+        let size = Size::new(
+            columns * self.text_size * GLYPH_WIDTH + 2.0 * PADDING + self.text_size,
+            lines * self.text_size + 2.0 * PADDING,
+        );
+        let x = if at.x + OFFSET + size.width <= canvas.width {
+            at.x + OFFSET
+        } else {
+            at.x - OFFSET - size.width
+        };
+        let y = if at.y - OFFSET - size.height >= 0.0 {
+            at.y - OFFSET - size.height
+        } else {
+            at.y + OFFSET
+        };
+        Rectangle::new(Point::new(x, y), size)
+    }
+
+    /// The close button in the corner of a pinned tooltip drawn at `bounds`.
+    pub(crate) fn close_bounds(&self, bounds: Rectangle) -> Rectangle {
+        Rectangle::new(
+            Point::new(bounds.x + bounds.width - self.text_size, bounds.y),
+            Size::new(self.text_size, self.text_size),
+        )
+    }
+
+    /// Draws the tooltip of `element` at `at`, with a close button if it is `pinned`.
+    pub(crate) fn draw(&self, frame: &mut Frame, element: &PinnedTooltip, at: Point, pinned: bool) {
+        let bounds = self.bounds(element, at, frame.size());
+        frame.fill_rectangle(
+            bounds.position(),
+            bounds.size(),
+            Color::from_rgba(1.0, 1.0, 0.95, 0.95),
+        );
+        let border = Stroke {
+            color: Color::from_rgb(0.5, 0.5, 0.5),
+            width: 1.0,
+            ..Stroke::default()
+        };
+        frame.stroke(&Path::rectangle(bounds.position(), bounds.size()), border);
+        frame.fill_text(Text {
+            content: (self.formatter)(element),
+            position: Point::new(bounds.x + PADDING, bounds.y + PADDING),
+            size: self.text_size,
+            ..Text::default()
+        });
+        if pinned {
+            frame.fill(&Path::circle(at, 3.0), Color::from_rgb(0.3, 0.3, 0.3));
+            let close = self.close_bounds(bounds);
+            frame.fill_text(Text {
+                content: "×".to_string(),
+                position: Point::new(close.x + close.width * 0.2, close.y),
+                size: self.text_size,
+                ..Text::default()
+            });
+        }
+    }
+}
+
+impl Default for Tooltip {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn default_format(element: &PinnedTooltip) -> String {
+    format!("x: {:.4}\ny: {:.4}", element.x, element.y)
+}
@@ -0,0 +1,21 @@
+//! Instrumentation through `tracing`, compiled in only with the `tracing` feature so the
+//! crate doesn't depend on it otherwise.
+
+/// Enters a debug span until the end of the enclosing block, taking the arguments of
+/// `tracing::debug_span!`.
+macro_rules! span {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!($($arg)*).entered();
+    };
+}
+
+/// Emits a debug event, taking the arguments of `tracing::debug!`.
+macro_rules! event {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::debug!($($arg)*);
+    };
+}
+
+pub(crate) use {event, span};
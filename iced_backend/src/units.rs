@@ -0,0 +1,237 @@
+use crate::Axis;
+
+/// A unit values can be displayed in, as a linear transform of the stored value.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Unit {
+    pub symbol: String,
+    /// The displayed value is `value * scale + offset`.
+    pub scale: f64,
+    pub offset: f64,
+}
+
+impl Unit {
+    pub fn new(symbol: impl Into<String>, scale: f64) -> Self {
+        Self {
+            symbol: symbol.into(),
+            scale,
+            offset: 0.0,
+        }
+    }
+
+    pub fn with_offset(mut self, offset: f64) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Converts a stored value into this unit.
+    pub fn convert(&self, value: f64) -> f64 {
+        value * self.scale + self.offset
+    }
+
+    /// Converts a value in this unit back into a stored value.
+    pub fn invert(&self, value: f64) -> f64 {
+        (value - self.offset) / self.scale
+    }
+}
+
+/// The units an axis can be toggled between, one of them active.
+///
+/// Switching units only changes how values are labelled; the data and the view ranges
+/// stay in the first (base) unit.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "UnitSetData"))]
+pub struct UnitSet {
+    units: Vec<Unit>,
+    active: usize,
+    precision: usize,
+}
+
+/// A [`UnitSet`] as deserialized, before its active index is checked.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct UnitSetData {
+    units: Vec<Unit>,
+    active: usize,
+    precision: usize,
+}
+
+#[cfg(feature = "serde")]
+impl std::convert::TryFrom<UnitSetData> for UnitSet {
+    type Error = &'static str;
+
+    fn try_from(data: UnitSetData) -> Result<Self, Self::Error> {
+        if data.units.is_empty() {
+            return Err("a unit set needs at least one unit");
+        }
+        // A saved index can outlive the unit it pointed to; fall back to the base unit.
+        let active = if data.active < data.units.len() {
+            data.active
+        } else {
+            0
+        };
+        Ok(Self {
+            units: data.units,
+            active,
+            precision: data.precision,
+        })
+    }
+}
+
+impl UnitSet {
+    /// Starts a set with the unit the data is stored in.
+    pub fn new(base: Unit) -> Self {
+        Self {
+            units: vec![base],
+            active: 0,
+            precision: 2,
+        }
+    }
+
+    /// Adds an alternative unit.
+    pub fn with(mut self, unit: Unit) -> Self {
+        self.units.push(unit);
+        self
+    }
+
+    /// Sets the number of decimals used by [`UnitSet::format`].
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Degrees Celsius, Fahrenheit and Kelvin, for data stored in °C.
+    pub fn temperature() -> Self {
+        Self::new(Unit::new("°C", 1.0))
+            .with(Unit::new("°F", 1.8).with_offset(32.0))
+            .with(Unit::new("K", 1.0).with_offset(273.15))
+    }
+
+    /// Metres and feet, for data stored in metres.
+    pub fn length() -> Self {
+        Self::new(Unit::new("m", 1.0)).with(Unit::new("ft", 1.0 / 0.3048))
+    }
+
+    /// Bytes and their binary multiples, for data stored in bytes.
+    pub fn bytes() -> Self {
+        Self::new(Unit::new("B", 1.0))
+            .with(Unit::new("KiB", 1.0 / 1024.0))
+            .with(Unit::new("MiB", 1.0 / (1024.0 * 1024.0)))
+            .with(Unit::new("GiB", 1.0 / (1024.0 * 1024.0 * 1024.0)))
+    }
+
+    pub fn units(&self) -> &[Unit] {
+        &self.units
+    }
+
+    pub fn active(&self) -> &Unit {
+        &self.units[self.active]
+    }
+
+    /// The index of the active unit.
+    pub fn active_index(&self) -> usize {
+        self.active
+    }
+
+    /// Activates the unit at `index`; out-of-range indices are ignored.
+    pub fn select(&mut self, index: usize) {
+        if index < self.units.len() {
+            self.active = index;
+        }
+    }
+
+    /// Activates the next unit, wrapping around to the base unit.
+    pub fn cycle(&mut self) {
+        self.active = (self.active + 1) % self.units.len();
+    }
+
+    /// Formats a stored value in the active unit, including its symbol.
+    pub fn format(&self, value: f64) -> String {
+        let unit = self.active();
+        format!("{:.*} {}", self.precision, unit.convert(value), unit.symbol)
+    }
+}
+
+/// The unit sets attached to the axes of a chart.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AxisUnits {
+    x: Option<UnitSet>,
+    y: Option<UnitSet>,
+    secondary_y: Option<UnitSet>,
+}
+
+impl AxisUnits {
+    pub(crate) fn get(&self, axis: Axis) -> Option<&UnitSet> {
+        match axis {
+            Axis::X => self.x.as_ref(),
+            Axis::Y => self.y.as_ref(),
+            Axis::SecondaryY => self.secondary_y.as_ref(),
+        }
+    }
+
+    /// The active unit index of every axis that has units.
+    pub(crate) fn active(&self) -> Vec<(Axis, usize)> {
+        [Axis::X, Axis::Y, Axis::SecondaryY]
+            .iter()
+            .filter_map(|&axis| Some((axis, self.get(axis)?.active_index())))
+            .collect()
+    }
+
+    pub(crate) fn get_mut(&mut self, axis: Axis) -> &mut Option<UnitSet> {
+        match axis {
+            Axis::X => &mut self.x,
+            Axis::Y => &mut self.y,
+            Axis::SecondaryY => &mut self.secondary_y,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_and_inverts() {
+        let set = UnitSet::temperature();
+        let [celsius, fahrenheit, kelvin] = [&set.units()[0], &set.units()[1], &set.units()[2]];
+        assert_eq!(celsius.convert(21.5), 21.5);
+        assert!((fahrenheit.convert(100.0) - 212.0).abs() < 1e-9);
+        assert!((kelvin.convert(-273.15)).abs() < 1e-9);
+        for unit in set.units().iter().chain(UnitSet::bytes().units()) {
+            for &value in &[-40.0, 0.0, 1.5, 1e6] {
+                assert!((unit.invert(unit.convert(value)) - value).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn cycles_and_selects() {
+        let mut set = UnitSet::length();
+        assert_eq!(set.active().symbol, "m");
+        set.cycle();
+        assert_eq!(set.active().symbol, "ft");
+        set.cycle();
+        assert_eq!(set.active_index(), 0);
+        set.select(5);
+        assert_eq!(set.active_index(), 0);
+        set.select(1);
+        assert_eq!(set.format(1.0), "3.28 ft");
+
+        let mut single = UnitSet::new(Unit::new("s", 1.0));
+        single.cycle();
+        assert_eq!(single.active_index(), 0);
+    }
+
+    #[cfg(all(feature = "serde", feature = "json"))]
+    #[test]
+    fn checks_the_deserialized_active_index() {
+        let mut json = serde_json::to_value(UnitSet::temperature()).unwrap();
+        json["active"] = 7.into();
+        let set: UnitSet = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(set.active().symbol, "°C");
+
+        json["units"] = serde_json::Value::Array(Vec::new());
+        assert!(serde_json::from_value::<UnitSet>(json).is_err());
+    }
+}
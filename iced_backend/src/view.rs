@@ -0,0 +1,583 @@
+use crate::{LegendPosition, PinnedTooltip};
+use iced::{Point, Rectangle, Size};
+use std::ops::Range;
+use std::time::{Duration, Instant};
+
+/// How data values are laid out along an axis.
+///
+/// Zooming and panning happen in the scaled space, so on a log axis a zoom step
+/// multiplies the range and a pan moves it by a constant ratio per pixel.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AxisScale {
+    #[default]
+    Linear,
+    /// Logarithmic; the range must be strictly positive.
+    Log,
+    /// Logarithmic for magnitudes above `threshold`, approximately linear within
+    /// `-threshold..threshold`, so it can cross zero.
+    Symlog { threshold: f64 },
+}
+
+impl AxisScale {
+    fn forward(&self, value: f64) -> f64 {
+        match *self {
+            AxisScale::Linear => value,
+            AxisScale::Log => value.ln(),
+            AxisScale::Symlog { threshold } => value.signum() * (value.abs() / threshold).ln_1p(),
+        }
+    }
+
+    fn inverse(&self, value: f64) -> f64 {
+        match *self {
+            AxisScale::Linear => value,
+            AxisScale::Log => value.exp(),
+            AxisScale::Symlog { threshold } => value.signum() * threshold * value.abs().exp_m1(),
+        }
+    }
+}
+
+/// The visible data range along one axis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AxisRange {
+    pub start: f64,
+    pub end: f64,
+    pub scale: AxisScale,
+}
+
+impl AxisRange {
+    pub fn new(start: f64, end: f64) -> Self {
+        Self {
+            start,
+            end,
+            scale: AxisScale::Linear,
+        }
+    }
+
+    pub fn with_scale(self, scale: AxisScale) -> Self {
+        Self { scale, ..self }
+    }
+
+    pub fn span(&self) -> f64 {
+        self.end - self.start
+    }
+
+    pub fn as_range(&self) -> Range<f64> {
+        self.start..self.end
+    }
+
+    /// Returns where `value` lies in the range, `0.0` being the start and `1.0` the end.
+    pub fn fraction(&self, value: f64) -> f64 {
+        let (start, end) = self.scaled();
+        (self.scale.forward(value) - start) / (end - start)
+    }
+
+    /// The inverse of [`AxisRange::fraction`].
+    pub fn value_at(&self, fraction: f64) -> f64 {
+        let (start, end) = self.scaled();
+        self.scale.inverse(start + fraction * (end - start))
+    }
+
+    /// Scales the range by `factor` while keeping the value at `anchor` (a fraction of the
+    /// range) in place. Factors below `1.0` zoom in.
+    ///
+    /// Ranges the scale can't represent, such as non-positive ones on a log axis, are left
+    /// unchanged by zooming and panning.
+    pub fn zoom(&mut self, factor: f64, anchor: f64) {
+        let (start, end) = self.scaled();
+        let pivot = start + anchor * (end - start);
+        self.set_scaled(
+            pivot - (pivot - start) * factor,
+            pivot + (end - pivot) * factor,
+        );
+    }
+
+    /// Moves the range by `fraction` of its (scaled) span.
+    pub fn pan(&mut self, fraction: f64) {
+        let (start, end) = self.scaled();
+        let delta = fraction * (end - start);
+        self.set_scaled(start + delta, end + delta);
+    }
+
+    /// The distance between ticks when at most `count` of them are spread over the range:
+    /// a power of ten times 1, 2 or 5, like the ticks plotters places on linear axes.
+    pub fn tick_step(&self, count: usize) -> f64 {
+        let raw = self.span().abs() / count.max(1) as f64;
+        if !(raw.is_finite() && raw > 0.0) {
+            return 0.0;
+        }
+        let magnitude = 10f64.powf(raw.log10().floor());
+        [1.0, 2.0, 5.0, 10.0]
+            .iter()
+            .map(|factor| factor * magnitude)
+            .find(|step| *step >= raw)
+            .unwrap_or(10.0 * magnitude)
+    }
+
+    /// Rounds `value` to the nearest tick when at most `count` ticks are spread over the
+    /// range. Values on non-linear axes, whose ticks are not evenly spaced, are returned
+    /// unchanged.
+    pub fn snap_to_tick(&self, value: f64, count: usize) -> f64 {
+        let step = self.tick_step(count);
+        match self.scale {
+            AxisScale::Linear if step > 0.0 => (value / step).round() * step,
+            _ => value,
+        }
+    }
+
+    /// Shows `range`, keeping the scale.
+    pub(crate) fn set(&mut self, range: Range<f64>) {
+        self.start = range.start;
+        self.end = range.end;
+    }
+
+    fn scaled(&self) -> (f64, f64) {
+        (self.scale.forward(self.start), self.scale.forward(self.end))
+    }
+
+    fn set_scaled(&mut self, start: f64, end: f64) {
+        if !(start.is_finite() && end.is_finite()) {
+            return;
+        }
+        self.start = self.scale.inverse(start);
+        self.end = self.scale.inverse(end);
+    }
+}
+
+/// Constraints on the visible range of one axis, in data units, so the user can neither
+/// zoom into sub-sample noise nor out to ranges that overflow.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AxisLimits {
+    /// The lowest value that may be shown.
+    pub min: f64,
+    /// The highest value that may be shown.
+    pub max: f64,
+    /// The smallest visible span.
+    pub min_span: f64,
+    /// The largest visible span.
+    pub max_span: f64,
+}
+
+impl AxisLimits {
+    /// Limits that allow any range.
+    pub fn none() -> Self {
+        Self {
+            min: f64::NEG_INFINITY,
+            max: f64::INFINITY,
+            min_span: 0.0,
+            max_span: f64::INFINITY,
+        }
+    }
+
+    /// Keeps the view within `bounds`, e.g. the extent of the data.
+    pub fn bounds(mut self, bounds: Range<f64>) -> Self {
+        self.min = bounds.start;
+        self.max = bounds.end;
+        self
+    }
+
+    pub fn min_span(mut self, span: f64) -> Self {
+        self.min_span = span;
+        self
+    }
+
+    pub fn max_span(mut self, span: f64) -> Self {
+        self.max_span = span;
+        self
+    }
+}
+
+impl Default for AxisLimits {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+impl AxisRange {
+    /// Brings the range within `limits`: a span outside the allowed spans is resized around
+    /// its center, then the range is moved back within the bounds. Reversed ranges stay
+    /// reversed.
+    pub fn constrain(&mut self, limits: &AxisLimits) {
+        let (low, high) = (self.start.min(self.end), self.start.max(self.end));
+        let center = (low + high) / 2.0;
+        if !center.is_finite() {
+            return;
+        }
+        let span = (high - low)
+            .min(limits.max_span)
+            .min(limits.max - limits.min)
+            .max(limits.min_span);
+        let mut low = center - span / 2.0;
+        if low + span > limits.max {
+            low = limits.max - span;
+        }
+        if low < limits.min {
+            low = limits.min;
+        }
+        let high = low + span;
+        if self.start <= self.end {
+            self.start = low;
+            self.end = high;
+        } else {
+            self.start = high;
+            self.end = low;
+        }
+    }
+}
+
+impl From<Range<f64>> for AxisRange {
+    fn from(range: Range<f64>) -> Self {
+        Self::new(range.start, range.end)
+    }
+}
+
+/// One of the axes of a chart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Axis {
+    X,
+    Y,
+    /// The secondary y axis on the right, see [`ViewState::with_secondary_y`].
+    SecondaryY,
+}
+
+/// The visible ranges of a chart, driven by the widget's interactions.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ViewState {
+    pub x: AxisRange,
+    pub y: AxisRange,
+    /// The range of the secondary y axis drawn on the right, if the chart has one.
+    pub secondary_y: Option<AxisRange>,
+}
+
+impl ViewState {
+    pub fn new(x: Range<f64>, y: Range<f64>) -> Self {
+        Self {
+            x: x.into(),
+            y: y.into(),
+            secondary_y: None,
+        }
+    }
+
+    /// Adds a secondary y axis, matching a chart built with `set_secondary_coord`.
+    pub fn with_secondary_y(mut self, range: Range<f64>) -> Self {
+        self.secondary_y = Some(range.into());
+        self
+    }
+
+    /// Sets the scale of the x axis, e.g. to match a `.log_scale()` range in the chart.
+    pub fn with_x_scale(mut self, scale: AxisScale) -> Self {
+        self.x.scale = scale;
+        self
+    }
+
+    /// Sets the scale of the y axis, e.g. to match a `.log_scale()` range in the chart.
+    pub fn with_y_scale(mut self, scale: AxisScale) -> Self {
+        self.y.scale = scale;
+        self
+    }
+
+    /// Brings every range within its `limits`, see [`AxisRange::constrain`].
+    pub fn constrain(&mut self, limits: &ViewLimits) {
+        self.x.constrain(&limits.x);
+        self.y.constrain(&limits.y);
+        if let Some(y) = &mut self.secondary_y {
+            y.constrain(&limits.secondary_y);
+        }
+    }
+
+    /// Resizes both ranges around their centers so that, in a plotting area of `plot`
+    /// pixels, a pixel spans `ratio` times as many y units as x units; `1.0` gives x and y
+    /// equal scales, as maps and geometry need. Both axes move halfway towards the common
+    /// scale, so zooming a single axis scales the other one with it.
+    ///
+    /// Only linear axes are resized.
+    pub fn fit_aspect(&mut self, plot: Size, ratio: f64) {
+        let (width, height) = (f64::from(plot.width), f64::from(plot.height) * ratio);
+        let x_scale = self.x.span().abs() / width;
+        let y_scale = self.y.span().abs() / height;
+        let scale = (x_scale * y_scale).sqrt();
+        if !(scale.is_finite() && scale > 0.0)
+            || self.x.scale != AxisScale::Linear
+            || self.y.scale != AxisScale::Linear
+        {
+            return;
+        }
+        self.x.zoom(scale / x_scale, 0.5);
+        self.y.zoom(scale / y_scale, 0.5);
+    }
+
+    /// Converts a position inside `plot` (in pixels) into data coordinates.
+    pub fn to_data(&self, plot: Rectangle, point: Point) -> (f64, f64) {
+        (
+            self.x.value_at(f64::from((point.x - plot.x) / plot.width)),
+            self.y
+                .value_at(f64::from((plot.y + plot.height - point.y) / plot.height)),
+        )
+    }
+
+    /// Converts the vertical position of `point` into a value on the secondary y axis.
+    pub fn to_secondary_y(&self, plot: Rectangle, point: Point) -> Option<f64> {
+        self.secondary_y
+            .map(|y| y.value_at(f64::from((plot.y + plot.height - point.y) / plot.height)))
+    }
+
+    /// Converts data coordinates into a position inside `plot` (in pixels).
+    pub fn to_pixel(&self, plot: Rectangle, (x, y): (f64, f64)) -> Point {
+        Point::new(
+            plot.x + self.x.fraction(x) as f32 * plot.width,
+            plot.y + plot.height - self.y.fraction(y) as f32 * plot.height,
+        )
+    }
+}
+
+/// What moved the visible ranges, as reported by [`ChartWidget::on_view_change`].
+///
+/// [`ChartWidget::on_view_change`]: crate::ChartWidget::on_view_change
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViewChangeCause {
+    /// The user panned, zoomed or scaled an axis.
+    User,
+    /// The view coasted after a fling.
+    Coast,
+    /// A step of an animated transition started with [`ChartWidget::animate`].
+    ///
+    /// [`ChartWidget::animate`]: crate::ChartWidget::animate
+    Animation,
+    /// The application changed the view, e.g. with [`ChartWidget::apply`] or
+    /// [`ChartWidget::restore_state`].
+    ///
+    /// [`ChartWidget::apply`]: crate::ChartWidget::apply
+    /// [`ChartWidget::restore_state`]: crate::ChartWidget::restore_state
+    Programmatic,
+    /// A view change was undone or redone.
+    History,
+    /// The plotting area was resized and the view refitted to the locked aspect ratio.
+    Resize,
+}
+
+/// A change of the visible ranges requested by the application, e.g. to jump to a search
+/// result, an alarm or a bookmark. It can be carried in messages and applied with
+/// [`ChartWidget::apply`] or [`ChartWidget::animate`].
+///
+/// [`ChartWidget::apply`]: crate::ChartWidget::apply
+/// [`ChartWidget::animate`]: crate::ChartWidget::animate
+#[derive(Debug, Clone, PartialEq)]
+pub enum ViewCommand {
+    /// Shows this x range, keeping y.
+    SetXRange(Range<f64>),
+    /// Shows this y range, keeping x.
+    SetYRange(Range<f64>),
+    /// Shows this rectangle of data.
+    ZoomTo { x: Range<f64>, y: Range<f64> },
+    /// Shows this view, secondary y axis included.
+    SetView(ViewState),
+}
+
+impl ViewCommand {
+    /// The view `view` changes into.
+    pub(crate) fn target(self, view: &ViewState) -> ViewState {
+        let mut target = view.clone();
+        match self {
+            ViewCommand::SetXRange(x) => target.x.set(x),
+            ViewCommand::SetYRange(y) => target.y.set(y),
+            ViewCommand::ZoomTo { x, y } => {
+                target.x.set(x);
+                target.y.set(y);
+            }
+            ViewCommand::SetView(view) => target = view,
+        }
+        target
+    }
+}
+
+/// An animated change from one view to another.
+#[derive(Debug, Clone)]
+pub(crate) struct ViewTransition {
+    from: ViewState,
+    to: ViewState,
+    start: Instant,
+    duration: Duration,
+}
+
+impl ViewTransition {
+    pub(crate) fn new(from: ViewState, to: ViewState, duration: Duration) -> Self {
+        Self {
+            from,
+            to,
+            start: Instant::now(),
+            duration,
+        }
+    }
+
+    /// The view at `now`, and whether the transition is over.
+    pub(crate) fn at(&self, now: Instant) -> (ViewState, bool) {
+        let elapsed = now.saturating_duration_since(self.start).as_secs_f64();
+        let t = match self.duration.as_secs_f64() {
+            total if total > 0.0 => (elapsed / total).min(1.0),
+            _ => 1.0,
+        };
+        if t >= 1.0 {
+            return (self.to.clone(), true);
+        }
+        // Ease in and out, so the view neither jumps into motion nor stops abruptly.
+        let t = t * t * (3.0 - 2.0 * t);
+        let blend = |from: &AxisRange, to: &AxisRange| AxisRange {
+            start: from.start + (to.start - from.start) * t,
+            end: from.end + (to.end - from.end) * t,
+            scale: to.scale,
+        };
+        let view = ViewState {
+            x: blend(&self.from.x, &self.to.x),
+            y: blend(&self.from.y, &self.to.y),
+            secondary_y: match (&self.from.secondary_y, &self.to.secondary_y) {
+                (Some(from), Some(to)) => Some(blend(from, to)),
+                (_, to) => *to,
+            },
+        };
+        (view, false)
+    }
+}
+
+/// The [`AxisLimits`] of every axis of a view, see
+/// [`ChartWidget::limits`](crate::ChartWidget::limits).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ViewLimits {
+    pub x: AxisLimits,
+    pub y: AxisLimits,
+    pub secondary_y: AxisLimits,
+}
+
+impl Default for ViewState {
+    fn default() -> Self {
+        Self::new(0.0..1.0, 0.0..1.0)
+    }
+}
+
+/// The user-adjustable parts of a [`ChartWidget`](crate::ChartWidget), for persisting a
+/// chart configuration between sessions.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SavedState {
+    pub view: ViewState,
+    /// The index of the active unit of every axis that has units.
+    pub units: Vec<(Axis, usize)>,
+    /// Where the legend is drawn, including where the user dragged it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub legend: LegendPosition,
+    /// The tooltips the user pinned.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pinned: Vec<PinnedTooltip>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() <= 1e-9 * expected.abs().max(1.0),
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    fn assert_range(range: &AxisRange, start: f64, end: f64) {
+        assert_close(range.start, start);
+        assert_close(range.end, end);
+    }
+
+    /// Zooms `range` around `anchor` and checks the value under it stays put.
+    fn zoom_keeps_anchor(mut range: AxisRange, factor: f64, anchor: f64) -> AxisRange {
+        let before = range.value_at(anchor);
+        range.zoom(factor, anchor);
+        assert_close(range.value_at(anchor), before);
+        range
+    }
+
+    #[test]
+    fn linear_zoom_keeps_the_anchor() {
+        let range = zoom_keeps_anchor(AxisRange::new(0.0, 10.0), 0.5, 0.2);
+        assert_range(&range, 1.0, 6.0);
+        let range = zoom_keeps_anchor(AxisRange::new(0.0, 10.0), 2.0, 0.5);
+        assert_range(&range, -5.0, 15.0);
+    }
+
+    #[test]
+    fn log_zoom_multiplies_the_range() {
+        let log = AxisRange::new(1.0, 1000.0).with_scale(AxisScale::Log);
+        let range = zoom_keeps_anchor(log, 0.5, 0.5);
+        assert_range(&range, 1000f64.powf(0.25), 1000f64.powf(0.75));
+        zoom_keeps_anchor(log, 3.0, 0.8);
+    }
+
+    #[test]
+    fn symlog_zoom_crosses_zero() {
+        let symlog = AxisRange::new(-10.0, 10.0).with_scale(AxisScale::Symlog { threshold: 1.0 });
+        let range = zoom_keeps_anchor(symlog, 0.5, 0.5);
+        assert_range(&range, 1.0 - 11f64.sqrt(), 11f64.sqrt() - 1.0);
+        zoom_keeps_anchor(symlog, 0.25, 0.1);
+    }
+
+    #[test]
+    fn pan_moves_by_a_fraction_of_the_scaled_span() {
+        let mut linear = AxisRange::new(0.0, 10.0);
+        linear.pan(-0.25);
+        assert_range(&linear, -2.5, 7.5);
+
+        let mut log = AxisRange::new(1.0, 100.0).with_scale(AxisScale::Log);
+        log.pan(0.5);
+        assert_range(&log, 10.0, 1000.0);
+
+        let mut symlog = AxisRange::new(0.0, 9.0).with_scale(AxisScale::Symlog { threshold: 1.0 });
+        symlog.pan(-1.0);
+        assert_range(&symlog, -9.0, 0.0);
+    }
+
+    #[test]
+    fn non_positive_log_ranges_are_left_unchanged() {
+        for (start, end) in [(0.0, 10.0), (-1.0, 10.0), (-10.0, -1.0)] {
+            let mut range = AxisRange::new(start, end).with_scale(AxisScale::Log);
+            range.zoom(0.5, 0.5);
+            assert_eq!((range.start, range.end), (start, end));
+            range.pan(0.5);
+            assert_eq!((range.start, range.end), (start, end));
+        }
+    }
+
+    #[test]
+    fn constrain_moves_the_range_within_the_bounds() {
+        let limits = AxisLimits::none().bounds(0.0..100.0);
+        let mut range = AxisRange::new(-20.0, 10.0);
+        range.constrain(&limits);
+        assert_range(&range, 0.0, 30.0);
+        let mut range = AxisRange::new(95.0, 105.0);
+        range.constrain(&limits);
+        assert_range(&range, 90.0, 100.0);
+        let mut range = AxisRange::new(-50.0, 150.0);
+        range.constrain(&limits);
+        assert_range(&range, 0.0, 100.0);
+    }
+
+    #[test]
+    fn constrain_resizes_the_span_around_its_center() {
+        let limits = AxisLimits::none().min_span(2.0).max_span(10.0);
+        let mut range = AxisRange::new(4.5, 5.5);
+        range.constrain(&limits);
+        assert_range(&range, 4.0, 6.0);
+        let mut range = AxisRange::new(-20.0, 20.0);
+        range.constrain(&limits);
+        assert_range(&range, -5.0, 5.0);
+    }
+
+    #[test]
+    fn constrain_keeps_reversed_ranges_reversed() {
+        let limits = AxisLimits::none().bounds(0.0..10.0);
+        let mut range = AxisRange::new(12.0, 8.0);
+        range.constrain(&limits);
+        assert_range(&range, 10.0, 6.0);
+    }
+}
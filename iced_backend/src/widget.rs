@@ -0,0 +1,2620 @@
+#[cfg(feature = "export")]
+use crate::export;
+use crate::highlight::HoverHighlight;
+use crate::history::History;
+use crate::hit::{HitRegistry, HIT_RADIUS};
+use crate::interaction::{Interaction, Region};
+use crate::lasso::{self, Lasso};
+use crate::layer::{ChartLayers, LayerCache};
+use crate::legend::LegendEntry;
+use crate::loading::Loading;
+use crate::notation;
+#[cfg(feature = "export")]
+use crate::raster;
+use crate::recording::Recorder;
+use crate::tiles::Tiles;
+use crate::titles::Titles;
+use crate::trace;
+use crate::units::AxisUnits;
+use crate::view::ViewTransition;
+use crate::{
+    Annotation, AnnotationChange, AnnotationId, AnnotationStore, Axis, AxisLock, AxisRange,
+    BuildContext, BuildError, Chart, ChartError, CursorPosition, Dataset, Diagnostics, DroppedFile,
+    DualCursors, EmptyState, ErrorPanel, FileDrop, FocusChange, FocusStyle, FrameStats, Gutters,
+    HistoryStatus, Hit, HitKind, HitPriority, IcedBackend, KeyAction, KeyGroup, Keymap,
+    LabelDensity, Layer, LayerCoord, LegendPosition, LegendStyle, LoadingStyle, MeasureStyle,
+    Measurement, ModifierBindings, MouseBindings, PinnedTooltip, Placeholder, Prepared, Readout,
+    Recording, ReferenceLine, SavedState, ScrollAction, SeriesId, SeriesStats, SharedSeries,
+    StatsBox, TextHalo, TileSource, TimeZone, TitleStyle, Tooltip, UnitSet, ViewChangeCause,
+    ViewCommand, ViewLimits, ViewState,
+};
+use iced::canvas::{self, Cursor, Event, Frame, Geometry};
+use iced::{keyboard, mouse, Command, Point, Rectangle, Size, Vector};
+use plotters::chart::ChartState;
+use plotters::coord::Shift;
+use plotters::drawing::{DrawingArea, IntoDrawingArea};
+use plotters::style::{Color, RGBAColor, WHITE};
+use std::cell::{Cell, RefCell};
+use std::ops::Range;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+type OnLoad<Message> = Box<dyn Fn(Result<Dataset, ChartError>) -> Message>;
+type OnSelect<Message> = Box<dyn Fn(Vec<(SeriesId, usize)>) -> Message>;
+type OnViewChange<Message> = Box<dyn Fn(ViewState, ViewChangeCause) -> Message>;
+type CopyFormatter = Box<dyn Fn(&Hit, (f64, f64)) -> String>;
+type DrawHook =
+    Box<dyn Fn(&BuildContext<'_>, &DrawingArea<IcedBackend<'_>, Shift>) -> Result<(), BuildError>>;
+
+/// The number of views kept for undo unless set with [`ChartWidget::history_limit`].
+const DEFAULT_HISTORY_LIMIT: usize = 50;
+/// The fraction of the view a pan key moves it by.
+const KEY_PAN_STEP: f64 = 0.1;
+/// The factor a zoom key scales the view by.
+const KEY_ZOOM_STEP: f64 = 0.8;
+/// How far the cursor may move between pressing and releasing on a legend entry or a
+/// hovered element for it to count as a click.
+const CLICK_TOLERANCE: f32 = 3.0;
+
+/// A canvas program that draws a [`Chart`] and lets the user pan and zoom it.
+///
+/// Dragging inside the plotting area pans (see [`MouseBindings`] for other buttons and box
+/// zoom), scrolling a mouse wheel zooms around the cursor, scrolling on a trackpad pans and
+/// dragging along an axis gutter scales only that axis. Holding Shift while panning or
+/// zooming moves only x, holding Alt only y (see [`ModifierBindings`] for other
+/// combinations, such as Ctrl+wheel zooming). Every such gesture can be undone with Ctrl+Z
+/// and redone with Ctrl+Shift+Z or Ctrl+Y while the cursor is over the chart, which also
+/// pans and zooms with the arrow and +/- keys; see [`Keymap`] to rebind them.
+pub struct ChartWidget<Message, C> {
+    chart: C,
+    cache: ChartLayers,
+    view: ViewState,
+    /// The view [`KeyAction::ResetView`] goes back to.
+    home: ViewState,
+    limits: ViewLimits,
+    /// The y units per x unit of a pixel, if the aspect ratio is locked.
+    aspect: Option<f64>,
+    /// The size of the plotting area the view was last fitted to the aspect ratio for.
+    fitted_size: Option<Size>,
+    transition: Option<ViewTransition>,
+    density: LabelDensity,
+    snap_to_ticks: bool,
+    background: Option<RGBAColor>,
+    text_halo: Option<TextHalo>,
+    supersampling: u32,
+    /// The problems with the drawing calls of the last chart build, if they are checked.
+    diagnostics: Option<RefCell<Diagnostics>>,
+    debug_overlay: bool,
+    frame_stats: RefCell<FrameStats>,
+    pre_draw: Option<DrawHook>,
+    post_draw: Option<DrawHook>,
+    recorder: Option<Recorder>,
+    /// The shared series the chart draws, with the version last drawn.
+    watched: Vec<(SharedSeries, u64)>,
+    tiles: Option<Tiles>,
+    /// Set with [`ChartWidget::tile_capacity`], applied to tiles set before or after.
+    tile_capacity: Option<usize>,
+    titles: Titles,
+    gutters: Option<Gutters>,
+    time_zone: TimeZone,
+    units: AxisUnits,
+    interaction: Interaction,
+    history: History,
+    /// Whether the running gesture has already been recorded in the history.
+    gesture_recorded: bool,
+    reported_history: HistoryStatus,
+    on_history_change: Option<Box<dyn Fn(HistoryStatus) -> Message>>,
+    keymap: Keymap,
+    focusable: bool,
+    focused: bool,
+    focus_style: FocusStyle,
+    on_focus_change: Option<Box<dyn Fn(FocusChange) -> Message>>,
+    on_key_action: Option<Box<dyn Fn(KeyAction) -> Message>>,
+    legend: LegendPosition,
+    legend_draggable: bool,
+    /// How the widget draws the legend, if it draws it instead of the chart.
+    legend_style: Option<LegendStyle>,
+    /// The series named by the last chart build, for the legend the widget draws.
+    legend_entries: RefCell<Vec<LegendEntry>>,
+    /// Where the legend was last drawn, if the chart reported it or the widget drew it.
+    legend_bounds: RefCell<Option<Rectangle>>,
+    /// The offset of the cursor from the top left corner of the legend being dragged.
+    legend_drag: Option<Vector>,
+    /// Where the legend was pressed, and the series of the entry pressed.
+    legend_press: Option<(Point, Option<SeriesId>)>,
+    hidden: Vec<SeriesId>,
+    on_legend_toggle: Option<Box<dyn Fn(SeriesId, bool) -> Message>>,
+    plot_area: RefCell<Option<Rectangle>>,
+    /// The size of the canvas at the last draw, for [`ChartWidget::screenshot`].
+    size: Cell<Option<Size>>,
+    /// The plotting area of the last build with tiles and the canvas size it was built at,
+    /// where the background of the next build at that size leaves the tiles open.
+    measured_plot: Cell<Option<(Size, Rectangle)>>,
+    /// The coordinate system saved by the last chart build, for [`Chart::build_overlay`].
+    chart_state: RefCell<Option<ChartState<LayerCoord>>>,
+    hits: RefCell<HitRegistry>,
+    hit_radius: f32,
+    hit_priority: HitPriority,
+    /// The generations of the last started and the last finished preparation.
+    preparing: u64,
+    prepared: u64,
+    loading: Option<Loading>,
+    loading_style: LoadingStyle,
+    /// Shown instead of the chart until its first data arrives.
+    placeholder: Option<Placeholder>,
+    empty_state: Option<EmptyState>,
+    /// Whether the last build left no data in the plotting area.
+    empty: Cell<bool>,
+    error: RefCell<Option<ChartError>>,
+    on_error: Option<Box<dyn Fn(ChartError) -> Message>>,
+    /// The error of the last chart build, if it failed.
+    failure: RefCell<Option<ChartError>>,
+    error_panel: Option<ErrorPanel>,
+    on_retry: Option<Box<dyn Fn() -> Message>>,
+    readout: Option<Readout>,
+    cursor: Option<CursorPosition>,
+    on_cursor_move: Option<Box<dyn Fn(Option<CursorPosition>) -> Message>>,
+    file_drop: Option<(FileDrop, OnLoad<Message>)>,
+    highlight: Option<HoverHighlight>,
+    hovered: Option<SeriesId>,
+    hover_pending: bool,
+    on_hover: Option<Box<dyn Fn(Option<SeriesId>) -> Message>>,
+    hovered_element: Option<Hit>,
+    element_pending: bool,
+    on_hover_element: Option<Box<dyn Fn(Option<Hit>) -> Message>>,
+    tooltip: Option<Tooltip>,
+    on_copy: Option<Box<dyn Fn(String) -> Message>>,
+    copy_formatter: CopyFormatter,
+    pinned: Vec<PinnedTooltip>,
+    /// Where the plot was pressed and the element hovered then, for pinning its tooltip.
+    pin_press: Option<(Point, Hit)>,
+    reference_lines: Vec<ReferenceLine>,
+    /// The index of the line being dragged, counting the reference lines first and then
+    /// the dual cursors.
+    dragged_line: Option<usize>,
+    on_reference_move: Option<Box<dyn Fn(usize, f64) -> Message>>,
+    dual_cursors: Option<DualCursors>,
+    on_dual_cursors_move: Option<Box<dyn Fn(f64, f64) -> Message>>,
+    annotations: AnnotationStore,
+    /// The annotations drawn for the view and plotting area they were drawn at.
+    annotation_layer: LayerCache<(ViewState, Rectangle)>,
+    on_annotation_change: Option<Box<dyn Fn(AnnotationChange) -> Message>>,
+    measure_style: MeasureStyle,
+    measuring: bool,
+    /// The points picked for the current measurement, in data coordinates.
+    picks: Vec<(f64, f64)>,
+    on_measure: Option<Box<dyn Fn(Measurement) -> Message>>,
+    lasso_mode: bool,
+    lasso: Option<Lasso>,
+    selection: Vec<(SeriesId, usize)>,
+    on_select: Option<OnSelect<Message>>,
+    stats_box: Option<StatsBox>,
+    stats: RefCell<Vec<SeriesStats>>,
+    on_x_range_change: Option<Box<dyn Fn(AxisRange) -> Message>>,
+    /// The cause of the last view change not reported yet.
+    view_cause: Option<ViewChangeCause>,
+    reported_view: ViewState,
+    on_view_change: Option<OnViewChange<Message>>,
+}
+
+impl<Message, C: Chart> ChartWidget<Message, C> {
+    pub fn new(chart: C) -> Self {
+        Self {
+            chart,
+            cache: ChartLayers::new(),
+            view: ViewState::default(),
+            home: ViewState::default(),
+            limits: ViewLimits::default(),
+            aspect: None,
+            fitted_size: None,
+            transition: None,
+            density: LabelDensity::default(),
+            snap_to_ticks: false,
+            background: Some(WHITE.to_rgba()),
+            text_halo: None,
+            supersampling: 1,
+            diagnostics: None,
+            debug_overlay: false,
+            frame_stats: RefCell::default(),
+            pre_draw: None,
+            post_draw: None,
+            recorder: None,
+            watched: vec![],
+            tiles: None,
+            tile_capacity: None,
+            titles: Titles::default(),
+            gutters: None,
+            time_zone: TimeZone::Utc,
+            units: AxisUnits::default(),
+            interaction: Interaction::default(),
+            history: History::new(DEFAULT_HISTORY_LIMIT),
+            gesture_recorded: false,
+            reported_history: HistoryStatus::default(),
+            on_history_change: None,
+            keymap: Keymap::default(),
+            focusable: false,
+            focused: false,
+            focus_style: FocusStyle::default(),
+            on_focus_change: None,
+            on_key_action: None,
+            legend: LegendPosition::default(),
+            legend_draggable: false,
+            legend_style: None,
+            legend_entries: RefCell::new(vec![]),
+            legend_bounds: RefCell::new(None),
+            legend_drag: None,
+            legend_press: None,
+            hidden: vec![],
+            on_legend_toggle: None,
+            plot_area: RefCell::new(None),
+            size: Cell::new(None),
+            measured_plot: Cell::new(None),
+            chart_state: RefCell::new(None),
+            hits: RefCell::new(HitRegistry::default()),
+            hit_radius: HIT_RADIUS,
+            hit_priority: HitPriority::Closest,
+            preparing: 0,
+            prepared: 0,
+            loading: None,
+            loading_style: LoadingStyle::default(),
+            placeholder: None,
+            empty_state: Some(EmptyState::default()),
+            empty: Cell::new(false),
+            error: RefCell::new(None),
+            on_error: None,
+            failure: RefCell::new(None),
+            error_panel: Some(ErrorPanel::default()),
+            on_retry: None,
+            readout: None,
+            cursor: None,
+            on_cursor_move: None,
+            file_drop: None,
+            highlight: None,
+            hovered: None,
+            hover_pending: false,
+            on_hover: None,
+            hovered_element: None,
+            element_pending: false,
+            on_hover_element: None,
+            tooltip: None,
+            on_copy: None,
+            copy_formatter: Box::new(|_, (x, y)| format!("{}\t{}", x, y)),
+            pinned: vec![],
+            pin_press: None,
+            reference_lines: vec![],
+            dragged_line: None,
+            on_reference_move: None,
+            dual_cursors: None,
+            on_dual_cursors_move: None,
+            annotations: AnnotationStore::new(),
+            annotation_layer: LayerCache::new(),
+            on_annotation_change: None,
+            measure_style: MeasureStyle::default(),
+            measuring: false,
+            picks: vec![],
+            on_measure: None,
+            lasso_mode: false,
+            lasso: None,
+            selection: vec![],
+            on_select: None,
+            stats_box: None,
+            stats: RefCell::new(vec![]),
+            on_x_range_change: None,
+            view_cause: None,
+            reported_view: ViewState::default(),
+            on_view_change: None,
+        }
+    }
+
+    /// Sets the initially visible ranges.
+    pub fn view(mut self, view: ViewState) -> Self {
+        self.view = view;
+        self.view.constrain(&self.limits);
+        self.reported_view = self.view.clone();
+        self.home = self.view.clone();
+        self
+    }
+
+    /// Keeps x and y at the same scale, so a pixel spans as many units along both axes,
+    /// e.g. for maps and XY geometry. See [`ChartWidget::aspect_ratio`].
+    pub fn equal_aspect(self) -> Self {
+        self.aspect_ratio(1.0)
+    }
+
+    /// Locks the scales of the axes so a pixel spans `ratio` times as many y units as x
+    /// units, through zooms, pans and resizes of the chart. Zooming along one axis gutter
+    /// zooms the other axis with it. Only linear axes are locked.
+    pub fn aspect_ratio(mut self, ratio: f64) -> Self {
+        self.aspect = Some(ratio);
+        self
+    }
+
+    /// Limits the ranges the view can show. The limits apply to the user's pans and zooms
+    /// as well as to views set through the widget's methods.
+    pub fn limits(mut self, limits: ViewLimits) -> Self {
+        self.limits = limits;
+        self.view.constrain(&self.limits);
+        self
+    }
+
+    /// Sets how much room tick labels need, see [`BuildContext::x_labels`].
+    pub fn label_density(mut self, density: LabelDensity) -> Self {
+        self.density = density;
+        self
+    }
+
+    /// Sets what scrolling over the plot does: `lines` for mouse wheels and `pixels` for
+    /// trackpads. Swap the defaults, [`ScrollAction::Zoom`] and [`ScrollAction::Pan`],
+    /// to zoom with the trackpad and pan with the wheel.
+    pub fn scroll_actions(mut self, lines: ScrollAction, pixels: ScrollAction) -> Self {
+        self.interaction.line_scroll = lines;
+        self.interaction.pixel_scroll = pixels;
+        self
+    }
+
+    /// Lets the view keep panning after a fast drag is released, slowing down until it
+    /// stops or any mouse button or the wheel is used.
+    ///
+    /// The canvas has no clock of its own, so while [`ChartWidget::is_animating`] holds, the
+    /// application should subscribe to `iced::time::every` at the frame rate and pass each
+    /// instant to [`ChartWidget::tick`].
+    pub fn kinetic_panning(mut self, enabled: bool) -> Self {
+        self.interaction.kinetic = enabled;
+        self
+    }
+
+    /// Sets what dragging with each mouse button does, see [`MouseBindings`].
+    pub fn mouse_bindings(mut self, bindings: MouseBindings) -> Self {
+        self.interaction.bindings = bindings;
+        self
+    }
+
+    /// Sets how held modifier keys change panning and zooming, see [`ModifierBindings`].
+    pub fn modifier_bindings(mut self, bindings: ModifierBindings) -> Self {
+        self.interaction.modifier_bindings = bindings;
+        self
+    }
+
+    /// Restricts panning and zooming in the plotting area to one axis. Dragging an axis
+    /// gutter still scales that axis.
+    pub fn axis_lock(mut self, lock: AxisLock) -> Self {
+        self.interaction.lock = lock;
+        self
+    }
+
+    /// Sets how far from an element, in pixels, the cursor still hits it, e.g. larger for
+    /// touch input. This also applies to grabbing reference lines and snapping
+    /// measurement picks to points.
+    pub fn hit_radius(mut self, radius: f32) -> Self {
+        self.hit_radius = radius;
+        self
+    }
+
+    /// Sets which element is hit when several lie within the hit radius.
+    pub fn hit_priority(mut self, priority: HitPriority) -> Self {
+        self.hit_priority = priority;
+        self
+    }
+
+    /// Snaps the cursor position, measurement picks and dragged reference lines to the
+    /// nearest tick of the axes, with the tick spacing following the zoom level. Picks
+    /// still snap to a data point within reach first.
+    pub fn snap_to_ticks(mut self, enabled: bool) -> Self {
+        self.snap_to_ticks = enabled;
+        self
+    }
+
+    /// Sets the color charts fill the canvas with through
+    /// [`BuildContext::fill_background`], white by default. A translucent color lets the
+    /// content behind the chart show through.
+    pub fn background(mut self, color: impl Color) -> Self {
+        self.background = Some(color.to_rgba());
+        self
+    }
+
+    /// Leaves the canvas unfilled, so the chart is drawn directly over whatever lies
+    /// behind it, such as an image or another canvas.
+    pub fn transparent_background(mut self) -> Self {
+        self.background = None;
+        self
+    }
+
+    /// Outlines all text of the chart, such as tick labels and labels drawn over the data,
+    /// so it stays readable over dense series.
+    pub fn text_halo(mut self, halo: TextHalo) -> Self {
+        self.text_halo = Some(halo);
+        self
+    }
+
+    /// Sets a title the widget draws centered above the chart, in the font of the
+    /// [`ChartWidget::title_style`]. Unlike a plotters caption, the space it takes is
+    /// taken off the top of the area the chart is built on.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.titles.title = Some(title.into());
+        self
+    }
+
+    /// Sets a subtitle drawn centered below the [`ChartWidget::title`].
+    pub fn subtitle(mut self, subtitle: impl Into<String>) -> Self {
+        self.titles.subtitle = Some(subtitle.into());
+        self
+    }
+
+    /// Sets a caption or footnote drawn below the chart, e.g. the source of the data.
+    pub fn caption(mut self, caption: impl Into<String>) -> Self {
+        self.titles.caption = Some(caption.into());
+        self
+    }
+
+    pub fn title_style(mut self, style: TitleStyle) -> Self {
+        self.titles.style = style;
+        self
+    }
+
+    /// Sets the margin around the chart and the size of its label areas, replacing the
+    /// chart's own, e.g. [`LabelArea::Auto`](crate::LabelArea::Auto) to fit long y labels.
+    /// Charts read it through [`BuildContext::margin`],
+    /// [`BuildContext::x_label_area_size`] and [`BuildContext::y_label_area_size`].
+    pub fn gutters(mut self, gutters: Gutters) -> Self {
+        self.gutters = Some(gutters);
+        self
+    }
+
+    /// Draws the chart at `factor` (1 to 4) times the canvas size and scales it down,
+    /// so thin diagonal lines and text stay smooth while the view is animated. The extra
+    /// geometry costs memory and tessellation time.
+    ///
+    /// The chart should multiply its pixel sizes by [`BuildContext::supersampling`]; the
+    /// widget's own layers are drawn at the canvas size.
+    pub fn supersampling(mut self, factor: u32) -> Self {
+        self.supersampling = factor.clamp(1, 4);
+        self
+    }
+
+    /// Checks every drawing call of the chart build for why it might draw nothing, such as
+    /// transparent colors or shapes outside the canvas, and collects the findings for
+    /// [`ChartWidget::diagnostics`]. Meant for development, as it slows down drawing.
+    pub fn validate_drawing(mut self, enabled: bool) -> Self {
+        self.diagnostics = Some(RefCell::default()).filter(|_| enabled);
+        self
+    }
+
+    /// Shows how long the last draw took per stage, whether the cached chart was reused, the
+    /// points before and after decimation and the view ranges over the chart, while tuning
+    /// charts of large data. F12 toggles it by default, see [`KeyAction::ToggleDebugOverlay`].
+    pub fn debug_overlay(mut self, shown: bool) -> Self {
+        self.debug_overlay = shown;
+        self
+    }
+
+    /// Sets a function drawing onto the whole canvas before the chart is built, e.g. a
+    /// frame border or a watermark behind the data. Its area includes the space taken by the
+    /// [`ChartWidget::title`], the subtitle and the caption, which are drawn over it.
+    ///
+    /// A chart filling its root area with a background covers what it draws; set
+    /// [`ChartWidget::background`] instead and leave the root unfilled.
+    pub fn pre_draw<F>(mut self, f: F) -> Self
+    where
+        F: 'static
+            + Fn(&BuildContext<'_>, &DrawingArea<IcedBackend<'_>, Shift>) -> Result<(), BuildError>,
+    {
+        self.pre_draw = Some(Box::new(f));
+        self
+    }
+
+    /// Sets a function drawing onto the whole canvas after the chart is built, e.g. a
+    /// watermark or a timestamp over every chart. Like [`ChartWidget::pre_draw`], it
+    /// draws on the space of the titles too.
+    ///
+    /// Its errors are reported like the chart's own, see [`ChartWidget::on_error`].
+    pub fn post_draw<F>(mut self, f: F) -> Self
+    where
+        F: 'static
+            + Fn(&BuildContext<'_>, &DrawingArea<IcedBackend<'_>, Shift>) -> Result<(), BuildError>,
+    {
+        self.post_draw = Some(Box::new(f));
+        self
+    }
+
+    /// Sets the zone timestamps are displayed in, by the chart through
+    /// [`BuildContext::time_zone`] and by a [`Readout::time_x`]. UTC by default.
+    pub fn time_zone(mut self, zone: TimeZone) -> Self {
+        self.time_zone = zone;
+        self
+    }
+
+    /// Lets `axis` be displayed in any of `units`. Right-clicking the axis gutter cycles
+    /// through them.
+    pub fn units(mut self, axis: Axis, units: UnitSet) -> Self {
+        *self.units.get_mut(axis) = Some(units);
+        self
+    }
+
+    /// Sets how many views are kept for undo.
+    pub fn history_limit(mut self, limit: usize) -> Self {
+        self.history.set_limit(limit);
+        self
+    }
+
+    /// Sets the message produced when views become available or unavailable for undo
+    /// and redo.
+    pub fn on_history_change<F>(mut self, f: F) -> Self
+    where
+        F: 'static + Fn(HistoryStatus) -> Message,
+    {
+        self.on_history_change = Some(Box::new(f));
+        self
+    }
+
+    /// Lets the chart take the keyboard focus when clicked, so it only reacts to keys
+    /// while focused instead of while the cursor is over it, e.g. with several charts on
+    /// one screen. A ring is drawn around the focused chart, see
+    /// [`ChartWidget::focus_style`].
+    ///
+    /// Tab and Shift+Tab give the focus up and are reported through
+    /// [`ChartWidget::on_focus_change`], for the application to focus the next widget; call
+    /// [`ChartWidget::focus`] when tabbing into the chart.
+    pub fn focusable(mut self, focusable: bool) -> Self {
+        self.focusable = focusable;
+        self
+    }
+
+    pub fn focus_style(mut self, style: FocusStyle) -> Self {
+        self.focus_style = style;
+        self
+    }
+
+    /// Sets the message produced when the chart gains or loses focus by the user.
+    pub fn on_focus_change<F>(mut self, f: F) -> Self
+    where
+        F: 'static + Fn(FocusChange) -> Message,
+    {
+        self.on_focus_change = Some(Box::new(f));
+        self
+    }
+
+    /// Sets where the chart draws its series legend. Charts that draw one read it from
+    /// [`BuildContext::legend_position`].
+    pub fn legend_position(mut self, position: LegendPosition) -> Self {
+        self.legend = position;
+        self
+    }
+
+    /// Has the widget draw the legend itself in `style`, listing the series charts name with
+    /// [`BuildContext::label`]. Clicking an entry hides or shows its series, and hovering it
+    /// highlights the series with [`ChartWidget::hover_highlight`].
+    pub fn legend(mut self, style: LegendStyle) -> Self {
+        self.legend_style = Some(style);
+        self
+    }
+
+    /// Sets the message produced when the user hides or shows a series through the legend,
+    /// with whether it is now visible.
+    pub fn on_legend_toggle<F>(mut self, f: F) -> Self
+    where
+        F: 'static + Fn(SeriesId, bool) -> Message,
+    {
+        self.on_legend_toggle = Some(Box::new(f));
+        self
+    }
+
+    /// Lets the user drag the legend to a [`LegendPosition::Floating`] position, kept by
+    /// [`ChartWidget::save_state`].
+    pub fn legend_draggable(mut self, draggable: bool) -> Self {
+        self.legend_draggable = draggable;
+        self
+    }
+
+    /// Sets the keys the widget reacts to while the cursor is over it, or while it has focus
+    /// if it is [`ChartWidget::focusable`], see [`Keymap`].
+    pub fn keymap(mut self, keymap: Keymap) -> Self {
+        self.keymap = keymap;
+        self
+    }
+
+    /// Sets the message produced for key actions the widget leaves to the application,
+    /// such as [`KeyAction::Screenshot`].
+    pub fn on_key_action<F>(mut self, f: F) -> Self
+    where
+        F: 'static + Fn(KeyAction) -> Message,
+    {
+        self.on_key_action = Some(Box::new(f));
+        self
+    }
+
+    /// Sets the message produced when building the chart fails.
+    ///
+    /// The draw pass cannot emit messages, so the error is delivered with the next canvas
+    /// event.
+    pub fn on_error<F>(mut self, f: F) -> Self
+    where
+        F: 'static + Fn(ChartError) -> Message,
+    {
+        self.on_error = Some(Box::new(f));
+        self
+    }
+
+    /// Sets the panel shown instead of the chart after building it failed. By default a
+    /// panel with the error message and a retry button is shown.
+    pub fn error_panel(mut self, panel: ErrorPanel) -> Self {
+        self.error_panel = Some(panel);
+        self
+    }
+
+    /// Leaves whatever the failed build drew on screen instead of an error panel.
+    pub fn no_error_panel(mut self) -> Self {
+        self.error_panel = None;
+        self
+    }
+
+    /// Sets the message produced when the retry button of the error panel is clicked,
+    /// e.g. to fetch the data again. The chart is built again either way.
+    pub fn on_retry<F>(mut self, f: F) -> Self
+    where
+        F: 'static + Fn() -> Message,
+    {
+        self.on_retry = Some(Box::new(f));
+        self
+    }
+
+    /// Shows the cursor's data coordinates in a corner of the plotting area.
+    pub fn readout(mut self, readout: Readout) -> Self {
+        self.readout = Some(readout);
+        self
+    }
+
+    /// Sets the message produced when the data coordinates under the cursor change, to
+    /// feed a readout outside the canvas. `None` means the cursor left the plotting area.
+    pub fn on_cursor_move<F>(mut self, f: F) -> Self
+    where
+        F: 'static + Fn(Option<CursorPosition>) -> Message,
+    {
+        self.on_cursor_move = Some(Box::new(f));
+        self
+    }
+
+    /// Emphasizes the registered series under the cursor.
+    pub fn hover_highlight(mut self, highlight: HoverHighlight) -> Self {
+        self.highlight = Some(highlight);
+        self
+    }
+
+    /// Sets the message produced when the registered series under the cursor changes.
+    pub fn on_hover<F>(mut self, f: F) -> Self
+    where
+        F: 'static + Fn(Option<SeriesId>) -> Message,
+    {
+        self.on_hover = Some(Box::new(f));
+        self
+    }
+
+    /// Adds a reference line; lines are indexed in the order they are added.
+    pub fn reference_line(mut self, line: ReferenceLine) -> Self {
+        self.reference_lines.push(line);
+        self
+    }
+
+    /// Sets the message produced while the user drags the reference line at an index,
+    /// carrying its new value.
+    pub fn on_reference_move<F>(mut self, f: F) -> Self
+    where
+        F: 'static + Fn(usize, f64) -> Message,
+    {
+        self.on_reference_move = Some(Box::new(f));
+        self
+    }
+
+    /// Sets the message produced when the registered element under the cursor changes,
+    /// e.g. to report the contributions of a [`Stacked`](crate::Stacked) layer at the
+    /// hovered index.
+    pub fn on_hover_element<F>(mut self, f: F) -> Self
+    where
+        F: 'static + Fn(Option<Hit>) -> Message,
+    {
+        self.on_hover_element = Some(Box::new(f));
+        self
+    }
+
+    /// Shows a tooltip for the registered element under the cursor. Clicking the element
+    /// pins the tooltip, which then stays at its data point while the view changes.
+    pub fn tooltip(mut self, tooltip: Tooltip) -> Self {
+        self.tooltip = Some(tooltip);
+        self
+    }
+
+    /// Sets the message produced with the text to copy when [`KeyAction::CopyValue`] is
+    /// triggered, Ctrl+C by default, instead of reporting the action through
+    /// [`ChartWidget::on_key_action`]. Widgets can't write to the clipboard, so the
+    /// application does.
+    pub fn on_copy<F>(mut self, f: F) -> Self
+    where
+        F: 'static + Fn(String) -> Message,
+    {
+        self.on_copy = Some(Box::new(f));
+        self
+    }
+
+    /// Sets how a copied element is written, given the element and its data point, e.g.
+    /// to look up and copy the whole record behind it. By default x and y are separated by
+    /// a tab, which spreadsheets paste into two cells.
+    pub fn copy_formatter<F>(mut self, f: F) -> Self
+    where
+        F: 'static + Fn(&Hit, (f64, f64)) -> String,
+    {
+        self.copy_formatter = Box::new(f);
+        self
+    }
+
+    /// Shows two draggable x cursors and a table comparing the series values at them.
+    pub fn dual_cursors(mut self, cursors: DualCursors) -> Self {
+        self.dual_cursors = Some(cursors);
+        self
+    }
+
+    /// Sets the message produced while the user drags one of the dual cursors, carrying
+    /// the positions of A and B.
+    pub fn on_dual_cursors_move<F>(mut self, f: F) -> Self
+    where
+        F: 'static + Fn(f64, f64) -> Message,
+    {
+        self.on_dual_cursors_move = Some(Box::new(f));
+        self
+    }
+
+    /// Starts with the annotations of `store`, e.g. ones loaded from disk.
+    pub fn annotations(mut self, store: AnnotationStore) -> Self {
+        self.annotations = store;
+        self
+    }
+
+    /// Sets the message produced when an annotation is added, updated or removed.
+    pub fn on_annotation_change<F>(mut self, f: F) -> Self
+    where
+        F: 'static + Fn(AnnotationChange) -> Message,
+    {
+        self.on_annotation_change = Some(Box::new(f));
+        self
+    }
+
+    /// Sets how measurements are presented, see [`ChartWidget::set_measuring`].
+    pub fn measure_style(mut self, style: MeasureStyle) -> Self {
+        self.measure_style = style;
+        self
+    }
+
+    /// Sets the message produced when a measurement is completed.
+    pub fn on_measure<F>(mut self, f: F) -> Self
+    where
+        F: 'static + Fn(Measurement) -> Message,
+    {
+        self.on_measure = Some(Box::new(f));
+        self
+    }
+
+    /// Sets the message produced when a lasso selection is completed, carrying the
+    /// series and index of every selected point.
+    pub fn on_select<F>(mut self, f: F) -> Self
+    where
+        F: 'static + Fn(Vec<(SeriesId, usize)>) -> Message,
+    {
+        self.on_select = Some(Box::new(f));
+        self
+    }
+
+    /// Redraws the chart whenever `series` changes, see [`SharedSeries`].
+    ///
+    /// Changes are picked up with the next canvas event, or by calling
+    /// [`ChartWidget::sync`], e.g. on a timer.
+    pub fn watch(mut self, series: &SharedSeries) -> Self {
+        self.watched.push((series.clone(), series.version()));
+        self
+    }
+
+    /// Draws the data in tiles of [`TILE_SIZE`](crate::TILE_SIZE) pixels by `source`,
+    /// under the chart, for data too large to draw whole on every pan.
+    ///
+    /// Tiles are cached per zoom level, so panning only draws the tiles coming into view.
+    /// The chart should then only draw its axes and fill its background with
+    /// [`BuildContext::fill_background`], which leaves the plotting area open for the
+    /// tiles. Tiles are only drawn while both axes are linear. Call
+    /// [`ChartWidget::clear_tiles`] after the data changed.
+    pub fn tiles(mut self, source: impl TileSource + 'static) -> Self {
+        let mut tiles = Tiles::new(source);
+        if let Some(capacity) = self.tile_capacity {
+            tiles.set_capacity(capacity);
+        }
+        self.tiles = Some(tiles);
+        self
+    }
+
+    /// Sets how many tiles are kept across zoom levels, 256 by default. The tiles used
+    /// least recently are dropped first.
+    pub fn tile_capacity(mut self, capacity: usize) -> Self {
+        self.tile_capacity = Some(capacity);
+        if let Some(tiles) = &mut self.tiles {
+            tiles.set_capacity(capacity);
+        }
+        self
+    }
+
+    /// Drops the cached [`ChartWidget::tiles`], so they are drawn again from the data.
+    pub fn clear_tiles(&mut self) {
+        if let Some(tiles) = &mut self.tiles {
+            tiles.clear();
+        }
+        self.cache.clear();
+    }
+
+    /// Shows statistics of the visible part of the series tracked with
+    /// [`BuildContext::track_stats`].
+    pub fn stats_box(mut self, stats_box: StatsBox) -> Self {
+        self.stats_box = Some(stats_box);
+        self
+    }
+
+    /// Sets the message produced when the user changes the visible x range, e.g. to keep
+    /// an external control in sync through an [`XRangeBinding`](crate::XRangeBinding).
+    pub fn on_x_range_change<F>(mut self, f: F) -> Self
+    where
+        F: 'static + Fn(AxisRange) -> Message,
+    {
+        self.on_x_range_change = Some(Box::new(f));
+        self
+    }
+
+    /// Sets the message produced whenever the visible ranges change, carrying the new view
+    /// and what changed it, e.g. to fetch finer data for the new window or to keep the
+    /// application's state in sync.
+    ///
+    /// Changes by the user are reported from the event that caused them, coasting and
+    /// animations from [`ChartWidget::tick`]. Changes made by the application through the
+    /// widget's methods are reported by [`ChartWidget::take_view_change`], or otherwise
+    /// with the next event.
+    pub fn on_view_change<F>(mut self, f: F) -> Self
+    where
+        F: 'static + Fn(ViewState, ViewChangeCause) -> Message,
+    {
+        self.on_view_change = Some(Box::new(f));
+        self
+    }
+
+    /// Shows `placeholder` instead of the chart until the first data arrives, which is
+    /// when [`ChartWidget::chart_mut`] or [`ChartWidget::finish_preparation`] is first
+    /// called. The chart is neither built nor interactive until then.
+    pub fn placeholder(mut self, placeholder: Placeholder) -> Self {
+        self.placeholder = Some(placeholder);
+        self
+    }
+
+    /// Sets what is shown when no data lies in the visible ranges, by default "No data in
+    /// range".
+    pub fn empty_state(mut self, empty_state: EmptyState) -> Self {
+        self.empty_state = Some(empty_state);
+        self
+    }
+
+    /// Leaves the plotting area as the chart drew it when no data lies in the visible
+    /// ranges.
+    pub fn no_empty_state(mut self) -> Self {
+        self.empty_state = None;
+        self
+    }
+
+    /// Sets how loading data is shown, see [`ChartWidget::set_loading`].
+    pub fn loading_style(mut self, style: LoadingStyle) -> Self {
+        self.loading_style = style;
+        self
+    }
+
+    /// Opts into loading files dropped onto the chart, see [`ChartWidget::file_dropped`].
+    /// `f` builds the "data loaded" message from the parsed file, or from a
+    /// [`ChartError::File`] naming the file and the malformed line.
+    pub fn on_file_drop<F>(mut self, file_drop: FileDrop, f: F) -> Self
+    where
+        F: 'static + Fn(Result<Dataset, ChartError>) -> Message,
+    {
+        self.file_drop = Some((file_drop, Box::new(f)));
+        self
+    }
+
+    /// Reads a dropped file on the application's executor and returns the command
+    /// producing the message `map` builds from its contents. Hand them to
+    /// [`ChartWidget::finish_file_drop`] to parse them. Nothing is read if file drops are
+    /// not enabled.
+    ///
+    /// Call this with the path of a `window::Event::FileDropped`; the canvas itself never
+    /// receives window events.
+    pub fn file_dropped<M>(&self, path: PathBuf, map: M) -> Command<Message>
+    where
+        M: 'static + Send + Fn(DroppedFile) -> Message,
+    {
+        match &self.file_drop {
+            Some((file_drop, _)) => {
+                let read = file_drop.read(path);
+                Command::perform(async move { read() }, map)
+            }
+            None => Command::none(),
+        }
+    }
+
+    /// Parses a file read by [`ChartWidget::file_dropped`] and returns the "data loaded"
+    /// message, or `None` if file drops are not enabled.
+    pub fn finish_file_drop(&self, file: DroppedFile) -> Option<Message> {
+        let (file_drop, on_load) = self.file_drop.as_ref()?;
+        Some(on_load(file_drop.parse(file)))
+    }
+
+    pub fn chart(&self) -> &C {
+        &self.chart
+    }
+
+    /// Gives mutable access to the chart and schedules a redraw. This also ends the
+    /// [`ChartWidget::placeholder`].
+    pub fn chart_mut(&mut self) -> &mut C {
+        self.placeholder = None;
+        self.cache.clear();
+        &mut self.chart
+    }
+
+    /// Runs `prepare` on the application's executor instead of the UI thread, for heavy
+    /// transforms such as decimation, binning or contouring, and returns the command
+    /// producing the message `map` builds from its result. Meanwhile the chart keeps
+    /// showing its current data and stays interactive; hand the result to
+    /// [`ChartWidget::finish_preparation`] to swap it in.
+    ///
+    /// Starting a preparation supersedes a running one, whose result is discarded when it
+    /// arrives, so a preparation can be started for every view change. The chart shows a
+    /// spinner until the result is swapped in.
+    pub fn prepare<T, F, M>(&mut self, prepare: F, map: M) -> Command<Message>
+    where
+        T: 'static + Send,
+        F: 'static + Send + FnOnce() -> T,
+        M: 'static + Send + Fn(Prepared<T>) -> Message,
+    {
+        self.preparing = self.preparing.wrapping_add(1);
+        self.loading.get_or_insert_with(|| Loading::new(None));
+        let generation = self.preparing;
+        Command::perform(
+            async move {
+                Prepared {
+                    generation,
+                    value: prepare(),
+                }
+            },
+            map,
+        )
+    }
+
+    /// Swaps in the result of the latest preparation by handing it to `apply` along with
+    /// the chart, and redraws the chart. Returns `false` without calling `apply` if the
+    /// result was superseded by a newer preparation.
+    pub fn finish_preparation<T>(
+        &mut self,
+        prepared: Prepared<T>,
+        apply: impl FnOnce(&mut C, T),
+    ) -> bool {
+        if prepared.generation != self.preparing {
+            return false;
+        }
+        self.prepared = prepared.generation;
+        self.loading = None;
+        self.placeholder = None;
+        apply(&mut self.chart, prepared.value);
+        self.cache.clear();
+        true
+    }
+
+    /// Whether a preparation started with [`ChartWidget::prepare`] has not finished yet.
+    pub fn is_preparing(&self) -> bool {
+        self.prepared != self.preparing
+    }
+
+    /// Shows or hides a spinner over the last known chart while data is fetched in the
+    /// background, so the plot neither goes blank nor looks frozen. The spinner turns as
+    /// long as [`ChartWidget::tick`] is called, see [`ChartWidget::is_animating`].
+    pub fn set_loading(&mut self, loading: bool) {
+        self.loading = if loading {
+            Some(self.loading.unwrap_or_else(|| Loading::new(None)))
+        } else {
+            None
+        };
+    }
+
+    /// Shows a progress bar filled to `progress`, between 0 and 1, over the last known
+    /// chart, e.g. as progress messages of a fetch arrive. End it by passing `false` to
+    /// [`ChartWidget::set_loading`].
+    pub fn set_progress(&mut self, progress: f32) {
+        let mut loading = self.loading.unwrap_or_else(|| Loading::new(None));
+        loading.progress = Some(progress);
+        self.loading = Some(loading);
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.loading.is_some()
+    }
+
+    /// The error of the last chart build, if it failed.
+    pub fn build_error(&self) -> Option<ChartError> {
+        self.failure.borrow().clone()
+    }
+
+    /// Builds the chart again, e.g. after the cause of a failed build was fixed.
+    pub fn retry(&mut self) {
+        *self.failure.get_mut() = None;
+        self.cache.clear();
+    }
+
+    /// Redraws the chart if a [`ChartWidget::watch`]ed series changed since it was last
+    /// drawn, and returns whether one did. Only the data layer is rebuilt (see
+    /// [`ChartWidget::invalidate_layer`]); the view and the interaction state are kept.
+    /// [`ChartWidget::tiles`] are kept too: call [`ChartWidget::clear_tiles`] if their
+    /// source reads a watched series.
+    pub fn sync(&mut self) -> bool {
+        let mut changed = false;
+        for (series, seen) in &mut self.watched {
+            let version = series.version();
+            if version != *seen {
+                *seen = version;
+                changed = true;
+            }
+        }
+        if changed {
+            self.cache.clear_layer(Layer::Data);
+        }
+        changed
+    }
+
+    /// Whether the [`ChartWidget::placeholder`] is shown because no data arrived yet.
+    pub fn is_waiting_for_data(&self) -> bool {
+        self.placeholder.is_some()
+    }
+
+    /// Switches the zone timestamps are displayed in, e.g. between UTC and local time,
+    /// and redraws the chart.
+    pub fn set_time_zone(&mut self, zone: TimeZone) {
+        self.time_zone = zone;
+        self.cache.clear();
+    }
+
+    pub fn displayed_time_zone(&self) -> TimeZone {
+        self.time_zone
+    }
+
+    /// The currently visible ranges.
+    pub fn view_state(&self) -> &ViewState {
+        &self.view
+    }
+
+    /// The plotting area of the last chart build, relative to the canvas, or `None` before
+    /// the chart was first drawn.
+    pub fn plotting_area(&self) -> Option<Rectangle> {
+        *self.plot_area.borrow()
+    }
+
+    /// Shows `range` along x, keeping the axis scale, and redraws the chart. Use it to apply
+    /// the value of an external control; it produces no
+    /// [`ChartWidget::on_x_range_change`] message.
+    pub fn set_x_range(&mut self, range: Range<f64>) {
+        self.apply(ViewCommand::SetXRange(range));
+    }
+
+    /// Shows `range` along y, keeping the axis scale, and redraws the chart.
+    pub fn set_y_range(&mut self, range: Range<f64>) {
+        self.apply(ViewCommand::SetYRange(range));
+    }
+
+    /// Shows the data rectangle spanned by `x` and `y` and redraws the chart.
+    pub fn zoom_to(&mut self, x: Range<f64>, y: Range<f64>) {
+        self.apply(ViewCommand::ZoomTo { x, y });
+    }
+
+    /// Changes the view at once, within the widget's limits, and redraws the chart. Any
+    /// running transition or coasting stops.
+    pub fn apply(&mut self, command: ViewCommand) {
+        self.transition = None;
+        self.interaction.stop_coasting();
+        self.view = command.target(&self.view);
+        self.view.constrain(&self.limits);
+        self.fit_aspect();
+        self.cache.clear();
+        self.view_cause = Some(ViewChangeCause::Programmatic);
+    }
+
+    /// Moves the view smoothly to the result of `command` over `duration`, driven by
+    /// [`ChartWidget::tick`] (see [`ChartWidget::is_animating`]). Like
+    /// [`ChartWidget::apply`], the target and every step towards it keep within the
+    /// [`ChartWidget::limits`] and the [`ChartWidget::aspect_ratio`].
+    pub fn animate(&mut self, command: ViewCommand, duration: Duration) {
+        self.interaction.stop_coasting();
+        let mut target = command.target(&self.view);
+        target.constrain(&self.limits);
+        if let (Some(ratio), Some(plot)) = (self.aspect, *self.plot_area.get_mut()) {
+            target.fit_aspect(plot.size(), ratio);
+        }
+        self.transition = Some(ViewTransition::new(self.view.clone(), target, duration));
+    }
+
+    /// Whether the view is coasting after a fling, see [`ChartWidget::kinetic_panning`].
+    pub fn is_coasting(&self) -> bool {
+        self.interaction.is_coasting()
+    }
+
+    /// Whether the view is coasting or in an animated transition, or a loading spinner or
+    /// skeleton is shown, and needs [`ChartWidget::tick`] to be called at the frame rate.
+    pub fn is_animating(&self) -> bool {
+        self.is_coasting()
+            || self.transition.is_some()
+            || self
+                .loading
+                .is_some_and(|loading| loading.progress.is_none())
+            || self
+                .placeholder
+                .as_ref()
+                .is_some_and(|placeholder| placeholder.is_animated())
+    }
+
+    /// Advances a coasting view or an animated transition to `now` and redraws the chart,
+    /// returning the [`ChartWidget::on_view_change`] message, or else the
+    /// [`ChartWidget::on_x_range_change`] message, if the view moved.
+    pub fn tick(&mut self, now: Instant) -> Option<Message> {
+        if let Some(transition) = &self.transition {
+            let (view, done) = transition.at(now);
+            self.view = view;
+            if done {
+                self.transition = None;
+            }
+            self.view_cause = Some(ViewChangeCause::Animation);
+        } else {
+            let plot = (*self.plot_area.get_mut())?;
+            if !self.interaction.coast(&mut self.view, plot, now) {
+                return None;
+            }
+            self.view_cause = Some(ViewChangeCause::Coast);
+        }
+        self.view.constrain(&self.limits);
+        self.fit_aspect();
+        self.cache.clear();
+        if self.on_view_change.is_some() {
+            return self.take_view_change();
+        }
+        self.on_x_range_change
+            .as_ref()
+            .map(|on_x_range_change| on_x_range_change(self.view.x))
+    }
+
+    /// Returns the [`ChartWidget::on_view_change`] message for a view change not reported
+    /// yet, e.g. right after [`ChartWidget::apply`], so the application can react before the
+    /// next event.
+    pub fn take_view_change(&mut self) -> Option<Message> {
+        let cause = self.view_cause.take()?;
+        if self.view == self.reported_view {
+            return None;
+        }
+        self.reported_view = self.view.clone();
+        self.on_view_change
+            .as_ref()
+            .map(|on_view_change| on_view_change(self.view.clone(), cause))
+    }
+
+    /// Activates the unit at `index` of `axis`'s unit set and redraws the chart.
+    pub fn select_unit(&mut self, axis: Axis, index: usize) {
+        if let Some(units) = self.units.get_mut(axis) {
+            units.select(index);
+            self.cache.clear();
+        }
+    }
+
+    /// Switches `axis` to its next unit and redraws the chart.
+    pub fn cycle_unit(&mut self, axis: Axis) {
+        if let Some(units) = self.units.get_mut(axis) {
+            units.cycle();
+            self.cache.clear();
+        }
+    }
+
+    /// The data coordinates under the cursor, if it is over the plotting area.
+    pub fn cursor_position(&self) -> Option<CursorPosition> {
+        self.cursor
+    }
+
+    /// Goes back to the view before the last change, returning whether there was one.
+    pub fn undo_view(&mut self) -> bool {
+        match self.history.undo(&self.view) {
+            Some(view) => {
+                self.view = view;
+                self.cache.clear();
+                self.view_cause = Some(ViewChangeCause::History);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Reapplies the last undone view change, returning whether there was one.
+    pub fn redo_view(&mut self) -> bool {
+        match self.history.redo(&self.view) {
+            Some(view) => {
+                self.view = view;
+                self.cache.clear();
+                self.view_cause = Some(ViewChangeCause::History);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Goes back to the view the widget was created with, recording the current one for
+    /// undo.
+    pub fn reset_view(&mut self) {
+        if self.view != self.home {
+            self.history.record(self.view.clone());
+            self.apply(ViewCommand::SetView(self.home.clone()));
+        }
+    }
+
+    /// Where the legend is drawn, including where the user dragged it.
+    pub fn current_legend_position(&self) -> LegendPosition {
+        self.legend
+    }
+
+    pub fn set_legend_position(&mut self, position: LegendPosition) {
+        if self.legend != position {
+            self.legend = position;
+            self.cache.clear();
+        }
+    }
+
+    /// Hides or shows `series`, for charts that check [`BuildContext::is_hidden`].
+    pub fn set_series_visible(&mut self, series: SeriesId, visible: bool) {
+        if visible != self.is_series_visible(series) {
+            if visible {
+                self.hidden.retain(|hidden| *hidden != series);
+            } else {
+                self.hidden.push(series);
+            }
+            self.cache.clear();
+        }
+    }
+
+    pub fn is_series_visible(&self, series: SeriesId) -> bool {
+        !self.hidden.contains(&series)
+    }
+
+    /// Gives the chart the keyboard focus, if it is [`ChartWidget::focusable`].
+    pub fn focus(&mut self) {
+        self.focused = self.focusable;
+    }
+
+    pub fn unfocus(&mut self) {
+        self.focused = false;
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    pub fn history_status(&self) -> HistoryStatus {
+        self.history.status()
+    }
+
+    /// Starts recording the canvas events the widget receives, dropping a recording in
+    /// progress.
+    pub fn start_recording(&mut self) {
+        self.recorder = Some(Recorder::new());
+    }
+
+    /// Stops recording and returns the events received since
+    /// [`ChartWidget::start_recording`], if it was called.
+    pub fn stop_recording(&mut self) -> Option<Recording> {
+        self.recorder.take().map(Recorder::finish)
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
+    }
+
+    /// Captures the view and the active units, e.g. to persist them with serde.
+    pub fn save_state(&self) -> SavedState {
+        SavedState {
+            view: self.view.clone(),
+            units: self.units.active(),
+            legend: self.legend,
+            pinned: self.pinned.clone(),
+        }
+    }
+
+    /// Restores a state captured by [`ChartWidget::save_state`] and redraws the chart.
+    /// Units of axes that no longer have a unit set are ignored. The view is only added to
+    /// the history if the restored one differs from it.
+    pub fn restore_state(&mut self, state: SavedState) {
+        let previous = std::mem::replace(&mut self.view, state.view);
+        self.view.constrain(&self.limits);
+        self.fit_aspect();
+        if self.view != previous {
+            self.history.record(previous);
+        }
+        self.view_cause = Some(ViewChangeCause::Programmatic);
+        for (axis, index) in state.units {
+            if let Some(units) = self.units.get_mut(axis) {
+                units.select(index);
+            }
+        }
+        self.legend = state.legend;
+        self.pinned = state.pinned;
+        self.cache.clear();
+    }
+
+    /// Returns the registered series element closest to `point` (relative to the canvas),
+    /// as drawn by the last chart build.
+    ///
+    /// Only elements registered through [`BuildContext::register_points`] and its siblings
+    /// can be hit.
+    pub fn hit_test(&self, point: Point) -> Option<Hit> {
+        self.hits
+            .borrow()
+            .hit_test(point, self.hit_radius, self.hit_priority)
+    }
+
+    /// The registered series under the cursor, if hover tracking is enabled through
+    /// [`ChartWidget::hover_highlight`] or [`ChartWidget::on_hover`].
+    pub fn hovered_series(&self) -> Option<SeriesId> {
+        self.hovered
+    }
+
+    pub fn reference_lines(&self) -> &[ReferenceLine] {
+        &self.reference_lines
+    }
+
+    /// The registered element under the cursor, if it is tracked for
+    /// [`ChartWidget::on_hover_element`].
+    pub fn hovered_element(&self) -> Option<Hit> {
+        self.hovered_element
+    }
+
+    /// The tooltips the user pinned, in the order they were pinned.
+    pub fn pinned_tooltips(&self) -> &[PinnedTooltip] {
+        &self.pinned
+    }
+
+    /// Replaces the pinned tooltips, e.g. with ones pinned in a linked chart.
+    pub fn set_pinned_tooltips(&mut self, pinned: Vec<PinnedTooltip>) {
+        self.pinned = pinned;
+    }
+
+    /// Dismisses all pinned tooltips.
+    pub fn clear_pinned_tooltips(&mut self) {
+        self.pinned.clear();
+    }
+
+    /// The text [`KeyAction::CopyValue`] copies: the hovered element, or else the selected
+    /// points one per line, written with [`ChartWidget::copy_formatter`]. Call it e.g. for
+    /// a "Copy" item of a context menu.
+    pub fn copy_text(&self) -> Option<String> {
+        let plot = (*self.plot_area.borrow())?;
+        let hits = self.hits.borrow();
+        let copy = |hit: &Hit, near: Point| {
+            let anchor = hits.anchor(hit, near)?;
+            Some((self.copy_formatter)(hit, self.view.to_data(plot, anchor)))
+        };
+        if let Some(hit) = self.hovered_element {
+            let near = self.cursor.map_or(plot.center(), |cursor| {
+                self.view.to_pixel(plot, (cursor.x, cursor.y))
+            });
+            return copy(&hit, near);
+        }
+        let lines: Vec<String> = self
+            .selection
+            .iter()
+            .filter_map(|&(series, index)| {
+                let hit = Hit {
+                    series,
+                    kind: HitKind::Point,
+                    index,
+                    distance: 0.0,
+                };
+                copy(&hit, plot.center())
+            })
+            .collect();
+        Some(lines.join("\n")).filter(|text| !text.is_empty())
+    }
+
+    /// Gives mutable access to the reference lines, e.g. to add, remove or move them.
+    ///
+    /// Reference lines are drawn on their own layer, so this does not rebuild the chart.
+    pub fn reference_lines_mut(&mut self) -> &mut Vec<ReferenceLine> {
+        self.dragged_line = None;
+        &mut self.reference_lines
+    }
+
+    pub fn dual_cursors_state(&self) -> Option<&DualCursors> {
+        self.dual_cursors.as_ref()
+    }
+
+    /// Gives mutable access to the dual cursors, e.g. to move them programmatically.
+    pub fn dual_cursors_mut(&mut self) -> Option<&mut DualCursors> {
+        self.dragged_line = None;
+        self.dual_cursors.as_mut()
+    }
+
+    /// Runs `draw` with a backend onto `frame`, drawing at the supersampling factor's
+    /// scale into the frame scaled down by as much.
+    fn with_backend<F>(&self, frame: &mut Frame, draw: F) -> Result<(), ChartError>
+    where
+        F: FnOnce(IcedBackend<'_>) -> Result<(), ChartError>,
+    {
+        let factor = self.supersampling;
+        let mut result = Ok(());
+        frame.with_save(|frame| {
+            frame.scale(1.0 / factor as f32);
+            result = IcedBackend::supersampled(frame, factor)
+                .map(|backend| backend.with_text_halo(self.text_halo.clone()))
+                .map_err(ChartError::from)
+                .and_then(draw);
+        });
+        result
+    }
+
+    /// The area of the canvas the chart is built on, below the title and the subtitle and
+    /// above the caption.
+    fn chart_root<'b>(
+        &self,
+        canvas: &DrawingArea<IcedBackend<'b>, Shift>,
+    ) -> DrawingArea<IcedBackend<'b>, Shift> {
+        let scale = self.supersampling as f32;
+        let above = (self.titles.above() * scale) as u32;
+        let below = (self.titles.below() * scale) as u32;
+        canvas.margin(above, below, 0, 0)
+    }
+
+    /// The size of the area of a canvas of `size` the chart is built on.
+    fn chart_size(&self, size: Size) -> Size {
+        let bands = self.titles.above() + self.titles.below();
+        Size::new(size.width, (size.height - bands).max(0.0))
+    }
+
+    /// Takes focus on a click on the chart and gives it up on a click elsewhere or on Tab,
+    /// returning the change.
+    fn update_focus(
+        &mut self,
+        event: Event,
+        bounds: Rectangle,
+        cursor: Cursor,
+    ) -> Option<FocusChange> {
+        let change = match event {
+            Event::Mouse(mouse::Event::ButtonPressed(_)) => {
+                match (cursor.is_over(&bounds), self.focused) {
+                    (true, false) => FocusChange::Gained,
+                    (false, true) => FocusChange::Lost,
+                    _ => return None,
+                }
+            }
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code: keyboard::KeyCode::Tab,
+                modifiers,
+            }) if self.focused => {
+                if modifiers.shift {
+                    FocusChange::Previous
+                } else {
+                    FocusChange::Next
+                }
+            }
+            _ => return None,
+        };
+        self.focused = change == FocusChange::Gained;
+        Some(change)
+    }
+
+    /// Runs a key action, returning it if it is left to the application.
+    fn run_key_action(&mut self, action: KeyAction) -> Option<KeyAction> {
+        let before = self.view.clone();
+        match action {
+            KeyAction::PanLeft => self.view.x.pan(-KEY_PAN_STEP),
+            KeyAction::PanRight => self.view.x.pan(KEY_PAN_STEP),
+            KeyAction::PanUp => self.view.y.pan(KEY_PAN_STEP),
+            KeyAction::PanDown => self.view.y.pan(-KEY_PAN_STEP),
+            KeyAction::ZoomIn | KeyAction::ZoomOut => {
+                let factor = if action == KeyAction::ZoomIn {
+                    KEY_ZOOM_STEP
+                } else {
+                    1.0 / KEY_ZOOM_STEP
+                };
+                self.view.x.zoom(factor, 0.5);
+                self.view.y.zoom(factor, 0.5);
+            }
+            KeyAction::ResetView => self.reset_view(),
+            KeyAction::UndoView => {
+                self.undo_view();
+            }
+            KeyAction::RedoView => {
+                self.redo_view();
+            }
+            KeyAction::CancelMeasurement => self.picks.clear(),
+            KeyAction::ToggleDebugOverlay => self.debug_overlay = !self.debug_overlay,
+            KeyAction::Screenshot | KeyAction::CopyValue => return Some(action),
+        }
+        if action.group() == KeyGroup::Navigation && action != KeyAction::ResetView {
+            self.transition = None;
+            self.view.constrain(&self.limits);
+            self.fit_aspect();
+            if self.view != before {
+                self.history.record(before);
+                self.cache.clear();
+            }
+        }
+        None
+    }
+
+    /// The offset of `point` from the top left corner of the legend, if it is over it.
+    fn legend_at(&self, point: Option<Point>) -> Option<Vector> {
+        let legend = (*self.legend_bounds.borrow())?;
+        point
+            .filter(|point| legend.contains(*point))
+            .map(|point| point - legend.position())
+    }
+
+    /// The series of the entry of the legend the widget draws under `point`.
+    fn legend_entry_at(&self, point: Point) -> Option<SeriesId> {
+        let style = self.legend_style.as_ref()?;
+        let legend = (*self.legend_bounds.borrow())?;
+        style.entry_at(legend, &self.legend_entries.borrow(), point)
+    }
+
+    /// The pinned tooltips with their positions on the canvas, if they are in the plotting
+    /// area `plot`.
+    fn pinned_anchors(
+        &self,
+        plot: Rectangle,
+    ) -> impl DoubleEndedIterator<Item = (usize, &PinnedTooltip, Point)> + '_ {
+        self.pinned
+            .iter()
+            .enumerate()
+            .map(move |(index, pin)| (index, pin, self.view.to_pixel(plot, (pin.x, pin.y))))
+            .filter(move |(_, _, at)| plot.contains(*at))
+    }
+
+    /// The index of the pinned tooltip whose close button is under `point`, on a canvas of
+    /// `size`.
+    fn pinned_close_at(&self, plot: Rectangle, size: Size, point: Point) -> Option<usize> {
+        let tooltip = self.tooltip.as_ref()?;
+        // Later tooltips are drawn on top.
+        self.pinned_anchors(plot)
+            .rev()
+            .find(|(_, pin, at)| {
+                tooltip
+                    .close_bounds(tooltip.bounds(pin, *at, size))
+                    .contains(point)
+            })
+            .map(|(index, ..)| index)
+    }
+
+    /// The hovered element and where its tooltip points to, with the cursor at `point`,
+    /// unless its tooltip is pinned.
+    fn hover_tooltip(&self, plot: Rectangle, point: Point) -> Option<(PinnedTooltip, Point)> {
+        let hit = self.hovered_element?;
+        if self.is_pinned(&hit) {
+            return None;
+        }
+        let anchor = self.hits.borrow().anchor(&hit, point)?;
+        let (x, y) = self.view.to_data(plot, anchor);
+        let element = PinnedTooltip {
+            series: hit.series,
+            index: hit.index,
+            x,
+            y,
+        };
+        Some((element, anchor))
+    }
+
+    /// Pins the tooltip of `hit`, which was clicked at `point`.
+    fn pin(&mut self, plot: Rectangle, hit: Hit, point: Point) {
+        if self.is_pinned(&hit) {
+            return;
+        }
+        if let Some(anchor) = self.hits.get_mut().anchor(&hit, point) {
+            let (x, y) = self.view.to_data(plot, anchor);
+            self.pinned.push(PinnedTooltip {
+                series: hit.series,
+                index: hit.index,
+                x,
+                y,
+            });
+        }
+    }
+
+    fn is_pinned(&self, hit: &Hit) -> bool {
+        self.pinned
+            .iter()
+            .any(|pin| pin.series == hit.series && pin.index == hit.index)
+    }
+
+    /// The reference lines followed by the dual cursor lines.
+    fn lines(&self) -> impl Iterator<Item = &ReferenceLine> + '_ {
+        self.reference_lines.iter().chain(
+            self.dual_cursors
+                .iter()
+                .flat_map(|cursors| cursors.lines.iter()),
+        )
+    }
+
+    fn line_mut(&mut self, index: usize) -> Option<&mut ReferenceLine> {
+        let count = self.reference_lines.len();
+        match self.reference_lines.get_mut(index) {
+            Some(line) => Some(line),
+            None => self
+                .dual_cursors
+                .as_mut()
+                .and_then(|cursors| cursors.lines.get_mut(index - count)),
+        }
+    }
+
+    /// Fits the view to the locked aspect ratio, if any. After a resize, the view keeps its
+    /// scale and shows more or less of the data instead.
+    fn fit_aspect(&mut self) {
+        let (ratio, plot) = match (self.aspect, *self.plot_area.get_mut()) {
+            (Some(ratio), Some(plot)) => (ratio, plot.size()),
+            _ => return,
+        };
+        if let Some(fitted) = self.fitted_size.filter(|fitted| *fitted != plot) {
+            self.view.x.zoom(f64::from(plot.width / fitted.width), 0.5);
+            self.view
+                .y
+                .zoom(f64::from(plot.height / fitted.height), 0.5);
+        }
+        self.view.fit_aspect(plot, ratio);
+        self.fitted_size = Some(plot);
+    }
+
+    /// Rounds `value` of `axis` to the nearest tick if snapping to ticks is enabled, with
+    /// as many ticks as fit `plot`.
+    fn snap(&self, axis: Axis, value: f64, plot: Rectangle) -> f64 {
+        if !self.snap_to_ticks {
+            return value;
+        }
+        let (range, count) = match axis {
+            Axis::X => (Some(self.view.x), self.density.x_labels(plot.width)),
+            Axis::Y => (Some(self.view.y), self.density.y_labels(plot.height)),
+            Axis::SecondaryY => (self.view.secondary_y, self.density.y_labels(plot.height)),
+        };
+        range.map_or(value, |range| range.snap_to_tick(value, count))
+    }
+
+    /// The data position under `point`, snapped to ticks if enabled.
+    fn locate(&self, plot: Rectangle, point: Point) -> Option<CursorPosition> {
+        let position = CursorPosition::locate(&self.view, plot, point)?;
+        Some(CursorPosition {
+            x: self.snap(Axis::X, position.x, plot),
+            y: self.snap(Axis::Y, position.y, plot),
+            secondary_y: position
+                .secondary_y
+                .map(|y| self.snap(Axis::SecondaryY, y, plot)),
+        })
+    }
+
+    /// The draggable line within grabbing distance of `point`.
+    fn reference_line_at(&self, plot: Rectangle, point: Point) -> Option<usize> {
+        self.lines()
+            .enumerate()
+            .filter(|(_, line)| line.is_draggable())
+            .filter_map(|(index, line)| Some((index, line.distance(&self.view, plot, point)?)))
+            .filter(|(_, distance)| *distance <= self.hit_radius)
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(index, _)| index)
+    }
+
+    /// The annotations, e.g. to persist them with serde.
+    pub fn annotation_store(&self) -> &AnnotationStore {
+        &self.annotations
+    }
+
+    /// Replaces all annotations, e.g. with ones loaded from disk. No change messages are
+    /// produced.
+    pub fn restore_annotations(&mut self, store: AnnotationStore) {
+        self.annotations = store;
+        self.annotation_layer.clear();
+    }
+
+    /// Adds an annotation, returning its id and the change message.
+    ///
+    /// Like [`ChartWidget::finish_file_drop`], the message is returned rather than emitted, as
+    /// these methods are called from outside the canvas; feed it back into the
+    /// application's `update`.
+    pub fn add_annotation(&mut self, annotation: Annotation) -> (AnnotationId, Option<Message>) {
+        let id = self.annotations.add(annotation.clone());
+        self.annotation_layer.clear();
+        (
+            id,
+            self.annotation_changed(AnnotationChange::Added(id, annotation)),
+        )
+    }
+
+    /// Replaces the annotation with `id`, returning the change message. Nothing happens
+    /// for unknown ids.
+    pub fn update_annotation(
+        &mut self,
+        id: AnnotationId,
+        annotation: Annotation,
+    ) -> Option<Message> {
+        self.annotations.update(id, annotation.clone())?;
+        self.annotation_layer.clear();
+        self.annotation_changed(AnnotationChange::Updated(id, annotation))
+    }
+
+    /// Removes the annotation with `id`, returning the change message. Nothing happens for
+    /// unknown ids.
+    pub fn remove_annotation(&mut self, id: AnnotationId) -> Option<Message> {
+        self.annotations.remove(id)?;
+        self.annotation_layer.clear();
+        self.annotation_changed(AnnotationChange::Removed(id))
+    }
+
+    fn annotation_changed(&self, change: AnnotationChange) -> Option<Message> {
+        self.on_annotation_change.as_ref().map(|f| f(change))
+    }
+
+    /// Switches the measurement mode on or off.
+    ///
+    /// While measuring, left clicks in the plotting area pick points instead of panning,
+    /// snapping to registered data points near the cursor. After two picks the connector
+    /// and its Δx, Δy and slope stay on screen until a third click starts over, Escape is
+    /// pressed or the mode is switched off.
+    pub fn set_measuring(&mut self, measuring: bool) {
+        self.measuring = measuring;
+        if measuring {
+            self.set_lasso_mode(false);
+        } else {
+            self.picks.clear();
+        }
+    }
+
+    pub fn is_measuring(&self) -> bool {
+        self.measuring
+    }
+
+    /// The completed measurement on screen, if any.
+    pub fn measurement(&self) -> Option<Measurement> {
+        match self.picks[..] {
+            [from, to] => Some(Measurement { from, to }),
+            _ => None,
+        }
+    }
+
+    pub fn clear_measurement(&mut self) {
+        self.picks.clear();
+    }
+
+    /// Switches the lasso selection mode on or off.
+    ///
+    /// While it is on, dragging in the plotting area draws a freeform region instead of
+    /// panning. Releasing the button selects the data points registered through
+    /// [`BuildContext::register_points`] and its siblings that lie inside the region.
+    pub fn set_lasso_mode(&mut self, enabled: bool) {
+        self.lasso_mode = enabled;
+        self.lasso = None;
+        if enabled {
+            self.set_measuring(false);
+        }
+    }
+
+    pub fn is_lasso_mode(&self) -> bool {
+        self.lasso_mode
+    }
+
+    /// The points picked by the last lasso selection, as series and index.
+    pub fn selection(&self) -> &[(SeriesId, usize)] {
+        &self.selection
+    }
+
+    /// Replaces the selection, e.g. with one made in a linked chart. Only the selection
+    /// layer is redrawn.
+    pub fn set_selection(&mut self, selection: Vec<(SeriesId, usize)>) {
+        self.selection = selection;
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.selection.clear();
+    }
+
+    /// The problems found with the drawing calls of the last chart build, if
+    /// [`ChartWidget::validate_drawing`] is on.
+    pub fn diagnostics(&self) -> Option<Diagnostics> {
+        self.diagnostics
+            .as_ref()
+            .map(|diagnostics| diagnostics.borrow().clone())
+    }
+
+    /// Shows or hides the [`ChartWidget::debug_overlay`].
+    pub fn set_debug_overlay(&mut self, shown: bool) {
+        self.debug_overlay = shown;
+    }
+
+    pub fn is_debug_overlay_shown(&self) -> bool {
+        self.debug_overlay
+    }
+
+    /// What the last draw cost, as shown by the [`ChartWidget::debug_overlay`]. It is
+    /// measured whether the overlay is shown or not.
+    pub fn frame_stats(&self) -> FrameStats {
+        *self.frame_stats.borrow()
+    }
+
+    /// The statistics of the tracked series as of the last chart build.
+    pub fn visible_stats(&self) -> Vec<SeriesStats> {
+        self.stats.borrow().clone()
+    }
+
+    /// The registered points within the visible window as of the last chart build, as
+    /// comma-separated values with a shared x column and a column per series, named after
+    /// their [`BuildContext::label`]. The values are read back from the drawn elements, so
+    /// they are exact to a pixel.
+    #[cfg(feature = "export")]
+    pub fn visible_csv(&self) -> String {
+        match *self.plot_area.borrow() {
+            Some(plot) => export::visible_csv(
+                &self.hits.borrow(),
+                &self.view,
+                plot,
+                &self.legend_entries.borrow(),
+            ),
+            None => String::from("x\n"),
+        }
+    }
+
+    /// Writes [`ChartWidget::visible_csv`] to `path` on the application's executor and
+    /// returns the command producing the message `f` builds from the written path or the
+    /// error. On the web the browser downloads the file instead, named after the file name
+    /// of `path`.
+    #[cfg(feature = "export")]
+    pub fn export_csv<F>(&self, path: PathBuf, f: F) -> Command<Message>
+    where
+        F: 'static + Send + Fn(Result<PathBuf, String>) -> Message,
+    {
+        let csv = self.visible_csv();
+        Command::perform(
+            async move { export::save(path, "text/csv", csv.as_bytes()) },
+            f,
+        )
+    }
+
+    /// Renders the chart as last drawn, at the same size and in the same view, and returns
+    /// the command producing the message `f` builds from the PNG bytes or the error.
+    ///
+    /// The image is rendered on the application's executor. It leaves out what only follows
+    /// the cursor, such as tooltips and crosshairs, and sets text in iced's built-in font.
+    #[cfg(feature = "export")]
+    pub fn screenshot<F>(&self, f: F) -> Command<Message>
+    where
+        F: 'static + Send + Fn(Result<Vec<u8>, String>) -> Message,
+    {
+        let capture = self.size.get().map(|size| {
+            let geometry =
+                canvas::Program::draw(self, Rectangle::with_size(size), Cursor::Unavailable);
+            (geometry, size)
+        });
+        Command::perform(
+            async move {
+                let (geometry, size) = capture.ok_or("the chart has not been drawn yet")?;
+                raster::png(geometry, size)
+            },
+            f,
+        )
+    }
+
+    /// Discards the cached geometry so the chart is rebuilt on the next draw.
+    pub fn redraw(&mut self) {
+        self.cache.clear();
+        self.annotation_layer.clear();
+    }
+
+    /// Discards the cached geometry of `layer` alone, e.g. after restyling the grid.
+    ///
+    /// The grid and the data are only cached apart if the chart separates them with
+    /// [`BuildContext::begin_layer`]; otherwise either rebuilds the whole chart.
+    /// [`Layer::Overlay`] is drawn again on every frame anyway, and the annotations
+    /// whenever they or the view change.
+    pub fn invalidate_layer(&mut self, layer: Layer) {
+        match layer {
+            Layer::Grid | Layer::Data => self.cache.clear_layer(layer),
+            Layer::Overlay => {}
+            Layer::Annotations => self.annotation_layer.clear(),
+        }
+    }
+}
+
+impl<Message, C: Chart> canvas::Program<Message> for ChartWidget<Message, C> {
+    fn update(&mut self, event: Event, bounds: Rectangle, cursor: Cursor) -> Option<Message> {
+        trace::span!("chart_event", ?event);
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record(event, bounds, cursor);
+        }
+        self.sync();
+        if self.placeholder.is_some() {
+            return None;
+        }
+        // A failed chart only reports its error and offers the retry button.
+        if let (Some(panel), Some(failure)) = (&self.error_panel, self.failure.get_mut().clone()) {
+            if let Some(error) = self.error.get_mut().take() {
+                if let Some(on_error) = &self.on_error {
+                    return Some(on_error(error));
+                }
+            }
+            let retry = panel.retry_bounds(bounds.size(), &failure.to_string());
+            if let (Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)), Some(point)) =
+                (event, cursor.position_in(&bounds))
+            {
+                if retry.contains(point) {
+                    self.retry();
+                    return self.on_retry.as_ref().map(|on_retry| on_retry());
+                }
+            }
+            return None;
+        }
+        let mut moved = false;
+        let x_before = self.view.x;
+        let view_before = self.view.clone();
+        let pending_cause = self.view_cause.take();
+        let mut reported_action = None;
+        let mut focus_change = None;
+        if self.focusable {
+            focus_change = self.update_focus(event, bounds, cursor);
+        }
+        if let Event::Keyboard(keyboard::Event::KeyPressed {
+            key_code,
+            modifiers,
+        }) = event
+        {
+            // A focusable chart only reacts to keys while focused. Otherwise measuring is
+            // cancelled wherever the cursor is, the rest only over the chart.
+            let action = self.keymap.action(key_code, modifiers).filter(|action| {
+                if self.focusable {
+                    self.focused
+                } else {
+                    *action == KeyAction::CancelMeasurement || cursor.is_over(&bounds)
+                }
+            });
+            if let Some(action) = action.filter(|_| focus_change.is_none()) {
+                reported_action = self.run_key_action(action);
+            }
+        }
+        match event {
+            Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers))
+            | Event::Keyboard(keyboard::Event::KeyPressed { modifiers, .. })
+            | Event::Keyboard(keyboard::Event::KeyReleased { modifiers, .. }) => {
+                self.interaction.modifiers = modifiers;
+            }
+            // The user taking over stops an animated transition.
+            Event::Mouse(mouse::Event::ButtonPressed(_))
+            | Event::Mouse(mouse::Event::WheelScrolled { .. })
+                if cursor.is_over(&bounds) =>
+            {
+                self.transition = None;
+            }
+            _ => {}
+        }
+        let mut line_moved = None;
+        let mut measured = None;
+        let mut selected = false;
+        let mut toggled = None;
+        let plot = *self.plot_area.get_mut();
+        // The canvas gets no event for a resize, so the aspect ratio is restored with the
+        // first event after one.
+        if let (Some(_), Some(plot)) = (self.aspect, plot) {
+            if self.fitted_size != Some(plot.size()) {
+                self.fit_aspect();
+                self.cache.clear();
+                self.view_cause = Some(ViewChangeCause::Resize);
+            }
+        }
+        if let (Event::Mouse(event), Some(plot)) = (event, plot) {
+            // Reference lines take precedence over panning, and only their layer is redrawn
+            // while one is dragged.
+            let point = cursor.position_in(&bounds);
+            let close = point.and_then(|point| self.pinned_close_at(plot, bounds.size(), point));
+            let grabbed = match event {
+                mouse::Event::ButtonPressed(mouse::Button::Left)
+                    if (self.legend_draggable || self.legend_style.is_some())
+                        && self.legend_at(point).is_some() =>
+                {
+                    self.legend_drag = self.legend_at(point).filter(|_| self.legend_draggable);
+                    self.legend_press = point.map(|point| (point, self.legend_entry_at(point)));
+                    true
+                }
+                mouse::Event::CursorMoved { .. } if self.legend_drag.is_some() => {
+                    if let (Some(grab), Some(point)) =
+                        (self.legend_drag, cursor.position_from(bounds.position()))
+                    {
+                        let corner = point - grab;
+                        self.legend = LegendPosition::Floating {
+                            x: (corner.x - plot.x) / plot.width,
+                            y: (corner.y - plot.y) / plot.height,
+                        };
+                        self.cache.clear();
+                    }
+                    true
+                }
+                mouse::Event::ButtonReleased(mouse::Button::Left)
+                    if self.legend_press.is_some() =>
+                {
+                    self.legend_drag = None;
+                    if let (Some((pressed, Some(series))), Some(point)) =
+                        (self.legend_press.take(), point)
+                    {
+                        if pressed.distance(point) <= CLICK_TOLERANCE {
+                            let visible = !self.is_series_visible(series);
+                            self.set_series_visible(series, visible);
+                            toggled = Some((series, visible));
+                        }
+                    }
+                    true
+                }
+                mouse::Event::ButtonPressed(mouse::Button::Left) if close.is_some() => {
+                    if let Some(index) = close {
+                        self.pinned.remove(index);
+                    }
+                    true
+                }
+                mouse::Event::ButtonPressed(mouse::Button::Left) if self.lasso_mode => {
+                    self.lasso = point
+                        .filter(|point| plot.contains(*point))
+                        .map(Lasso::start);
+                    true
+                }
+                mouse::Event::CursorMoved { .. } if self.lasso.is_some() => {
+                    if let (Some(lasso), Some(point)) =
+                        (&mut self.lasso, cursor.position_from(bounds.position()))
+                    {
+                        lasso.extend(point);
+                    }
+                    true
+                }
+                mouse::Event::ButtonReleased(mouse::Button::Left) if self.lasso.is_some() => {
+                    if let Some(lasso) = self.lasso.take() {
+                        self.selection = lasso.select(self.hits.get_mut());
+                        selected = true;
+                    }
+                    true
+                }
+                mouse::Event::ButtonPressed(mouse::Button::Left) if self.measuring => {
+                    if let Some(point) = point.filter(|point| plot.contains(*point)) {
+                        let pick = match self.hits.get_mut().nearest_point(point, self.hit_radius) {
+                            Some(snapped) => self.view.to_data(plot, snapped),
+                            None => {
+                                let (x, y) = self.view.to_data(plot, point);
+                                (self.snap(Axis::X, x, plot), self.snap(Axis::Y, y, plot))
+                            }
+                        };
+                        if self.picks.len() == 2 {
+                            self.picks.clear();
+                        }
+                        self.picks.push(pick);
+                        measured = self.measurement();
+                    }
+                    true
+                }
+                mouse::Event::ButtonPressed(mouse::Button::Left) => {
+                    self.dragged_line = point.and_then(|point| self.reference_line_at(plot, point));
+                    let pinnable = self.tooltip.as_ref().is_some_and(Tooltip::is_pinnable);
+                    self.pin_press = point
+                        .zip(self.hovered_element)
+                        .filter(|_| pinnable && self.dragged_line.is_none());
+                    self.dragged_line.is_some()
+                }
+                mouse::Event::ButtonReleased(mouse::Button::Left)
+                    if self.dragged_line.is_some() =>
+                {
+                    self.dragged_line = None;
+                    true
+                }
+                mouse::Event::CursorMoved { .. } => match self.dragged_line {
+                    Some(index) => {
+                        let point = cursor.position_from(bounds.position());
+                        let value = self.lines().nth(index).and_then(|line| {
+                            let value = line.value_at(&self.view, plot, point?)?;
+                            Some(self.snap(line.axis(), value, plot))
+                        });
+                        if let Some(line) = self.line_mut(index) {
+                            if value.is_some_and(|value| line.set_value(value)) {
+                                line_moved = Some((index, line.value()));
+                            }
+                        }
+                        true
+                    }
+                    None => false,
+                },
+                _ => false,
+            };
+
+            if let mouse::Event::ButtonReleased(mouse::Button::Left) = event {
+                if let (Some((pressed, hit)), Some(point)) = (self.pin_press.take(), point) {
+                    if pressed.distance(point) <= CLICK_TOLERANCE {
+                        self.pin(plot, hit, pressed);
+                    }
+                }
+            }
+
+            let before = self.view.clone();
+            if !grabbed
+                && self
+                    .interaction
+                    .on_mouse(event, &mut self.view, plot, bounds, cursor)
+            {
+                self.view.constrain(&self.limits);
+                self.fit_aspect();
+                self.cache.clear();
+                if !self.gesture_recorded {
+                    self.history.record(before);
+                    self.gesture_recorded = true;
+                }
+            }
+            // A drag is one gesture, and so is a run of wheel steps without cursor movement.
+            match event {
+                mouse::Event::ButtonPressed(_) | mouse::Event::ButtonReleased(_) => {
+                    self.gesture_recorded = false
+                }
+                mouse::Event::CursorMoved { .. } if !self.interaction.is_dragging() => {
+                    self.gesture_recorded = false
+                }
+                _ => {}
+            }
+            if let mouse::Event::ButtonPressed(mouse::Button::Right) = event {
+                let axis = cursor
+                    .position_in(&bounds)
+                    .and_then(|point| Region::at(plot, bounds, point).axis());
+                if let Some(axis) = axis {
+                    self.cycle_unit(axis);
+                }
+            }
+            let position = cursor
+                .position_in(&bounds)
+                .and_then(|point| self.locate(plot, point));
+            moved = position != self.cursor;
+            self.cursor = position;
+
+            if self.highlight.is_some()
+                || self.on_hover.is_some()
+                || self.on_hover_element.is_some()
+                || self.tooltip.is_some()
+                || self.on_copy.is_some()
+            {
+                let hit = match cursor.position_in(&bounds) {
+                    Some(point) if !self.interaction.is_dragging() => {
+                        self.hits
+                            .get_mut()
+                            .hit_test(point, self.hit_radius, self.hit_priority)
+                    }
+                    _ => None,
+                };
+                let hovered = cursor
+                    .position_in(&bounds)
+                    .and_then(|point| self.legend_entry_at(point))
+                    .or(hit.map(|hit| hit.series));
+                if hovered != self.hovered {
+                    self.hovered = hovered;
+                    self.hover_pending = true;
+                }
+                let element = |hit: Option<Hit>| hit.map(|hit| (hit.series, hit.kind, hit.index));
+                if element(hit) != element(self.hovered_element) {
+                    self.element_pending = true;
+                }
+                self.hovered_element = hit;
+            }
+        }
+
+        // The cause set by the last change in this event wins over one still pending. It is
+        // settled before any other message is returned, so the change is reported by a later
+        // event then.
+        self.view_cause = match self.view_cause {
+            None if self.view != view_before => Some(ViewChangeCause::User),
+            None => pending_cause,
+            cause => cause,
+        };
+        if let Some(error) = self.error.get_mut().take() {
+            if let Some(on_error) = &self.on_error {
+                return Some(on_error(error));
+            }
+        }
+        match line_moved {
+            Some((index, value)) if index < self.reference_lines.len() => {
+                if let Some(on_reference_move) = &self.on_reference_move {
+                    return Some(on_reference_move(index, value));
+                }
+            }
+            Some(_) => {
+                if let (Some(cursors), Some(on_move)) =
+                    (&self.dual_cursors, &self.on_dual_cursors_move)
+                {
+                    return Some(on_move(cursors.a(), cursors.b()));
+                }
+            }
+            None => {}
+        }
+        if let (true, Some(on_select)) = (selected, &self.on_select) {
+            return Some(on_select(self.selection.clone()));
+        }
+        if let (Some(measurement), Some(on_measure)) = (measured, &self.on_measure) {
+            return Some(on_measure(measurement));
+        }
+        if let (Some(KeyAction::CopyValue), Some(on_copy)) = (reported_action, &self.on_copy) {
+            return self.copy_text().map(on_copy);
+        }
+        if let (Some(action), Some(on_key_action)) = (reported_action, &self.on_key_action) {
+            return Some(on_key_action(action));
+        }
+        if let (Some((series, visible)), Some(on_legend_toggle)) = (toggled, &self.on_legend_toggle)
+        {
+            return Some(on_legend_toggle(series, visible));
+        }
+        if let (Some(change), Some(on_focus_change)) = (focus_change, &self.on_focus_change) {
+            return Some(on_focus_change(change));
+        }
+        if let (true, Some(on_x_range_change)) = (self.view.x != x_before, &self.on_x_range_change)
+        {
+            return Some(on_x_range_change(self.view.x));
+        }
+        if let Some(message) = self.take_view_change() {
+            return Some(message);
+        }
+        let history = self.history.status();
+        if history != self.reported_history {
+            self.reported_history = history;
+            if let Some(on_history_change) = &self.on_history_change {
+                return Some(on_history_change(history));
+            }
+        }
+        if self.hover_pending {
+            self.hover_pending = false;
+            if let Some(on_hover) = &self.on_hover {
+                return Some(on_hover(self.hovered));
+            }
+        }
+        if self.element_pending {
+            self.element_pending = false;
+            if let Some(on_hover_element) = &self.on_hover_element {
+                return Some(on_hover_element(self.hovered_element));
+            }
+        }
+        match &self.on_cursor_move {
+            Some(on_cursor_move) if moved => Some(on_cursor_move(self.cursor)),
+            _ => None,
+        }
+    }
+
+    fn draw(&self, bounds: Rectangle, cursor: Cursor) -> Vec<Geometry> {
+        // Collapsed layouts are transient; skip them instead of reporting an error.
+        if IcedBackend::is_degenerate(bounds.size()) {
+            return vec![];
+        }
+        self.size.set(Some(bounds.size()));
+        if let Some(placeholder) = &self.placeholder {
+            let mut frame = Frame::new(bounds.size());
+            placeholder.draw(&mut frame, bounds.size());
+            return vec![frame.into_geometry()];
+        }
+        trace::span!("chart_draw", width = bounds.width, height = bounds.height);
+        let rebuilt = Cell::new(false);
+        let scale = 1.0 / self.supersampling as f32;
+        let (grid, data) = self.cache.draw(bounds.size(), scale, |groups| {
+            trace::span!("chart_build");
+            rebuilt.set(true);
+            let started = Instant::now();
+            // With tiles under the chart, its background must leave the plotting area open.
+            // It is where the last build at this size put it, unless the titles or the label
+            // areas moved it since; then the chart is built again around the new area.
+            let mut hole = match (&self.tiles, self.measured_plot.get()) {
+                (Some(_), Some((size, plot))) if size == bounds.size() => Some(plot),
+                _ => None,
+            };
+            let mut retried = false;
+            let (mut ctx, result) = loop {
+                let mut ctx = BuildContext::new(
+                    &self.view,
+                    &self.units,
+                    self.chart_size(bounds.size()),
+                    self.density,
+                    self.background.clone(),
+                )
+                .with_supersampling(self.supersampling)
+                .with_time_zone(self.time_zone)
+                .with_hole(hole)
+                .with_legend(self.legend, self.legend_style, &self.hidden)
+                .with_gutters(self.gutters)
+                .with_groups(Some(groups));
+                if let Some(diagnostics) = &self.diagnostics {
+                    diagnostics.borrow_mut().clear();
+                }
+                // The backend draws into the groups; the frame only gives it its size.
+                let mut frame = Frame::new(bounds.size());
+                let result = self.with_backend(&mut frame, |backend| {
+                    let backend = backend
+                        .with_groups(Some(groups))
+                        .with_diagnostics(self.diagnostics.as_ref());
+                    let canvas = backend.into_drawing_area();
+                    if let Some(pre_draw) = &self.pre_draw {
+                        pre_draw(&ctx, &canvas)?;
+                    }
+                    self.chart.build_chart(&mut ctx, self.chart_root(&canvas))?;
+                    if let Some(post_draw) = &self.post_draw {
+                        post_draw(&ctx, &canvas)?;
+                    }
+                    Ok(())
+                });
+                let plot = match (&self.tiles, ctx.plot_area()) {
+                    (Some(_), Some(plot)) if hole != Some(plot) && !retried => plot,
+                    _ => break (ctx, result),
+                };
+                self.measured_plot.set(Some((bounds.size(), plot)));
+                groups.take(Layer::Grid.group());
+                groups.take(Layer::Data.group());
+                hole = Some(plot);
+                retried = true;
+            };
+            let background = self.background.as_ref().map(|color| {
+                let (r, g, b) = color.rgb();
+                iced::Color::from_rgba8(r, g, b, color.alpha() as f32)
+            });
+            groups.draw(Layer::Grid.group(), |frame| {
+                // Undo the supersampling the groups are scaled for.
+                frame.with_save(|frame| {
+                    frame.scale(self.supersampling as f32);
+                    if let Some(plot) = ctx.plot_area() {
+                        notation::draw_exponents(frame, plot, ctx.exponents());
+                    }
+                    self.titles.draw(frame, bounds.size(), background);
+                });
+            });
+            *self.plot_area.borrow_mut() = ctx.plot_area();
+            *self.legend_bounds.borrow_mut() = ctx.legend_bounds();
+            *self.legend_entries.borrow_mut() = ctx.take_legend_entries();
+            *self.chart_state.borrow_mut() = ctx.take_chart_state();
+            *self.hits.borrow_mut() = ctx.take_hits();
+            self.empty.set(
+                ctx.plot_area()
+                    .is_some_and(|plot| self.hits.borrow().is_empty_within(plot)),
+            );
+            *self.stats.borrow_mut() = ctx.take_stats();
+            {
+                let mut stats = self.frame_stats.borrow_mut();
+                stats.build = started.elapsed();
+                stats.points = ctx.decimation().0;
+                stats.drawn_points = ctx.decimation().1;
+                stats.registered = self.hits.borrow().len();
+            }
+            *self.failure.borrow_mut() = result.as_ref().err().cloned();
+            if let Err(error) = result {
+                *self.error.borrow_mut() = Some(error);
+            }
+        });
+        trace::event!(cache_hit = !rebuilt.get(), "chart geometry");
+        self.frame_stats.borrow_mut().cache_hit = !rebuilt.get();
+        if let (Some(panel), Some(failure)) = (&self.error_panel, &*self.failure.borrow()) {
+            let failure = failure.to_string();
+            let hovered = cursor
+                .position_in(&bounds)
+                .is_some_and(|point| panel.retry_bounds(bounds.size(), &failure).contains(point));
+            let mut frame = Frame::new(bounds.size());
+            panel.draw(&mut frame, &failure, hovered);
+            return vec![frame.into_geometry()];
+        }
+        let plot = *self.plot_area.borrow();
+        let mut layers = vec![];
+        if let (Some(tiles), Some(plot)) = (&self.tiles, plot) {
+            let mut frame = Frame::new(bounds.size());
+            if let Err(error) = tiles.draw(&mut frame, &self.view, plot) {
+                *self.error.borrow_mut() = Some(error.into());
+            }
+            layers.push(frame.into_geometry());
+        }
+        layers.push(grid);
+        layers.push(data);
+
+        let overlay_started = Instant::now();
+        if let Some(state) = &*self.chart_state.borrow() {
+            let position = cursor
+                .position_in(&bounds)
+                .zip(plot)
+                .filter(|(point, plot)| plot.contains(*point))
+                .map(|(point, plot)| self.view.to_data(plot, point));
+            trace::span!("chart_overlay");
+            let mut frame = Frame::new(bounds.size());
+            let result = self.with_backend(&mut frame, |backend| {
+                let canvas = backend.into_drawing_area();
+                let mut chart = state.clone().restore(&self.chart_root(&canvas));
+                self.chart
+                    .build_overlay(&self.view, position, &mut chart)
+                    .map_err(ChartError::from)
+            });
+            if let Err(error) = result {
+                *self.error.borrow_mut() = Some(error);
+            }
+            layers.push(frame.into_geometry());
+        }
+        let layers_started = Instant::now();
+        self.frame_stats.borrow_mut().overlay = layers_started - overlay_started;
+        if let (true, Some(empty_state), Some(plot)) = (self.empty.get(), &self.empty_state, plot) {
+            let mut frame = Frame::new(bounds.size());
+            empty_state.draw(&mut frame, plot);
+            layers.push(frame.into_geometry());
+        }
+        if let (true, Some(plot)) = (self.lines().next().is_some(), plot) {
+            let mut frame = Frame::new(bounds.size());
+            for (index, line) in self.lines().enumerate() {
+                line.draw(
+                    &mut frame,
+                    &self.view,
+                    plot,
+                    self.dragged_line == Some(index),
+                );
+            }
+            layers.push(frame.into_geometry());
+        }
+        if let (Some(cursors), Some(plot)) = (&self.dual_cursors, plot) {
+            let mut frame = Frame::new(bounds.size());
+            cursors.draw_table(&mut frame, &self.view, plot, &self.hits.borrow());
+            layers.push(frame.into_geometry());
+        }
+        if let (false, Some(plot)) = (self.annotations.is_empty(), plot) {
+            let key = (self.view.clone(), plot);
+            layers.push(self.annotation_layer.draw(bounds.size(), key, |frame| {
+                self.annotations.draw(frame, &self.view, plot)
+            }));
+        }
+        if let (false, Some(plot)) = (self.picks.is_empty(), plot) {
+            let position = cursor
+                .position_in(&bounds)
+                .filter(|point| plot.contains(*point))
+                .map(|point| self.view.to_data(plot, point));
+            let mut frame = Frame::new(bounds.size());
+            self.measure_style
+                .draw(&mut frame, &self.view, plot, &self.picks, position);
+            layers.push(frame.into_geometry());
+        }
+        if !self.selection.is_empty() || self.lasso.is_some() {
+            let mut frame = Frame::new(bounds.size());
+            lasso::draw_selection(&mut frame, &self.hits.borrow(), &self.selection);
+            if let Some(lasso) = &self.lasso {
+                lasso.draw(&mut frame);
+            }
+            layers.push(frame.into_geometry());
+        }
+        if let Some(plot) = plot.filter(|plot| self.interaction.zoom_box(*plot).is_some()) {
+            let mut frame = Frame::new(bounds.size());
+            self.interaction.draw_zoom_box(&mut frame, plot);
+            layers.push(frame.into_geometry());
+        }
+        if let (Some(highlight), Some(plot), Some(series)) = (&self.highlight, plot, self.hovered) {
+            let mut frame = Frame::new(bounds.size());
+            highlight.draw(&mut frame, plot, &self.hits.borrow(), series);
+            layers.push(frame.into_geometry());
+        }
+        if let (Some(style), Some(plot)) = (&self.legend_style, plot) {
+            let entries = self.legend_entries.borrow();
+            let area = Rectangle::new(
+                Point::new(0.0, self.titles.above()),
+                self.chart_size(bounds.size()),
+            );
+            let legend = style.bounds(self.legend, area, plot, &entries);
+            *self.legend_bounds.borrow_mut() = Some(legend).filter(|_| !entries.is_empty());
+            let mut frame = Frame::new(bounds.size());
+            style.draw(
+                &mut frame,
+                legend,
+                &entries,
+                &self.hits.borrow(),
+                &self.hidden,
+                self.hovered,
+            );
+            layers.push(frame.into_geometry());
+        }
+        if let (Some(stats_box), Some(plot)) = (&self.stats_box, plot) {
+            let mut frame = Frame::new(bounds.size());
+            stats_box.draw(&mut frame, plot, &self.stats.borrow());
+            layers.push(frame.into_geometry());
+        }
+        if let (Some(readout), Some(plot)) = (&self.readout, plot) {
+            let position = cursor
+                .position_in(&bounds)
+                .and_then(|point| self.locate(plot, point));
+            if let Some(position) = position {
+                let mut frame = Frame::new(bounds.size());
+                readout.draw(&mut frame, plot, &position, &self.units, self.time_zone);
+                layers.push(frame.into_geometry());
+            }
+        }
+        if let (Some(tooltip), Some(plot)) = (&self.tooltip, plot) {
+            let mut frame = Frame::new(bounds.size());
+            for (_, pin, at) in self.pinned_anchors(plot) {
+                tooltip.draw(&mut frame, pin, at, true);
+            }
+            let hovered = cursor
+                .position_in(&bounds)
+                .and_then(|point| self.hover_tooltip(plot, point));
+            if let Some((element, at)) = hovered {
+                tooltip.draw(&mut frame, &element, at, false);
+            }
+            layers.push(frame.into_geometry());
+        }
+        if let Some(loading) = &self.loading {
+            let area = plot.unwrap_or(Rectangle::with_size(bounds.size()));
+            let mut frame = Frame::new(bounds.size());
+            self.loading_style.draw(&mut frame, area, loading);
+            layers.push(frame.into_geometry());
+        }
+        if self.focused {
+            let mut frame = Frame::new(bounds.size());
+            self.focus_style.draw(&mut frame, bounds.size());
+            layers.push(frame.into_geometry());
+        }
+        let mut stats = self.frame_stats.borrow_mut();
+        stats.layers = layers_started.elapsed();
+        if self.debug_overlay {
+            let mut frame = Frame::new(bounds.size());
+            stats.draw(&mut frame, &self.view);
+            layers.push(frame.into_geometry());
+        }
+        layers
+    }
+
+    fn mouse_interaction(&self, bounds: Rectangle, cursor: Cursor) -> mouse::Interaction {
+        if let (Some(panel), Some(failure)) = (&self.error_panel, &*self.failure.borrow()) {
+            let failure = failure.to_string();
+            let over_retry = cursor
+                .position_in(&bounds)
+                .is_some_and(|point| panel.retry_bounds(bounds.size(), &failure).contains(point));
+            return if over_retry {
+                mouse::Interaction::Pointer
+            } else {
+                mouse::Interaction::default()
+            };
+        }
+        let plot = match *self.plot_area.borrow() {
+            Some(plot) => plot,
+            None => return mouse::Interaction::default(),
+        };
+        if self.legend_drag.is_some() {
+            return mouse::Interaction::Grabbing;
+        }
+        if self.legend_draggable
+            && !self.interaction.is_dragging()
+            && self.legend_at(cursor.position_in(&bounds)).is_some()
+        {
+            return mouse::Interaction::Grab;
+        }
+        if cursor
+            .position_in(&bounds)
+            .and_then(|point| self.legend_entry_at(point))
+            .is_some()
+        {
+            return mouse::Interaction::Pointer;
+        }
+        if cursor
+            .position_in(&bounds)
+            .and_then(|point| self.pinned_close_at(plot, bounds.size(), point))
+            .is_some()
+        {
+            return mouse::Interaction::Pointer;
+        }
+        if (self.measuring || self.lasso_mode)
+            && !self.interaction.is_dragging()
+            && cursor
+                .position_in(&bounds)
+                .is_some_and(|point| plot.contains(point))
+        {
+            return mouse::Interaction::Crosshair;
+        }
+        let line = self.dragged_line.or_else(|| {
+            cursor
+                .position_in(&bounds)
+                .filter(|_| !self.interaction.is_dragging())
+                .and_then(|point| self.reference_line_at(plot, point))
+        });
+        match line
+            .and_then(|index| self.lines().nth(index))
+            .map(|line| line.axis())
+        {
+            Some(Axis::X) => mouse::Interaction::ResizingHorizontally,
+            Some(_) => mouse::Interaction::ResizingVertically,
+            None => self
+                .interaction
+                .mouse_interaction(&self.view, plot, bounds, cursor),
+        }
+    }
+}
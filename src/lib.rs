@@ -0,0 +1,24 @@
+//! Draws [plotters](https://docs.rs/plotters) charts in [iced](https://docs.rs/iced)
+//! applications.
+//!
+//! The crate has two layers:
+//! - [`IcedBackend`], a plotters `DrawingBackend` drawing onto an iced canvas `Frame`, for
+//!   applications that manage the canvas themselves.
+//! - [`ChartWidget`], a canvas program drawing a [`Chart`] with panning, zooming, readouts
+//!   and the other interactions built on top of the backend.
+//!
+//! Most charts only need [`prelude`]. See `examples/demo.rs` for a chart using the widget.
+//!
+//! # Features
+//! - `export` (default): exporting the visible data as CSV and the chart as PNG, see
+//!   `ChartWidget::export_csv` and `ChartWidget::screenshot`.
+//! - `serde`: serialization of views, interaction settings and [`ChartSpec`]s.
+//! - `json`: reading datasets and chart specs from JSON.
+//! - `chrono`: building time ranges from chrono date and times, see `TimeRange`.
+//! - `time-zones`: time axes in the local or a named time zone, see [`TimeZone`].
+//! - `ndarray`: ndarray arrays as series, and 2D arrays as [`Heatmap`]s.
+//! - `polars`: plotting columns of polars `DataFrame`s, see `FramePlot`.
+//! - `tracing`: debug spans and events for building, drawing and level of detail, through
+//!   `tracing`.
+
+pub use iced_backend::*;